@@ -0,0 +1,218 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Accepted-hole justifications
+//!
+//! An accept site is an `accept(...)` call or a construct marked `// accept hole`.
+//! Each one should say why it is accepted, in a comment on its line or in the
+//! comment lines just above it:
+//!
+//! ```text
+//! // veracity-accept: reason=eq-clone-workaround ticket=#42 Verus cannot see Clone::clone
+//! proof { accept(equal == (*self == *other)); }
+//! ```
+//!
+//! `reason` is a category from veracity.toml's `[justification] categories` (any
+//! category when the list is empty). `ticket` is optional unless `require_ticket`
+//! is set. Text after the `key=value` pairs is a free-form note.
+//!
+//! A `veracity-accept:` comment also marks the construct it annotates as accepted,
+//! so it can stand in for `// accept hole`, but only if it passes: a malformed
+//! justification, an unknown category or a missing required ticket leaves the
+//! construct a hole (see `FileHoles::apply_justification_policy`).
+
+use crate::config::JustificationPolicy;
+use crate::holes::{DetectedHole, HoleKind};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Comment marker that starts a justification
+pub const MARKER: &str = "veracity-accept:";
+
+/// A parsed `// veracity-accept:` comment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Justification {
+    /// Category, e.g. "verus-limitation"
+    pub reason: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticket: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+}
+
+/// Why an accept site's justification is not acceptable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JustificationProblem {
+    Missing,
+    Malformed(String),
+    UnknownCategory(String),
+    MissingTicket,
+}
+
+impl fmt::Display for JustificationProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JustificationProblem::Missing => write!(f, "accepted without justification; add // {} reason=<category> ticket=<id>", MARKER),
+            JustificationProblem::Malformed(msg) => write!(f, "malformed justification: {}", msg),
+            JustificationProblem::UnknownCategory(c) => write!(f, "unknown justification category `{}`", c),
+            JustificationProblem::MissingTicket => write!(f, "justification has no ticket="),
+        }
+    }
+}
+
+/// An accept site and its justification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcceptSite {
+    pub line: usize,
+    pub kind: HoleKind,
+    /// Set whenever the comment parses, even if the category is unknown
+    pub justification: Option<Justification>,
+    pub problem: Option<JustificationProblem>,
+}
+
+/// `accept(...)` calls and constructs marked `// accept hole`
+pub fn is_accept_site(kind: HoleKind) -> bool {
+    kind == HoleKind::Accept || kind.is_accept_hole()
+}
+
+/// Parse the text after the marker: `reason=<category> [ticket=<id>] [note...]`
+pub fn parse_justification(text: &str) -> Result<Justification, String> {
+    let mut reason = None;
+    let mut ticket = None;
+    let mut words = text.split_whitespace().peekable();
+    while let Some((key, value)) = words.peek().and_then(|w| w.split_once('=')) {
+        if value.is_empty() {
+            return Err(format!("empty value for `{}`", key));
+        }
+        match key {
+            "reason" => reason = Some(value.to_string()),
+            "ticket" => ticket = Some(value.to_string()),
+            _ => return Err(format!("unknown key `{}`", key)),
+        }
+        words.next();
+    }
+    let note = words.collect::<Vec<_>>().join(" ");
+    let reason = reason.ok_or_else(|| "missing reason=<category>".to_string())?;
+    Ok(Justification { reason, ticket, note })
+}
+
+/// Text after the marker in the comment on `line` (1-based), the comment lines just above it,
+/// or — for constructs marked `// accept hole` — the `after` lines below it.
+///
+/// Returns the line the marker is on and the text after it.
+pub fn find_justification(content: &str, line: usize, after: usize) -> Option<(usize, String)> {
+    let lines: Vec<&str> = content.lines().collect();
+    let marker_text = |n: usize| -> Option<(usize, String)> {
+        let text = lines.get(n.checked_sub(1)?)?;
+        let (_, comment) = text.split_once("//")?;
+        let (_, rest) = comment.split_once(MARKER)?;
+        Some((n, rest.trim().to_string()))
+    };
+    if let Some(found) = marker_text(line) {
+        return Some(found);
+    }
+    let mut above = line.saturating_sub(1);
+    while above > 0 && lines.get(above - 1).is_some_and(|l| l.trim_start().starts_with("//")) {
+        if let Some(found) = marker_text(above) {
+            return Some(found);
+        }
+        above -= 1;
+    }
+    (line + 1..=line + after).find_map(marker_text)
+}
+
+/// Check one accept site against the policy
+pub fn check_accept_site(content: &str, hole: &DetectedHole, policy: &JustificationPolicy) -> AcceptSite {
+    // Marked constructs take the same window as their `// accept hole` comment
+    let after = match hole.kind {
+        HoleKind::UnsafeBlockAcceptHole => 6,
+        kind if kind.is_accept_hole() => 2,
+        _ => 0,
+    };
+    let (justification, problem) = match find_justification(content, hole.line, after) {
+        None => (None, Some(JustificationProblem::Missing)),
+        Some((_, text)) => match parse_justification(&text) {
+            Err(msg) => (None, Some(JustificationProblem::Malformed(msg))),
+            Ok(j) => {
+                let problem = if !policy.allows(&j.reason) {
+                    Some(JustificationProblem::UnknownCategory(j.reason.clone()))
+                } else if policy.require_ticket && j.ticket.is_none() {
+                    Some(JustificationProblem::MissingTicket)
+                } else {
+                    None
+                };
+                (Some(j), problem)
+            }
+        },
+    };
+    AcceptSite { line: hole.line, kind: hole.kind, justification, problem }
+}
+
+/// The accept sites among a file's infos
+pub fn accept_sites(content: &str, infos: &[DetectedHole], policy: &JustificationPolicy) -> Vec<AcceptSite> {
+    infos.iter()
+        .filter(|i| is_accept_site(i.kind))
+        .map(|i| check_accept_site(content, i, policy))
+        .collect()
+}
+
+/// An accepted hole in the inventory
+#[derive(Debug, Clone, Serialize)]
+pub struct AcceptedEntry {
+    pub file: String,
+    pub line: usize,
+    /// Enclosing or annotated item; empty at module level
+    pub item: String,
+    pub hole_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ticket: Option<String>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub note: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub problem: Option<String>,
+}
+
+/// Accepted holes grouped by justification category
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AcceptedInventory {
+    /// category -> accepted holes, including categories the policy does not list
+    pub by_category: BTreeMap<String, Vec<AcceptedEntry>>,
+    /// Accept sites with no parseable justification
+    pub unjustified: Vec<AcceptedEntry>,
+}
+
+impl AcceptedInventory {
+    pub fn add(&mut self, file: &str, item: String, site: &AcceptSite) {
+        let mut entry = AcceptedEntry {
+            file: file.to_string(),
+            line: site.line,
+            item,
+            hole_type: site.kind.to_string(),
+            ticket: None,
+            note: String::new(),
+            problem: site.problem.as_ref().map(|p| p.to_string()),
+        };
+        match &site.justification {
+            Some(j) => {
+                entry.ticket = j.ticket.clone();
+                entry.note = j.note.clone();
+                self.by_category.entry(j.reason.clone()).or_default().push(entry);
+            }
+            None => self.unjustified.push(entry),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_category.is_empty() && self.unjustified.is_empty()
+    }
+
+    pub fn total(&self) -> usize {
+        self.by_category.values().map(Vec::len).sum::<usize>() + self.unjustified.len()
+    }
+
+    /// Entries whose justification is missing or fails the policy
+    pub fn problems(&self) -> impl Iterator<Item = &AcceptedEntry> {
+        self.by_category.values().flatten().chain(&self.unjustified).filter(|e| e.problem.is_some())
+    }
+}
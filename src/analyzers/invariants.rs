@@ -0,0 +1,121 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Invariant coverage: loop invariants and struct/enum invariants
+
+use crate::config::Severity;
+use crate::driver::{Analyzer, AnalyzerOutput, ParsedFile};
+use ra_ap_syntax::{SyntaxKind, SyntaxToken};
+
+#[derive(Default, Debug)]
+pub struct FileStats {
+    pub loops_with_invariant: usize,
+    pub loops_without_invariant: usize,
+    pub structs_with_invariant: usize,
+    pub structs_without_invariant: usize,
+    pub enums_with_invariant: usize,
+    pub enums_without_invariant: usize,
+    /// Lines of `while` loops without an invariant
+    pub loops_missing: Vec<usize>,
+}
+
+pub fn analyze(file: &ParsedFile) -> FileStats {
+    let mut stats = FileStats::default();
+    for block in &file.verus_blocks {
+        let tokens = block.tokens();
+        for (i, token) in tokens.iter().enumerate() {
+            match token.kind() {
+                // Look for while loops
+                SyntaxKind::WHILE_KW => {
+                    if check_loop_invariant_at(&tokens, i) {
+                        stats.loops_with_invariant += 1;
+                    } else {
+                        stats.loops_without_invariant += 1;
+                        stats.loops_missing.push(file.token_line(token));
+                    }
+                }
+                // Look for structs
+                SyntaxKind::STRUCT_KW => {
+                    if check_struct_invariant_at(&tokens, i) {
+                        stats.structs_with_invariant += 1;
+                    } else {
+                        stats.structs_without_invariant += 1;
+                    }
+                }
+                // Look for enums
+                SyntaxKind::ENUM_KW => {
+                    if check_struct_invariant_at(&tokens, i) {
+                        stats.enums_with_invariant += 1;
+                    } else {
+                        stats.enums_without_invariant += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    stats
+}
+
+fn check_loop_invariant_at(tokens: &[SyntaxToken], while_idx: usize) -> bool {
+    // Scan forward from while to find the opening brace
+    let Some(brace) = tokens[while_idx..].iter().position(|t| t.kind() == SyntaxKind::L_CURLY) else {
+        return false;
+    };
+    // Found opening brace, look for invariant within first few tokens of loop body
+    tokens[while_idx + brace + 1..].iter()
+        .take(20)
+        .any(|t| t.kind() == SyntaxKind::IDENT && t.text() == "invariant")
+}
+
+fn check_struct_invariant_at(tokens: &[SyntaxToken], struct_idx: usize) -> bool {
+    // Scan the entire struct/enum definition for "invariant" keyword
+    let mut brace_depth = 0;
+    let mut found_opening_brace = false;
+
+    for token in &tokens[struct_idx..] {
+        match token.kind() {
+            SyntaxKind::L_CURLY => {
+                brace_depth += 1;
+                found_opening_brace = true;
+            }
+            SyntaxKind::R_CURLY => {
+                brace_depth -= 1;
+                if found_opening_brace && brace_depth == 0 {
+                    return false; // End of struct/enum without invariant
+                }
+            }
+            SyntaxKind::IDENT => {
+                if token.text() == "invariant" {
+                    return true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    false
+}
+
+pub struct Invariants;
+
+impl Analyzer for Invariants {
+    fn name(&self) -> &'static str {
+        "invariants"
+    }
+
+    fn analyze(&self, file: &ParsedFile) -> AnalyzerOutput {
+        let stats = analyze(file);
+        let mut out = AnalyzerOutput::default();
+        out.count("loops", stats.loops_with_invariant + stats.loops_without_invariant);
+        out.count("loops with invariant", stats.loops_with_invariant);
+        out.count("structs", stats.structs_with_invariant + stats.structs_without_invariant);
+        out.count("structs with invariant", stats.structs_with_invariant);
+        out.count("enums", stats.enums_with_invariant + stats.enums_without_invariant);
+        out.count("enums with invariant", stats.enums_with_invariant);
+        for line in &stats.loops_missing {
+            out.finding(file, *line, Severity::Warning, "while loop without invariant".to_string());
+        }
+        out
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Review analyzers run by the single-parse driver
+//!
+//! Each module holds one review's analysis over a [`ParsedFile`](crate::driver::ParsedFile):
+//! a per-file stats type and `analyze()` used by its own `veracity-review-*` binary, plus
+//! an [`Analyzer`] that turns those stats into findings for `veracity-review-all`.
+
+pub mod invariants;
+pub mod proof_state;
+pub mod requires_ensures;
+pub mod termination;
+pub mod trigger_patterns;
+
+use crate::driver::{Analyzer, Driver};
+
+/// Every analyzer in the library, in report order
+pub fn all() -> Vec<Box<dyn Analyzer>> {
+    vec![
+        Box::new(requires_ensures::RequiresEnsures),
+        Box::new(termination::Termination),
+        Box::new(trigger_patterns::TriggerPatterns),
+        Box::new(invariants::Invariants),
+        Box::new(proof_state::ProofState),
+    ]
+}
+
+/// A driver with the analyzers named in `names`, or all of them when `names` is empty
+pub fn driver_for(names: &[String]) -> anyhow::Result<Driver> {
+    let mut driver = Driver::new();
+    let mut unknown: Vec<&String> = names.iter().collect();
+    for analyzer in all() {
+        if names.is_empty() || names.iter().any(|n| n == analyzer.name()) {
+            unknown.retain(|n| *n != analyzer.name());
+            driver.register(analyzer);
+        }
+    }
+    if !unknown.is_empty() {
+        let known: Vec<&str> = all().iter().map(|a| a.name()).collect();
+        return Err(anyhow::anyhow!(
+            "Unknown analyzer(s): {} (available: {})",
+            unknown.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", "),
+            known.join(", ")
+        ));
+    }
+    Ok(driver)
+}
@@ -0,0 +1,365 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Proof state: assume/admit, external_body, trivial spec bodies, fns without specs
+
+use crate::config::Severity;
+use crate::driver::{Analyzer, AnalyzerOutput, ParsedFile};
+use ra_ap_syntax::{AstNode, SyntaxKind, SyntaxToken};
+
+#[derive(Debug, Default, Clone)]
+pub struct ProofStateStats {
+    pub assume_false_count: usize,
+    pub assume_count: usize,
+    pub admit_count: usize,
+    pub external_body_count: usize,
+    pub spec_trivial_body_count: usize,
+    pub fns_without_spec_count: usize,
+    pub total_holes: usize,
+}
+
+impl ProofStateStats {
+    /// Add another file's (or project's) counts to these
+    pub fn add(&mut self, other: &ProofStateStats) {
+        self.assume_false_count += other.assume_false_count;
+        self.assume_count += other.assume_count;
+        self.admit_count += other.admit_count;
+        self.external_body_count += other.external_body_count;
+        self.spec_trivial_body_count += other.spec_trivial_body_count;
+        self.fns_without_spec_count += other.fns_without_spec_count;
+        self.total_holes += other.total_holes;
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct SpecFnInfo {
+    pub name: String,
+    pub context_name: String,  // trait name, impl name, or empty for free functions
+    pub body: String,          // "true" or "false"
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct FnWithoutSpecInfo {
+    pub name: String,
+    pub fn_type: String,  // "exec" or "proof"
+}
+
+#[derive(Debug, Default)]
+pub struct FileStats {
+    pub stats: ProofStateStats,
+    pub trivial_spec_fns: Vec<SpecFnInfo>,
+    pub fns_without_spec: Vec<FnWithoutSpecInfo>,
+    /// Line and description of every hole, in file order
+    pub holes: Vec<(usize, String)>,
+}
+
+pub fn analyze(file: &ParsedFile) -> FileStats {
+    let mut stats = FileStats::default();
+
+    if file.verus_blocks.is_empty() {
+        // Plain Rust: only external_body attributes can be holes
+        let tokens: Vec<_> = file.source.syntax().descendants_with_tokens()
+            .filter_map(|n| n.into_token())
+            .collect();
+        for (i, token) in tokens.iter().enumerate() {
+            if token.kind() == SyntaxKind::POUND && detect_external_body(&tokens, i) {
+                stats.stats.external_body_count += 1;
+                stats.stats.total_holes += 1;
+                stats.holes.push((file.token_line(token), "external_body".to_string()));
+            }
+        }
+        return stats;
+    }
+
+    for block in &file.verus_blocks {
+        let tokens = block.tokens();
+        analyze_tokens(file, &tokens, &mut stats);
+    }
+    stats
+}
+
+fn analyze_tokens(file: &ParsedFile, tokens: &[SyntaxToken], stats: &mut FileStats) {
+    for (i, token) in tokens.iter().enumerate() {
+        let line = file.token_line(token);
+
+        // Look for fn keyword to check various function properties
+        if token.kind() == SyntaxKind::FN_KW {
+            // Check for spec fn with trivial body
+            if let Some(spec_info) = check_spec_fn_trivial_body(tokens, i) {
+                stats.stats.spec_trivial_body_count += 1;
+                stats.stats.total_holes += 1;
+                stats.holes.push((line, format!("spec fn {} with trivial body {{ {} }}", spec_info.name, spec_info.body)));
+                stats.trivial_spec_fns.push(spec_info);
+            }
+
+            // Check for exec/proof fn without requires or ensures
+            if let Some(fn_info) = check_fn_without_spec(tokens, i) {
+                stats.stats.fns_without_spec_count += 1;
+                stats.stats.total_holes += 1;
+                stats.holes.push((line, format!("{} fn {} without requires/ensures", fn_info.fn_type, fn_info.name)));
+                stats.fns_without_spec.push(fn_info);
+            }
+        }
+
+        // Look for assume/admit function calls
+        if token.kind() == SyntaxKind::IDENT || token.text() == "broadcast" {
+            let text = token.text();
+            if (text == "assume" || text == "admit")
+                && i + 1 < tokens.len() && tokens[i + 1].kind() == SyntaxKind::L_PAREN
+            {
+                if text == "assume" {
+                    if i + 2 < tokens.len() && tokens[i + 2].text() == "false" {
+                        stats.stats.assume_false_count += 1;
+                        stats.holes.push((line, "assume(false)".to_string()));
+                    } else {
+                        stats.stats.assume_count += 1;
+                        stats.holes.push((line, "assume()".to_string()));
+                    }
+                } else {
+                    stats.stats.admit_count += 1;
+                    stats.holes.push((line, "admit()".to_string()));
+                }
+                stats.stats.total_holes += 1;
+            }
+        }
+
+        // Look for external_body attribute
+        if token.kind() == SyntaxKind::POUND && detect_external_body(tokens, i) {
+            stats.stats.external_body_count += 1;
+            stats.stats.total_holes += 1;
+            stats.holes.push((line, "external_body".to_string()));
+        }
+    }
+}
+
+fn detect_external_body(tokens: &[SyntaxToken], start_idx: usize) -> bool {
+    // Look for patterns:
+    // #[verifier::external_body]
+    // #[verifier(external_body)]
+
+    let mut i = start_idx;
+
+    if i >= tokens.len() || tokens[i].kind() != SyntaxKind::POUND {
+        return false;
+    }
+    i += 1;
+
+    // Skip whitespace
+    while i < tokens.len() && tokens[i].kind() == SyntaxKind::WHITESPACE {
+        i += 1;
+    }
+
+    if i >= tokens.len() || tokens[i].kind() != SyntaxKind::L_BRACK {
+        return false;
+    }
+    i += 1;
+
+    // Skip whitespace
+    while i < tokens.len() && tokens[i].kind() == SyntaxKind::WHITESPACE {
+        i += 1;
+    }
+
+    // Look for "verifier"
+    if i >= tokens.len() || tokens[i].kind() != SyntaxKind::IDENT || tokens[i].text() != "verifier" {
+        return false;
+    }
+    i += 1;
+
+    // Skip whitespace
+    while i < tokens.len() && tokens[i].kind() == SyntaxKind::WHITESPACE {
+        i += 1;
+    }
+
+    if i >= tokens.len() {
+        return false;
+    }
+
+    // Check for :: (path) or ( (call syntax)
+    let use_path_syntax = tokens[i].kind() == SyntaxKind::COLON2 ||
+                          (tokens[i].kind() == SyntaxKind::COLON &&
+                           i + 1 < tokens.len() && tokens[i + 1].kind() == SyntaxKind::COLON);
+    let use_call_syntax = tokens[i].kind() == SyntaxKind::L_PAREN;
+
+    if !use_path_syntax && !use_call_syntax {
+        return false;
+    }
+
+    // Skip past :: (might be COLON2 or two COLON tokens)
+    if tokens[i].kind() == SyntaxKind::COLON2 {
+        i += 1;
+    } else if tokens[i].kind() == SyntaxKind::COLON {
+        i += 2; // Skip both colons
+    } else {
+        i += 1; // L_PAREN case
+    }
+
+    // Skip whitespace
+    while i < tokens.len() && tokens[i].kind() == SyntaxKind::WHITESPACE {
+        i += 1;
+    }
+
+    // Get the attribute name
+    if i >= tokens.len() || tokens[i].kind() != SyntaxKind::IDENT {
+        return false;
+    }
+
+    tokens[i].text() == "external_body"
+}
+
+/// Check if a function at fn_idx is a spec fn with a trivial body (just `true` or `false`)
+fn check_spec_fn_trivial_body(tokens: &[SyntaxToken], fn_idx: usize) -> Option<SpecFnInfo> {
+    // Look backwards for "spec" modifier (up to 15 tokens back to account for attributes, open, etc.)
+    let start_idx = fn_idx.saturating_sub(15);
+    let is_spec = tokens[start_idx..fn_idx].iter()
+        .any(|t| t.kind() == SyntaxKind::IDENT && t.text() == "spec");
+
+    if !is_spec {
+        return None;
+    }
+
+    // Get function name
+    let fn_name = get_next_ident(tokens, fn_idx);
+
+    // Find the function body (look for opening brace)
+    let mut i = fn_idx + 1;
+    while i < tokens.len() && tokens[i].kind() != SyntaxKind::L_CURLY {
+        // If we hit a semicolon, this is a declaration without body
+        if tokens[i].kind() == SyntaxKind::SEMICOLON {
+            return None;
+        }
+        i += 1;
+    }
+
+    if i >= tokens.len() {
+        return None;
+    }
+
+    let body_start = i + 1;  // After the {
+    let mut brace_depth = 1;
+    i += 1;
+
+    // Find the matching closing brace
+    while i < tokens.len() && brace_depth > 0 {
+        match tokens[i].kind() {
+            SyntaxKind::L_CURLY => brace_depth += 1,
+            SyntaxKind::R_CURLY => brace_depth -= 1,
+            _ => {}
+        }
+        i += 1;
+    }
+
+    let body_end = i - 1;  // Before the }
+
+    // Extract non-whitespace tokens from the body
+    let body_tokens: Vec<_> = tokens[body_start..body_end]
+        .iter()
+        .filter(|t| t.kind() != SyntaxKind::WHITESPACE && t.kind() != SyntaxKind::COMMENT)
+        .collect();
+
+    // Check if body is just `true` or `false`
+    if body_tokens.len() == 1 {
+        let text = body_tokens[0].text();
+        if text == "true" || text == "false" {
+            return Some(SpecFnInfo {
+                name: fn_name,
+                context_name: String::new(),  // Could track trait/impl name if needed
+                body: text.to_string(),
+            });
+        }
+    }
+
+    None
+}
+
+/// Check if a function at fn_idx is an exec/proof fn without requires or ensures
+fn check_fn_without_spec(tokens: &[SyntaxToken], fn_idx: usize) -> Option<FnWithoutSpecInfo> {
+    // Look backwards for modifiers (up to 15 tokens back)
+    let start_idx = fn_idx.saturating_sub(15);
+    let mut is_spec = false;
+    let mut is_proof = false;
+
+    for token in &tokens[start_idx..fn_idx] {
+        if token.kind() == SyntaxKind::IDENT {
+            match token.text() {
+                "spec" => is_spec = true,
+                "proof" => is_proof = true,
+                _ => {}
+            }
+        }
+    }
+
+    // Skip spec functions - they don't need requires/ensures
+    if is_spec {
+        return None;
+    }
+
+    // Default to exec if no proof modifier
+    let fn_type = if is_proof { "proof" } else { "exec" };
+
+    // Get function name
+    let fn_name = get_next_ident(tokens, fn_idx);
+
+    // Scan from fn to the opening brace or semicolon for requires/ensures
+    let mut has_requires = false;
+    let mut has_ensures = false;
+
+    for token in &tokens[fn_idx + 1..] {
+        match token.kind() {
+            SyntaxKind::L_CURLY => break,  // Found function body
+            SyntaxKind::SEMICOLON => break, // Declaration without body
+            SyntaxKind::IDENT => {
+                let text = token.text();
+                if text == "requires" {
+                    has_requires = true;
+                } else if text == "ensures" {
+                    has_ensures = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // If no requires AND no ensures, this is a hole
+    if !has_requires && !has_ensures {
+        return Some(FnWithoutSpecInfo {
+            name: fn_name,
+            fn_type: fn_type.to_string(),
+        });
+    }
+
+    None
+}
+
+fn get_next_ident(tokens: &[SyntaxToken], start_idx: usize) -> String {
+    tokens.iter()
+        .take((start_idx + 10).min(tokens.len()))
+        .skip(start_idx + 1)
+        .find(|t| t.kind() == SyntaxKind::IDENT)
+        .map(|t| t.text().to_string())
+        .unwrap_or_default()
+}
+
+pub struct ProofState;
+
+impl Analyzer for ProofState {
+    fn name(&self) -> &'static str {
+        "proof-state"
+    }
+
+    fn analyze(&self, file: &ParsedFile) -> AnalyzerOutput {
+        let stats = analyze(file);
+        let mut out = AnalyzerOutput::default();
+        out.count("assume(false)", stats.stats.assume_false_count);
+        out.count("assume()", stats.stats.assume_count);
+        out.count("admit()", stats.stats.admit_count);
+        out.count("external_body", stats.stats.external_body_count);
+        out.count("spec fn with trivial body", stats.stats.spec_trivial_body_count);
+        out.count("fn without requires/ensures", stats.stats.fns_without_spec_count);
+        out.count("holes", stats.stats.total_holes);
+        out.count("holed files", usize::from(stats.stats.total_holes > 0));
+        for (line, what) in stats.holes {
+            out.finding(file, line, Severity::Warning, what);
+        }
+        out
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Requires/ensures completeness of exec functions
+
+use crate::config::Severity;
+use crate::driver::{Analyzer, AnalyzerOutput, ParsedFile};
+use ra_ap_syntax::{SyntaxKind, SyntaxToken};
+
+#[derive(Default, Debug)]
+pub struct FunctionSpec {
+    pub has_requires: bool,
+    pub has_ensures: bool,
+    pub is_spec: bool,
+    pub is_proof: bool,
+    pub is_exec: bool,
+    pub name: String,
+    pub line: usize,
+}
+
+#[derive(Default, Debug)]
+pub struct FileStats {
+    pub exec_fns_with_requires: usize,
+    pub exec_fns_without_requires: usize,
+    pub exec_fns_with_ensures: usize,
+    pub exec_fns_without_ensures: usize,
+    pub spec_fns: usize,
+    pub proof_fns: usize,
+    pub functions: Vec<FunctionSpec>,
+}
+
+pub fn analyze(file: &ParsedFile) -> FileStats {
+    let mut stats = FileStats::default();
+
+    for block in &file.verus_blocks {
+        let tokens = block.tokens();
+        for (i, token) in tokens.iter().enumerate() {
+            if token.kind() == SyntaxKind::FN_KW {
+                let mut func_spec = analyze_function_at(&tokens, i);
+                func_spec.line = file.token_line(token);
+                stats.functions.push(func_spec);
+            }
+        }
+    }
+
+    // Calculate summary stats
+    for func in &stats.functions {
+        if func.is_exec {
+            if func.has_requires {
+                stats.exec_fns_with_requires += 1;
+            } else {
+                stats.exec_fns_without_requires += 1;
+            }
+            if func.has_ensures {
+                stats.exec_fns_with_ensures += 1;
+            } else {
+                stats.exec_fns_without_ensures += 1;
+            }
+        } else if func.is_spec {
+            stats.spec_fns += 1;
+        } else if func.is_proof {
+            stats.proof_fns += 1;
+        }
+    }
+
+    stats
+}
+
+fn analyze_function_at(tokens: &[SyntaxToken], fn_idx: usize) -> FunctionSpec {
+    let mut spec = FunctionSpec::default();
+
+    // Check modifiers before fn keyword
+    let mut i = fn_idx.saturating_sub(10);
+    while i < fn_idx {
+        if tokens[i].kind() == SyntaxKind::IDENT {
+            let text = tokens[i].text();
+            match text {
+                "spec" => spec.is_spec = true,
+                "proof" => spec.is_proof = true,
+                "exec" => spec.is_exec = true,
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+
+    // Default to exec if no modifier specified
+    if !spec.is_spec && !spec.is_proof && !spec.is_exec {
+        spec.is_exec = true;
+    }
+
+    // Get function name
+    i = fn_idx + 1;
+    while i < tokens.len() && tokens[i].kind() != SyntaxKind::IDENT {
+        i += 1;
+    }
+    if i < tokens.len() {
+        spec.name = tokens[i].text().to_string();
+    }
+
+    // Scan the function body for requires/ensures
+    i = fn_idx;
+    let mut brace_depth = 0;
+    let mut found_opening_brace = false;
+
+    while i < tokens.len() {
+        match tokens[i].kind() {
+            SyntaxKind::L_CURLY => {
+                brace_depth += 1;
+                found_opening_brace = true;
+            }
+            SyntaxKind::R_CURLY => {
+                brace_depth -= 1;
+                if found_opening_brace && brace_depth == 0 {
+                    break; // End of function
+                }
+            }
+            SyntaxKind::IDENT => {
+                let text = tokens[i].text();
+                if text == "requires" {
+                    spec.has_requires = true;
+                }
+                if text == "ensures" {
+                    spec.has_ensures = true;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    spec
+}
+
+pub struct RequiresEnsures;
+
+impl Analyzer for RequiresEnsures {
+    fn name(&self) -> &'static str {
+        "requires-ensures"
+    }
+
+    fn analyze(&self, file: &ParsedFile) -> AnalyzerOutput {
+        let stats = analyze(file);
+        let mut out = AnalyzerOutput::default();
+        out.count("exec functions", stats.exec_fns_with_requires + stats.exec_fns_without_requires);
+        out.count("exec with requires", stats.exec_fns_with_requires);
+        out.count("exec with ensures", stats.exec_fns_with_ensures);
+        out.count("spec functions", stats.spec_fns);
+        out.count("proof functions", stats.proof_fns);
+        for func in stats.functions.iter().filter(|f| f.is_exec) {
+            let missing = match (func.has_requires, func.has_ensures) {
+                (false, false) => "no requires, no ensures",
+                (false, true) => "no requires",
+                (true, false) => "no ensures",
+                (true, true) => continue,
+            };
+            out.finding(file, func.line, Severity::Info, format!("fn {} ({})", func.name, missing));
+        }
+        out
+    }
+}
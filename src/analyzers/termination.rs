@@ -0,0 +1,139 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Termination measures: recursive spec/proof functions need a decreases clause
+
+use crate::config::Severity;
+use crate::driver::{Analyzer, AnalyzerOutput, ParsedFile};
+use ra_ap_syntax::{SyntaxKind, SyntaxToken};
+
+#[derive(Default, Debug, Clone)]
+pub struct FunctionInfo {
+    pub name: String,
+    pub line: usize,
+    pub is_spec: bool,
+    pub is_proof: bool,
+    pub has_decreases: bool,
+    pub is_recursive: bool,
+}
+
+impl FunctionInfo {
+    /// Recursive spec/proof function without a decreases clause
+    pub fn missing_decreases(&self) -> bool {
+        (self.is_spec || self.is_proof) && self.is_recursive && !self.has_decreases
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct FileStats {
+    pub functions: Vec<FunctionInfo>,
+}
+
+pub fn analyze(file: &ParsedFile) -> FileStats {
+    let mut stats = FileStats::default();
+    for block in &file.verus_blocks {
+        let tokens = block.tokens();
+        for (i, token) in tokens.iter().enumerate() {
+            if token.kind() == SyntaxKind::FN_KW {
+                let mut info = analyze_function_at(&tokens, i);
+                info.line = file.token_line(token);
+                stats.functions.push(info);
+            }
+        }
+    }
+    stats
+}
+
+fn analyze_function_at(tokens: &[SyntaxToken], fn_idx: usize) -> FunctionInfo {
+    let mut info = FunctionInfo::default();
+
+    // Check modifiers before fn keyword
+    let mut i = fn_idx.saturating_sub(10);
+    while i < fn_idx {
+        if tokens[i].kind() == SyntaxKind::IDENT {
+            let text = tokens[i].text();
+            match text {
+                "spec" => info.is_spec = true,
+                "proof" => info.is_proof = true,
+                _ => {}
+            }
+        }
+        i += 1;
+    }
+
+    // Get function name
+    i = fn_idx + 1;
+    while i < tokens.len() && tokens[i].kind() != SyntaxKind::IDENT {
+        i += 1;
+    }
+    if i < tokens.len() {
+        info.name = tokens[i].text().to_string();
+    }
+    let name_idx = i;
+
+    // Scan function body for "decreases" keyword and recursion
+    i = fn_idx;
+    let mut brace_depth = 0;
+    let mut found_opening_brace = false;
+    let fn_name = info.name.clone();
+
+    while i < tokens.len() {
+        match tokens[i].kind() {
+            SyntaxKind::L_CURLY => {
+                brace_depth += 1;
+                found_opening_brace = true;
+            }
+            SyntaxKind::R_CURLY => {
+                brace_depth -= 1;
+                if found_opening_brace && brace_depth == 0 {
+                    break; // End of function
+                }
+            }
+            SyntaxKind::IDENT => {
+                let text = tokens[i].text();
+                if text == "decreases" {
+                    info.has_decreases = true;
+                }
+                // Check if function calls itself (recursion); the name in the signature doesn't count
+                if text == fn_name && i > name_idx {
+                    // Look ahead for '(' to confirm it's a function call
+                    let mut j = i + 1;
+                    while j < tokens.len() && tokens[j].kind() == SyntaxKind::WHITESPACE {
+                        j += 1;
+                    }
+                    if j < tokens.len() && tokens[j].kind() == SyntaxKind::L_PAREN {
+                        info.is_recursive = true;
+                    }
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    info
+}
+
+pub struct Termination;
+
+impl Analyzer for Termination {
+    fn name(&self) -> &'static str {
+        "termination"
+    }
+
+    fn analyze(&self, file: &ParsedFile) -> AnalyzerOutput {
+        let stats = analyze(file);
+        let mut out = AnalyzerOutput::default();
+        let spec_proof: Vec<_> = stats.functions.iter().filter(|f| f.is_spec || f.is_proof).collect();
+        let recursive: Vec<_> = spec_proof.iter().filter(|f| f.is_recursive).collect();
+        out.count("spec/proof functions", spec_proof.len());
+        out.count("recursive", recursive.len());
+        out.count("recursive with decreases", recursive.iter().filter(|f| f.has_decreases).count());
+        for f in stats.functions.iter().filter(|f| f.missing_decreases()) {
+            let mode = if f.is_spec { "spec" } else { "proof" };
+            out.finding(file, f.line, Severity::Warning,
+                format!("recursive {} fn {} has no decreases clause", mode, f.name));
+        }
+        out
+    }
+}
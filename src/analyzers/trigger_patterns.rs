@@ -0,0 +1,83 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Trigger patterns: forall/exists quantifiers should name their triggers
+
+use crate::config::Severity;
+use crate::driver::{Analyzer, AnalyzerOutput, ParsedFile};
+use ra_ap_syntax::{SyntaxKind, SyntaxToken};
+
+#[derive(Default, Debug)]
+pub struct FileStats {
+    pub forall_count: usize,
+    pub forall_with_triggers: usize,
+    pub exists_count: usize,
+    pub exists_with_triggers: usize,
+    /// (line, "forall" or "exists") of each quantifier without triggers
+    pub untriggered: Vec<(usize, &'static str)>,
+}
+
+pub fn analyze(file: &ParsedFile) -> FileStats {
+    let mut stats = FileStats::default();
+    for block in &file.verus_blocks {
+        let tokens = block.tokens();
+        for (i, token) in tokens.iter().enumerate() {
+            if token.kind() != SyntaxKind::IDENT {
+                continue;
+            }
+            let quantifier = match token.text() {
+                "forall" => "forall",
+                "exists" => "exists",
+                _ => continue,
+            };
+            let triggered = has_trigger_nearby(&tokens, i);
+            if quantifier == "forall" {
+                stats.forall_count += 1;
+                stats.forall_with_triggers += triggered as usize;
+            } else {
+                stats.exists_count += 1;
+                stats.exists_with_triggers += triggered as usize;
+            }
+            if !triggered {
+                stats.untriggered.push((file.token_line(token), quantifier));
+            }
+        }
+    }
+    stats
+}
+
+fn has_trigger_nearby(tokens: &[SyntaxToken], start_idx: usize) -> bool {
+    // Search within the next 50 tokens for "triggers" keyword
+    let end = (start_idx + 50).min(tokens.len());
+    for token in &tokens[start_idx..end] {
+        if token.kind() == SyntaxKind::IDENT && token.text() == "triggers" {
+            return true;
+        }
+        // Stop if we hit a semicolon (end of statement)
+        if token.kind() == SyntaxKind::SEMICOLON {
+            return false;
+        }
+    }
+    false
+}
+
+pub struct TriggerPatterns;
+
+impl Analyzer for TriggerPatterns {
+    fn name(&self) -> &'static str {
+        "trigger-patterns"
+    }
+
+    fn analyze(&self, file: &ParsedFile) -> AnalyzerOutput {
+        let stats = analyze(file);
+        let mut out = AnalyzerOutput::default();
+        out.count("forall", stats.forall_count);
+        out.count("forall with triggers", stats.forall_with_triggers);
+        out.count("exists", stats.exists_count);
+        out.count("exists with triggers", stats.exists_with_triggers);
+        for (line, quantifier) in &stats.untriggered {
+            out.finding(file, *line, Severity::Warning, format!("{} without triggers", quantifier));
+        }
+        out
+    }
+}
@@ -8,6 +8,8 @@
 use anyhow::Result;
 use std::path::PathBuf;
 
+use crate::config::VeracityConfig;
+
 /// Standard arguments common to all Rusticate tools
 pub struct StandardArgs {
         /// Directories or files to analyze
@@ -28,6 +30,8 @@ pub struct StandardArgs {
         pub test_dirs: Vec<String>,
         /// Bench directory names to search (default: ["benches", "bench", "benchmark"])
         pub bench_dirs: Vec<String>,
+        /// Project policy from veracity.toml (empty if there is none)
+        pub config: VeracityConfig,
     }
 
     impl StandardArgs {
@@ -80,7 +84,7 @@ pub struct StandardArgs {
             if args.len() == 1 {
                 // No arguments - default to codebase (src/, tests/, benches/)
                 let current_dir = std::env::current_dir()?;
-                return Ok(StandardArgs { 
+                return StandardArgs { 
                     paths: vec![current_dir],
                     is_module_search: false,
                     project: None,
@@ -90,7 +94,8 @@ pub struct StandardArgs {
                     src_dirs: Self::default_src_dirs(),
                     test_dirs: Self::default_test_dirs(),
                     bench_dirs: Self::default_bench_dirs(),
-                });
+                    config: VeracityConfig::default(),
+                }.with_discovered_config();
             }
             
             let mut i = 1;
@@ -235,11 +240,11 @@ pub struct StandardArgs {
                         Self::print_usage(&args[0]);
                         std::process::exit(0);
                     }
-                    "--dry-run" => {
+                    "--dry-run" | "--no-cache" | "--json" => {
                         // Tool-specific flag, ignore here (handled by individual tools)
                         i += 1;
                     }
-                    "-i" | "--ignore-fn" => {
+                    "-i" | "--ignore-fn" | "--only" => {
                         // Tool-specific flag with one argument, skip both
                         i += 2;
                     }
@@ -263,7 +268,7 @@ pub struct StandardArgs {
                 return Err(anyhow::anyhow!("No paths specified"));
             }
             
-            Ok(StandardArgs { 
+            StandardArgs { 
                 paths, 
                 is_module_search, 
                 project, 
@@ -273,7 +278,22 @@ pub struct StandardArgs {
                 src_dirs,
                 test_dirs,
                 bench_dirs,
-            })
+                config: VeracityConfig::default(),
+            }.with_discovered_config()
+        }
+        
+        /// Load veracity.toml from the project root (the analyzed directory or an ancestor)
+        pub fn with_discovered_config(mut self) -> Result<Self> {
+            let start = self.repositories.clone()
+                .or_else(|| self.multi_codebase.clone())
+                .unwrap_or_else(|| self.base_dir());
+            self.config = VeracityConfig::discover(&start)?;
+            Ok(self)
+        }
+        
+        /// Drop files excluded by veracity.toml
+        pub fn without_excluded(&self, files: Vec<PathBuf>) -> Vec<PathBuf> {
+            files.into_iter().filter(|f| !self.config.is_excluded(f)).collect()
         }
         
         /// Find a module by name in src/, and its corresponding test and bench files
@@ -371,6 +391,7 @@ pub struct StandardArgs {
                 src_dirs: Self::default_src_dirs(),
                 test_dirs: Self::default_test_dirs(),
                 bench_dirs: Self::default_bench_dirs(),
+                config: VeracityConfig::default(),
             })
         }
         
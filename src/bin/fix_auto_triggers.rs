@@ -27,6 +27,7 @@ use regex::Regex;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use veracity::diagnostics::{parse_stderr, Span};
 use veracity::find_rust_files;
 
 // ---------------------------------------------------------------------------
@@ -933,111 +934,57 @@ fn check_git_clean(file: &Path) -> Result<bool> {
 ///   note:   trigger 1 of N:
 ///     --> file:line:col
 ///
-/// Returns a list of (quant_file, quant_line, Vec<(trigger_file, trigger_line, trigger_col_start, trigger_col_end)>)
+/// Each "automatically chose" note starts a recommendation, and the "trigger k of N"
+/// notes right after it are its trigger groups, spanning their underlines.
 fn parse_stderr_triggers(stderr: &str) -> Vec<TriggerRecommendation> {
-    let mut results = Vec::new();
-    let lines: Vec<&str> = stderr.lines().collect();
-    let arrow_re = Regex::new(r"-->\s+(.+?):(\d+):(\d+)").unwrap();
-    let trigger_note_re = Regex::new(r"trigger\s+(\d+)\s+of\s+(\d+):").unwrap();
-    let caret_re = Regex::new(r"^\s*\|?\s*(\^+)").unwrap();
-
-    let mut i = 0;
-    while i < lines.len() {
-        if lines[i].contains("automatically chose triggers for this expression:") {
-            // Next line should have --> file:line:col for the quantifier
-            let mut quant_span: Option<SpanInfo> = None;
-            let mut trigger_groups: Vec<TriggerGroup> = Vec::new();
-
-            // Look for the quantifier span
-            let mut j = i + 1;
-            while j < lines.len() && j < i + 5 {
-                if let Some(caps) = arrow_re.captures(lines[j]) {
-                    quant_span = Some(SpanInfo {
-                        file: caps[1].to_string(),
-                        start_line: caps[2].parse().unwrap_or(0),
-                        start_col: caps[3].parse().unwrap_or(0),
-                        end_line: caps[2].parse().unwrap_or(0),
-                        end_col: caps[3].parse().unwrap_or(0),
-                    });
-                    break;
-                }
-                j += 1;
-            }
-
-            // Look for trigger entries
-            j = i + 1;
-            while j < lines.len() {
-                if trigger_note_re.is_match(lines[j]) {
-                    // Find the span for this trigger
-                    let mut k = j + 1;
-                    while k < lines.len() && k < j + 5 {
-                        if let Some(caps) = arrow_re.captures(lines[k]) {
-                            let trigger_file = caps[1].to_string();
-                            let trigger_line: usize = caps[2].parse().unwrap_or(0);
-                            let trigger_col: usize = caps[3].parse().unwrap_or(0);
-
-                            // Try to find the caret line to determine span width
-                            let mut end_col = trigger_col;
-                            for m in (k + 1)..lines.len().min(k + 5) {
-                                if let Some(ccaps) = caret_re.captures(lines[m]) {
-                                    end_col = trigger_col + ccaps[1].len();
-                                    break;
-                                }
-                            }
-
-                            let span = SpanInfo {
-                                file: trigger_file,
-                                start_line: trigger_line,
-                                start_col: trigger_col,
-                                end_line: trigger_line,
-                                end_col,
-                            };
-                            trigger_groups.push(TriggerGroup {
-                                expressions: vec![TriggerExpr {
-                                    span_as_string: format!(
-                                        "{}:{}:{}: {}:{}",
-                                        span.file,
-                                        span.start_line,
-                                        span.start_col,
-                                        span.end_line,
-                                        span.end_col
-                                    ),
-                                    vir_repr: String::new(),
-                                    span: Some(span),
-                                }],
-                            });
-                            break;
-                        }
-                        k += 1;
-                    }
-                } else if lines[j].contains("automatically chose triggers")
-                    || lines[j].contains("note: Verus printed one or more")
-                    || lines[j].trim().is_empty()
-                        && j + 1 < lines.len()
-                        && !lines[j + 1].trim().starts_with('|')
-                        && !lines[j + 1].trim().starts_with("-->")
-                {
-                    break;
-                }
-                j += 1;
-            }
-
+    let mut results: Vec<TriggerRecommendation> = Vec::new();
+    let mut in_recommendation = false;
+    for diagnostic in parse_stderr(stderr) {
+        if diagnostic.message.contains("automatically chose triggers for this expression") {
             results.push(TriggerRecommendation {
-                quant_span,
-                trigger_groups,
+                quant_span: diagnostic.primary.as_ref().map(|p| span_info(p, false)),
+                trigger_groups: Vec::new(),
                 manual: false,
                 low_confidence: false,
             });
-
-            i = j;
+            in_recommendation = true;
+        } else if in_recommendation && is_trigger_note(&diagnostic.message) {
+            let (Some(recommendation), Some(primary)) = (results.last_mut(), &diagnostic.primary) else { continue };
+            let span = span_info(primary, true);
+            recommendation.trigger_groups.push(TriggerGroup {
+                expressions: vec![TriggerExpr {
+                    span_as_string: format!(
+                        "{}:{}:{}: {}:{}",
+                        span.file, span.start_line, span.start_col, span.end_line, span.end_col
+                    ),
+                    vir_repr: String::new(),
+                    span: Some(span),
+                }],
+            });
         } else {
-            i += 1;
+            in_recommendation = false;
         }
     }
-
     results
 }
 
+/// "trigger 1 of 2:"
+fn is_trigger_note(message: &str) -> bool {
+    let words: Vec<&str> = message.trim_end_matches(':').split_whitespace().collect();
+    matches!(words.as_slice(), ["trigger", k, "of", n] if k.parse::<usize>().is_ok() && n.parse::<usize>().is_ok())
+}
+
+/// A diagnostic span as a SpanInfo on its start line, ending at its underline if `with_extent`
+fn span_info(span: &Span, with_extent: bool) -> SpanInfo {
+    SpanInfo {
+        file: span.file.clone(),
+        start_line: span.line,
+        start_col: span.column,
+        end_line: span.line,
+        end_col: if with_extent && span.end_line == span.line { span.end_column } else { span.column },
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Main
 // ---------------------------------------------------------------------------
@@ -21,7 +21,7 @@ use anyhow::Result;
 use ra_ap_syntax::{ast::{self, AstNode, AstToken}, SyntaxKind};
 use std::fs;
 use std::path::PathBuf;
-use veracity::{find_rust_files, StandardArgs};
+use veracity::{find_rust_files, StandardArgs, VeracityConfig};
 
 /// Configuration for comment formatting
 struct FormatConfig {
@@ -158,7 +158,7 @@ fn main() -> Result<()> {
     
     // Get files to process
     let search_dirs = std_args.get_search_dirs();
-    let files = find_rust_files(&search_dirs);
+    let files = std_args.without_excluded(find_rust_files(&search_dirs));
     
     println!("Scanning {} files...", files.len());
     println!();
@@ -279,7 +279,7 @@ fn parse_standard_args(args: &[String]) -> Result<StandardArgs> {
         paths.push(std::env::current_dir()?);
     }
     
-    Ok(StandardArgs {
+    StandardArgs {
         paths,
         is_module_search: false,
         project: None,
@@ -289,7 +289,8 @@ fn parse_standard_args(args: &[String]) -> Result<StandardArgs> {
         src_dirs: vec!["src".to_string(), "source".to_string()],
         test_dirs: vec!["tests".to_string(), "test".to_string()],
         bench_dirs: vec!["benches".to_string(), "bench".to_string()],
-    })
+        config: VeracityConfig::default(),
+    }.with_discovered_config()
 }
 
 /// Parse a file and extract comment information using ra_ap_syntax
@@ -9,17 +9,27 @@
 //! Usage:
 //!   veracity-minimize-lib -c /path/to/codebase -l /path/to/library
 //!   veracity-minimize-lib -c /path/to/codebase -l /path/to/library --dry-run
+//!   veracity-minimize-lib -c /path/to/codebase -l /path/to/library --jobs 8
+//!   veracity-minimize-lib -c /path/to/codebase -l /path/to/library --strategy ddmin
+//!   veracity-minimize-lib -c /path/to/codebase -l /path/to/library --scoped
+//!   veracity-minimize-lib -c /path/to/codebase -l /path/to/library --resume
 //!
 //! Binary: veracity-minimize-lib
 //!
 //! Logs to: analyses/veracity-minimize-lib.log
+//! Run state: analyses/veracity-minimize-lib.state.json (removed when a run completes)
 
 use anyhow::Result;
 use ra_ap_syntax::{ast::{self, HasName}, AstNode, SyntaxKind};
-use std::collections::HashSet;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::{Duration, Instant};
+use veracity::diagnostics::{self, OutputFormat, VerifyScope, VerusCommand, VerusRun};
+use veracity::holes::analyze_source;
+use veracity::minimize::{format_duration, group_test, merge_verdicts, InFlightEdit, RemovalSet, ResumeGuard, SearchEnv};
+use veracity::module_graph::{module_path, ModuleGraph, ModuleNode};
 use walkdir::WalkDir;
 
 use std::cell::RefCell;
@@ -96,6 +106,30 @@ struct MinimizeArgs {
     type_minimization: bool,
     types_file: Option<PathBuf>,
     single_file: Option<PathBuf>,
+    jobs: usize,
+    strategy: Strategy,
+    /// Verify only the modules a candidate affects, confirming merges on the whole crate
+    scoped: bool,
+    resume: bool,
+    recover: bool,
+}
+
+/// How removal candidates (lemmas, asserts, proof blocks) are searched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Strategy {
+    /// Comment out each candidate alone: one Verus run per candidate
+    OneByOne,
+    /// Remove whole subsets at once, splitting a subset only when it fails
+    Ddmin,
+}
+
+impl Strategy {
+    fn name(self) -> &'static str {
+        match self {
+            Strategy::OneByOne => "one-by-one",
+            Strategy::Ddmin => "ddmin",
+        }
+    }
 }
 
 /// A discovered broadcast group from vstd
@@ -107,7 +141,7 @@ struct BroadcastGroup {
     relevant_types: Vec<String>, // types defined in the vstd module (parsed via AST)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct ProofFn {
     name: String,
     file: PathBuf,
@@ -129,7 +163,7 @@ struct SpecFn {
     impl_type: Option<String>,  // e.g., "bool", "u32" for impl blocks
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct CallSite {
     file: PathBuf,
     line: usize,
@@ -138,7 +172,7 @@ struct CallSite {
     in_library: bool,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(dead_code)]
 enum LemmaStatus {
     Used,
@@ -147,7 +181,7 @@ enum LemmaStatus {
     Untested,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 struct LemmaResult {
     lemma: ProofFn,
@@ -160,7 +194,7 @@ struct LemmaResult {
 /// Tracks line number shifts caused by inserting USED markers
 /// When a `// Veracity: USED` line is inserted before a lemma,
 /// all subsequent line numbers in that file shift by 1.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 struct LineShiftTracker {
     /// Map from file path to list of (insertion_line, shift_amount) pairs
     /// sorted by insertion_line ascending
@@ -248,6 +282,11 @@ impl MinimizeArgs {
         let mut type_minimization = false;
         let mut types_file: Option<PathBuf> = None;
         let mut single_file: Option<PathBuf> = None;
+        let mut jobs: usize = 1;
+        let mut strategy = Strategy::OneByOne;
+        let mut scoped = false;
+        let mut resume = false;
+        let mut recover = false;
         
         let mut i = 1;
         while i < args.len() {
@@ -407,6 +446,43 @@ impl MinimizeArgs {
                     type_minimization = true; // -T implies type minimization
                     i += 1;
                 }
+                "--jobs" | "-j" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(anyhow::anyhow!("-j/--jobs requires a number"));
+                    }
+                    let n: usize = args[i].parse()
+                        .map_err(|_| anyhow::anyhow!("Invalid number: {}", args[i]))?;
+                    if n == 0 {
+                        return Err(anyhow::anyhow!("-j/--jobs must be at least 1"));
+                    }
+                    jobs = n;
+                    i += 1;
+                }
+                "--strategy" | "-s" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(anyhow::anyhow!("-s/--strategy requires one-by-one or ddmin"));
+                    }
+                    strategy = match args[i].as_str() {
+                        "one-by-one" => Strategy::OneByOne,
+                        "ddmin" => Strategy::Ddmin,
+                        other => return Err(anyhow::anyhow!("Unknown strategy: {} (expected one-by-one or ddmin)", other)),
+                    };
+                    i += 1;
+                }
+                "--scoped" => {
+                    scoped = true;
+                    i += 1;
+                }
+                "--resume" => {
+                    resume = true;
+                    i += 1;
+                }
+                "--recover" => {
+                    recover = true;
+                    i += 1;
+                }
                 "--help" | "-h" => {
                     Self::print_usage(&args[0]);
                     std::process::exit(0);
@@ -420,6 +496,34 @@ impl MinimizeArgs {
         let codebase = codebase.ok_or_else(|| anyhow::anyhow!("-c/--codebase is required"))?;
         let library = library.ok_or_else(|| anyhow::anyhow!("-l/--library is required"))?;
         
+        if jobs > 1 || scoped {
+            // Both test candidates in scratch copies, which hold the codebase only,
+            // so the library must live inside it
+            let option = if jobs > 1 { "-j/--jobs" } else { "--scoped" };
+            if !library.canonicalize()?.starts_with(codebase.canonicalize()?) {
+                return Err(anyhow::anyhow!("{} needs the library inside the codebase: {}", option, library.display()));
+            }
+            if fail_fast {
+                return Err(anyhow::anyhow!("{} cannot be combined with -f/--fail-fast", option));
+            }
+            if strategy == Strategy::Ddmin {
+                return Err(anyhow::anyhow!("{} cannot be combined with -s/--strategy ddmin", option));
+            }
+        }
+        if scoped && single_file.is_some() {
+            return Err(anyhow::anyhow!("--scoped cannot be combined with -F/--file"));
+        }
+        if strategy == Strategy::Ddmin && fail_fast {
+            return Err(anyhow::anyhow!("-s/--strategy ddmin cannot be combined with -f/--fail-fast"));
+        }
+        if resume && recover {
+            return Err(anyhow::anyhow!("--resume and --recover are separate steps: --recover first, then --resume"));
+        }
+        if resume && (dry_run || single_file.is_some()) {
+            // Only full library runs are checkpointed
+            return Err(anyhow::anyhow!("--resume cannot be combined with -n/--dry-run or -F/--file"));
+        }
+        
         Ok(MinimizeArgs { 
             codebase, 
             library, 
@@ -440,6 +544,11 @@ impl MinimizeArgs {
             type_minimization,
             types_file,
             single_file,
+            jobs,
+            strategy,
+            scoped,
+            resume,
+            recover,
         })
     }
     
@@ -471,6 +580,11 @@ impl MinimizeArgs {
         log!("  -L, --apply-lib-broadcasts  Apply broadcast groups to library files");
         log!("  -n, --dry-run               Show what would be done without modifying files");
         log!("  -f, --fail-fast             Exit on first verification failure (for debugging)");
+        log!("  -j, --jobs N                Test candidates N at a time in scratch copies (Phases 7-10, 12)");
+        log!("  -s, --strategy S            one-by-one (default) or ddmin: remove subsets, split on failure");
+        log!("  --scoped                    Verify only the modules a candidate affects (Phases 7-10, 12)");
+        log!("  --resume                    Continue an interrupted run from its saved state (same options)");
+        log!("  --recover                   Put back files an interrupted run was editing, then exit");
         log!("  --danger                    Run even with uncommitted changes (DANGEROUS!)");
         log!("  -h, --help                  Show this help message");
         log!();
@@ -496,6 +610,19 @@ impl MinimizeArgs {
         log!("  # Quick test: 5 proof blocks in a single file:");
         log!("  {} -c ./my-project -l ./my-project/src/lib -F ./my-project/src/main.rs -P 5", name);
         log!();
+        log!("  # Overnight run on 8 cores:");
+        log!("  {} -c ./my-project -l ./my-project/src/lib -L -b -a -p -j 8", name);
+        log!();
+        log!("  # Shorter Verus runs: verify only the modules each candidate affects:");
+        log!("  {} -c ./my-project -l ./my-project/src/lib -L -b -a -p --scoped", name);
+        log!();
+        log!("  # Fewer Verus runs when most candidates are unneeded:");
+        log!("  {} -c ./my-project -l ./my-project/src/lib -a -p -s ddmin", name);
+        log!();
+        log!("  # Continue a run that was interrupted (rerun the same command plus --resume):");
+        log!("  {} -c ./my-project -l ./my-project/src/lib -a -p --recover", name);
+        log!("  {} -c ./my-project -l ./my-project/src/lib -a -p --resume", name);
+        log!();
         log!("  # Test if types in a file are used:");
         log!("  {} -c ./my-project -l ./my-project/src/lib -t ./my-project/src/lib/types.rs", name);
     }
@@ -1136,38 +1263,49 @@ fn find_call_sites(lemma_name: &str, codebase: &Path, library: &Path) -> Result<
     Ok((lib_calls, codebase_calls))
 }
 
+/// Verus runs so far, to compare search strategies
+static VERUS_RUNS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+fn verus_runs() -> usize {
+    VERUS_RUNS.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Failed runs that broke the code rather than a proof, as diagnostic summaries
+static BREAKAGES: std::sync::Mutex<Vec<String>> = std::sync::Mutex::new(Vec::new());
+
+fn breakages() -> Vec<String> {
+    BREAKAGES.lock().map(|b| b.clone()).unwrap_or_default()
+}
+
+/// Run verus verification of `scope` and parse its diagnostics, noting runs that broke the code
+fn run_verus_diagnosed(codebase: &Path, scope: &VerifyScope) -> Result<VerusRun> {
+    VERUS_RUNS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let run = diagnostics::run_verus_with(codebase, VerusCommand::detect(codebase), OutputFormat::Human, scope)?;
+    if let Some(modules) = scope.module_count() {
+        note_scoped_run(modules, run.duration);
+    }
+    if run.is_breakage() {
+        // A candidate counts as needed either way, but a removal that stops the code
+        // compiling says nothing about the proof; keep what it was for the report
+        let summary = run.errors().next()
+            .map(|d| d.summary())
+            .unwrap_or_else(|| "Verus failed without an error message".to_string());
+        if let Ok(mut b) = BREAKAGES.lock() {
+            b.push(summary);
+        }
+    }
+    Ok(run)
+}
+
 /// Run verus verification and return (success, stderr_output)
 fn run_verus(codebase: &Path) -> Result<(bool, String)> {
-    // Check if this is a cargo-verus project (has Cargo.toml with verus metadata)
-    let cargo_toml = codebase.join("Cargo.toml");
-    if cargo_toml.exists() {
-        let content = std::fs::read_to_string(&cargo_toml).unwrap_or_default();
-        if content.contains("[package.metadata.verus]") || content.contains("vstd") {
-            // Use cargo verus build for cargo-verus projects
-            let mut cmd = Command::new("cargo");
-            cmd.current_dir(codebase);
-            cmd.args(["verus", "build"]);
-            
-            let output = cmd.output()?;
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            return Ok((output.status.success(), stderr));
-        }
-    }
-    
-    // Fall back to direct verus invocation for non-cargo projects
-    let mut cmd = Command::new("verus");
-    cmd.current_dir(codebase);
-    cmd.args([
-        "--crate-type=lib",
-        "src/lib.rs",
-        "--multiple-errors",
-        "20",
-        "--expand-errors",
-    ]);
-    
-    let output = cmd.output()?;
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-    Ok((output.status.success(), stderr))
+    run_verus_scoped(codebase, &VerifyScope::Crate)
+}
+
+/// Run verus verification of `scope` only and return (success, stderr_output)
+fn run_verus_scoped(codebase: &Path, scope: &VerifyScope) -> Result<(bool, String)> {
+    let run = run_verus_diagnosed(codebase, scope)?;
+    Ok((run.success, run.stderr))
 }
 
 fn run_verus_timed(codebase: &Path) -> Result<(bool, String, Duration)> {
@@ -1176,19 +1314,20 @@ fn run_verus_timed(codebase: &Path) -> Result<(bool, String, Duration)> {
     Ok((success, stderr, start.elapsed()))
 }
 
-fn format_duration(d: Duration) -> String {
-    let secs = d.as_secs();
-    if secs < 60 {
-        format!("{:.1}s", d.as_secs_f64())
-    } else if secs < 3600 {
-        format!("{}m {}s", secs / 60, secs % 60)
+/// Format: [initial N -> now N (incremental N)]
+fn format_timing(initial: Duration, now: Duration) -> String {
+    let delta_str = if now < initial {
+        format!("incremental -{}", format_duration(initial - now))
+    } else if now > initial {
+        format!("incremental +{}", format_duration(now - initial))
     } else {
-        format!("{}h {}m {}s", secs / 3600, (secs % 3600) / 60, secs % 60)
-    }
+        "incremental ~0s".to_string()
+    };
+    format!("[initial {} -> now {} ({})]", format_duration(initial), format_duration(now), delta_str)
 }
 
 /// LOC counts from veracity-count-loc
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
 struct LocCounts {
     spec: usize,
     proof: usize,
@@ -1273,6 +1412,23 @@ fn filter_unused_considering_type_variants(spec_fn_usage: &[(SpecFn, usize, usiz
     truly_unused
 }
 
+/// Format type info for a lemma group: its variants' impl types, or the single lemma's info
+fn format_group_type_info(variants: &[LemmaResult]) -> String {
+    let variant_count = variants.len();
+    if variant_count > 1 {
+        let types: Vec<String> = variants.iter()
+            .filter_map(|lr| lr.lemma.impl_type.clone())
+            .collect();
+        if types.is_empty() {
+            format!(" ({} variants)", variant_count)
+        } else {
+            format!("<{}> ({} type variants)", types.join(", "), types.len())
+        }
+    } else {
+        format_lemma_type_info(&variants[0].lemma)
+    }
+}
+
 /// Format lemma type info for display: <ImplType> or [int,nat] or both
 fn format_lemma_type_info(lemma: &ProofFn) -> String {
     let mut parts = Vec::new();
@@ -2093,39 +2249,14 @@ fn restore_file(file: &Path, original: &str) -> Result<()> {
 /// Run verus and check for Z3 errors
 /// Returns (success, has_z3_errors, duration)
 fn run_verus_check_z3(codebase: &Path) -> Result<(bool, bool, String, Duration)> {
-    let start = Instant::now();
-    
-    // Check if this is a cargo-verus project
-    let cargo_toml = codebase.join("Cargo.toml");
-    let output = if cargo_toml.exists() {
-        let content = std::fs::read_to_string(&cargo_toml).unwrap_or_default();
-        if content.contains("[package.metadata.verus]") || content.contains("vstd") {
-            let mut cmd = Command::new("cargo");
-            cmd.current_dir(codebase);
-            cmd.args(["verus", "build"]);
-            cmd.output()?
-        } else {
-            let mut cmd = Command::new("verus");
-            cmd.current_dir(codebase);
-            cmd.args(["--crate-type=lib", "src/lib.rs", "--multiple-errors", "20", "--expand-errors"]);
-            cmd.output()?
-        }
-    } else {
-        let mut cmd = Command::new("verus");
-        cmd.current_dir(codebase);
-        cmd.args(["--crate-type=lib", "src/lib.rs", "--multiple-errors", "20", "--expand-errors"]);
-        cmd.output()?
-    };
-    
-    let duration = start.elapsed();
-    let success = output.status.success();
+    let run = run_verus_diagnosed(codebase, &VerifyScope::Crate)?;
     
     // Check for Z3 errors in stderr
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let stderr = run.stderr;
     let has_z3_errors = stderr.contains("Z3") && 
                         (stderr.contains("error") || stderr.contains("timeout") || stderr.contains("unknown"));
     
-    Ok((success, has_z3_errors, stderr, duration))
+    Ok((run.success, has_z3_errors, stderr, run.duration))
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -2477,6 +2608,7 @@ fn test_dependence(
 fn test_dependence_group(
     lemmas: &[&ProofFn],
     codebase: &Path,
+    scope: &VerifyScope,
 ) -> Result<(bool, Duration)> {
     let start = Instant::now();
     
@@ -2496,7 +2628,7 @@ fn test_dependence_group(
     }
     
     // Step 2: Run verification
-    let (success, _stderr) = run_verus(codebase)?;
+    let (success, _stderr) = run_verus_scoped(codebase, scope)?;
     
     let duration = start.elapsed();
     
@@ -2587,41 +2719,114 @@ fn test_lemma(
     }
 }
 
-/// Test if a GROUP of lemmas (type variants) is needed by commenting them all out together
-fn test_lemma_group(
-    lemmas: &[&ProofFn],
-    codebase_calls: &[CallSite],
-    codebase: &Path,
-    line_shifts: &mut LineShiftTracker,
-) -> Result<(bool, Duration)> {
-    let start = Instant::now();
-    
-    // Step 1: Comment out ALL lemma definitions in the group
-    // Track adjusted line numbers for each lemma
-    let mut original_lemmas: Vec<(&ProofFn, Vec<String>, usize, usize)> = Vec::new();
+/// A lemma group commented out with TESTING markers, with the original text of
+/// each lemma and call site. Lines are kept unadjusted; every step maps them
+/// through the LineShiftTracker, so a group can be restored after later USED
+/// insertions have moved it.
+struct CommentedGroup<'a> {
+    lemmas: Vec<(&'a ProofFn, Vec<String>)>,
+    calls: Vec<(&'a CallSite, String)>,
+}
+
+/// Step 1 of a necessity test: comment out every lemma of the group and its codebase call sites
+fn comment_out_lemma_group<'a>(
+    lemmas: &[&'a ProofFn],
+    codebase_calls: &'a [CallSite],
+    line_shifts: &LineShiftTracker,
+) -> Result<CommentedGroup<'a>> {
+    let mut group = CommentedGroup { lemmas: Vec::new(), calls: Vec::new() };
     for lemma in lemmas {
         let adjusted_start = line_shifts.adjust_line(&lemma.file, lemma.start_line);
         let adjusted_end = line_shifts.adjust_line(&lemma.file, lemma.end_line);
-        let orig = comment_out_lines(
-            &lemma.file, 
-            adjusted_start, 
-            adjusted_end, 
-            "TESTING"
-        )?;
-        original_lemmas.push((*lemma, orig, adjusted_start, adjusted_end));
+        let orig = comment_out_lines(&lemma.file, adjusted_start, adjusted_end, "TESTING")?;
+        group.lemmas.push((*lemma, orig));
     }
-    
-    // Step 2: Comment out all call sites in codebase for ALL variants
-    let mut call_originals: Vec<(PathBuf, usize, String)> = Vec::new();
     for cs in codebase_calls {
         if !cs.in_library {
             let adjusted_line = line_shifts.adjust_line(&cs.file, cs.line);
             let orig = comment_out_line(&cs.file, adjusted_line, "TESTING")?;
-            call_originals.push((cs.file.clone(), adjusted_line, orig));
+            group.calls.push((cs, orig));
         }
     }
+    Ok(group)
+}
+
+/// Put back the original text of a commented-out group, whatever its markers
+fn restore_lemma_group(group: &CommentedGroup, line_shifts: &LineShiftTracker) -> Result<()> {
+    for (lemma, orig) in &group.lemmas {
+        restore_lines(&lemma.file, line_shifts.adjust_line(&lemma.file, lemma.start_line), orig)?;
+    }
+    for (cs, orig) in &group.calls {
+        restore_line(&cs.file, line_shifts.adjust_line(&cs.file, cs.line), orig)?;
+    }
+    Ok(())
+}
+
+/// Turn a group's TESTING markers into permanent UNUSED / UNNEEDED call markers
+fn keep_lemma_group_unused(group: &CommentedGroup, line_shifts: &LineShiftTracker) -> Result<()> {
+    restore_lemma_group(group, line_shifts)?;
+    for (lemma, _) in &group.lemmas {
+        let adjusted_start = line_shifts.adjust_line(&lemma.file, lemma.start_line);
+        let adjusted_end = line_shifts.adjust_line(&lemma.file, lemma.end_line);
+        comment_out_lines(&lemma.file, adjusted_start, adjusted_end, "UNUSED")?;
+    }
+    for (cs, _) in &group.calls {
+        comment_out_line(&cs.file, line_shifts.adjust_line(&cs.file, cs.line), "UNNEEDED call")?;
+    }
+    Ok(())
+}
+
+/// Insert `// Veracity: USED` before every lemma of a group, recording the insertions
+fn mark_lemma_group_used(lemmas: &[&ProofFn], line_shifts: &mut LineShiftTracker) -> Result<()> {
+    // Group lemmas by file to handle line number shifts correctly
+    let mut by_file: std::collections::HashMap<&Path, Vec<usize>> = 
+        std::collections::HashMap::new();
+    for lemma in lemmas {
+        by_file.entry(lemma.file.as_path())
+            .or_default()
+            .push(line_shifts.adjust_line(&lemma.file, lemma.start_line));
+    }
     
-    // Step 3: Run verification
+    // For each file, add "// USED" markers in REVERSE order (highest line first)
+    // so that earlier insertions don't shift later line numbers within this group
+    for (file, mut lines) in by_file {
+        lines.sort();
+        lines.reverse(); // Process highest line numbers first
+        
+        for target_line in lines {
+            let content = std::fs::read_to_string(file)?;
+            let file_lines: Vec<&str> = content.lines().collect();
+            let mut new_lines: Vec<String> = Vec::new();
+            
+            for (i, line) in file_lines.iter().enumerate() {
+                if i + 1 == target_line {
+                    new_lines.push("// Veracity: USED".to_string());
+                }
+                new_lines.push(line.to_string());
+            }
+            std::fs::write(file, new_lines.join("\n") + "\n")?;
+            
+            // Record the insertion for subsequent lemma groups
+            line_shifts.record_insertion(file, target_line);
+        }
+    }
+    checkpoint_line_shifts(line_shifts);
+    Ok(())
+}
+
+/// Test if a GROUP of lemmas (type variants) is needed by commenting them all out together
+fn test_lemma_group(
+    lemmas: &[&ProofFn],
+    codebase_calls: &[CallSite],
+    codebase: &Path,
+    line_shifts: &mut LineShiftTracker,
+) -> Result<(bool, Duration)> {
+    let start = Instant::now();
+    
+    // Step 1: Comment out ALL lemma definitions in the group and their call sites
+    let group = comment_out_lemma_group(lemmas, codebase_calls, line_shifts)?;
+    
+    // Step 2: Run verification
     let (success, _stderr) = run_verus(codebase)?;
     
     let duration = start.elapsed();
@@ -2629,63 +2834,14 @@ fn test_lemma_group(
     if success {
         // Verification passed - NO lemma in the group is needed
         // Update markers to permanent for ALL variants
-        for (lemma, orig, adjusted_start, adjusted_end) in &original_lemmas {
-            restore_lines(&lemma.file, *adjusted_start, orig)?;
-            comment_out_lines(&lemma.file, *adjusted_start, *adjusted_end, "UNUSED")?;
-        }
-        
-        for (file, line, orig) in &call_originals {
-            restore_line(file, *line, orig)?;
-            comment_out_line(file, *line, "UNNEEDED call")?;
-        }
+        keep_lemma_group_unused(&group, line_shifts)?;
         
         Ok((false, duration)) // false = not needed
     } else {
         // Verification failed - at least ONE lemma in the group IS needed
         // Restore ALL variants and mark them ALL as USED
-        
-        // First, restore all lemma definitions
-        for (lemma, orig, adjusted_start, _) in &original_lemmas {
-            restore_lines(&lemma.file, *adjusted_start, orig)?;
-        }
-        
-        // Restore call sites
-        for (file, line, orig) in &call_originals {
-            restore_line(file, *line, orig)?;
-        }
-        
-        // Group lemmas by file to handle line number shifts correctly
-        let mut by_file: std::collections::HashMap<&Path, Vec<usize>> = 
-            std::collections::HashMap::new();
-        for (lemma, _, adjusted_start, _) in &original_lemmas {
-            by_file.entry(lemma.file.as_path())
-                .or_default()
-                .push(*adjusted_start);
-        }
-        
-        // For each file, add "// USED" markers in REVERSE order (highest line first)
-        // so that earlier insertions don't shift later line numbers within this group
-        for (file, mut lines) in by_file {
-            lines.sort();
-            lines.reverse(); // Process highest line numbers first
-            
-            for target_line in lines {
-                let content = std::fs::read_to_string(file)?;
-                let file_lines: Vec<&str> = content.lines().collect();
-                let mut new_lines: Vec<String> = Vec::new();
-                
-                for (i, line) in file_lines.iter().enumerate() {
-                    if i + 1 == target_line {
-                        new_lines.push("// Veracity: USED".to_string());
-                    }
-                    new_lines.push(line.to_string());
-                }
-                std::fs::write(file, new_lines.join("\n") + "\n")?;
-                
-                // Record the insertion for subsequent lemma groups
-                line_shifts.record_insertion(file, target_line);
-            }
-        }
+        restore_lemma_group(&group, line_shifts)?;
+        mark_lemma_group_used(lemmas, line_shifts)?;
         
         Ok((true, duration)) // true = needed
     }
@@ -2695,7 +2851,7 @@ fn test_lemma_group(
 // Assert detection and testing
 // ═══════════════════════════════════════════════════════════════════════════════
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 struct AssertInfo {
     file: PathBuf,
@@ -2766,21 +2922,13 @@ fn find_asserts_in_file(file: &Path) -> Result<Vec<AssertInfo>> {
     Ok(asserts)
 }
 
-/// Comment out an assert and run verification
-/// Returns (needed, time_saved) where needed=true means verification failed
-fn test_assert(
-    assert_info: &AssertInfo,
-    codebase: &Path,
-    baseline_time: Duration,
-) -> Result<(bool, Duration, Duration)> {
-    // Read the file
-    let content = std::fs::read_to_string(&assert_info.file)?;
+/// Last line of the assert starting at `start_line` (may span multiple lines).
+/// First track parentheses for assert(...), then if followed by `by {`
+/// continue tracking braces to include the whole `by { ... };` block.
+fn assert_end_line(file: &Path, start_line: usize) -> Result<usize> {
+    let content = std::fs::read_to_string(file)?;
     let lines: Vec<&str> = content.lines().collect();
     
-    // Find the full extent of the assert (may span multiple lines).
-    // First track parentheses for assert(...), then if followed by `by {`
-    // continue tracking braces to include the whole `by { ... };` block.
-    let start_line = assert_info.line;
     let mut end_line;
     let mut paren_depth = 0;
     let mut found_open_paren = false;
@@ -2847,6 +2995,19 @@ fn test_assert(
         }
     }
     
+    Ok(end_line)
+}
+
+/// Comment out an assert and run verification
+/// Returns (needed, time_saved) where needed=true means verification failed
+fn test_assert(
+    assert_info: &AssertInfo,
+    codebase: &Path,
+    baseline_time: Duration,
+) -> Result<(bool, Duration, Duration)> {
+    let start_line = assert_info.line;
+    let end_line = assert_end_line(&assert_info.file, start_line)?;
+    
     // Comment out the assert
     let original = comment_out_lines(&assert_info.file, start_line, end_line, "TESTING assert")?;
     
@@ -2880,7 +3041,7 @@ fn test_assert(
 // Admit detection and testing
 // ═══════════════════════════════════════════════════════════════════════════════
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 struct AdmitInfo {
     file: PathBuf,
@@ -3011,7 +3172,7 @@ fn test_admit(
 // ═══════════════════════════════════════════════════════════════════════════════
 
 /// A proof { } block found inside a function
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct ProofBlock {
     file: PathBuf,
     start_line: usize,
@@ -3186,7 +3347,7 @@ fn test_proof_block(
 // ═══════════════════════════════════════════════════════════════════════════════
 
 /// A type definition (struct, enum, type alias, trait) found in a file
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct TypeInfo {
     file: PathBuf,
     name: String,
@@ -3364,67 +3525,957 @@ fn mark_type_used(type_info: &TypeInfo) -> Result<()> {
     Ok(())
 }
 
-/// Run single-file mode: just test asserts and proof blocks in one file
-/// Skips all library analysis (phases 2-8)
-fn run_single_file_mode(args: &MinimizeArgs, baseline_time: Duration) -> Result<()> {
-    let start_time = Instant::now();
-    let file = args.single_file.as_ref().unwrap();
-    let rel_path = file.strip_prefix(&args.codebase).unwrap_or(file);
+// ═══════════════════════════════════════════════════════════════════════════════
+// Parallel testing in scratch copies (-j/--jobs)
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Directories left out of scratch copies
+const SCRATCH_SKIP_DIRS: &[&str] = &["target", ".git", "analyses"];
+
+/// Scratch copies of the codebase, one per job, where candidates are tested
+/// while the real tree is left alone.
+///
+/// Copies sit next to the codebase as `.<name>.veracity-job-N`, so relative
+/// `path = "../..."` dependencies still resolve. Each keeps its own target/,
+/// so only its first build is cold. Logging stays on the main thread: the log
+/// file is thread-local.
+struct ScratchPool {
+    codebase: PathBuf,
+    dirs: Vec<PathBuf>,
+    /// Set with --scoped: candidates verify only the modules they affect
+    scope: Option<ModuleScope>,
+}
+
+impl ScratchPool {
+    fn new(codebase: &Path, jobs: usize) -> Result<Self> {
+        let codebase = codebase.canonicalize()?;
+        let parent = codebase.parent()
+            .ok_or_else(|| anyhow::anyhow!("Codebase has no parent directory: {}", codebase.display()))?
+            .to_path_buf();
+        let name = codebase.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let dirs = (1..=jobs)
+            .map(|k| parent.join(format!(".{}.veracity-job-{}", name, k)))
+            .collect();
+        let pool = ScratchPool { codebase, dirs, scope: None };
+        pool.sync()?;
+        Ok(pool)
+    }
     
-    log!("Target file: {}", rel_path.display());
-    log!();
+    /// Bring every copy up to date with the real tree; returns files copied
+    fn sync(&self) -> Result<usize> {
+        let mut copied = 0;
+        for dir in &self.dirs {
+            copied += sync_tree(&self.codebase, dir)?;
+        }
+        Ok(copied)
+    }
     
-    // Find asserts in the file
-    let asserts = find_asserts_in_file(file)?;
-    log!("Found {} assert statements", asserts.len());
+    /// Where `path` in the real tree lives in the copy `dir`
+    fn rebase(&self, path: &Path, dir: &Path) -> PathBuf {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        match path.strip_prefix(&self.codebase) {
+            Ok(rel) => dir.join(rel),
+            Err(_) => path,
+        }
+    }
     
-    // Find proof blocks in the file
-    let proof_blocks = find_proof_blocks_in_file(file)?;
-    log!("Found {} proof {{}} blocks", proof_blocks.len());
-    log!();
+    fn rebase_lemma(&self, lemma: &ProofFn, dir: &Path) -> ProofFn {
+        ProofFn { file: self.rebase(&lemma.file, dir), ..lemma.clone() }
+    }
     
-    if asserts.is_empty() && proof_blocks.is_empty() {
-        log!("Nothing to test in this file.");
-        return Ok(());
+    /// What to verify after editing `files` of the real tree: the whole crate without --scoped
+    fn scope_after(&self, files: &[&Path]) -> VerifyScope {
+        self.scope.as_ref().map_or(VerifyScope::Crate, |s| s.after_editing(files))
     }
     
-    // Test asserts if enabled
-    if args.assert_minimization && !asserts.is_empty() {
-        log!("═══════════════════════════════════════════════════════════════");
-        log!("Testing asserts");
-        log!("═══════════════════════════════════════════════════════════════");
-        log!();
-        
-        let test_count = match args.max_asserts {
-            Some(n) => asserts.len().min(n),
-            None => asserts.len(),
-        };
+    /// What to verify after commenting out the bodies of `lemmas` of the real tree
+    fn scope_for_bodies(&self, lemmas: &[&ProofFn]) -> VerifyScope {
+        self.scope.as_ref().map_or(VerifyScope::Crate, |s| s.for_bodies_of(lemmas))
+    }
+    
+    /// Test every item in some copy, one item per copy at a time. `done` sees each
+    /// result on the main thread as it arrives; results come back in item order.
+    fn run<T: Sync, R: Send>(
+        &self,
+        items: &[T],
+        test: impl Fn(&T, &Path) -> Result<R> + Sync,
+        mut done: impl FnMut(usize, &R),
+    ) -> Result<Vec<R>> {
+        use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
         
-        let mut tested = 0;
-        let mut removed = 0;
-        let mut time_saved = Duration::ZERO;
+        let next = AtomicUsize::new(0);
+        let failed = AtomicBool::new(false);
+        let mut results: Vec<Option<R>> = items.iter().map(|_| None).collect();
+        let mut first_error = None;
         
-        for (i, assert_info) in asserts.iter().take(test_count).enumerate() {
-            log_no_newline!("  [{}/{}] {} at line {} in fn {}... ",
-                i + 1, test_count,
-                assert_info.assert_type,
-                assert_info.line,
-                assert_info.context);
-            
-            if args.dry_run {
-                log!("(dry-run, skipped)");
-                continue;
+        std::thread::scope(|scope| {
+            let (tx, rx) = std::sync::mpsc::channel();
+            for dir in &self.dirs {
+                let tx = tx.clone();
+                let (next, failed, test) = (&next, &failed, &test);
+                scope.spawn(move || loop {
+                    if failed.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= items.len() {
+                        break;
+                    }
+                    let result = test(&items[i], dir.as_path());
+                    if result.is_err() {
+                        // Stop handing out work; the copies may be in an unknown state
+                        failed.store(true, Ordering::Relaxed);
+                    }
+                    if tx.send((i, result)).is_err() {
+                        break;
+                    }
+                });
             }
+            drop(tx);
             
-            let (needed, verify_time, saved) = test_assert(assert_info, &args.codebase, baseline_time)?;
-            tested += 1;
-            
-            if needed {
-                log!("NEEDED [{}]", format_duration(verify_time));
-            } else {
-                removed += 1;
-                time_saved += saved;
-                log!("UNNEEDED [{}]", format_duration(verify_time));
+            for (i, result) in rx {
+                match result {
+                    Ok(r) => {
+                        done(i, &r);
+                        results[i] = Some(r);
+                    }
+                    Err(e) => {
+                        first_error.get_or_insert(e);
+                    }
+                }
+            }
+        });
+        
+        if let Some(e) = first_error {
+            return Err(e);
+        }
+        Ok(results.into_iter().map(|r| r.expect("every item is tested")).collect())
+    }
+}
+
+impl Drop for ScratchPool {
+    fn drop(&mut self) {
+        for dir in &self.dirs {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+    }
+}
+
+/// Copy every file of `src` that `dst` lacks or holds differently, skipping
+/// SCRATCH_SKIP_DIRS; returns files copied. Unchanged files keep their
+/// timestamps, so cargo only rebuilds what changed.
+fn sync_tree(src: &Path, dst: &Path) -> Result<usize> {
+    let mut copied = 0;
+    let walker = WalkDir::new(src).into_iter().filter_entry(|e| {
+        e.depth() == 0 || !e.file_type().is_dir()
+            || !e.file_name().to_str().is_some_and(|n| SCRATCH_SKIP_DIRS.contains(&n))
+    });
+    for entry in walker.filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let target = dst.join(entry.path().strip_prefix(src)?);
+        let content = std::fs::read(entry.path())?;
+        if std::fs::read(&target).ok().as_deref() != Some(content.as_slice()) {
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&target, &content)?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
+/// Comment out lines, verify `scope`, and put them back.
+/// Returns (verified, verify_time): whether the codebase verifies without them.
+fn verifies_without_lines(file: &Path, start_line: usize, end_line: usize, codebase: &Path, scope: &VerifyScope) -> Result<(bool, Duration)> {
+    let original = comment_out_lines(file, start_line, end_line, "TESTING")?;
+    let start = Instant::now();
+    let (success, _stderr) = run_verus_scoped(codebase, scope)?;
+    let verify_time = start.elapsed();
+    restore_lines(file, start_line, &original)?;
+    Ok((success, verify_time))
+}
+
+/// An assert or proof block tested for removal in the scratch copies
+struct LineCandidate {
+    file: PathBuf,
+    start_line: usize,
+    end_line: usize,
+    /// Shown in progress lines, e.g. "assert L12 in lemma_foo"
+    label: String,
+}
+
+/// Test line candidates in the scratch copies, then comment the removable ones
+/// out of the real tree with `marker` (e.g. "UNNEEDED assert").
+///
+/// Each candidate is tested alone against the tree as of the last sync, so two
+/// that verify alone may fail together. The merged tree is verified once; if it
+/// fails, the removals are rolled back and re-tested one at a time in the real
+/// tree, as without -j. Each verdict is saved as it arrives; candidates `settled`
+/// by a resumed run are not re-tested, only merged if they never were.
+/// Returns (needed, verify_time) per candidate.
+fn minimize_lines_parallel(
+    pool: &ScratchPool,
+    candidates: &[LineCandidate],
+    settled: &[Option<(bool, Duration)>],
+    codebase: &Path,
+    baseline_time: Duration,
+    marker: &str,
+) -> Result<Vec<(bool, Duration)>> {
+    pool.sync()?;
+    let todo: Vec<usize> = (0..candidates.len()).filter(|&i| settled[i].is_none()).collect();
+    let total = todo.len();
+    log!("Testing {} candidates in {} scratch copies...", total, pool.dirs.len());
+    log!();
+    
+    let mut save_error = None;
+    let tested = pool.run(&todo, |&i, dir| {
+        let c = &candidates[i];
+        let scope = pool.scope_after(&[c.file.as_path()]);
+        let (success, verify_time) = verifies_without_lines(&pool.rebase(&c.file, dir), c.start_line, c.end_line, dir, &scope)?;
+        Ok((!success, verify_time))
+    }, |k, (needed, verify_time)| {
+        log!("[{}/{}] {}... {} {}", k + 1, total, candidates[todo[k]].label,
+            if *needed { "NEEDED" } else { "UNNEEDED" },
+            format_timing(baseline_time, *verify_time));
+        if let Err(e) = checkpoint_scratch_verdict(todo[k], *needed, *verify_time) {
+            save_error.get_or_insert(e);
+        }
+    })?;
+    if let Some(e) = save_error {
+        return Err(e);
+    }
+    let mut verdicts: Vec<(bool, Duration)> = settled.iter().map(|v| v.unwrap_or((true, Duration::ZERO))).collect();
+    for (k, &i) in todo.iter().enumerate() {
+        verdicts[i] = tested[k];
+    }
+    let merging = merge_order(candidates.len(), &todo);
+    let mut set = LineRemovals { candidates, marker, pending: Vec::new() };
+    merge_verdicts(&mut set, &mut MinimizeEnv { codebase, scoped: pool.scope.is_some() }, &merging, &mut verdicts)?;
+    Ok(verdicts)
+}
+
+/// Candidates to merge into the real tree, in candidate order: those just
+/// tested plus any whose verdict an interrupted run saved but never merged
+fn merge_order(count: usize, tested: &[usize]) -> Vec<usize> {
+    let mut merge = vec![false; count];
+    for &i in tested.iter().chain(&checkpoint_unmerged()) {
+        if i < count {
+            merge[i] = true;
+        }
+    }
+    (0..count).filter(|&i| merge[i]).collect()
+}
+
+/// A lemma group tested for necessity in the scratch copies
+struct LemmaCandidate<'a> {
+    lemmas: Vec<&'a ProofFn>,
+    calls: Vec<CallSite>,
+    /// Shown in progress lines: the lemma name and type info
+    label: String,
+}
+
+impl LemmaCandidate<'_> {
+    /// Files a necessity test edits: the lemmas' and their codebase call sites'
+    fn files(&self) -> Vec<&Path> {
+        self.lemmas.iter().map(|l| l.file.as_path())
+            .chain(self.calls.iter().filter(|cs| !cs.in_library).map(|cs| cs.file.as_path()))
+            .collect()
+    }
+}
+
+/// Phase 8 across the scratch copies: test each lemma group, then merge the
+/// verdicts into the real tree with the usual UNUSED and USED markers, tracking
+/// the USED insertions in `line_shifts`. As with line candidates, a merged tree
+/// that fails to verify is rolled back and re-tested one group at a time.
+/// Verdicts are saved as they arrive; groups `settled` by a resumed run are not
+/// re-tested, only merged if they never were. Returns (needed, verify_time) per group.
+fn minimize_lemmas_parallel(
+    pool: &ScratchPool,
+    candidates: &[LemmaCandidate],
+    settled: &[Option<(bool, Duration)>],
+    codebase: &Path,
+    baseline_time: Duration,
+    line_shifts: &mut LineShiftTracker,
+) -> Result<Vec<(bool, Duration)>> {
+    pool.sync()?;
+    let todo: Vec<usize> = (0..candidates.len()).filter(|&i| settled[i].is_none()).collect();
+    let total = todo.len();
+    log!("Testing {} lemma groups in {} scratch copies...", total, pool.dirs.len());
+    log!();
+    
+    let mut save_error = None;
+    let tested = pool.run(&todo, |&i, dir| {
+        let c = &candidates[i];
+        let lemmas: Vec<ProofFn> = c.lemmas.iter().map(|l| pool.rebase_lemma(l, dir)).collect();
+        let lemma_refs: Vec<&ProofFn> = lemmas.iter().collect();
+        let calls: Vec<CallSite> = c.calls.iter()
+            .map(|cs| CallSite { file: pool.rebase(&cs.file, dir), ..cs.clone() })
+            .collect();
+        // Copies are synced before any USED insertion, so their lines are unshifted
+        let no_shifts = LineShiftTracker::new();
+        let start = Instant::now();
+        let group = comment_out_lemma_group(&lemma_refs, &calls, &no_shifts)?;
+        let (success, _stderr) = run_verus_scoped(dir, &pool.scope_after(&c.files()))?;
+        let duration = start.elapsed();
+        restore_lemma_group(&group, &no_shifts)?;
+        Ok((!success, duration))
+    }, |k, (needed, duration)| {
+        log!("[{}/{}] Testing necessity of {}... {} {}", k + 1, total, candidates[todo[k]].label,
+            if *needed { "FAILED → USED" } else { "PASSED → UNUSED" },
+            format_timing(baseline_time, *duration));
+        if let Err(e) = checkpoint_scratch_verdict(todo[k], *needed, *duration) {
+            save_error.get_or_insert(e);
+        }
+    })?;
+    if let Some(e) = save_error {
+        return Err(e);
+    }
+    let mut verdicts: Vec<(bool, Duration)> = settled.iter().map(|v| v.unwrap_or((true, Duration::ZERO))).collect();
+    for (k, &i) in todo.iter().enumerate() {
+        verdicts[i] = tested[k];
+    }
+    let merging = merge_order(candidates.len(), &todo);
+    let mut set = LemmaRemovals { candidates, line_shifts, pending: Vec::new() };
+    merge_verdicts(&mut set, &mut MinimizeEnv { codebase, scoped: pool.scope.is_some() }, &merging, &mut verdicts)?;
+    Ok(verdicts)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Module-scoped verification (--scoped)
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// The codebase's `use crate::` module graph, for picking what a candidate
+/// needs verified: the modules it edits and every module that depends on them.
+///
+/// The graph misses dependencies that never name a `crate::` path (a `super::`
+/// import, an item reached through a re-export), so every merge is confirmed
+/// on the whole crate.
+struct ModuleScope {
+    src: PathBuf,
+    graph: ModuleGraph,
+}
+
+impl ModuleScope {
+    fn new(codebase: &Path) -> Result<Self> {
+        let src = codebase.join("src").canonicalize()?;
+        let mut files = Vec::new();
+        for file in find_rust_files(&src) {
+            let rel = file.strip_prefix(&src)?.to_string_lossy().replace('\\', "/");
+            let content = std::fs::read_to_string(&file)?;
+            let deps = analyze_source(&content, &file).crate_deps.into_iter().collect();
+            let node = ModuleNode { module: module_path(&format!("src/{}", rel)), path: rel, verusified: true, ..Default::default() };
+            files.push((node, deps));
+        }
+        Ok(ModuleScope { src, graph: ModuleGraph::new(files) })
+    }
+    
+    /// Module of a file of the real tree, if it is one of the graph's
+    fn module_of(&self, file: &Path) -> Option<String> {
+        let file = file.canonicalize().ok()?;
+        let rel = file.strip_prefix(&self.src).ok()?;
+        let module = module_path(&format!("src/{}", rel.to_string_lossy().replace('\\', "/")));
+        self.graph.nodes.iter().any(|n| n.module == module).then_some(module)
+    }
+    
+    /// Everything that depends on `files`. Editing the crate root, or a file
+    /// outside the graph, needs the whole crate.
+    fn after_editing(&self, files: &[&Path]) -> VerifyScope {
+        let mut modules = Vec::new();
+        for file in files {
+            match self.module_of(file) {
+                Some(m) if !m.is_empty() => modules.push(m),
+                _ => return VerifyScope::Crate,
+            }
+        }
+        let modules: Vec<&str> = modules.iter().map(String::as_str).collect();
+        VerifyScope::Modules(self.graph.dependents(&modules))
+    }
+    
+    /// Emptying lemma bodies leaves their specs alone, so callers need no
+    /// re-verification: just the lemmas' modules, or the lemma itself when
+    /// there is one and it is a free function.
+    fn for_bodies_of(&self, lemmas: &[&ProofFn]) -> VerifyScope {
+        let mut modules = Vec::new();
+        for lemma in lemmas {
+            match self.module_of(&lemma.file) {
+                Some(m) => modules.push(m),
+                None => return VerifyScope::Crate,
+            }
+        }
+        if let ([lemma], [module]) = (lemmas, modules.as_slice()) {
+            if lemma.impl_type.is_none() {
+                return VerifyScope::Function { module: module.clone(), function: lemma.name.clone() };
+            }
+        }
+        modules.sort();
+        modules.dedup();
+        VerifyScope::Modules(modules)
+    }
+}
+
+/// Where the Verus time of a --scoped run went
+#[derive(Default)]
+struct ScopeSavings {
+    /// Scoped Verus runs, the modules they verified, and their time
+    runs: usize,
+    modules: usize,
+    scoped_time: Duration,
+    /// Whole-crate runs that --scoped adds: merge confirmations and re-tests after a failed merge
+    whole_crate_checks: usize,
+    check_time: Duration,
+}
+
+static SCOPE_SAVINGS: std::sync::Mutex<ScopeSavings> = std::sync::Mutex::new(ScopeSavings {
+    runs: 0, modules: 0, scoped_time: Duration::ZERO, whole_crate_checks: 0, check_time: Duration::ZERO,
+});
+
+fn note_scoped_run(modules: usize, duration: Duration) {
+    if let Ok(mut savings) = SCOPE_SAVINGS.lock() {
+        savings.runs += 1;
+        savings.modules += modules;
+        savings.scoped_time += duration;
+    }
+}
+
+fn note_whole_crate_check(duration: Duration) {
+    if let Ok(mut savings) = SCOPE_SAVINGS.lock() {
+        savings.whole_crate_checks += 1;
+        savings.check_time += duration;
+    }
+}
+
+/// Report Verus time saved by --scoped against verifying the whole crate for
+/// every candidate, estimated at the initial full verification's time.
+fn report_scope_savings(scope: &ModuleScope, whole_crate_time: Duration, jobs: usize) {
+    let Ok(savings) = SCOPE_SAVINGS.lock() else {
+        return;
+    };
+    log!();
+    log!("Module-scoped verification (--scoped):");
+    if savings.runs == 0 {
+        log!("  No scoped Verus runs.");
+        return;
+    }
+    let estimate = whole_crate_time * savings.runs as u32;
+    log!("  Scoped runs: {}, verifying {:.1} of {} modules on average",
+        savings.runs, savings.modules as f64 / savings.runs as f64, scope.graph.nodes.len());
+    log!("  Scoped Verus time: {} (whole crate each time: ~{})",
+        format_duration(savings.scoped_time), format_duration(estimate));
+    log!("  Whole-crate checks: {} [{}]", savings.whole_crate_checks, format_duration(savings.check_time));
+    let saved = estimate.saturating_sub(savings.scoped_time + savings.check_time);
+    if saved.is_zero() {
+        log!("  Saved: nothing; the scoped runs plus checks took as long as whole-crate runs would.");
+    } else if jobs > 1 {
+        log!("  Saved: ~{} of Verus time, ~{} wall-clock across {} jobs",
+            format_duration(saved), format_duration(saved / jobs as u32), jobs);
+    } else {
+        log!("  Saved: ~{} wall-clock", format_duration(saved));
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Group testing (-s/--strategy ddmin)
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Asserts or proof blocks, kept with `marker` (e.g. "UNNEEDED assert")
+struct LineRemovals<'a> {
+    candidates: &'a [LineCandidate],
+    marker: &'a str,
+    pending: Vec<(usize, Vec<String>)>,
+}
+
+impl RemovalSet for LineRemovals<'_> {
+    fn label(&self, i: usize) -> &str {
+        &self.candidates[i].label
+    }
+    
+    fn files(&self, idx: &[usize]) -> Vec<&Path> {
+        idx.iter().map(|&i| self.candidates[i].file.as_path()).collect()
+    }
+    
+    fn comment_out(&mut self, idx: &[usize]) -> Result<()> {
+        for &i in idx {
+            let c = &self.candidates[i];
+            let original = comment_out_lines(&c.file, c.start_line, c.end_line, "TESTING")?;
+            self.pending.push((i, original));
+        }
+        Ok(())
+    }
+    
+    fn restore(&mut self) -> Result<()> {
+        // In reverse, so nested candidates (an assert inside an assert-by) unwind cleanly
+        for (i, original) in self.pending.drain(..).rev() {
+            restore_lines(&self.candidates[i].file, self.candidates[i].start_line, &original)?;
+        }
+        Ok(())
+    }
+    
+    fn keep(&mut self) -> Result<()> {
+        let idx: Vec<usize> = self.pending.iter().map(|(i, _)| *i).collect();
+        self.restore()?;
+        for i in idx {
+            let c = &self.candidates[i];
+            comment_out_lines(&c.file, c.start_line, c.end_line, self.marker)?;
+        }
+        Ok(())
+    }
+}
+
+/// Lemma groups, with the usual UNUSED / USED markers and line-shift bookkeeping
+struct LemmaRemovals<'a, 'b> {
+    candidates: &'a [LemmaCandidate<'b>],
+    line_shifts: &'a mut LineShiftTracker,
+    pending: Vec<CommentedGroup<'a>>,
+}
+
+impl RemovalSet for LemmaRemovals<'_, '_> {
+    fn label(&self, i: usize) -> &str {
+        &self.candidates[i].label
+    }
+    
+    fn files(&self, idx: &[usize]) -> Vec<&Path> {
+        idx.iter().flat_map(|&i| self.candidates[i].files()).collect()
+    }
+    
+    fn comment_out(&mut self, idx: &[usize]) -> Result<()> {
+        let candidates = self.candidates;
+        for &i in idx {
+            let group = comment_out_lemma_group(&candidates[i].lemmas, &candidates[i].calls, self.line_shifts)?;
+            self.pending.push(group);
+        }
+        Ok(())
+    }
+    
+    fn restore(&mut self) -> Result<()> {
+        for group in self.pending.drain(..) {
+            restore_lemma_group(&group, self.line_shifts)?;
+        }
+        Ok(())
+    }
+    
+    fn keep(&mut self) -> Result<()> {
+        for group in self.pending.drain(..) {
+            keep_lemma_group_unused(&group, self.line_shifts)?;
+        }
+        Ok(())
+    }
+    
+    fn mark_needed(&mut self, i: usize) -> Result<()> {
+        mark_lemma_group_used(&self.candidates[i].lemmas, self.line_shifts)
+    }
+}
+
+/// Group testing and merges in the real tree: Verus on the whole crate, every test checkpointed
+struct MinimizeEnv<'a> {
+    codebase: &'a Path,
+    /// Count the runs as --scoped's whole-crate checks
+    scoped: bool,
+}
+
+impl SearchEnv for MinimizeEnv<'_> {
+    fn verify(&mut self) -> Result<(bool, Duration)> {
+        let (success, _stderr, verify_time) = run_verus_timed(self.codebase)?;
+        if self.scoped {
+            note_whole_crate_check(verify_time);
+        }
+        Ok((success, verify_time))
+    }
+    
+    fn begin_edit(&mut self, label: &str, files: &[&Path]) -> Result<()> {
+        checkpoint_begin_edit(label, files)
+    }
+    
+    fn settle(&mut self, i: usize, needed: bool, verify_time: Duration) {
+        checkpoint_verdict(i, needed, verify_time);
+    }
+    
+    fn end_edit(&mut self) -> Result<()> {
+        checkpoint_end_edit()
+    }
+    
+    fn merged(&mut self, i: usize) {
+        checkpoint_merged(i);
+    }
+    
+    fn log(&mut self, line: &str) {
+        log!("{}", line);
+    }
+    
+    fn log_start(&mut self, text: &str) {
+        log_no_newline!("{}", text);
+    }
+}
+
+fn minimize_lines_ddmin(
+    candidates: &[LineCandidate],
+    settled: &[Option<(bool, Duration)>],
+    codebase: &Path,
+    baseline_time: Duration,
+    marker: &str,
+) -> Result<Vec<(bool, Duration)>> {
+    let mut set = LineRemovals { candidates, marker, pending: Vec::new() };
+    group_test(&mut set, &mut MinimizeEnv { codebase, scoped: false }, settled, baseline_time)
+}
+
+fn minimize_lemmas_ddmin(
+    candidates: &[LemmaCandidate],
+    settled: &[Option<(bool, Duration)>],
+    codebase: &Path,
+    baseline_time: Duration,
+    line_shifts: &mut LineShiftTracker,
+) -> Result<Vec<(bool, Duration)>> {
+    let mut set = LemmaRemovals { candidates, line_shifts, pending: Vec::new() };
+    group_test(&mut set, &mut MinimizeEnv { codebase, scoped: false }, settled, baseline_time)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// Run state checkpoints (--resume, --recover)
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Phase whose verdicts are also kept as each lemma's LemmaStatus
+const LEMMA_NECESSITY_PHASE: &str = "Phase 8";
+
+/// (verdict, verify_time) per candidate as its phase reports it; None until the test settles
+type Settled = Vec<Option<(bool, Duration)>>;
+
+/// One search phase: its candidates as first found and the verdicts settled so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PhaseState {
+    /// Null for Phases 7 and 8, whose candidates are RunState::groups
+    candidates: serde_json::Value,
+    verdicts: Settled,
+    /// Candidates whose verdict came from a scratch copy and is not merged into the real tree yet
+    #[serde(default)]
+    unmerged: Vec<usize>,
+}
+
+/// What a run needs to carry on from Phase 7 after being interrupted. Saved to
+/// analyses/veracity-minimize-lib.state.json whenever a test settles, together
+/// with the hash of every source file at that point, so a resume can tell
+/// whether anything else has touched the tree since.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RunState {
+    /// Command line, source hashes and any edit in flight
+    #[serde(flatten)]
+    resume: ResumeGuard,
+    initial_duration: Duration,
+    initial_loc: LocCounts,
+    /// Library proof fns as first scanned: UNUSED ones no longer parse once commented out
+    proof_fns: Vec<ProofFn>,
+    /// Phase 7/8 candidates, each variant with its LemmaStatus
+    groups: Vec<((String, PathBuf), Vec<LemmaResult>)>,
+    line_shifts: LineShiftTracker,
+    phases: BTreeMap<String, PhaseState>,
+    /// Phase that verdicts are being recorded for
+    current_phase: Option<String>,
+}
+
+/// The live run state and where it is saved
+struct Checkpoint {
+    path: PathBuf,
+    /// Roots of the hashed source files: the codebase and the library
+    roots: Vec<PathBuf>,
+    state: RunState,
+}
+
+thread_local! {
+    // Thread-local like the log file, so scratch-copy workers never checkpoint
+    static CHECKPOINT: RefCell<Option<Checkpoint>> = const { RefCell::new(None) };
+}
+
+fn run_state_path(codebase: &Path) -> PathBuf {
+    codebase.join("analyses").join("veracity-minimize-lib.state.json")
+}
+
+/// The command line as a run state records it
+fn run_state_args() -> Vec<String> {
+    std::env::args().skip(1)
+        .filter(|a| !matches!(a.as_str(), "--resume" | "--recover" | "--danger"))
+        .collect()
+}
+
+fn load_run_state(codebase: &Path) -> Result<Option<RunState>> {
+    let path = run_state_path(codebase);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)?;
+    let state = serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Unreadable run state {}: {}", path.display(), e))?;
+    Ok(Some(state))
+}
+
+/// Why a saved run cannot be resumed with `args`. Empty if it can.
+fn resume_problems(state: &RunState, args: &MinimizeArgs) -> Result<Vec<String>> {
+    state.resume.problems(&run_state_args(), &[args.codebase.clone(), args.library.clone()])
+}
+
+/// --recover: write back the original content of the files an interrupted
+/// test was editing, so the tree is as it was when that test's run state was saved
+fn recover_run_state(codebase: &Path) -> Result<()> {
+    let Some(mut state) = load_run_state(codebase)? else {
+        log!("No saved run state at {}; nothing to recover.", run_state_path(codebase).display());
+        return Ok(());
+    };
+    match state.resume.in_flight.take() {
+        None => log!("The saved run was not in the middle of an edit; nothing to recover."),
+        Some(edit) => {
+            log!("The run stopped while testing {}. Restoring:", edit.label);
+            edit.restore()?;
+            for (file, _) in &edit.originals {
+                log!("  {}", file.display());
+            }
+            let path = run_state_path(codebase);
+            std::fs::write(&path, serde_json::to_string_pretty(&state)?)?;
+        }
+    }
+    log!();
+    log!("Continue the run by repeating its command with --resume.");
+    Ok(())
+}
+
+/// Start checkpointing `state`, saving it at once
+fn checkpoint_start(args: &MinimizeArgs, state: RunState) -> Result<()> {
+    let checkpoint = Checkpoint {
+        path: run_state_path(&args.codebase),
+        roots: vec![args.codebase.clone(), args.library.clone()],
+        state,
+    };
+    CHECKPOINT.with(|c| *c.borrow_mut() = Some(checkpoint));
+    checkpoint_save()
+}
+
+/// Write the run state, atomically, with fresh source hashes
+fn checkpoint_save() -> Result<()> {
+    CHECKPOINT.with(|c| {
+        let mut guard = c.borrow_mut();
+        let Some(checkpoint) = guard.as_mut() else {
+            return Ok(());
+        };
+        checkpoint.state.resume.record(&checkpoint.roots)?;
+        let tmp = checkpoint.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(&checkpoint.state)?)?;
+        std::fs::rename(&tmp, &checkpoint.path)?;
+        Ok(())
+    })
+}
+
+/// Enter `phase`, recording `discovered` as its candidates. A resumed run gets
+/// back the candidates it saved for the phase, with their settled verdicts.
+fn checkpoint_phase<T: Serialize + DeserializeOwned>(phase: &str, discovered: Vec<T>) -> Result<(Vec<T>, Settled)> {
+    let saved = CHECKPOINT.with(|c| {
+        c.borrow().as_ref().and_then(|cp| cp.state.phases.get(phase).cloned())
+    });
+    let (candidates, verdicts) = match saved {
+        Some(PhaseState { candidates, verdicts, .. }) if !candidates.is_null() => {
+            (serde_json::from_value(candidates)?, verdicts)
+        }
+        _ => {
+            let verdicts = vec![None; discovered.len()];
+            (discovered, verdicts)
+        }
+    };
+    let json = serde_json::to_value(&candidates)?;
+    let verdicts = enter_phase(phase, json, verdicts)?;
+    Ok((candidates, verdicts))
+}
+
+/// Enter Phase 7 or 8, whose `count` candidates are the saved lemma groups
+fn checkpoint_lemma_phase(phase: &str, count: usize) -> Result<Settled> {
+    enter_phase(phase, serde_json::Value::Null, vec![None; count])
+}
+
+fn enter_phase(phase: &str, candidates: serde_json::Value, fresh: Settled) -> Result<Settled> {
+    let verdicts = CHECKPOINT.with(|c| {
+        let mut guard = c.borrow_mut();
+        let Some(checkpoint) = guard.as_mut() else {
+            return fresh;
+        };
+        let state = checkpoint.state.phases.entry(phase.to_string())
+            .or_insert(PhaseState { candidates, verdicts: fresh, unmerged: Vec::new() });
+        checkpoint.state.current_phase = Some(phase.to_string());
+        state.verdicts.clone()
+    });
+    let settled = verdicts.iter().filter(|v| v.is_some()).count();
+    if settled > 0 {
+        log!("Resuming: {} of {} candidates were settled before the run stopped.", settled, verdicts.len());
+        log!();
+    }
+    checkpoint_save()?;
+    Ok(verdicts)
+}
+
+/// Note that a test is about to edit `files`, saving their current content
+/// so --recover can put them back if the run dies before the test settles
+fn checkpoint_begin_edit(label: &str, files: &[&Path]) -> Result<()> {
+    let active = CHECKPOINT.with(|c| c.borrow().is_some());
+    if !active {
+        return Ok(());
+    }
+    let mut originals: Vec<(PathBuf, String)> = Vec::new();
+    for file in files {
+        if !originals.iter().any(|(f, _)| f == file) {
+            originals.push((file.to_path_buf(), std::fs::read_to_string(file)?));
+        }
+    }
+    CHECKPOINT.with(|c| {
+        if let Some(checkpoint) = c.borrow_mut().as_mut() {
+            checkpoint.state.resume.in_flight = Some(InFlightEdit { label: label.to_string(), originals });
+        }
+    });
+    checkpoint_save()
+}
+
+/// The edit has settled: save the verdicts recorded during it with the new file hashes
+fn checkpoint_end_edit() -> Result<()> {
+    CHECKPOINT.with(|c| {
+        if let Some(checkpoint) = c.borrow_mut().as_mut() {
+            checkpoint.state.resume.in_flight = None;
+        }
+    });
+    checkpoint_save()
+}
+
+/// Record candidate `i` of the current phase as settled; saved with the next checkpoint
+fn checkpoint_verdict(i: usize, verdict: bool, verify_time: Duration) {
+    CHECKPOINT.with(|c| {
+        let mut guard = c.borrow_mut();
+        let Some(checkpoint) = guard.as_mut() else {
+            return;
+        };
+        let state = &mut checkpoint.state;
+        let Some(phase) = state.current_phase.clone() else {
+            return;
+        };
+        if let Some(slot) = state.phases.get_mut(&phase).and_then(|p| p.verdicts.get_mut(i)) {
+            *slot = Some((verdict, verify_time));
+        }
+        if phase == LEMMA_NECESSITY_PHASE {
+            if let Some((_, variants)) = state.groups.get_mut(i) {
+                for lr in variants {
+                    lr.status = if verdict { LemmaStatus::Used } else { LemmaStatus::Unused };
+                }
+            }
+        }
+    });
+}
+
+/// Record a verdict from a scratch copy and save it at once. The real tree
+/// catches up when the phase merges; until then the candidate stays unmerged.
+fn checkpoint_scratch_verdict(i: usize, verdict: bool, verify_time: Duration) -> Result<()> {
+    checkpoint_verdict(i, verdict, verify_time);
+    with_current_phase(|phase| {
+        if !phase.unmerged.contains(&i) {
+            phase.unmerged.push(i);
+        }
+    });
+    checkpoint_save()
+}
+
+/// Candidate `i`'s verdict now holds in the real tree; saved with the next checkpoint
+fn checkpoint_merged(i: usize) {
+    with_current_phase(|phase| phase.unmerged.retain(|&u| u != i));
+}
+
+/// Candidates of the current phase with a scratch-copy verdict that was never merged
+fn checkpoint_unmerged() -> Vec<usize> {
+    let mut unmerged = Vec::new();
+    with_current_phase(|phase| unmerged = phase.unmerged.clone());
+    unmerged
+}
+
+fn with_current_phase(f: impl FnOnce(&mut PhaseState)) {
+    CHECKPOINT.with(|c| {
+        if let Some(checkpoint) = c.borrow_mut().as_mut() {
+            let state = &mut checkpoint.state;
+            if let Some(phase) = state.current_phase.as_ref().and_then(|p| state.phases.get_mut(p)) {
+                f(phase);
+            }
+        }
+    });
+}
+
+/// Record the USED-marker insertions made so far; saved with the next checkpoint
+fn checkpoint_line_shifts(line_shifts: &LineShiftTracker) {
+    CHECKPOINT.with(|c| {
+        if let Some(checkpoint) = c.borrow_mut().as_mut() {
+            checkpoint.state.line_shifts = line_shifts.clone();
+        }
+    });
+}
+
+/// The run completed: its state is no longer needed
+fn checkpoint_finish() -> Result<()> {
+    if let Some(checkpoint) = CHECKPOINT.with(|c| c.borrow_mut().take()) {
+        std::fs::remove_file(&checkpoint.path)?;
+    }
+    Ok(())
+}
+
+/// Run single-file mode: just test asserts and proof blocks in one file
+/// Skips all library analysis (phases 2-8)
+fn run_single_file_mode(args: &MinimizeArgs, baseline_time: Duration) -> Result<()> {
+    let start_time = Instant::now();
+    let file = args.single_file.as_ref().unwrap();
+    let rel_path = file.strip_prefix(&args.codebase).unwrap_or(file);
+    
+    log!("Target file: {}", rel_path.display());
+    log!();
+    
+    // Find asserts in the file
+    let asserts = find_asserts_in_file(file)?;
+    log!("Found {} assert statements", asserts.len());
+    
+    // Find proof blocks in the file
+    let proof_blocks = find_proof_blocks_in_file(file)?;
+    log!("Found {} proof {{}} blocks", proof_blocks.len());
+    log!();
+    
+    if asserts.is_empty() && proof_blocks.is_empty() {
+        log!("Nothing to test in this file.");
+        return Ok(());
+    }
+    
+    // Test asserts if enabled
+    if args.assert_minimization && !asserts.is_empty() {
+        log!("═══════════════════════════════════════════════════════════════");
+        log!("Testing asserts");
+        log!("═══════════════════════════════════════════════════════════════");
+        log!();
+        
+        let test_count = match args.max_asserts {
+            Some(n) => asserts.len().min(n),
+            None => asserts.len(),
+        };
+        
+        let mut tested = 0;
+        let mut removed = 0;
+        let mut time_saved = Duration::ZERO;
+        
+        for (i, assert_info) in asserts.iter().take(test_count).enumerate() {
+            log_no_newline!("  [{}/{}] {} at line {} in fn {}... ",
+                i + 1, test_count,
+                assert_info.assert_type,
+                assert_info.line,
+                assert_info.context);
+            
+            if args.dry_run {
+                log!("(dry-run, skipped)");
+                continue;
+            }
+            
+            let (needed, verify_time, saved) = test_assert(assert_info, &args.codebase, baseline_time)?;
+            tested += 1;
+            
+            if needed {
+                log!("NEEDED [{}]", format_duration(verify_time));
+            } else {
+                removed += 1;
+                time_saved += saved;
+                log!("UNNEEDED [{}]", format_duration(verify_time));
             }
             
             if args.fail_fast && !needed {
@@ -3576,9 +4627,52 @@ fn main() -> Result<()> {
     log!("  -P, --max-proof-blocks: {}", args.max_proof_blocks.map(|n| n.to_string()).unwrap_or_else(|| "all".to_string()));
     log!("  -e, --exclude:      {}", if args.exclude_dirs.is_empty() { "(none)".to_string() } else { args.exclude_dirs.join(", ") });
     log!("  -f, --fail-fast:    {}", args.fail_fast);
+    log!("  -j, --jobs:         {}", args.jobs);
+    log!("  -s, --strategy:     {}", args.strategy.name());
+    log!("  --scoped:           {}", args.scoped);
+    log!("  --resume:           {}", args.resume);
+    log!("  --recover:          {}", args.recover);
     log!("  --danger:           {}", args.danger_mode);
     log!();
     
+    if args.recover {
+        return recover_run_state(&args.codebase);
+    }
+    
+    // A saved run state is resumed with --resume, or refused if it was cut off mid-edit
+    let resumed = match load_run_state(&args.codebase)? {
+        Some(state) if args.resume => {
+            let problems = resume_problems(&state, &args)?;
+            if !problems.is_empty() {
+                log!("✗ Cannot resume the saved run ({}):", run_state_path(&args.codebase).display());
+                for problem in &problems {
+                    log!("    {}", problem);
+                }
+                log!();
+                log!("  Exiting. Run without --resume to start over (after --recover, if an edit is in flight).");
+                return Ok(());
+            }
+            log!("Resuming the run saved in {}", run_state_path(&args.codebase).display());
+            log!();
+            Some(state)
+        }
+        Some(state) if state.resume.in_flight.is_some() && !args.dry_run => {
+            log!("✗ A previous run stopped while testing {}.", state.resume.in_flight.as_ref().map_or("", |e| e.label.as_str()));
+            log!("  Run with --recover to restore its files, then --resume (or rerun from scratch).");
+            return Ok(());
+        }
+        Some(_) if !args.dry_run => {
+            log!("Starting over: the saved state of an earlier run will be replaced (use --resume to continue it).");
+            log!();
+            None
+        }
+        None if args.resume => {
+            log!("✗ No saved run state at {}; nothing to resume.", run_state_path(&args.codebase).display());
+            return Ok(());
+        }
+        _ => None,
+    };
+    
     // Print reassurance and phase overview
     log!("═══════════════════════════════════════════════════════════════════════════════");
     log!("IMPORTANT: No code will be harmed in the improving of your codebase!");
@@ -3590,6 +4684,9 @@ fn main() -> Result<()> {
         GitStatus::Clean => {
             log!("✓ Codebase is in git and committed. Proceeding safely.");
         }
+        GitStatus::Uncommitted if resumed.is_some() => {
+            log!("✓ Uncommitted changes are the resumed run's own markers. Proceeding.");
+        }
         GitStatus::Uncommitted => {
             log!("✗ Codebase is in git but has uncommitted changes.");
             log!();
@@ -3679,8 +4776,8 @@ fn main() -> Result<()> {
     log!("═══════════════════════════════════════════════════════════════════════════════");
     log!();
     
-    // Phase 0: Strip existing Veracity markers if any
-    let marker_count = count_veracity_markers(&args.codebase)?;
+    // Phase 0: Strip existing Veracity markers if any (a resumed run keeps its own)
+    let marker_count = if resumed.is_some() { 0 } else { count_veracity_markers(&args.codebase)? };
     if marker_count > 0 {
         log!("═══════════════════════════════════════════════════════════════");
         log!("Phase 0: Stripping existing Veracity markers");
@@ -3700,8 +4797,11 @@ fn main() -> Result<()> {
     log!("═══════════════════════════════════════════════════════════════");
     log!();
     
-    // Count initial LOC (comments not counted)
-    let initial_loc = count_loc(&args.codebase)?;
+    // Count initial LOC (comments not counted); a resumed run compares against its first count
+    let initial_loc = match &resumed {
+        Some(state) => state.initial_loc,
+        None => count_loc(&args.codebase)?,
+    };
     log!("  Initial LOC (comments not counted):");
     log!("    Spec:  {:>6}", initial_loc.spec);
     log!("    Proof: {:>6}", initial_loc.proof);
@@ -3710,9 +4810,11 @@ fn main() -> Result<()> {
     log!();
     
     log!("  Verifying...");
-    let (initial_success, initial_stderr, initial_duration) = run_verus_timed(&args.codebase)?;
+    let (initial_success, initial_stderr, verify_duration) = run_verus_timed(&args.codebase)?;
+    // Timings compare against the first verification of the original run
+    let initial_duration = resumed.as_ref().map_or(verify_duration, |state| state.initial_duration);
     if initial_success {
-        log!("  ✓ Verification passed in {}. Continuing.", format_duration(initial_duration));
+        log!("  ✓ Verification passed in {}. Continuing.", format_duration(verify_duration));
     } else {
         log!("  ✗ Verification failed. Exiting.");
         log!("  Fix verification errors before running Veracity.");
@@ -3742,7 +4844,11 @@ fn main() -> Result<()> {
     log!("Phase 2: Analyzing library structure...");
     
     log!("  Scanning library for proof functions (lemmas)...");
-    let proof_fns = list_library_proof_functions(&args.library)?;
+    let proof_fns = match &resumed {
+        // Lemmas the run has commented out no longer parse, so take the first scan
+        Some(state) => state.proof_fns.clone(),
+        None => list_library_proof_functions(&args.library)?,
+    };
     log!("  Found {} proof functions", proof_fns.len());
     
     let modules: HashSet<String> = proof_fns.iter().map(|pf| pf.module.clone()).collect();
@@ -3763,34 +4869,38 @@ fn main() -> Result<()> {
         }
     }
     
-    log!("  Scanning for lemma call sites...");
     let mut lemma_results: Vec<LemmaResult> = Vec::new();
     let mut total_lib_calls = 0;
     let mut total_codebase_calls = 0;
     
-    for pf in &proof_fns {
-        let module_used = used_modules.contains(&pf.module);
-        let (lib_calls, codebase_calls) = find_call_sites(&pf.name, &args.codebase, &args.library)?;
-        total_lib_calls += lib_calls.len();
-        total_codebase_calls += codebase_calls.len();
-        
-        let status = if !module_used {
-            LemmaStatus::ModuleNotUsed
-        } else {
-            LemmaStatus::Untested
-        };
+    if resumed.is_some() {
+        log!("  Lemma groups and call sites: from the saved run state");
+    } else {
+        log!("  Scanning for lemma call sites...");
+        for pf in &proof_fns {
+            let module_used = used_modules.contains(&pf.module);
+            let (lib_calls, codebase_calls) = find_call_sites(&pf.name, &args.codebase, &args.library)?;
+            total_lib_calls += lib_calls.len();
+            total_codebase_calls += codebase_calls.len();
+        
+            let status = if !module_used {
+                LemmaStatus::ModuleNotUsed
+            } else {
+                LemmaStatus::Untested
+            };
         
-        lemma_results.push(LemmaResult {
-            lemma: pf.clone(),
-            status,
-            call_sites_in_lib: lib_calls,
-            call_sites_in_codebase: codebase_calls,
-            module_used,
-        });
-    }
+            lemma_results.push(LemmaResult {
+                lemma: pf.clone(),
+                status,
+                call_sites_in_lib: lib_calls,
+                call_sites_in_codebase: codebase_calls,
+                module_used,
+            });
+        }
     
-    log!("  {} call sites in library", total_lib_calls);
-    log!("  {} call sites in codebase (outside library)", total_codebase_calls);
+        log!("  {} call sites in library", total_lib_calls);
+        log!("  {} call sites in codebase (outside library)", total_codebase_calls);
+    }
     
     log!("  Scanning library for spec functions...");
     let spec_fns = list_library_spec_functions(&args.library)?;
@@ -3874,6 +4984,11 @@ fn main() -> Result<()> {
         Duration::from_secs(0)
     };
     
+    // With -j, Phases 7-10 test `jobs` candidates at a time
+    let jobs = args.jobs as u32;
+    let (estimated_phase7, estimated_phase8, estimated_phase9, estimated_phase10) =
+        (estimated_phase7 / jobs, estimated_phase8 / jobs, estimated_phase9 / jobs, estimated_phase10 / jobs);
+    
     let estimated_total = estimated_phase1 + estimated_phase2 + estimated_phase3 + 
                           estimated_phase4 + estimated_phase5 + estimated_phase6 + 
                           estimated_phase7 + estimated_phase8 + estimated_phase9 + estimated_phase10;
@@ -3894,6 +5009,9 @@ fn main() -> Result<()> {
     log!("    Phase 7:   verification_time × num_lemmas (empty body test)");
     log!("    Phase 8:   verification_time × num_lemmas (comment out test)");
     log!("    Phase 9/10: verification_time × num_asserts (comment out test)");
+    if args.jobs > 1 {
+        log!("    -j {}:      Phases 7-10 divided by {} (parallel scratch copies)", args.jobs, args.jobs);
+    }
     log!();
     log!("  Phase 1 (verify codebase):       {} (done)", format_duration(estimated_phase1));
     log!("  Phase 2 (analyze library):       ~0s (no verification)");
@@ -3942,7 +5060,9 @@ fn main() -> Result<()> {
         Vec::new()
     };
     
-    if args.apply_lib_broadcasts && !args.dry_run && !lib_recommendations.is_empty() {
+    if args.apply_lib_broadcasts && resumed.is_some() {
+        log!("  Skipped (applied before the resumed run stopped)");
+    } else if args.apply_lib_broadcasts && !args.dry_run && !lib_recommendations.is_empty() {
         log!();
         log!("  ADDING broadcast groups to library files:");
         for rec in &lib_recommendations {
@@ -3980,7 +5100,9 @@ fn main() -> Result<()> {
     log!("  Analyzing broadcast groups per file...");
     let broadcast_recommendations = analyze_broadcast_groups_per_file(&args.codebase, &args.library, &args.exclude_dirs, &broadcast_groups)?;
     
-    if args.update_broadcasts && !args.dry_run && !broadcast_recommendations.is_empty() {
+    if args.update_broadcasts && resumed.is_some() {
+        log!("  Skipped (applied before the resumed run stopped)");
+    } else if args.update_broadcasts && !args.dry_run && !broadcast_recommendations.is_empty() {
         log!();
         log!("  ADDING broadcast groups to codebase files (testing each):");
         log!();
@@ -4234,32 +5356,53 @@ fn main() -> Result<()> {
     let total_start = Instant::now();
     let mut stats = MinimizationStats::default();
     
-    // Group lemmas by (name, file) to handle type variants as a unit
-    let filtered_results: Vec<_> = lemma_results.into_iter()
-        .filter(|lr| lr.module_used)
-        .collect();
+    // A resumed run tests the groups it saved, with the statuses settled so far
+    let sorted_groups: Vec<((String, PathBuf), Vec<LemmaResult>)> = if let Some(state) = &resumed {
+        state.groups.clone()
+    } else {
+        // Group lemmas by (name, file) to handle type variants as a unit
+        let filtered_results: Vec<_> = lemma_results.into_iter()
+            .filter(|lr| lr.module_used)
+            .collect();
     
-    let mut lemma_groups: std::collections::HashMap<(String, PathBuf), Vec<LemmaResult>> = 
-        std::collections::HashMap::new();
-    for lr in filtered_results {
-        let key = (lr.lemma.name.clone(), lr.lemma.file.clone());
-        lemma_groups.entry(key).or_default().push(lr);
-    }
+        let mut lemma_groups: std::collections::HashMap<(String, PathBuf), Vec<LemmaResult>> = 
+            std::collections::HashMap::new();
+        for lr in filtered_results {
+            let key = (lr.lemma.name.clone(), lr.lemma.file.clone());
+            lemma_groups.entry(key).or_default().push(lr);
+        }
     
-    // Convert to sorted vec (by total codebase calls in group)
-    let mut sorted_groups: Vec<_> = lemma_groups.into_iter().collect();
-    sorted_groups.sort_by(|a, b| {
-        let a_calls: usize = a.1.iter().map(|lr| lr.call_sites_in_codebase.len()).sum();
-        let b_calls: usize = b.1.iter().map(|lr| lr.call_sites_in_codebase.len()).sum();
-        a_calls.cmp(&b_calls)
-    });
+        // Convert to sorted vec (by total codebase calls in group)
+        let mut sorted_groups: Vec<_> = lemma_groups.into_iter().collect();
+        sorted_groups.sort_by(|a, b| {
+            let a_calls: usize = a.1.iter().map(|lr| lr.call_sites_in_codebase.len()).sum();
+            let b_calls: usize = b.1.iter().map(|lr| lr.call_sites_in_codebase.len()).sum();
+            a_calls.cmp(&b_calls)
+        });
     
-    // Apply limit if specified (to groups, not individual lemmas)
-    let test_count = match args.max_lemmas {
-        Some(n) => sorted_groups.len().min(n),
-        None => sorted_groups.len(),
+        // Apply limit if specified (to groups, not individual lemmas)
+        let test_count = match args.max_lemmas {
+            Some(n) => sorted_groups.len().min(n),
+            None => sorted_groups.len(),
+        };
+        sorted_groups.into_iter().take(test_count).collect()
+    };
+    
+    // From here on, every test that settles is checkpointed for --resume
+    let (saved_line_shifts, state) = match resumed {
+        Some(state) => (state.line_shifts.clone(), state),
+        None => (LineShiftTracker::new(), RunState {
+            resume: ResumeGuard { args: run_state_args(), ..Default::default() },
+            initial_duration,
+            initial_loc,
+            proof_fns: proof_fns.clone(),
+            groups: sorted_groups.clone(),
+            line_shifts: LineShiftTracker::new(),
+            phases: BTreeMap::new(),
+            current_phase: None,
+        }),
     };
-    let sorted_groups: Vec<_> = sorted_groups.into_iter().take(test_count).collect();
+    checkpoint_start(&args, state)?;
     
     // Track module lemma status
     let mut module_lemma_status: std::collections::HashMap<String, (usize, usize)> = 
@@ -4279,6 +5422,29 @@ fn main() -> Result<()> {
         stats.modules_removable.insert(m.clone());
     }
     
+    // Verus runs per search phase: (phase, candidates, invocations)
+    let mut verus_invocations: Vec<(&str, usize, usize)> = Vec::new();
+    
+    // With -j or --scoped, candidates are tested in scratch copies and merged back into the codebase
+    let pool = if args.jobs > 1 || args.scoped {
+        log!("Creating {} scratch copies of {}...", args.jobs, args.codebase.display());
+        let mut pool = ScratchPool::new(&args.codebase, args.jobs)?;
+        for dir in &pool.dirs {
+            log!("  {}", dir.display());
+        }
+        if args.scoped {
+            // Built after Phase 6, whose broadcast uses are dependencies too
+            let scope = ModuleScope::new(&args.codebase)?;
+            log!("Module graph for --scoped: {} modules, {} use crate:: edges",
+                scope.graph.nodes.len(), scope.graph.edges.len());
+            pool.scope = Some(scope);
+        }
+        log!();
+        Some(pool)
+    } else {
+        None
+    };
+    
     // ═══════════════════════════════════════════════════════════════════════
     // PHASE 7: Test lemma dependence on vstd
     // ═══════════════════════════════════════════════════════════════════════
@@ -4296,49 +5462,71 @@ fn main() -> Result<()> {
     // Track dependent lemmas: (name, file, type_info)
     let mut dependent_lemmas: Vec<(String, PathBuf, String)> = Vec::new();
     
-    for (i, ((name, file), variants)) in sorted_groups.iter().enumerate() {
-        let variant_count = variants.len();
-        let type_info = if variant_count > 1 {
-            let types: Vec<String> = variants.iter()
-                .filter_map(|lr| lr.lemma.impl_type.clone())
-                .collect();
-            if types.is_empty() {
-                format!(" ({} variants)", variant_count)
-            } else {
-                format!("<{}> ({} type variants)", types.join(", "), types.len())
+    let type_infos: Vec<String> = sorted_groups.iter()
+        .map(|(_, variants)| format_group_type_info(variants))
+        .collect();
+    let dependence_verdict = |is_dependent: bool, test_duration: Duration| {
+        format!("{} {}",
+            if is_dependent { "PASSED → DEPENDENT" } else { "FAILED → INDEPENDENT" },
+            format_timing(initial_duration, test_duration))
+    };
+    
+    let settled = checkpoint_lemma_phase("Phase 7", sorted_groups.len())?;
+    let dependence: Vec<(bool, Duration)> = if let Some(pool) = &pool {
+        // Emptying bodies leaves the tree as it was, so there is nothing to merge
+        pool.sync()?;
+        let todo: Vec<usize> = (0..sorted_groups.len()).filter(|&i| settled[i].is_none()).collect();
+        log!("Testing {} lemma groups in {} scratch copies...", todo.len(), pool.dirs.len());
+        log!();
+        let tested = pool.run(&todo, |&i, dir| {
+            let originals: Vec<&ProofFn> = sorted_groups[i].1.iter().map(|lr| &lr.lemma).collect();
+            let lemmas: Vec<ProofFn> = originals.iter().map(|l| pool.rebase_lemma(l, dir)).collect();
+            let lemma_refs: Vec<&ProofFn> = lemmas.iter().collect();
+            test_dependence_group(&lemma_refs, dir, &pool.scope_for_bodies(&originals))
+        }, |k, (is_dependent, test_duration)| {
+            let i = todo[k];
+            log!("[{}/{}] Testing dependence of {}{}... {}", k + 1, todo.len(),
+                sorted_groups[i].0.0, type_infos[i], dependence_verdict(*is_dependent, *test_duration));
+            checkpoint_verdict(i, *is_dependent, *test_duration);
+        })?;
+        checkpoint_save()?;
+        let mut results: Vec<(bool, Duration)> = settled.iter().map(|v| v.unwrap_or((false, Duration::ZERO))).collect();
+        for (k, &i) in todo.iter().enumerate() {
+            results[i] = tested[k];
+        }
+        results
+    } else {
+        let mut results = Vec::new();
+        for (i, ((name, _), variants)) in sorted_groups.iter().enumerate() {
+            if let Some(verdict) = settled[i] {
+                results.push(verdict);
+                continue;
             }
+            log_no_newline!("[{}/{}] Testing dependence of {}{}... ", i + 1, sorted_groups.len(), name, type_infos[i]);
+            log_no_newline!("emptying body... ");
+            log_no_newline!("verifying... ");
+            
+            // Collect all lemmas in this group
+            let group_lemmas: Vec<_> = variants.iter().map(|lr| &lr.lemma).collect();
+            let files: Vec<&Path> = group_lemmas.iter().map(|l| l.file.as_path()).collect();
+            
+            // Test if vstd can prove this lemma with an empty body
+            checkpoint_begin_edit(&format!("dependence of {}", name), &files)?;
+            let (is_dependent, test_duration) = test_dependence_group(&group_lemmas, &args.codebase, &VerifyScope::Crate)?;
+            checkpoint_verdict(i, is_dependent, test_duration);
+            checkpoint_end_edit()?;
+            log!("{}", dependence_verdict(is_dependent, test_duration));
+            results.push((is_dependent, test_duration));
+        }
+        results
+    };
+    
+    for (i, ((name, file), variants)) in sorted_groups.iter().enumerate() {
+        if dependence[i].0 {
+            dependent_count += variants.len();
+            dependent_lemmas.push((name.clone(), file.clone(), type_infos[i].clone()));
         } else {
-            format_lemma_type_info(&variants[0].lemma)
-        };
-        
-        log_no_newline!("[{}/{}] Testing dependence of {}{}... ", i + 1, sorted_groups.len(), name, type_info);
-        log_no_newline!("emptying body... ");
-        log_no_newline!("verifying... ");
-        
-        // Collect all lemmas in this group
-        let group_lemmas: Vec<_> = variants.iter().map(|lr| &lr.lemma).collect();
-        
-        // Test if vstd can prove this lemma with an empty body
-        let (is_dependent, test_duration) = test_dependence_group(&group_lemmas, &args.codebase)?;
-        
-        // Format: [initial N -> now N (incremental N)]
-        let delta_str = if test_duration < initial_duration {
-            format!("incremental -{}", format_duration(initial_duration - test_duration))
-        } else if test_duration > initial_duration {
-            format!("incremental +{}", format_duration(test_duration - initial_duration))
-        } else {
-            "incremental ~0s".to_string()
-        };
-        
-        if is_dependent {
-            log!("PASSED → DEPENDENT [initial {} -> now {} ({})]", 
-                format_duration(initial_duration), format_duration(test_duration), delta_str);
-            dependent_count += variant_count;
-            dependent_lemmas.push((name.clone(), file.clone(), type_info.clone()));
-        } else {
-            log!("FAILED → INDEPENDENT [initial {} -> now {} ({})]", 
-                format_duration(initial_duration), format_duration(test_duration), delta_str);
-            independent_count += variant_count;
+            independent_count += variants.len();
         }
     }
     
@@ -4369,72 +5557,71 @@ fn main() -> Result<()> {
     // Track dependent lemmas that are still needed (DEPENDENT but USED)
     let mut dependent_but_used: Vec<(String, PathBuf, String)> = Vec::new();
     
-    // Track line shifts from USED marker insertions
-    let mut line_shifts = LineShiftTracker::new();
+    // Track line shifts from USED marker insertions (a resumed run's, so far)
+    let mut line_shifts = saved_line_shifts;
     
-    // Test each lemma GROUP (type variants tested together)
-    for (i, ((name, file), variants)) in sorted_groups.iter().enumerate() {
-        let variant_count = variants.len();
-        let type_info = if variant_count > 1 {
-            let types: Vec<String> = variants.iter()
-                .filter_map(|lr| lr.lemma.impl_type.clone())
-                .collect();
-            if types.is_empty() {
-                format!(" ({} variants)", variant_count)
+    // Each lemma GROUP (type variants) is tested together, with the call sites of all variants
+    let candidates: Vec<LemmaCandidate> = sorted_groups.iter().enumerate()
+        .map(|(i, ((name, _), variants))| LemmaCandidate {
+            lemmas: variants.iter().map(|lr| &lr.lemma).collect(),
+            calls: variants.iter().flat_map(|lr| lr.call_sites_in_codebase.iter().cloned()).collect(),
+            label: format!("{}{}", name, type_infos[i]),
+        })
+        .collect();
+    
+    let settled = checkpoint_lemma_phase(LEMMA_NECESSITY_PHASE, candidates.len())?;
+    let runs_before = verus_runs();
+    let necessity: Vec<(bool, Duration)> = if let Some(pool) = &pool {
+        minimize_lemmas_parallel(pool, &candidates, &settled, &args.codebase, initial_duration, &mut line_shifts)?
+    } else if args.strategy == Strategy::Ddmin {
+        minimize_lemmas_ddmin(&candidates, &settled, &args.codebase, initial_duration, &mut line_shifts)?
+    } else {
+        let mut results = Vec::new();
+        for (i, c) in candidates.iter().enumerate() {
+            if let Some(verdict) = settled[i] {
+                results.push(verdict);
+                continue;
+            }
+            log_no_newline!("[{}/{}] Testing necessity of {}... ", i + 1, candidates.len(), c.label);
+            log_no_newline!("commenting out ({} calls)... ", c.calls.len());
+            log_no_newline!("verifying... ");
+            
+            // Test the entire group together
+            checkpoint_begin_edit(&format!("necessity of {}", c.label), &c.files())?;
+            let (needed, test_duration) = test_lemma_group(
+                &c.lemmas,
+                &c.calls,
+                &args.codebase,
+                &mut line_shifts,
+            )?;
+            checkpoint_verdict(i, needed, test_duration);
+            checkpoint_end_edit()?;
+            if needed {
+                log!("FAILED → USED (restored) {}", format_timing(initial_duration, test_duration));
             } else {
-                format!("<{}> ({} type variants)", types.join(", "), types.len())
+                log!("PASSED → UNUSED (kept commented) {}", format_timing(initial_duration, test_duration));
             }
-        } else {
-            format_lemma_type_info(&variants[0].lemma)
-        };
-        
-        // Collect all call sites from all variants
-        let all_call_sites: Vec<_> = variants.iter()
-            .flat_map(|lr| lr.call_sites_in_codebase.iter().cloned())
-            .collect();
-        
-        // Collect all lemmas in this group
-        let group_lemmas: Vec<_> = variants.iter().map(|lr| &lr.lemma).collect();
-        
-        let call_count = all_call_sites.len();
-        log_no_newline!("[{}/{}] Testing necessity of {}{}... ", i + 1, sorted_groups.len(), name, type_info);
-        log_no_newline!("commenting out ({} calls)... ", call_count);
-        log_no_newline!("verifying... ");
-        
-        // Test the entire group together
-        let (needed, test_duration) = test_lemma_group(
-            &group_lemmas,
-            &all_call_sites,
-            &args.codebase,
-            &mut line_shifts,
-        )?;
-        
+            results.push((needed, test_duration));
+        }
+        results
+    };
+    verus_invocations.push(("Phase 8 (lemma necessity)", candidates.len(), verus_runs() - runs_before));
+    
+    for (i, ((name, file), variants)) in sorted_groups.iter().enumerate() {
+        let variant_count = variants.len();
+        let type_info = &type_infos[i];
         stats.lemmas_tested += variant_count;
-        let is_dependent = dependent_names.contains(name);
         
-        // Format: [initial N -> now N (incremental N)]
-        let delta_str = if test_duration < initial_duration {
-            format!("incremental -{}", format_duration(initial_duration - test_duration))
-        } else if test_duration > initial_duration {
-            format!("incremental +{}", format_duration(test_duration - initial_duration))
-        } else {
-            "incremental ~0s".to_string()
-        };
-        
-        if needed {
-            log!("FAILED → USED (restored) [initial {} -> now {} ({})]", 
-                format_duration(initial_duration), format_duration(test_duration), delta_str);
+        if necessity[i].0 {
             stats.lemmas_used += variant_count;
             
             // If this lemma was DEPENDENT but still USED, track it
-            if is_dependent {
+            if dependent_names.contains(name) {
                 dependent_but_used.push((name.clone(), file.clone(), type_info.clone()));
             }
         } else {
-            log!("PASSED → UNUSED (kept commented) [initial {} -> now {} ({})]", 
-                format_duration(initial_duration), format_duration(test_duration), delta_str);
             stats.lemmas_unused += variant_count;
-            stats.call_sites_commented += all_call_sites.len();
+            stats.call_sites_commented += candidates[i].calls.len();
             
             // Track this unused lemma for summary
             unused_lemmas.push((name.clone(), file.clone(), type_info.clone()));
@@ -4488,36 +5675,74 @@ fn main() -> Result<()> {
         let test_count = args.max_asserts.unwrap_or(lib_asserts.len()).min(lib_asserts.len());
         log!("Found {} asserts in library, testing {}", lib_asserts.len(), test_count);
         log!();
-        
-        for (i, assert_info) in lib_asserts.iter().take(test_count).enumerate() {
-            log_no_newline!("[{}/{}] {} L{} in {}... ", 
-                i + 1, test_count,
-                assert_info.assert_type,
-                assert_info.line,
-                assert_info.context);
+        let (lib_asserts, settled) = checkpoint_phase("Phase 9", lib_asserts.into_iter().take(test_count).collect())?;
+        let test_count = lib_asserts.len();
+        
+        let runs_before = verus_runs();
+        if pool.is_some() || args.strategy == Strategy::Ddmin {
+            let mut candidates = Vec::new();
+            for assert_info in lib_asserts.iter().take(test_count) {
+                candidates.push(LineCandidate {
+                    file: assert_info.file.clone(),
+                    start_line: assert_info.line,
+                    end_line: assert_end_line(&assert_info.file, assert_info.line)?,
+                    label: format!("{} L{} in {}", assert_info.assert_type, assert_info.line, assert_info.context),
+                });
+            }
+            let verdicts = match &pool {
+                Some(pool) => minimize_lines_parallel(pool, &candidates, &settled, &args.codebase, baseline_time, "UNNEEDED assert")?,
+                None => minimize_lines_ddmin(&candidates, &settled, &args.codebase, baseline_time, "UNNEEDED assert")?,
+            };
+            for (needed, verify_time) in verdicts {
+                lib_asserts_tested += 1;
+                if !needed {
+                    lib_asserts_removed += 1;
+                    lib_time_saved += baseline_time.saturating_sub(verify_time);
+                }
+            }
+        } else {
+            for (i, assert_info) in lib_asserts.iter().take(test_count).enumerate() {
+                if let Some((needed, verify_time)) = settled[i] {
+                    lib_asserts_tested += 1;
+                    if !needed {
+                        lib_asserts_removed += 1;
+                        lib_time_saved += baseline_time.saturating_sub(verify_time);
+                    }
+                    continue;
+                }
+                log_no_newline!("[{}/{}] {} L{} in {}... ", 
+                    i + 1, test_count,
+                    assert_info.assert_type,
+                    assert_info.line,
+                    assert_info.context);
             
-            let (needed, verify_time, time_saved) = test_assert(assert_info, &args.codebase, baseline_time)?;
-            lib_asserts_tested += 1;
+                checkpoint_begin_edit(&format!("assert L{} in {}", assert_info.line, assert_info.context), &[assert_info.file.as_path()])?;
+                let (needed, verify_time, time_saved) = test_assert(assert_info, &args.codebase, baseline_time)?;
+                checkpoint_verdict(i, needed, verify_time);
+                checkpoint_end_edit()?;
+                lib_asserts_tested += 1;
             
-            // Format: [initial N -> now N (incremental N)]
-            let delta_str = if time_saved > Duration::ZERO {
-                format!("incremental -{}", format_duration(time_saved))
-            } else if verify_time > baseline_time {
-                format!("incremental +{}", format_duration(verify_time - baseline_time))
-            } else {
-                "incremental ~0s".to_string()
-            };
+                // Format: [initial N -> now N (incremental N)]
+                let delta_str = if time_saved > Duration::ZERO {
+                    format!("incremental -{}", format_duration(time_saved))
+                } else if verify_time > baseline_time {
+                    format!("incremental +{}", format_duration(verify_time - baseline_time))
+                } else {
+                    "incremental ~0s".to_string()
+                };
             
-            if needed {
-                log!("NEEDED (restored) [initial {} -> now {} ({})]", 
-                    format_duration(baseline_time), format_duration(verify_time), delta_str);
-            } else {
-                lib_asserts_removed += 1;
-                lib_time_saved += time_saved;
-                log!("UNNEEDED (commented) [initial {} -> now {} ({})]", 
-                    format_duration(baseline_time), format_duration(verify_time), delta_str);
+                if needed {
+                    log!("NEEDED (restored) [initial {} -> now {} ({})]", 
+                        format_duration(baseline_time), format_duration(verify_time), delta_str);
+                } else {
+                    lib_asserts_removed += 1;
+                    lib_time_saved += time_saved;
+                    log!("UNNEEDED (commented) [initial {} -> now {} ({})]", 
+                        format_duration(baseline_time), format_duration(verify_time), delta_str);
+                }
             }
         }
+        verus_invocations.push(("Phase 9 (library asserts)", test_count, verus_runs() - runs_before));
         
         log!();
         log!("Phase 9 Summary: {} tested, {} removed (commented), {} time saved", 
@@ -4559,38 +5784,78 @@ fn main() -> Result<()> {
         let test_count = args.max_asserts.unwrap_or(codebase_asserts.len()).min(codebase_asserts.len());
         log!("Found {} asserts in codebase, testing {}", codebase_asserts.len(), test_count);
         log!();
-        
-        for (i, assert_info) in codebase_asserts.iter().take(test_count).enumerate() {
-            let rel_path = assert_info.file.strip_prefix(&args.codebase).unwrap_or(&assert_info.file);
-            log_no_newline!("[{}/{}] {} L{} in {} ({})... ", 
-                i + 1, test_count,
-                assert_info.assert_type,
-                assert_info.line,
-                assert_info.context,
-                rel_path.display());
+        let (codebase_asserts, settled) = checkpoint_phase("Phase 10", codebase_asserts.into_iter().take(test_count).collect())?;
+        let test_count = codebase_asserts.len();
+        
+        let runs_before = verus_runs();
+        if pool.is_some() || args.strategy == Strategy::Ddmin {
+            let mut candidates = Vec::new();
+            for assert_info in codebase_asserts.iter().take(test_count) {
+                let rel_path = assert_info.file.strip_prefix(&args.codebase).unwrap_or(&assert_info.file);
+                candidates.push(LineCandidate {
+                    file: assert_info.file.clone(),
+                    start_line: assert_info.line,
+                    end_line: assert_end_line(&assert_info.file, assert_info.line)?,
+                    label: format!("{} L{} in {} ({})", assert_info.assert_type, assert_info.line,
+                        assert_info.context, rel_path.display()),
+                });
+            }
+            let verdicts = match &pool {
+                Some(pool) => minimize_lines_parallel(pool, &candidates, &settled, &args.codebase, baseline_time, "UNNEEDED assert")?,
+                None => minimize_lines_ddmin(&candidates, &settled, &args.codebase, baseline_time, "UNNEEDED assert")?,
+            };
+            for (needed, verify_time) in verdicts {
+                codebase_asserts_tested += 1;
+                if !needed {
+                    codebase_asserts_removed += 1;
+                    codebase_time_saved += baseline_time.saturating_sub(verify_time);
+                }
+            }
+        } else {
+            for (i, assert_info) in codebase_asserts.iter().take(test_count).enumerate() {
+                if let Some((needed, verify_time)) = settled[i] {
+                    codebase_asserts_tested += 1;
+                    if !needed {
+                        codebase_asserts_removed += 1;
+                        codebase_time_saved += baseline_time.saturating_sub(verify_time);
+                    }
+                    continue;
+                }
+                let rel_path = assert_info.file.strip_prefix(&args.codebase).unwrap_or(&assert_info.file);
+                log_no_newline!("[{}/{}] {} L{} in {} ({})... ", 
+                    i + 1, test_count,
+                    assert_info.assert_type,
+                    assert_info.line,
+                    assert_info.context,
+                    rel_path.display());
             
-            let (needed, verify_time, time_saved) = test_assert(assert_info, &args.codebase, baseline_time)?;
-            codebase_asserts_tested += 1;
+                checkpoint_begin_edit(&format!("assert L{} in {}", assert_info.line, assert_info.context), &[assert_info.file.as_path()])?;
+                let (needed, verify_time, time_saved) = test_assert(assert_info, &args.codebase, baseline_time)?;
+                checkpoint_verdict(i, needed, verify_time);
+                checkpoint_end_edit()?;
+                codebase_asserts_tested += 1;
             
-            // Format: [initial N -> now N (incremental N)]
-            let delta_str = if time_saved > Duration::ZERO {
-                format!("incremental -{}", format_duration(time_saved))
-            } else if verify_time > baseline_time {
-                format!("incremental +{}", format_duration(verify_time - baseline_time))
-            } else {
-                "incremental ~0s".to_string()
-            };
+                // Format: [initial N -> now N (incremental N)]
+                let delta_str = if time_saved > Duration::ZERO {
+                    format!("incremental -{}", format_duration(time_saved))
+                } else if verify_time > baseline_time {
+                    format!("incremental +{}", format_duration(verify_time - baseline_time))
+                } else {
+                    "incremental ~0s".to_string()
+                };
             
-            if needed {
-                log!("NEEDED (restored) [initial {} -> now {} ({})]", 
-                    format_duration(baseline_time), format_duration(verify_time), delta_str);
-            } else {
-                codebase_asserts_removed += 1;
-                codebase_time_saved += time_saved;
-                log!("UNNEEDED (commented) [initial {} -> now {} ({})]", 
-                    format_duration(baseline_time), format_duration(verify_time), delta_str);
+                if needed {
+                    log!("NEEDED (restored) [initial {} -> now {} ({})]", 
+                        format_duration(baseline_time), format_duration(verify_time), delta_str);
+                } else {
+                    codebase_asserts_removed += 1;
+                    codebase_time_saved += time_saved;
+                    log!("UNNEEDED (commented) [initial {} -> now {} ({})]", 
+                        format_duration(baseline_time), format_duration(verify_time), delta_str);
+                }
             }
         }
+        verus_invocations.push(("Phase 10 (codebase asserts)", test_count, verus_runs() - runs_before));
         
         log!();
         log!("Phase 10 Summary: {} tested, {} removed (commented), {} time saved", 
@@ -4637,12 +5902,25 @@ fn main() -> Result<()> {
         let test_count = args.max_admits.unwrap_or(all_admits.len()).min(all_admits.len());
         log!("Found {} admits, testing {}", all_admits.len(), test_count);
         log!();
+        let (all_admits, settled) = checkpoint_phase("Phase 11", all_admits.into_iter().take(test_count).collect())?;
+        let test_count = all_admits.len();
         
         let mut admits_tested = 0;
         let mut admits_removed = 0;
         let mut time_saved = Duration::ZERO;
         
         for (i, admit_info) in all_admits.iter().take(test_count).enumerate() {
+            if let Some((needed, verify_time)) = settled[i] {
+                admits_tested += 1;
+                if !needed {
+                    admits_removed += 1;
+                    time_saved += baseline_time.saturating_sub(verify_time);
+                }
+                if args.fail_fast && !needed {
+                    break;
+                }
+                continue;
+            }
             let rel_path = admit_info.file.strip_prefix(&args.codebase).unwrap_or(&admit_info.file);
             log_no_newline!("[{}/{}] admit L{} in {} ({})... ",
                 i + 1, test_count,
@@ -4655,7 +5933,10 @@ fn main() -> Result<()> {
                 continue;
             }
             
+            checkpoint_begin_edit(&format!("admit L{} in {}", admit_info.line, admit_info.context), &[admit_info.file.as_path()])?;
             let (needed, verify_time, saved) = test_admit(admit_info, &args.codebase, baseline_time)?;
+            checkpoint_verdict(i, needed, verify_time);
+            checkpoint_end_edit()?;
             admits_tested += 1;
             
             let time_diff = verify_time.as_secs_f64() - baseline_time.as_secs_f64();
@@ -4743,42 +6024,86 @@ fn main() -> Result<()> {
                     String::new() 
                 });
             log!();
+            let (all_proof_blocks, settled) = checkpoint_phase("Phase 12", all_proof_blocks.into_iter().take(test_count).collect())?;
+            let test_count = all_proof_blocks.len();
             
             let baseline_time = initial_duration;
             let mut blocks_tested = 0;
             let mut blocks_removed = 0;
             let mut time_saved = Duration::ZERO;
             
-            for (i, block) in all_proof_blocks.iter().take(test_count).enumerate() {
-                let rel_path = block.file.strip_prefix(&args.codebase).unwrap_or(&block.file);
-                log_no_newline!("  [{}/{}] Testing proof block at {}:{}-{} in fn {}... ",
-                    i + 1, test_count,
-                    rel_path.display(),
-                    block.start_line,
-                    block.end_line,
-                    block.context);
-                
-                if args.dry_run {
-                    log!("(dry-run, skipped)");
-                    continue;
+            let runs_before = verus_runs();
+            if pool.is_some() || args.strategy == Strategy::Ddmin {
+                let candidates: Vec<LineCandidate> = all_proof_blocks.iter().take(test_count)
+                    .map(|block| {
+                        let rel_path = block.file.strip_prefix(&args.codebase).unwrap_or(&block.file);
+                        LineCandidate {
+                            file: block.file.clone(),
+                            start_line: block.start_line,
+                            end_line: block.end_line,
+                            label: format!("Testing proof block at {}:{}-{} in fn {}",
+                                rel_path.display(), block.start_line, block.end_line, block.context),
+                        }
+                    })
+                    .collect();
+                let verdicts = match &pool {
+                    Some(pool) => minimize_lines_parallel(pool, &candidates, &settled, &args.codebase, baseline_time, "UNNEEDED proof block")?,
+                    None => minimize_lines_ddmin(&candidates, &settled, &args.codebase, baseline_time, "UNNEEDED proof block")?,
+                };
+                for (needed, verify_time) in verdicts {
+                    blocks_tested += 1;
+                    if !needed {
+                        blocks_removed += 1;
+                        time_saved += baseline_time.saturating_sub(verify_time);
+                    }
                 }
+            } else {
+                for (i, block) in all_proof_blocks.iter().take(test_count).enumerate() {
+                    if let Some((needed, verify_time)) = settled[i] {
+                        blocks_tested += 1;
+                        if !needed {
+                            blocks_removed += 1;
+                            time_saved += baseline_time.saturating_sub(verify_time);
+                        }
+                        if args.fail_fast && !needed {
+                            break;
+                        }
+                        continue;
+                    }
+                    let rel_path = block.file.strip_prefix(&args.codebase).unwrap_or(&block.file);
+                    log_no_newline!("  [{}/{}] Testing proof block at {}:{}-{} in fn {}... ",
+                        i + 1, test_count,
+                        rel_path.display(),
+                        block.start_line,
+                        block.end_line,
+                        block.context);
                 
-                let (needed, verify_time, saved) = test_proof_block(block, &args.codebase, baseline_time)?;
-                blocks_tested += 1;
+                    if args.dry_run {
+                        log!("(dry-run, skipped)");
+                        continue;
+                    }
                 
-                if needed {
-                    log!("NEEDED (restored) [{}]", format_duration(verify_time));
-                } else {
-                    blocks_removed += 1;
-                    time_saved += saved;
-                    log!("UNNEEDED (commented) [{}]", format_duration(verify_time));
-                }
+                    checkpoint_begin_edit(&format!("proof block L{} in {}", block.start_line, block.context), &[block.file.as_path()])?;
+                    let (needed, verify_time, saved) = test_proof_block(block, &args.codebase, baseline_time)?;
+                    checkpoint_verdict(i, needed, verify_time);
+                    checkpoint_end_edit()?;
+                    blocks_tested += 1;
                 
-                if args.fail_fast && !needed {
-                    log!("  (fail-fast: stopping after first unneeded proof block)");
-                    break;
+                    if needed {
+                        log!("NEEDED (restored) [{}]", format_duration(verify_time));
+                    } else {
+                        blocks_removed += 1;
+                        time_saved += saved;
+                        log!("UNNEEDED (commented) [{}]", format_duration(verify_time));
+                    }
+                
+                    if args.fail_fast && !needed {
+                        log!("  (fail-fast: stopping after first unneeded proof block)");
+                        break;
+                    }
                 }
             }
+            verus_invocations.push(("Phase 12 (proof blocks)", blocks_tested, verus_runs() - runs_before));
             
             log!();
             log!("Phase 12 Summary: {} tested, {} removed (commented), {} time saved", 
@@ -4841,12 +6166,26 @@ fn main() -> Result<()> {
                     String::new() 
                 });
             log!();
+            let (all_types, settled) = checkpoint_phase("Phase 13", all_types.into_iter().take(test_count).collect())?;
+            let test_count = all_types.len();
             
             let mut types_tested = 0;
             let mut types_unused = 0;
             let mut types_used = 0;
             
             for (i, type_info) in all_types.iter().take(test_count).enumerate() {
+                if let Some((used, _)) = settled[i] {
+                    types_tested += 1;
+                    if used {
+                        types_used += 1;
+                    } else {
+                        types_unused += 1;
+                    }
+                    if args.fail_fast && !used {
+                        break;
+                    }
+                    continue;
+                }
                 let pub_str = if type_info.is_pub { "pub " } else { "" };
                 log_no_newline!("  [{}/{}] Testing {}{} {}... ",
                     i + 1, test_count,
@@ -4857,7 +6196,10 @@ fn main() -> Result<()> {
                     continue;
                 }
                 
+                checkpoint_begin_edit(&format!("{} {}", type_info.kind, type_info.name), &[type_info.file.as_path()])?;
                 let (used, verify_time) = test_type(type_info, &args.codebase)?;
+                checkpoint_verdict(i, used, verify_time);
+                checkpoint_end_edit()?;
                 types_tested += 1;
                 
                 if used {
@@ -5001,6 +6343,27 @@ fn main() -> Result<()> {
         log!("Call sites commented: {}", stats.call_sites_commented);
     }
     log!();
+    let strategy = if args.jobs > 1 { format!("{} jobs", args.jobs) } else { args.strategy.name().to_string() };
+    log!("Verus invocations ({}; one-by-one needs one per candidate):", strategy);
+    for (phase, candidates, invocations) in &verus_invocations {
+        log!("  {:<28} {:>5} for {:>5} candidates", phase, invocations, candidates);
+    }
+    log!("  {:<28} {:>5}", "Whole run", verus_runs());
+    if let Some(scope) = pool.as_ref().and_then(|p| p.scope.as_ref()) {
+        report_scope_savings(scope, initial_duration, args.jobs);
+    }
+    log!();
+    let breakages = breakages();
+    if !breakages.is_empty() {
+        log!("Failed runs that broke the code rather than a proof: {} (their candidates were kept)", breakages.len());
+        for summary in breakages.iter().take(10) {
+            log!("  {}", summary);
+        }
+        if breakages.len() > 10 {
+            log!("  ... and {} more", breakages.len() - 10);
+        }
+        log!();
+    }
     
     // Calculate max filename width for alignment across all lemma tables
     let max_file_width = dependent_lemmas.iter()
@@ -5139,5 +6502,8 @@ fn main() -> Result<()> {
         log!("  You may need to restore some lemmas manually.");
     }
     
+    // Nothing left to resume
+    checkpoint_finish()?;
+    
     Ok(())
 }
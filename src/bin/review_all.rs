@@ -0,0 +1,98 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Run the review analyzers in one pass
+//!
+//! Reads and parses each file once (see `veracity::driver`), runs the
+//! requires/ensures, termination, trigger-pattern, invariant and proof-state reviews on it,
+//! files in parallel, and prints one combined report.
+//!
+//! Usage:
+//!   veracity-review-all -c
+//!   veracity-review-all -d src/ --only termination,invariants
+//!   veracity-review-all -c --json > review.json
+//!
+//! Binary: veracity-review-all
+
+use anyhow::Result;
+use std::time::Instant;
+use veracity::analyzers;
+use veracity::driver::DriverReport;
+use veracity::{StandardArgs, find_rust_files};
+
+/// Flags this tool adds to the standard ones
+struct ReviewAllOptions {
+    json: bool,
+    only: Vec<String>,
+}
+
+impl ReviewAllOptions {
+    fn parse() -> Result<Self> {
+        let args: Vec<String> = std::env::args().collect();
+        let mut options = ReviewAllOptions { json: false, only: Vec::new() };
+        let mut i = 1;
+        while i < args.len() {
+            match args[i].as_str() {
+                "--json" => options.json = true,
+                "--only" => {
+                    i += 1;
+                    let list = args.get(i)
+                        .ok_or_else(|| anyhow::anyhow!("--only requires a comma-separated list of analyzers"))?;
+                    options.only = list.split(',').map(|s| s.trim().to_string()).collect();
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        Ok(options)
+    }
+}
+
+fn print_report(report: &DriverReport) {
+    for analyzer in &report.analyzers {
+        println!("\n=== {} ===", analyzer.name);
+        for f in &analyzer.findings {
+            println!("{}:{}: {}: {}", f.path.display(), f.line, f.severity.as_str(), f.message);
+        }
+        if !analyzer.findings.is_empty() {
+            println!();
+        }
+        for (name, n) in &analyzer.counts {
+            println!("  {}: {}", name, n);
+        }
+        println!("  findings: {}", analyzer.findings.len());
+    }
+}
+
+fn main() -> Result<()> {
+    let options = ReviewAllOptions::parse()?;
+    let args = StandardArgs::parse()?;
+    let paths = args.get_search_dirs();
+    let all_files = args.without_excluded(find_rust_files(&paths));
+    let driver = analyzers::driver_for(&options.only)?.with_config(args.config.clone());
+
+    let start = Instant::now();
+    if !options.json {
+        println!("Reviewing {} files with {}...", all_files.len(), driver.analyzer_names().join(", "));
+    }
+
+    let report = driver.run(&all_files);
+
+    for (path, e) in &report.errors {
+        eprintln!("Error analyzing {}: {}", path.display(), e);
+    }
+
+    if options.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    print_report(&report);
+    println!("\n=== Summary ===");
+    println!("Files: {} ({} with verus!)", report.files, report.verus_files);
+    let total: usize = report.analyzers.iter().map(|a| a.findings.len()).sum();
+    println!("Findings: {}", total);
+    println!("Completed in {}ms", start.elapsed().as_millis());
+
+    Ok(())
+}
@@ -205,7 +205,7 @@ fn print_results(all_stats: &[(std::path::PathBuf, FileStats)]) {
 fn main() -> Result<()> {
     let args = StandardArgs::parse()?;
     let paths = args.get_search_dirs();
-    let all_files = find_rust_files(&paths);
+    let all_files = args.without_excluded(find_rust_files(&paths));
     
     println!("Checking generic equality usage in {} files...\n", all_files.len());
     
@@ -192,7 +192,7 @@ fn print_summary(all_stats: &[FileStats]) {
 fn main() -> Result<()> {
     let args = StandardArgs::parse()?;
     let paths = args.get_search_dirs();
-    let all_files = find_rust_files(&paths);
+    let all_files = args.without_excluded(find_rust_files(&paths));
     
     println!("Checking ghost/tracked naming in {} files...\n", all_files.len());
     
@@ -14,143 +14,13 @@
 //! Binary: veracity-review-invariants
 
 use anyhow::Result;
-use ra_ap_syntax::{ast, AstNode, SyntaxKind, SyntaxNode};
 use std::path::Path;
+use veracity::analyzers::invariants::{self, FileStats};
+use veracity::driver::ParsedFile;
 use veracity::{StandardArgs, find_rust_files};
 
-#[derive(Default, Debug)]
-struct FileStats {
-    loops_with_invariant: usize,
-    loops_without_invariant: usize,
-    structs_with_invariant: usize,
-    structs_without_invariant: usize,
-    enums_with_invariant: usize,
-    enums_without_invariant: usize,
-}
-
 fn analyze_file(path: &Path) -> Result<FileStats> {
-    let content = std::fs::read_to_string(path)?;
-    let parsed = ra_ap_syntax::SourceFile::parse(&content, ra_ap_syntax::Edition::Edition2021);
-    let tree = parsed.tree();
-    let root = tree.syntax();
-    
-    let mut stats = FileStats::default();
-    
-    // Find verus! macros
-    for node in root.descendants() {
-        if node.kind() == SyntaxKind::MACRO_CALL {
-            if let Some(macro_call) = ast::MacroCall::cast(node.clone()) {
-                if let Some(path) = macro_call.path() {
-                    let path_str = path.to_string();
-                    if path_str == "verus" || path_str == "verus_" {
-                        if let Some(token_tree) = macro_call.token_tree() {
-                            analyze_verus_macro(token_tree.syntax(), &mut stats);
-                        }
-                    }
-                }
-            }
-        }
-    }
-    
-    Ok(stats)
-}
-
-fn analyze_verus_macro(tree: &SyntaxNode, stats: &mut FileStats) {
-    let tokens: Vec<_> = tree.descendants_with_tokens()
-        .filter_map(|n| n.into_token())
-        .collect();
-    
-    let mut i = 0;
-    while i < tokens.len() {
-        let token = &tokens[i];
-        
-        // Look for while loops
-        if token.kind() == SyntaxKind::WHILE_KW {
-            let has_inv = check_loop_invariant_at(&tokens, i);
-            if has_inv {
-                stats.loops_with_invariant += 1;
-            } else {
-                stats.loops_without_invariant += 1;
-            }
-        }
-        
-        // Look for structs
-        if token.kind() == SyntaxKind::STRUCT_KW {
-            let has_inv = check_struct_invariant_at(&tokens, i);
-            if has_inv {
-                stats.structs_with_invariant += 1;
-            } else {
-                stats.structs_without_invariant += 1;
-            }
-        }
-        
-        // Look for enums
-        if token.kind() == SyntaxKind::ENUM_KW {
-            let has_inv = check_struct_invariant_at(&tokens, i);
-            if has_inv {
-                stats.enums_with_invariant += 1;
-            } else {
-                stats.enums_without_invariant += 1;
-            }
-        }
-        
-        i += 1;
-    }
-}
-
-fn check_loop_invariant_at(tokens: &[ra_ap_syntax::SyntaxToken], while_idx: usize) -> bool {
-    // Scan forward from while to find the opening brace
-    let mut i = while_idx;
-    
-    while i < tokens.len() {
-        if tokens[i].kind() == SyntaxKind::L_CURLY {
-            // Found opening brace, look for invariant within first few tokens of loop body
-            let mut j = i + 1;
-            let mut check_count = 0;
-            while j < tokens.len() && check_count < 20 {
-                if tokens[j].kind() == SyntaxKind::IDENT && tokens[j].text() == "invariant" {
-                    return true;
-                }
-                j += 1;
-                check_count += 1;
-            }
-            return false;
-        }
-        i += 1;
-    }
-    
-    false
-}
-
-fn check_struct_invariant_at(tokens: &[ra_ap_syntax::SyntaxToken], struct_idx: usize) -> bool {
-    // Scan the entire struct/enum definition for "invariant" keyword
-    let mut i = struct_idx;
-    let mut brace_depth = 0;
-    let mut found_opening_brace = false;
-    
-    while i < tokens.len() {
-        match tokens[i].kind() {
-            SyntaxKind::L_CURLY => {
-                brace_depth += 1;
-                found_opening_brace = true;
-            }
-            SyntaxKind::R_CURLY => {
-                brace_depth -= 1;
-                if found_opening_brace && brace_depth == 0 {
-                    return false; // End of struct/enum without invariant
-                }
-            }
-            SyntaxKind::IDENT => {
-                if tokens[i].text() == "invariant" {
-                    return true;
-                }
-            }
-            _ => {}
-        }
-        i += 1;
-    }
-    
-    false
+    Ok(invariants::analyze(&ParsedFile::read(path)?))
 }
 
 fn print_file_report(path: &Path, stats: &FileStats) {
@@ -235,7 +105,7 @@ fn print_summary(all_stats: &[FileStats]) {
 fn main() -> Result<()> {
     let args = StandardArgs::parse()?;
     let paths = args.get_search_dirs();
-    let all_files = find_rust_files(&paths);
+    let all_files = args.without_excluded(find_rust_files(&paths));
     
     println!("Checking invariant coverage in {} files...\n", all_files.len());
     
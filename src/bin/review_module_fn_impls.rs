@@ -6,11 +6,13 @@
 //! Generates a markdown report listing all functions per module,
 //! their context (trait, impl-trait, impl-struct, module-level),
 //! whether they're inside verus!, and their specification strength.
+//! Modules are grouped by veracity.toml's `[grouping]` (chapters by default).
 //!
 //! Usage:
 //!   veracity-review-module-fn-impls -d src/Chap18           # generate .md
 //!   veracity-review-module-fn-impls -f src/Chap18/ArraySeq.rs
 //!   veracity-review-module-fn-impls -i eq -i hash           # ignore specific functions
+//!   veracity-review-module-fn-impls --no-cache -d src       # re-analyze every file
 //!   veracity-review-module-fn-impls --extract PATH.md       # extract specs → .json
 //!   veracity-review-module-fn-impls --patch PATH.md PATH.json  # patch SpecStr from .json
 //!
@@ -25,6 +27,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use veracity::cache::AnalysisCache;
+use veracity::grouping::Grouping;
 use veracity::{find_rust_files, StandardArgs};
 
 // ── Data structures ─────────────────────────────────────────────────────
@@ -38,7 +42,7 @@ enum FnLocation {
     ModuleLevel,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 enum SpecStrength {
     Unknown, // has requires/ensures — strength not assessed
     Hole,    // body contains assume(), admit(), or fn has #[verifier::external_body]
@@ -61,7 +65,7 @@ struct FnInfo {
     end_line: usize,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct FnRecord {
     name: String,
     in_trait: bool,
@@ -75,8 +79,12 @@ struct FnRecord {
     end_line: usize,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 struct ModuleAnalysis {
     directory: String,
+    /// Group in the configured hierarchy; set per run, not cached
+    #[serde(skip)]
+    group: String,
     file_stem: String,
     functions: Vec<FnRecord>,
 }
@@ -114,12 +122,17 @@ fn main() -> Result<()> {
         return cmd_patch(&md_path, &json_path);
     }
 
-    // Parse -i/--ignore-fn before StandardArgs.
+    // Parse -i/--ignore-fn and --no-cache before StandardArgs.
     let mut ignore_fns: HashSet<String> = HashSet::new();
     let mut has_explicit_ignore = false;
+    let mut use_cache = true;
     let mut i = 1;
     while i < raw_args.len() {
         match raw_args[i].as_str() {
+            "--no-cache" => {
+                use_cache = false;
+                i += 1;
+            }
             "-i" | "--ignore-fn" => {
                 has_explicit_ignore = true;
                 if i + 1 < raw_args.len() {
@@ -142,7 +155,7 @@ fn main() -> Result<()> {
         }
     }
 
-    let filter = FnFilter { ignore_fns };
+    let filter = FnFilter { ignore_fns, use_cache };
 
     // Print active filter.
     if !filter.ignore_fns.is_empty() {
@@ -157,6 +170,8 @@ fn main() -> Result<()> {
 
 struct FnFilter {
     ignore_fns: HashSet<String>,
+    /// Reuse per-file fn rows from analyses/.cache/
+    use_cache: bool,
 }
 
 impl FnFilter {
@@ -168,7 +183,7 @@ impl FnFilter {
 fn cmd_generate(filter: FnFilter) -> Result<()> {
     let args = StandardArgs::parse()?;
     let paths = args.get_search_dirs();
-    let all_files = find_rust_files(&paths);
+    let all_files = args.without_excluded(find_rust_files(&paths));
 
     if all_files.is_empty() {
         eprintln!("No Rust files found.");
@@ -177,17 +192,30 @@ fn cmd_generate(filter: FnFilter) -> Result<()> {
 
     let base_dir = args.base_dir();
     let project_root = find_project_root(&base_dir);
+    let grouping = Grouping::new(&args.config)?;
 
     let mut analyses = Vec::new();
     let mut total_source_bytes: usize = 0;
 
+    // Fn rows come from the file alone, so entries carry no deps; the ignore filter applies after.
+    let mut cache: AnalysisCache<ModuleAnalysis> = if filter.use_cache {
+        AnalysisCache::load(&project_root, "veracity-review-module-fn-impls", "")
+    } else {
+        AnalysisCache::disabled()
+    };
+
     for file in &all_files {
         if let Ok(meta) = fs::metadata(file) {
             total_source_bytes += meta.len() as usize;
         }
-        match analyze_file(file) {
+        let analyzed = match cache.get(file) {
+            Some(analysis) => Ok(analysis),
+            None => analyze_file(file).inspect(|analysis| cache.insert(file, &[], analysis.clone())),
+        };
+        match analyzed {
             Ok(mut analysis) => {
                 analysis.functions.retain(|f| filter.keep(&f.name));
+                analysis.group = grouping.group_of_path(file).unwrap_or_else(|| analysis.directory.clone());
                 if !analysis.functions.is_empty() {
                     analyses.push(analysis);
                 }
@@ -198,19 +226,27 @@ fn cmd_generate(filter: FnFilter) -> Result<()> {
         }
     }
 
+    if let Err(e) = cache.save() {
+        eprintln!("warning: could not save analysis cache: {:#}", e);
+    }
+    if cache.hits > 0 {
+        eprintln!("Cache: {} unchanged files reused, {} analyzed", cache.hits, cache.misses);
+    }
+
     if analyses.is_empty() {
         eprintln!("No functions found in the analyzed files.");
         return Ok(());
     }
 
-    // Sort by directory first, then by file name.
+    // Sort by group (chapters in numeric order), then directory, then file name.
     analyses.sort_by(|a, b| {
-        a.directory
-            .cmp(&b.directory)
+        grouping
+            .compare(&a.group, &b.group)
+            .then(a.directory.cmp(&b.directory))
             .then(a.file_stem.cmp(&b.file_stem))
     });
 
-    let markdown = generate_markdown(&analyses);
+    let markdown = generate_markdown(&analyses, grouping.label());
 
     // Write to analyses/ directory at the project level
     let analyses_dir = project_root.join("analyses");
@@ -525,6 +561,7 @@ fn analyze_file(path: &Path) -> Result<ModuleAnalysis> {
 
     Ok(ModuleAnalysis {
         directory,
+        group: String::new(),
         file_stem,
         functions: records,
     })
@@ -1247,7 +1284,7 @@ fn merge_spec_strength(a: &SpecStrength, b: &SpecStrength) -> SpecStrength {
 
 // ── Markdown output ─────────────────────────────────────────────────────
 
-fn generate_markdown(analyses: &[ModuleAnalysis]) -> String {
+fn generate_markdown(analyses: &[ModuleAnalysis], label: &str) -> String {
     let mut md = String::new();
 
     md.push_str("<style>\n");
@@ -1270,12 +1307,14 @@ fn generate_markdown(analyses: &[ModuleAnalysis]) -> String {
     md.push_str("| Unk | has requires/ensures (strength not assessed) |\n");
     md.push_str("| Hole | contains `assume()`, `admit()`, or `#[verifier::external_body]` |\n");
     md.push_str("| NoSpec | no spec |\n\n");
-    md.push_str(
-        "| # | Dir | Module | Tr | IT | IBI | ML | V! | -V! | Unk | Hole | NoSpec |\n",
-    );
-    md.push_str(
-        "|---|-----|--------|:--:|:--:|:---:|:--:|:--:|:---:|:---:|:----:|:------:|\n",
-    );
+    md.push_str(&format!(
+        "| # | {} | Module | Tr | IT | IBI | ML | V! | -V! | Unk | Hole | NoSpec |\n",
+        label
+    ));
+    md.push_str(&format!(
+        "|---|{}|--------|:--:|:--:|:---:|:--:|:--:|:---:|:---:|:----:|:------:|\n",
+        "-".repeat(label.len() + 2)
+    ));
 
     for (idx, a) in analyses.iter().enumerate() {
         let trait_c = a.functions.iter().filter(|f| f.in_trait).count();
@@ -1303,7 +1342,7 @@ fn generate_markdown(analyses: &[ModuleAnalysis]) -> String {
         md.push_str(&format!(
             "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
             idx + 1,
-            a.directory,
+            a.group,
             a.file_stem,
             trait_c,
             it_c,
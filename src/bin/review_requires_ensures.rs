@@ -13,157 +13,13 @@
 //! Binary: veracity-review-requires-ensures
 
 use anyhow::Result;
-use ra_ap_syntax::{ast, AstNode, SyntaxKind, SyntaxNode};
 use std::path::Path;
+use veracity::analyzers::requires_ensures::{self, FileStats};
+use veracity::driver::ParsedFile;
 use veracity::{StandardArgs, find_rust_files};
 
-#[derive(Default, Debug)]
-struct FunctionSpec {
-    has_requires: bool,
-    has_ensures: bool,
-    is_spec: bool,
-    is_proof: bool,
-    is_exec: bool,
-    name: String,
-}
-
-#[derive(Default, Debug)]
-struct FileStats {
-    exec_fns_with_requires: usize,
-    exec_fns_without_requires: usize,
-    exec_fns_with_ensures: usize,
-    exec_fns_without_ensures: usize,
-    spec_fns: usize,
-    proof_fns: usize,
-    functions: Vec<FunctionSpec>,
-}
-
 fn analyze_file(path: &Path) -> Result<FileStats> {
-    let content = std::fs::read_to_string(path)?;
-    let parsed = ra_ap_syntax::SourceFile::parse(&content, ra_ap_syntax::Edition::Edition2021);
-    let tree = parsed.tree();
-    let root = tree.syntax();
-    
-    let mut stats = FileStats::default();
-    
-    // Find verus! macros
-    for node in root.descendants() {
-        if node.kind() == SyntaxKind::MACRO_CALL {
-            if let Some(macro_call) = ast::MacroCall::cast(node.clone()) {
-                if let Some(path) = macro_call.path() {
-                    let path_str = path.to_string();
-                    if path_str == "verus" || path_str == "verus_" {
-                        if let Some(token_tree) = macro_call.token_tree() {
-                            analyze_verus_macro(token_tree.syntax(), &mut stats);
-                        }
-                    }
-                }
-            }
-        }
-    }
-    
-    // Calculate summary stats
-    for func in &stats.functions {
-        if func.is_exec {
-            if func.has_requires {
-                stats.exec_fns_with_requires += 1;
-            } else {
-                stats.exec_fns_without_requires += 1;
-            }
-            if func.has_ensures {
-                stats.exec_fns_with_ensures += 1;
-            } else {
-                stats.exec_fns_without_ensures += 1;
-            }
-        } else if func.is_spec {
-            stats.spec_fns += 1;
-        } else if func.is_proof {
-            stats.proof_fns += 1;
-        }
-    }
-    
-    Ok(stats)
-}
-
-fn analyze_verus_macro(tree: &SyntaxNode, stats: &mut FileStats) {
-    let tokens: Vec<_> = tree.descendants_with_tokens()
-        .filter_map(|n| n.into_token())
-        .collect();
-    
-    let mut i = 0;
-    while i < tokens.len() {
-        if tokens[i].kind() == SyntaxKind::FN_KW {
-            let func_spec = analyze_function_at(&tokens, i);
-            stats.functions.push(func_spec);
-        }
-        i += 1;
-    }
-}
-
-fn analyze_function_at(tokens: &[ra_ap_syntax::SyntaxToken], fn_idx: usize) -> FunctionSpec {
-    let mut spec = FunctionSpec::default();
-    
-    // Check modifiers before fn keyword
-    let mut i = fn_idx.saturating_sub(10);
-    while i < fn_idx {
-        if tokens[i].kind() == SyntaxKind::IDENT {
-            let text = tokens[i].text();
-            match text {
-                "spec" => spec.is_spec = true,
-                "proof" => spec.is_proof = true,
-                "exec" => spec.is_exec = true,
-                _ => {}
-            }
-        }
-        i += 1;
-    }
-    
-    // Default to exec if no modifier specified
-    if !spec.is_spec && !spec.is_proof && !spec.is_exec {
-        spec.is_exec = true;
-    }
-    
-    // Get function name
-    i = fn_idx + 1;
-    while i < tokens.len() && tokens[i].kind() != SyntaxKind::IDENT {
-        i += 1;
-    }
-    if i < tokens.len() {
-        spec.name = tokens[i].text().to_string();
-    }
-    
-    // Scan the function body for requires/ensures
-    i = fn_idx;
-    let mut brace_depth = 0;
-    let mut found_opening_brace = false;
-    
-    while i < tokens.len() {
-        match tokens[i].kind() {
-            SyntaxKind::L_CURLY => {
-                brace_depth += 1;
-                found_opening_brace = true;
-            }
-            SyntaxKind::R_CURLY => {
-                brace_depth -= 1;
-                if found_opening_brace && brace_depth == 0 {
-                    break; // End of function
-                }
-            }
-            SyntaxKind::IDENT => {
-                let text = tokens[i].text();
-                if text == "requires" {
-                    spec.has_requires = true;
-                }
-                if text == "ensures" {
-                    spec.has_ensures = true;
-                }
-            }
-            _ => {}
-        }
-        i += 1;
-    }
-    
-    spec
+    Ok(requires_ensures::analyze(&ParsedFile::read(path)?))
 }
 
 fn print_file_report(path: &Path, stats: &FileStats) {
@@ -239,7 +95,7 @@ fn print_summary(all_stats: &[FileStats]) {
 fn main() -> Result<()> {
     let args = StandardArgs::parse()?;
     let paths = args.get_search_dirs();
-    let all_files = find_rust_files(&paths);
+    let all_files = args.without_excluded(find_rust_files(&paths));
     
     println!("Checking requires/ensures completeness in {} files...\n", all_files.len());
     
@@ -17,6 +17,7 @@ use anyhow::Result;
 use std::fs;
 use std::path::PathBuf;
 use std::time::Instant;
+use veracity::VeracityConfig;
 use verus_syn::visit::Visit;
 use verus_syn::{self, Expr, Lit};
 use quote::ToTokens;
@@ -61,10 +62,13 @@ fn main() -> Result<()> {
             println!("Entering directory '{}'", path.display());
             println!();
             
+            // Honor veracity.toml excludes
+            let config = VeracityConfig::discover(path)?;
             for entry in WalkDir::new(path)
                 .into_iter()
                 .filter_map(|e| e.ok())
                 .filter(|e| e.path().extension().map(|ext| ext == "rs").unwrap_or(false))
+                .filter(|e| !config.is_excluded(e.path()))
             {
                 if let Ok(violations) = check_file(entry.path()) {
                     for v in &violations {
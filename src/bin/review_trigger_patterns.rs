@@ -13,83 +13,13 @@
 //! Binary: veracity-review-trigger-patterns
 
 use anyhow::Result;
-use ra_ap_syntax::{ast, AstNode, SyntaxKind, SyntaxNode};
 use std::path::Path;
+use veracity::analyzers::trigger_patterns::{self, FileStats};
+use veracity::driver::ParsedFile;
 use veracity::{StandardArgs, find_rust_files};
 
-#[derive(Default, Debug)]
-struct FileStats {
-    forall_count: usize,
-    forall_with_triggers: usize,
-    exists_count: usize,
-    exists_with_triggers: usize,
-}
-
 fn analyze_file(path: &Path) -> Result<FileStats> {
-    let content = std::fs::read_to_string(path)?;
-    let parsed = ra_ap_syntax::SourceFile::parse(&content, ra_ap_syntax::Edition::Edition2021);
-    let tree = parsed.tree();
-    let root = tree.syntax();
-    
-    let mut stats = FileStats::default();
-    
-    // Find verus! macros
-    for node in root.descendants() {
-        if node.kind() == SyntaxKind::MACRO_CALL {
-            if let Some(macro_call) = ast::MacroCall::cast(node.clone()) {
-                if let Some(path) = macro_call.path() {
-                    let path_str = path.to_string();
-                    if path_str == "verus" || path_str == "verus_" {
-                        if let Some(token_tree) = macro_call.token_tree() {
-                            analyze_verus_macro(token_tree.syntax(), &mut stats);
-                        }
-                    }
-                }
-            }
-        }
-    }
-    
-    Ok(stats)
-}
-
-fn analyze_verus_macro(tree: &SyntaxNode, stats: &mut FileStats) {
-    let tokens: Vec<_> = tree.descendants_with_tokens()
-        .filter_map(|n| n.into_token())
-        .collect();
-    
-    let mut i = 0;
-    while i < tokens.len() {
-        if tokens[i].kind() == SyntaxKind::IDENT {
-            let text = tokens[i].text();
-            if text == "forall" {
-                stats.forall_count += 1;
-                if has_trigger_nearby(&tokens, i) {
-                    stats.forall_with_triggers += 1;
-                }
-            } else if text == "exists" {
-                stats.exists_count += 1;
-                if has_trigger_nearby(&tokens, i) {
-                    stats.exists_with_triggers += 1;
-                }
-            }
-        }
-        i += 1;
-    }
-}
-
-fn has_trigger_nearby(tokens: &[ra_ap_syntax::SyntaxToken], start_idx: usize) -> bool {
-    // Search within the next 50 tokens for "triggers" keyword
-    let end = (start_idx + 50).min(tokens.len());
-    for i in start_idx..end {
-        if tokens[i].kind() == SyntaxKind::IDENT && tokens[i].text() == "triggers" {
-            return true;
-        }
-        // Stop if we hit a semicolon (end of statement)
-        if tokens[i].kind() == SyntaxKind::SEMICOLON {
-            return false;
-        }
-    }
-    false
+    Ok(trigger_patterns::analyze(&ParsedFile::read(path)?))
 }
 
 fn print_file_report(path: &Path, stats: &FileStats) {
@@ -154,7 +84,7 @@ fn print_summary(all_stats: &[FileStats]) {
 fn main() -> Result<()> {
     let args = StandardArgs::parse()?;
     let paths = args.get_search_dirs();
-    let all_files = find_rust_files(&paths);
+    let all_files = args.without_excluded(find_rust_files(&paths));
     
     println!("Checking trigger patterns in {} files...\n", all_files.len());
     
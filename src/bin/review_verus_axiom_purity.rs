@@ -70,6 +70,7 @@ fn main() -> Result<()> {
         }
     }
     
+    let mut all_files = args.without_excluded(all_files);
     all_files.sort();
     
     // Analyze each file
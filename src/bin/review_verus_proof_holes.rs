@@ -1,14 +1,31 @@
-use anyhow::Result;
-use proc_macro2::Span;
-use quote::ToTokens;
-use ra_ap_syntax::{ast::{self, AstNode, HasName}, SyntaxKind, SyntaxNode};
-use verus_syn::spanned::Spanned;
-use veracity::{StandardArgs, find_rust_files};
-use verus_syn::visit::{self, Visit};
-use std::io::{self, BufRead, Write};
+use anyhow::{Context, Result};
+use veracity::{StandardArgs, VeracityConfig};
+use veracity::accepted::{AcceptedEntry, AcceptedInventory};
+use veracity::config::BudgetUsage;
+use veracity::deps::DepSource;
+use veracity::grouping::Grouping;
+use veracity::lift::lift_assumes;
+use veracity::module_graph::{ModuleGraph, ModuleNode};
+use veracity::triage::{accept_hole, has_accept_import, replace_assume_with_proof_accept, Triage};
+use veracity::tcb::{build_attestation, hash_tree, Attestation, Drift, FnTrust, TcbReport, TcbTotals, TrustKind};
+use veracity::holes::baseline::{collect_hole_keys, compare, find_enclosing_fn_line, get_line, load_baseline, write_baseline};
+use veracity::holes::collect::{
+    build_accepted_inventory, collect_files, compute_budgets, discover_verus_projects, run_verification, Analyzer,
+    HistoryAnalyzer, VerusError,
+};
+use veracity::holes::report::{
+    analyze_dependencies, build_json_report, build_sarif_report, history_csv, history_point, DepTrust, HistoryReport,
+    HoleAttribution, AGE_BUCKETS,
+};
+use veracity::holes::summary::{
+    build_module_graph, compute_depends_upon, compute_summary, path_str_to_module, GlobalSummaryStats, SummaryStats,
+};
+use veracity::holes::{
+    analyze_file_with_macros, has_accept_hole_comment, DetectedHole, FileHoles, HoleKind, MacroTable, ProofHoleStats,
+};
+use std::io::{self, BufRead, IsTerminal, Write};
 use std::{cell::RefCell, collections::{HashMap, HashSet}, fs, path::{Path, PathBuf}, time::Instant};
 use chrono::Local;
-use walkdir::WalkDir;
 
 thread_local! {
     static LOG_FILE_PATH: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
@@ -61,117 +78,6 @@ fn write_to_log(msg: &str) {
     });
 }
 
-#[derive(Debug, Clone, PartialEq)]
-enum VerifierAttribute {
-    ExternalBody,
-    ExecAllowsNoDecreasesClause,
-    ExternalFnSpec,
-    ExternalTraitSpec,
-    ExternalTypeSpec,
-    ExternalTraitExt,
-    External,
-    Opaque,
-    Axiom,
-}
-
-/// A single detected proof hole with its location
-#[derive(Debug, Clone)]
-struct DetectedHole {
-    line: usize,
-    hole_type: String,
-    context: String,  // Short snippet of code for context
-}
-
-#[derive(Debug, Default, Clone)]
-struct ProofHoleStats {
-    assume_false_count: usize,
-    assume_count: usize,
-    assume_new_count: usize,  // Tracked::assume_new()
-    assume_specification_count: usize,  // pub assume_specification
-    admit_count: usize,
-    unsafe_fn_count: usize,
-    unsafe_impl_count: usize,
-    unsafe_block_count: usize,
-    external_body_count: usize,
-    external_fn_spec_count: usize,
-    external_trait_spec_count: usize,
-    external_type_spec_count: usize,
-    external_trait_ext_count: usize,
-    external_count: usize,
-    opaque_count: usize,
-    trivial_spec_wf_count: usize,
-    axiom_count: usize,
-    total_holes: usize,
-    /// Detailed list of holes for Emacs-compatible output
-    holes: Vec<DetectedHole>,
-}
-
-#[derive(Debug, Default, Clone)]
-struct AxiomStats {
-    axiom_fn_count: usize,
-    broadcast_use_axiom_count: usize,
-    total_axioms: usize,
-    axiom_names: Vec<String>,  // Track axiom names for de-duplication
-}
-
-#[derive(Debug, Default, Clone)]
-struct FnSpecStats {
-    total_fns: usize,
-    exec_fns_complete: usize,
-    exec_fns_missing_spec: usize,
-    proof_spec_fns_clean: usize,
-    proof_spec_fns_with_holes: usize,
-}
-
-#[derive(Debug, Default)]
-struct FileStats {
-    holes: ProofHoleStats,
-    axioms: AxiomStats,
-    fn_spec: FnSpecStats,
-    proof_functions: usize,
-    clean_proof_functions: usize,
-    holed_proof_functions: usize,
-    warnings: Vec<DetectedHole>,
-    infos: Vec<DetectedHole>,
-    /// Crate module paths this file depends on (from use crate::...), excluding accept
-    crate_deps: HashSet<String>,
-}
-
-#[derive(Debug, Default)]
-struct SummaryStats {
-    total_files: usize,
-    clean_modules: usize,
-    holed_modules: usize,
-    total_proof_functions: usize,
-    clean_proof_functions: usize,
-    holed_proof_functions: usize,
-    holes: ProofHoleStats,
-    axioms: AxiomStats,
-    fn_spec: FnSpecStats,
-    total_warnings: usize,
-    total_infos: usize,
-    /// Count per hole_type for accurate summary message
-    warning_type_counts: HashMap<String, usize>,
-    /// All infos with path:line for summary listing
-    all_infos: Vec<(String, usize, String, String)>,
-    /// All errors (path, line, hole_type) for single-line summary
-    all_errors: Vec<(String, usize, String)>,
-    /// All warnings (path, line, hole_type) for single-line summary
-    all_warnings: Vec<(String, usize, String)>,
-    /// Per root (src, path, tests): top-level dirs -> (unused, holes, file_count) for Proof Targets
-    by_root_top: HashMap<String, HashMap<String, (usize, usize, usize)>>,
-    /// True if any path has subdirs (e.g. Chap05/SetStEph.rs)
-    has_subdir_paths: bool,
-    /// Next Target Files: src files that depend only on clean modules, (path, holes)
-    next_target_files: Vec<(String, usize)>,
-    /// Next Target Directories: src dirs where all files depend only on clean, (dir, holes, file_count)
-    next_target_dirs: Vec<(String, usize, usize)>,
-    /// Not verusified: src files with no verus! block
-    not_verusified_files: Vec<String>,
-    /// Not verusified with clean deps: not_verusified files that depend only on clean modules
-    not_verusified_clean_deps: Vec<String>,
-}
-
 #[derive(Debug)]
 #[allow(dead_code)]
 struct ProjectStats {
@@ -179,20 +85,29 @@ struct ProjectStats {
     path: PathBuf,
     verus_files: Vec<PathBuf>,
     summary: SummaryStats,
-    file_stats: HashMap<String, FileStats>,
+    file_stats: HashMap<String, FileHoles>,
+}
+
+/// Output format for the proof-holes report
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OutputFormat {
+    /// Emacs-compatible diagnostics plus the ToC'd text summary (default)
+    Text,
+    /// Machine-readable report matching schemas/proof_holes.schema.json
+    Json,
+    /// SARIF 2.1.0 log for code-scanning dashboards
+    Sarif,
 }
 
-#[derive(Debug, Default)]
-struct GlobalSummaryStats {
-    total_projects: usize,
-    total_files: usize,
-    clean_modules: usize,
-    holed_modules: usize,
-    total_proof_functions: usize,
-    clean_proof_functions: usize,
-    holed_proof_functions: usize,
-    holes: ProofHoleStats,
-    axioms: AxiomStats,
+impl OutputFormat {
+    fn parse(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "sarif" => Ok(OutputFormat::Sarif),
+            other => Err(anyhow::anyhow!("Unknown format: {} (expected text, json or sarif)", other)),
+        }
+    }
 }
 
 /// Tool-specific arguments for proof-holes tool
@@ -200,50 +115,109 @@ struct ProofHolesArgs {
     standard: StandardArgs,
     /// Emacs-compatible diagnostics output (file:line: message)
     emacs_mode: bool,
-    /// Interactive mode: prompt y/n to fix assume->accept, external->add accept hole
+    /// Interactive mode: triage TUI, or y/n prompts when not on a terminal
     interactive: bool,
     /// Directories to exclude from analysis
     exclude_dirs: Vec<PathBuf>,
     /// Import for accept (e.g. "use crate::vstdplus::accept::accept;"). Used in -i mode.
     accept_import: String,
+    /// Report format: text (default), json or sarif
+    format: OutputFormat,
+    /// Compare against this baseline; exit non-zero only on holes not in it
+    baseline: Option<PathBuf>,
+    /// Write the current holes as a baseline to this file
+    write_baseline: Option<PathBuf>,
+    /// Ignore and do not update analyses/.cache/
+    no_cache: bool,
+    /// Also analyze path and vendored dependency crates, reported separately
+    deps: bool,
+    /// Commit range for history mode (e.g. "v1.0..HEAD")
+    history: Option<String>,
+    /// Attribute each hole to the author and commit that last touched its line
+    blame: bool,
+    /// Write the module dependency graph as DOT and JSON under analyses/
+    graph: bool,
+    /// Rewrite each assume(P) into a call to a generated admitted lemma
+    lift_assumes: bool,
+    /// Run the project's Verus build and count functions that fail to verify as holes
+    verify: bool,
+    /// Write an attestation manifest (file hashes, tool versions, holes, trusted items) here
+    attest: Option<PathBuf>,
+    /// Re-hash the tree and report drift from this attestation manifest
+    check: Option<PathBuf>,
+    /// Report the trusted computing base: trusted items with line and token counts
+    tcb: bool,
 }
 
 impl ProofHolesArgs {
     fn parse() -> Result<Self> {
         let args: Vec<String> = std::env::args().collect();
-        
-        let (standard, exclude_dirs, accept_import) = Self::parse_args(&args)?;
-        
-        Ok(ProofHolesArgs {
-            standard,
-            emacs_mode: !args.iter().any(|a| a == "-i" || a == "--interactive"),
-            interactive: args.iter().any(|a| a == "-i" || a == "--interactive"),
-            exclude_dirs,
-            accept_import,
-        })
+        Self::parse_args(&args)
+    }
+    
+    fn standard_args(paths: Vec<PathBuf>, multi_codebase: Option<PathBuf>) -> Result<StandardArgs> {
+        StandardArgs {
+            paths,
+            is_module_search: false,
+            project: None,
+            language: "Verus".to_string(),
+            repositories: None,
+            multi_codebase,
+            src_dirs: vec!["src".to_string(), "source".to_string()],
+            test_dirs: vec!["tests".to_string(), "test".to_string()],
+            bench_dirs: vec!["benches".to_string()],
+            config: VeracityConfig::default(),
+        }.with_discovered_config()
     }
     
-    fn parse_args(args: &[String]) -> Result<(StandardArgs, Vec<PathBuf>, String)> {
+    fn parse_args(args: &[String]) -> Result<Self> {
+        let interactive = args.iter().any(|a| a == "-i" || a == "--interactive");
+        
         if args.len() == 1 {
             let current_dir = std::env::current_dir()?;
-            return Ok((StandardArgs { 
-                paths: vec![current_dir],
-                is_module_search: false,
-                project: None,
-                language: "Verus".to_string(),
-                repositories: None,
-                multi_codebase: None,
-                src_dirs: vec!["src".to_string(), "source".to_string()],
-                test_dirs: vec!["tests".to_string(), "test".to_string()],
-                bench_dirs: vec!["benches".to_string()],
-            }, Vec::new(), "use crate::vstdplus::accept::accept;".to_string()));
+            let standard = Self::standard_args(vec![current_dir], None)?;
+            let accept_import = standard.config.accept_import()
+                .unwrap_or_else(|| "use crate::vstdplus::accept::accept;".to_string());
+            return Ok(ProofHolesArgs {
+                standard,
+                emacs_mode: true,
+                interactive: false,
+                exclude_dirs: Vec::new(),
+                accept_import,
+                format: OutputFormat::Text,
+                baseline: None,
+                write_baseline: None,
+                no_cache: false,
+                deps: false,
+                history: None,
+                blame: false,
+                graph: false,
+                lift_assumes: false,
+                verify: false,
+                attest: None,
+                check: None,
+                tcb: false,
+            });
         }
         
         let mut i = 1;
         let mut paths = Vec::new();
         let mut multi_codebase = None;
         let mut exclude_dirs = Vec::new();
-        let mut accept_import = "use crate::vstdplus::accept::accept;".to_string();
+        let mut accept_import = None;
+        let mut format = OutputFormat::Text;
+        let mut baseline = None;
+        let mut write_baseline = None;
+        let mut no_cache = false;
+        let mut deps = false;
+        let mut history = None;
+        let mut blame = false;
+        let mut graph = false;
+        let mut lift_assumes = false;
+        let mut verify = false;
+        let mut attest = None;
+        let mut check = None;
+        let mut tcb = false;
         
         while i < args.len() {
             match args[i].as_str() {
@@ -293,7 +267,7 @@ impl ProofHolesArgs {
                     i += 1;
                 }
                 "-i" | "--interactive" => {
-                    // Handled in parse() for interactive flag
+                    // Handled above for interactive flag
                     i += 1;
                 }
                 "-a" | "--accept" => {
@@ -301,7 +275,91 @@ impl ProofHolesArgs {
                     if i >= args.len() {
                         return Err(anyhow::anyhow!("--accept requires an import string (e.g. 'use crate::vstdplus::accept::accept;')"));
                     }
-                    accept_import = args[i].clone();
+                    accept_import = Some(args[i].clone());
+                    i += 1;
+                }
+                "-f" | "--format" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(anyhow::anyhow!("--format requires one of: text, json, sarif"));
+                    }
+                    format = OutputFormat::parse(&args[i])?;
+                    i += 1;
+                }
+                "-b" | "--baseline" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(anyhow::anyhow!("--baseline requires a file path"));
+                    }
+                    let baseline_path = PathBuf::from(&args[i]);
+                    if !baseline_path.is_file() {
+                        return Err(anyhow::anyhow!("Baseline file not found: {}", args[i]));
+                    }
+                    baseline = Some(baseline_path);
+                    i += 1;
+                }
+                "--no-cache" => {
+                    no_cache = true;
+                    i += 1;
+                }
+                "--deps" => {
+                    deps = true;
+                    i += 1;
+                }
+                "--blame" => {
+                    blame = true;
+                    i += 1;
+                }
+                "--graph" => {
+                    graph = true;
+                    i += 1;
+                }
+                "--lift-assumes" => {
+                    lift_assumes = true;
+                    i += 1;
+                }
+                "--verify" => {
+                    verify = true;
+                    i += 1;
+                }
+                "--tcb" => {
+                    tcb = true;
+                    i += 1;
+                }
+                "--attest" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(anyhow::anyhow!("--attest requires a file path"));
+                    }
+                    attest = Some(PathBuf::from(&args[i]));
+                    i += 1;
+                }
+                "--check" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(anyhow::anyhow!("--check requires an attestation file"));
+                    }
+                    let check_path = PathBuf::from(&args[i]);
+                    if !check_path.is_file() {
+                        return Err(anyhow::anyhow!("Attestation file not found: {}", args[i]));
+                    }
+                    check = Some(check_path);
+                    i += 1;
+                }
+                "-H" | "--history" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(anyhow::anyhow!("--history requires a commit range (e.g. v1.0..HEAD)"));
+                    }
+                    history = Some(args[i].clone());
+                    i += 1;
+                }
+                "-B" | "--write-baseline" => {
+                    i += 1;
+                    if i >= args.len() {
+                        return Err(anyhow::anyhow!("--write-baseline requires a file path"));
+                    }
+                    write_baseline = Some(PathBuf::from(&args[i]));
                     i += 1;
                 }
                 "--help" | "-h" => {
@@ -311,17 +369,44 @@ impl ProofHolesArgs {
                     println!("Output format: file:line: type - context");
                     println!();
                     println!("Options:");
-                    println!("  -a, --accept IMPORT        Import for accept (default: use crate::vstdplus::accept::accept;)");
+                    println!("  -a, --accept IMPORT        Import for accept (default: veracity.toml accept, else use crate::vstdplus::accept::accept;)");
+                    println!("      --attest FILE          Write an attestation manifest: file SHA-256s, tool versions, holes, trusted items");
+                    println!("  -b, --baseline FILE        Fail only on holes not recorded in FILE; report fixed holes");
+                    println!("      --blame                Attribute each hole to its git blame author, commit and date");
+                    println!("  -B, --write-baseline FILE  Record the current holes in FILE");
+                    println!("      --check FILE           Re-hash the tree and report drift from attestation FILE");
                     println!("  -d, --dir DIR [DIR...]     Analyze specific directories");
+                    println!("      --deps                 Also report on path and vendored dependency crates");
                     println!("  -e, --exclude DIR          Exclude directory (can be repeated)");
-                    println!("  -i, --interactive          Prompt y/n to fix assume->accept, external->add accept hole");
+                    println!("  -f, --format FORMAT        Report format: text (default), json, sarif");
+                    println!("      --graph                Write the module dependency graph to analyses/ as DOT and JSON");
+                    println!("  -H, --history RANGE        Hole counts at each commit in RANGE, as CSV and JSON");
+                    println!("  -i, --interactive          Triage holes in a full-screen view (y/n prompt when not a terminal)");
+                    println!("      --lift-assumes         Rewrite each assume(P) into a call to a new admitted lemma ensuring P");
                     println!("  -M, --multi-codebase DIR   Scan multiple independent projects");
+                    println!("      --no-cache             Re-analyze every file (ignore analyses/.cache/)");
+                    println!("      --tcb                  Report trusted code: items, lines and tokens per module and crate");
+                    println!("      --verify               Run the Verus build; functions that fail to verify count as holes");
                     println!("  -h, --help                 Show this help message");
                     println!();
+                    println!("Policy: veracity.toml in the analyzed directory or an ancestor sets excludes,");
+                    println!("the accept path, hole severities per type and path glob, and hole budgets.");
+                    println!();
                     println!("Examples:");
                     println!("  veracity-review-proof-holes");
                     println!("  veracity-review-proof-holes -e src/experiments -e tests");
                     println!("  veracity-review-proof-holes -d src -e src/legacy");
+                    println!("  veracity-review-proof-holes -d src --format sarif > holes.sarif");
+                    println!("  veracity-review-proof-holes -d src --write-baseline proof-holes.baseline.json");
+                    println!("  veracity-review-proof-holes -d src --baseline proof-holes.baseline.json");
+                    println!("  veracity-review-proof-holes --deps");
+                    println!("  veracity-review-proof-holes -d src --blame");
+                    println!("  veracity-review-proof-holes -d src --history v1.0..HEAD");
+                    println!("  veracity-review-proof-holes -d src --lift-assumes");
+                    println!("  veracity-review-proof-holes --verify");
+                    println!("  veracity-review-proof-holes -d src --tcb --deps");
+                    println!("  veracity-review-proof-holes -d src --verify --attest attestation.json");
+                    println!("  veracity-review-proof-holes -d src --check attestation.json");
                     std::process::exit(0);
                 }
                 other if other.starts_with('-') => {
@@ -351,17 +436,55 @@ impl ProofHolesArgs {
             paths.push(current_dir);
         }
         
-        Ok((StandardArgs {
-            paths,
-            is_module_search: false,
-            project: None,
-            language: "Verus".to_string(),
-            repositories: None,
-            multi_codebase,
-            src_dirs: vec!["src".to_string(), "source".to_string()],
-            test_dirs: vec!["tests".to_string(), "test".to_string()],
-            bench_dirs: vec!["benches".to_string()],
-        }, exclude_dirs, accept_import))
+        if interactive && format != OutputFormat::Text {
+            return Err(anyhow::anyhow!("--format json/sarif cannot be combined with --interactive"));
+        }
+        if interactive && (baseline.is_some() || write_baseline.is_some()) {
+            return Err(anyhow::anyhow!("--baseline/--write-baseline cannot be combined with --interactive"));
+        }
+        if history.is_some() && (interactive || multi_codebase.is_some() || format == OutputFormat::Sarif) {
+            return Err(anyhow::anyhow!("--history cannot be combined with --interactive, --multi-codebase or --format sarif"));
+        }
+        if lift_assumes && (interactive || history.is_some() || multi_codebase.is_some() || format != OutputFormat::Text) {
+            return Err(anyhow::anyhow!("--lift-assumes cannot be combined with --interactive, --history, --multi-codebase or --format"));
+        }
+        if verify && (interactive || history.is_some() || lift_assumes || multi_codebase.is_some()) {
+            return Err(anyhow::anyhow!("--verify cannot be combined with --interactive, --history, --lift-assumes or --multi-codebase"));
+        }
+        let attesting = attest.is_some() || check.is_some();
+        if attesting && (interactive || history.is_some() || lift_assumes || multi_codebase.is_some() || format != OutputFormat::Text) {
+            return Err(anyhow::anyhow!("--attest/--check cannot be combined with --interactive, --history, --lift-assumes, --multi-codebase or --format"));
+        }
+        if attest.is_some() && check.is_some() {
+            return Err(anyhow::anyhow!("--attest and --check are separate runs"));
+        }
+        
+        // -a wins over veracity.toml's accept, which wins over the default
+        let standard = Self::standard_args(paths, multi_codebase)?;
+        let accept_import = accept_import
+            .or_else(|| standard.config.accept_import())
+            .unwrap_or_else(|| "use crate::vstdplus::accept::accept;".to_string());
+        
+        Ok(ProofHolesArgs {
+            standard,
+            emacs_mode: !interactive,
+            interactive,
+            exclude_dirs,
+            accept_import,
+            format,
+            baseline,
+            write_baseline,
+            no_cache,
+            deps,
+            history,
+            blame,
+            graph,
+            lift_assumes,
+            verify,
+            attest,
+            check,
+            tcb,
+        })
     }
 }
 
@@ -370,7 +493,27 @@ fn main() -> Result<()> {
     let start_date = Local::now().format("%Y-%m-%d %H:%M:%S %Z").to_string();
     
     let args = ProofHolesArgs::parse()?;
+
+    if let Some(range) = &args.history {
+        return run_history_mode(&args, range);
+    }
+    if args.lift_assumes {
+        return run_lift_assumes(&args);
+    }
+    if let Some(path) = &args.attest {
+        return run_attest(&args, path);
+    }
+    if let Some(path) = &args.check {
+        return run_check(&args, path);
+    }
+    
+    // Machine-readable formats write a single document to stdout (and analyses/), no text log
+    if args.format != OutputFormat::Text {
+        return run_report_mode(&args);
+    }
     
+    let grouping = Grouping::new(&args.standard.config)?;
+
     // Initialize logging to the codebase's analyses directory
     let log_path = init_logging(&args.standard.base_dir());
     
@@ -391,18 +534,57 @@ fn main() -> Result<()> {
     log!("     4.3. Next Target Directories");
     log!("     4.4. Not Verusified");
     log!("     4.5. Not Verusified (clean deps only)");
-    log!("     4.6. Chapter by Chapter Proof Targeting");
+    log!("     4.6. {} by {} Proof Targeting", grouping.label(), grouping.label());
+    log!("     4.7. Transitive Trust");
+    log!("     4.8. Dependency Cycles");
+    if args.baseline.is_some() || args.write_baseline.is_some() {
+        log!("  B. Baseline Comparison");
+    }
+    if !args.standard.config.budget.is_empty() {
+        log!("  P. Hole Budgets");
+    }
+    log!("  J. Accepted Holes");
+    if args.deps {
+        log!("  D. Dependency Trust");
+    }
+    if args.blame {
+        log!("  A. Hole Attribution");
+    }
+    if args.tcb {
+        log!("  T. Trusted Computing Base");
+    }
     log!("  5. Started/Ended/Duration");
     log!("");
     
     if args.interactive {
-        run_interactive_mode(&args.standard, &args.exclude_dirs, &args.accept_import)?;
+        run_interactive_mode(&args.standard, &args.exclude_dirs, &args.accept_import, !args.no_cache)?;
         return Ok(());
     }
     
+    let mut new_holes = 0;
+    let mut over_budget = 0;
+    let mut tcb = None;
     if args.emacs_mode {
         // Emacs mode - interleaved file summaries and file:line: messages
-        run_emacs_mode(&args.standard, &args.exclude_dirs)?;
+        let verification = if args.verify {
+            run_verification(&args.standard.base_dir(), &|msg: &str| log!("{}", msg))?
+        } else {
+            HashMap::new()
+        };
+        let file_stats_map = run_emacs_mode(&args.standard, &args.exclude_dirs, !args.no_cache, args.blame, verification, &grouping)?;
+        if args.blame {
+            print_hole_attribution(&file_stats_map, &|msg: &str| log!("{}", msg));
+        }
+        if args.graph {
+            write_module_graph(&build_module_graph(&file_stats_map), &args.standard.base_dir(), &|msg: &str| log!("{}", msg))?;
+        }
+        new_holes = run_baseline(&args, &args.standard.base_dir(), &file_stats_map, &|msg: &str| log!("{}", msg))?;
+        over_budget = run_budgets(&args.standard.config, &args.standard.base_dir(), &file_stats_map, &|msg: &str| log!("{}", msg));
+        let accepted = build_accepted_inventory(&args.standard.config, &args.standard.base_dir(), &file_stats_map);
+        print_accepted_inventory(&accepted, &args.standard.config, &args.standard.base_dir(), &|msg: &str| log!("{}", msg));
+        if args.tcb {
+            tcb = Some(TcbReport::new(&args.standard.base_dir(), file_stats_map.into_keys()));
+        }
     } else {
         log!("Verus Proof Hole Detection");
         log!("Logging to: {}", log_path.display());
@@ -420,9 +602,17 @@ fn main() -> Result<()> {
         if let Some(multi_base) = &args.standard.multi_codebase {
             run_multi_codebase_analysis(multi_base, &args.exclude_dirs)?;
         } else {
-            run_single_project_analysis(&args.standard, &args.exclude_dirs)?;
+            run_single_project_analysis(&args.standard, &args.exclude_dirs, !args.no_cache, &grouping)?;
         }
     }
+
+    let deps = if args.deps { analyze_dependencies(&args.standard.base_dir())? } else { Vec::new() };
+    if args.deps {
+        print_dependency_trust(&deps, &|msg: &str| log!("{}", msg));
+    }
+    if let Some(tcb) = &tcb {
+        print_tcb(tcb, &args.standard.base_dir(), &deps, &|msg: &str| log!("{}", msg));
+    }
     
     let elapsed = start_time.elapsed();
     let end_date = Local::now().format("%Y-%m-%d %H:%M:%S %Z").to_string();
@@ -437,52 +627,26 @@ fn main() -> Result<()> {
     log!("");
     log!("Full output: {}", log_path.display());
     let _ = std::io::stdout().flush();
+    if new_holes > 0 || over_budget > 0 {
+        std::process::exit(1);
+    }
     Ok(())
 }
 
 /// Fixable hole types for interactive mode
-fn is_fixable_hole(hole_type: &str) -> bool {
+fn is_fixable_hole(kind: HoleKind) -> bool {
     matches!(
-        hole_type,
-        "assume()" | "assume(false)"
-            | "external_body"
-            | "external_fn_specification"
-            | "external_trait_specification"
-            | "external_type_specification"
-            | "external_trait_extension"
-            | "external"
+        kind,
+        HoleKind::Assume | HoleKind::AssumeFalse
+            | HoleKind::ExternalBody
+            | HoleKind::ExternalFnSpecification
+            | HoleKind::ExternalTraitSpecification
+            | HoleKind::ExternalTypeSpecification
+            | HoleKind::ExternalTraitExtension
+            | HoleKind::External
     )
 }
 
-/// Replace assume(...) with proof { accept(...); }, handling nested parens.
-fn replace_assume_with_proof_accept(line: &str) -> Option<String> {
-    let start = line.find("assume(").or_else(|| line.find("assume ("))?;
-    let open_paren = start + line[start..].find('(')?;
-    let mut depth = 1u32;
-    let mut end = open_paren;
-    for (i, c) in line[open_paren + 1..].chars().enumerate() {
-        match c {
-            '(' => depth += 1,
-            ')' => {
-                depth -= 1;
-                if depth == 0 {
-                    end = open_paren + 1 + i;
-                    break;
-                }
-            }
-            _ => {}
-        }
-    }
-    if depth != 0 {
-        return None;
-    }
-    let arg = &line[open_paren + 1..end];
-    let replacement = format!("proof {{ accept({}); }}", arg);
-    let mut result = line.to_string();
-    result.replace_range(start..=end, &replacement);
-    Some(result)
-}
-
 /// Return (proposed new line, needs_import) for display, or None if no change.
 fn proposed_fix_with_import(path: &Path, hole: &DetectedHole, accept_import: &str) -> Option<(String, bool)> {
     let content = fs::read_to_string(path).ok()?;
@@ -490,13 +654,9 @@ fn proposed_fix_with_import(path: &Path, hole: &DetectedHole, accept_import: &st
     let line_idx = hole.line.saturating_sub(1);
     let line = lines.get(line_idx)?;
 
-    if hole.hole_type == "assume()" || hole.hole_type == "assume(false)" {
-        if let Some(n) = replace_assume_with_proof_accept(line) {
-            Some((n, !has_accept_import(&content, accept_import)))
-        } else {
-            None
-        }
-    } else if hole.hole_type.starts_with("external") || hole.hole_type == "external" {
+    if matches!(hole.kind, HoleKind::Assume | HoleKind::AssumeFalse) {
+        replace_assume_with_proof_accept(line).map(|n| (n, !has_accept_import(&content, accept_import)))
+    } else if hole.kind.is_external() {
         if has_accept_hole_comment(&content, hole.line) {
             None
         } else {
@@ -507,119 +667,73 @@ fn proposed_fix_with_import(path: &Path, hole: &DetectedHole, accept_import: &st
     }
 }
 
-/// Check if content already has the accept import (by path substring).
-fn has_accept_import(content: &str, accept_import: &str) -> bool {
-    let path = accept_import
-        .trim()
-        .strip_prefix("use ")
-        .and_then(|s| s.strip_suffix(';'))
-        .map(|s| s.trim())
-        .unwrap_or(accept_import);
-    content.contains(path)
-}
-
-/// Insert accept import inside the verus! block, after the last use there.
-/// Returns true if a line was inserted (caller must add 1 to line_idx if it was before the hole).
-/// No-op if the import already exists (avoids duplicate).
-fn add_accept_import(lines: &mut Vec<String>, accept_import: &str) -> bool {
-    if lines.iter().any(|l| l.trim() == accept_import.trim()) {
-        return false;
-    }
-    let verus_start = lines.iter().position(|l| l.contains("verus!"));
-    let Some(vs) = verus_start else {
-        return false;
-    };
-    let mut last_use_idx = None;
-    for (i, line) in lines.iter().enumerate().skip(vs + 1) {
-        let t = line.trim_start();
-        if t.starts_with("use ") {
-            last_use_idx = Some(i);
-        } else if !t.is_empty() && !t.starts_with("//") && !t.starts_with("#[") && !t.starts_with("#!") {
-            break;
-        }
-    }
-    if let Some(idx) = last_use_idx {
-        lines.insert(idx + 1, accept_import.to_string());
-    } else {
-        lines.insert(vs + 1, accept_import.to_string());
-    }
-    true
-}
-
 /// Apply fix: assume->accept or external->add // accept hole
 fn apply_fix(path: &Path, hole: &DetectedHole, accept_import: &str) -> Result<bool> {
     let content = fs::read_to_string(path)?;
-    let mut lines: Vec<String> = content.lines().map(String::from).collect();
-    let line_idx = hole.line.saturating_sub(1);
-    let Some(line) = lines.get(line_idx) else {
+    let Some(new_content) = accept_hole(&content, hole.line, hole.kind, accept_import) else {
         return Ok(false);
     };
+    fs::write(path, new_content)?;
+    Ok(true)
+}
 
-    let (new_line, changed, line_idx_offset) = if hole.hole_type == "assume()" || hole.hole_type == "assume(false)" {
-        // assume -> proof { accept(...); }; must add accept import
-        let new_line = replace_assume_with_proof_accept(line).unwrap_or_else(|| line.clone());
-        let changed = new_line != line.as_str();
-        let offset = if changed && !has_accept_import(&content, accept_import) {
-            if add_accept_import(&mut lines, accept_import) { 1 } else { 0 }
-        } else {
-            0
-        };
-        (new_line, changed, offset)
-    } else if hole.hole_type.starts_with("external") || hole.hole_type == "external" {
-        // Add // accept hole if not already present
-        if has_accept_hole_comment(&content, hole.line) {
-            (line.to_string(), false, 0)
-        } else {
-            let trimmed = line.trim_end();
-            (format!("{} // accept hole", trimmed), true, 0)
+/// --lift-assumes: replace each assume(P) hole with a call to a new `proof fn lemma_<fn>_<n>`
+/// that ensures P by admit(), and report the assumes that could not be lifted.
+fn run_lift_assumes(args: &ProofHolesArgs) -> Result<()> {
+    let base_dir = args.standard.base_dir();
+    let mut analyzer = Analyzer::new(&args.standard, !args.no_cache);
+    let (mut lifted, mut skipped, mut files) = (0, 0, 0);
+    for file in collect_files(&args.standard, &args.exclude_dirs) {
+        let Ok(stats) = analyzer.analyze(&file) else { continue };
+        let lines: Vec<usize> = stats.holes.holes.iter()
+            .filter(|h| h.kind == HoleKind::Assume)
+            .map(|h| h.line)
+            .collect();
+        if lines.is_empty() {
+            continue;
         }
-    } else {
-        return Ok(false);
-    };
-
-    if !changed {
-        return Ok(false);
+        let content = fs::read_to_string(&file)?;
+        let result = lift_assumes(&content, &lines);
+        let path_str = file.strip_prefix(&base_dir).unwrap_or(&file).display().to_string();
+        for l in &result.lifted {
+            println!("{}:{}: lifted assume into {}", path_str, l.line, l.lemma);
+        }
+        for s in &result.skipped {
+            println!("{}:{}: assume not lifted: {}", path_str, s.line, s.reason);
+        }
+        if !result.lifted.is_empty() {
+            fs::write(&file, &result.content)
+                .with_context(|| format!("Failed to write {}", file.display()))?;
+            files += 1;
+        }
+        lifted += result.lifted.len();
+        skipped += result.skipped.len();
     }
-
-    let mut new_lines = lines;
-    let idx = line_idx + line_idx_offset;
-    new_lines[idx] = new_line;
-    fs::write(path, new_lines.join("\n"))?;
-    Ok(true)
+    println!();
+    println!("Lifted {} assumes into lemmas in {} files; {} not lifted", lifted, files, skipped);
+    Ok(())
 }
 
-/// Run interactive mode: loop over fixable holes, prompt y/n, apply fixes
-fn run_interactive_mode(args: &StandardArgs, exclude_dirs: &[PathBuf], accept_import: &str) -> Result<()> {
-
-    let mut all_files: Vec<PathBuf> = Vec::new();
+/// Run interactive mode: the triage TUI on a terminal, else loop over fixable holes, prompt y/n, apply fixes
+fn run_interactive_mode(args: &StandardArgs, exclude_dirs: &[PathBuf], accept_import: &str, use_cache: bool) -> Result<()> {
+    let all_files = collect_files(args, exclude_dirs);
     let base_dir = args.base_dir();
-
-    for path in &args.paths {
-        if path.is_file() && path.extension().map_or(false, |e| e == "rs") {
-            if !should_exclude(path, exclude_dirs) {
-                all_files.push(path.clone());
-            }
-        } else if path.is_dir() {
-            let files = find_rust_files(&[path.clone()]);
-            for file in files {
-                if !should_exclude(&file, exclude_dirs) {
-                    all_files.push(file);
-                }
-            }
-        }
+    if io::stdin().is_terminal() && io::stdout().is_terminal() {
+        return run_triage_tui(args, &all_files, accept_import, use_cache);
     }
-
+    let mut analyzer = Analyzer::new(args, use_cache);
     let mut fixable: Vec<(PathBuf, DetectedHole)> = Vec::new();
     for file in &all_files {
         let abs_path = file.canonicalize().unwrap_or_else(|_| file.clone());
-        if let Ok(stats) = analyze_file(file) {
+        if let Ok(stats) = analyzer.analyze(file) {
             for hole in &stats.holes.holes {
-                if is_fixable_hole(&hole.hole_type) {
+                if is_fixable_hole(hole.kind) {
                     fixable.push((abs_path.clone(), hole.clone()));
                 }
             }
         }
     }
+    analyzer.finish();
 
     if fixable.is_empty() {
         println!("No fixable holes found.");
@@ -638,7 +752,7 @@ fn run_interactive_mode(args: &StandardArgs, exclude_dirs: &[PathBuf], accept_im
             .strip_prefix(&base_dir)
             .map(|p| p.display().to_string())
             .unwrap_or_else(|_| path.display().to_string());
-        println!("{}:{}: {} - {}", path_str, hole.line, hole.hole_type, hole.context);
+        println!("{}:{}: {} - {}", path_str, hole.line, hole.kind, hole.context);
         if let Ok(content) = fs::read_to_string(path) {
             for ctx in context_lines_around(&content, hole.line, 3, 5) {
                 println!("{}", ctx);
@@ -693,69 +807,29 @@ fn run_interactive_mode(args: &StandardArgs, exclude_dirs: &[PathBuf], accept_im
     Ok(())
 }
 
-/// Check if a path should be excluded based on exclude_dirs
-fn should_exclude(path: &Path, exclude_dirs: &[PathBuf]) -> bool {
-    // Always exclude docs, path (legacy), src/lib.rs (crate root), and src/Types.rs
-    if path.file_name().map_or(false, |f| f == "lib.rs") && path.parent().map_or(false, |p| p.ends_with("src")) {
-        return true;
-    }
-    if path.file_name().map_or(false, |f| f == "Types.rs") && path.parent().map_or(false, |p| p.ends_with("src")) {
-        return true;
-    }
-    if path.components().any(|c| {
-        let s = c.as_os_str();
-        s == "docs" || s == "path"
-    }) {
-        return true;
-    }
-    for exclude in exclude_dirs {
-        // Check if the path starts with the exclude directory
-        if let Ok(canonical_path) = path.canonicalize() {
-            if let Ok(canonical_exclude) = exclude.canonicalize() {
-                if canonical_path.starts_with(&canonical_exclude) {
-                    return true;
-                }
-            }
-        }
-        // Also check without canonicalization for relative paths
-        if path.starts_with(exclude) {
-            return true;
-        }
-    }
-    false
-}
-
 /// Run in Emacs compilation buffer mode - outputs file:line: message format
 /// Interleaved with nice file summaries
-fn run_emacs_mode(args: &StandardArgs, exclude_dirs: &[PathBuf]) -> Result<()> {
-    let mut all_files: Vec<PathBuf> = Vec::new();
+fn run_emacs_mode(
+    args: &StandardArgs,
+    exclude_dirs: &[PathBuf],
+    use_cache: bool,
+    blame: bool,
+    verification: HashMap<PathBuf, Vec<VerusError>>,
+    grouping: &Grouping,
+) -> Result<HashMap<String, FileHoles>> {
+    let all_files = collect_files(args, exclude_dirs);
     let base_dir = args.base_dir();
     
-    // Handle both file and directory modes
-    for path in &args.paths {
-        if path.is_file() && path.extension().map_or(false, |e| e == "rs") {
-            if !should_exclude(path, exclude_dirs) {
-                all_files.push(path.clone());
-            }
-        } else if path.is_dir() {
-            let files = find_rust_files(&[path.clone()]);
-            for file in files {
-                if !should_exclude(&file, exclude_dirs) {
-                    all_files.push(file);
-                }
-            }
-        }
-    }
-    
-    let mut file_stats_map: HashMap<String, FileStats> = HashMap::new();
+    let mut file_stats_map: HashMap<String, FileHoles> = HashMap::new();
     
     log!("=================================================================");
     log!("1. File Holes");
     log!("=================================================================");
     log!("");
     // Interleaved output: for each file, show header + holes + counts
+    let mut analyzer = Analyzer::new(args, use_cache).with_blame(blame).with_verification(verification);
     for file in &all_files {
-        if let Ok(stats) = analyze_file(file) {
+        if let Ok(stats) = analyzer.analyze(file) {
             let abs_path = file.canonicalize().unwrap_or_else(|_| file.clone());
             let path_str = if let Ok(rel_path) = file.strip_prefix(&base_dir) {
                 rel_path.display().to_string()
@@ -777,7 +851,7 @@ fn run_emacs_mode(args: &StandardArgs, exclude_dirs: &[PathBuf]) -> Result<()> {
                 let file_content = fs::read_to_string(&abs_path).unwrap_or_default();
                 
                 for hole in &stats.holes.holes {
-                    let msg = format!("{}:{}: error: {} - {}", abs_path.display(), hole.line, hole.hole_type, hole.context);
+                    let msg = format!("{}:{}: error: {} - {}{}", abs_path.display(), hole.line, hole.kind, hole.context, blame_suffix(hole));
                     println!("{}", msg);
                     write_to_log(&msg);
                     for ctx in build_context_lines(&file_content, hole) {
@@ -787,12 +861,8 @@ fn run_emacs_mode(args: &StandardArgs, exclude_dirs: &[PathBuf]) -> Result<()> {
                 }
 
                 for warning in &stats.warnings {
-                    let level = if matches!(warning.hole_type.as_str(), "assume_eq_clone_workaround" | "requires_true") {
-                        "warning"
-                    } else {
-                        "error"
-                    };
-                    let msg = format!("{}:{}: {}: {} - {}", abs_path.display(), warning.line, level, warning.hole_type, warning.context);
+                    let level = stats.warning_level(warning.kind).as_str();
+                    let msg = format!("{}:{}: {}: {} - {}", abs_path.display(), warning.line, level, warning.kind, warning.context);
                     println!("{}", msg);
                     write_to_log(&msg);
                     for ctx in build_context_lines(&file_content, warning) {
@@ -802,10 +872,12 @@ fn run_emacs_mode(args: &StandardArgs, exclude_dirs: &[PathBuf]) -> Result<()> {
                 }
 
                 for info in &stats.infos {
-                    let msg = format!("{}:{}: info: {}", abs_path.display(), info.line, info.hole_type);
+                    let msg = format!("{}:{}: info: {}", abs_path.display(), info.line, info.kind);
                     println!("{}", msg);
                     write_to_log(&msg);
                 }
+
+                print_soft_holes(&abs_path, &stats);
                 
                 if has_holes {
                     let msg = format!("   Holes: {} total", stats.holes.total_holes);
@@ -829,15 +901,17 @@ fn run_emacs_mode(args: &StandardArgs, exclude_dirs: &[PathBuf]) -> Result<()> {
                     write_to_log(&msg);
                 }
             } else {
-                let icon = if has_infos { "ℹ" } else { "✓" };
+                let icon = if !stats.soft_holes.is_empty() { "⚠" } else if has_infos { "ℹ" } else { "✓" };
                 let msg = format!("{} {}", icon, path_str);
                 println!("{}", msg);
                 write_to_log(&msg);
 
+                print_soft_holes(&abs_path, &stats);
+
                 if has_infos {
                     let file_content = fs::read_to_string(&abs_path).unwrap_or_default();
                     for info in &stats.infos {
-                        let msg = format!("{}:{}: info: {}", abs_path.display(), info.line, info.hole_type);
+                        let msg = format!("{}:{}: info: {}", abs_path.display(), info.line, info.kind);
                         println!("{}", msg);
                         write_to_log(&msg);
                     }
@@ -859,6 +933,11 @@ fn run_emacs_mode(args: &StandardArgs, exclude_dirs: &[PathBuf]) -> Result<()> {
             file_stats_map.insert(path_str, stats);
         }
     }
+    let (reused, analyzed) = analyzer.finish();
+    if reused > 0 {
+        log!("");
+        log!("Cache: {} unchanged files reused, {} analyzed", reused, analyzed);
+    }
     
     // Print depends-upon section (before summary)
     print_depends_upon(&file_stats_map);
@@ -866,9 +945,26 @@ fn run_emacs_mode(args: &StandardArgs, exclude_dirs: &[PathBuf]) -> Result<()> {
     // Print summary (uses log! macro which writes to both stdout and log file)
     let summary = compute_summary(&file_stats_map, &base_dir);
     print_summary(&summary);
-    print_chapter_by_chapter_proof_targeting(&file_stats_map, &summary);
+    print_chapter_by_chapter_proof_targeting(&file_stats_map, &summary, &base_dir, grouping);
+    print_transitive_trust(&summary);
+    print_dependency_cycles(&summary);
     
-    Ok(())
+    Ok(file_stats_map)
+}
+
+/// Print a file's soft holes as file:line diagnostics followed by counts per kind
+fn print_soft_holes(abs_path: &Path, stats: &FileHoles) {
+    if stats.soft_holes.is_empty() {
+        return;
+    }
+    for soft in &stats.soft_holes {
+        let level = stats.warning_level(soft.kind).as_str();
+        log!("{}:{}: {}: soft hole {} - {}", abs_path.display(), soft.line, level, soft.kind, soft.context);
+    }
+    log!("   Soft holes: {} total", stats.soft_holes.len());
+    for (kind, n) in stats.soft_hole_counts() {
+        log!("      {} × {}", n, kind);
+    }
 }
 
 /// Print hole counts with a given prefix (and log)
@@ -953,6 +1049,11 @@ fn print_hole_counts_with_log(holes: &ProofHoleStats, prefix: &str) {
         println!("{}", msg);
         write_to_log(&msg);
     }
+    if holes.verification_failure_count > 0 {
+        let msg = format!("{}{} × verification failure", prefix, holes.verification_failure_count);
+        println!("{}", msg);
+        write_to_log(&msg);
+    }
 }
 
 /// Print hole counts with a given prefix (no log)
@@ -1006,38 +1107,26 @@ fn print_hole_counts(holes: &ProofHoleStats, prefix: &str) {
     if holes.trivial_spec_wf_count > 0 {
         println!("{}{} × trivial spec*wf {{ true }}", prefix, holes.trivial_spec_wf_count);
     }
+    if holes.verification_failure_count > 0 {
+        println!("{}{} × verification failure", prefix, holes.verification_failure_count);
+    }
 }
 
 /// Run analysis on a single project (standard mode)
-fn run_single_project_analysis(args: &StandardArgs, exclude_dirs: &[PathBuf]) -> Result<()> {
+fn run_single_project_analysis(args: &StandardArgs, exclude_dirs: &[PathBuf], use_cache: bool, grouping: &Grouping) -> Result<()> {
     // Collect all Rust files from the specified paths
-    let mut all_files: Vec<PathBuf> = Vec::new();
+    let all_files = collect_files(args, exclude_dirs);
     let base_dir = args.base_dir();
     
-    // Handle both file and directory modes
-    for path in &args.paths {
-        if path.is_file() && path.extension().map_or(false, |e| e == "rs") {
-            if !should_exclude(path, exclude_dirs) {
-                all_files.push(path.clone());
-            }
-        } else if path.is_dir() {
-            let files = find_rust_files(&[path.clone()]);
-            for file in files {
-                if !should_exclude(&file, exclude_dirs) {
-                    all_files.push(file);
-                }
-            }
-        }
-    }
-    
-    let mut file_stats_map: HashMap<String, FileStats> = HashMap::new();
+    let mut file_stats_map: HashMap<String, FileHoles> = HashMap::new();
     
     log!("=================================================================");
     log!("1. File Holes");
     log!("=================================================================");
     log!("");
+    let mut analyzer = Analyzer::new(args, use_cache);
     for file in &all_files {
-        if let Ok(stats) = analyze_file(file) {
+        if let Ok(stats) = analyzer.analyze(file) {
             // Use relative path if possible
             let path_str = if let Ok(rel_path) = file.strip_prefix(&base_dir) {
                 rel_path.display().to_string()
@@ -1048,6 +1137,7 @@ fn run_single_project_analysis(args: &StandardArgs, exclude_dirs: &[PathBuf]) ->
             file_stats_map.insert(path_str, stats);
         }
     }
+    analyzer.finish();
     
     // Print depends-upon section (before summary)
     print_depends_upon(&file_stats_map);
@@ -1055,7 +1145,9 @@ fn run_single_project_analysis(args: &StandardArgs, exclude_dirs: &[PathBuf]) ->
     // Print summary
     let summary = compute_summary(&file_stats_map, &base_dir);
     print_summary(&summary);
-    print_chapter_by_chapter_proof_targeting(&file_stats_map, &summary);
+    print_chapter_by_chapter_proof_targeting(&file_stats_map, &summary, &base_dir, grouping);
+    print_transitive_trust(&summary);
+    print_dependency_cycles(&summary);
     
     Ok(())
 }
@@ -1090,10 +1182,11 @@ fn run_multi_codebase_analysis(base_dir: &Path, exclude_dirs: &[PathBuf]) -> Res
         log!("Files: {} Verus files", verus_files.len());
         log!("");
         
-        let mut file_stats_map: HashMap<String, FileStats> = HashMap::new();
+        let mut file_stats_map: HashMap<String, FileHoles> = HashMap::new();
+        let macros = MacroTable::from_files(&verus_files);
         
         for file in &verus_files {
-            if let Ok(stats) = analyze_file(file) {
+            if let Ok(stats) = analyze_file_with_macros(file, &macros) {
                 let path_str = if let Ok(rel_path) = file.strip_prefix(base_dir) {
                     rel_path.display().to_string()
                 } else {
@@ -1108,7 +1201,14 @@ fn run_multi_codebase_analysis(base_dir: &Path, exclude_dirs: &[PathBuf]) -> Res
         print_depends_upon(&file_stats_map);
         let summary = compute_summary(&file_stats_map, base_dir);
         print_project_summary(&project_name, &summary);
-        print_chapter_by_chapter_proof_targeting(&file_stats_map, &summary);
+        // Each project groups by its own veracity.toml
+        let project_dir = base_dir.join(&project_name);
+        let grouping = VeracityConfig::discover(&project_dir).and_then(|c| Grouping::new(&c))
+            .unwrap_or_else(|e| {
+                log!("warning: {}: {:#}; grouping by chapter", project_name, e);
+                Grouping::chapters(&project_dir)
+            });
+        print_chapter_by_chapter_proof_targeting(&file_stats_map, &summary, base_dir, &grouping);
         
         project_stats_vec.push(ProjectStats {
             name: project_name.clone(),
@@ -1129,209 +1229,48 @@ fn run_multi_codebase_analysis(base_dir: &Path, exclude_dirs: &[PathBuf]) -> Res
     Ok(())
 }
 
-/// Discover all projects containing Verus files in a directory
-fn discover_verus_projects(base_dir: &Path, exclude_dirs: &[PathBuf]) -> Result<HashMap<String, Vec<PathBuf>>> {
-    let mut projects: HashMap<String, Vec<PathBuf>> = HashMap::new();
-    
-    // Find all subdirectories (potential projects)
-    for entry in fs::read_dir(base_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_dir() {
-            // Skip hidden directories and common non-project dirs
-            if let Some(name) = path.file_name() {
-                let name_str = name.to_string_lossy();
-                if !name_str.starts_with('.') && name_str != "target" {
-                    // Skip excluded directories
-                    if should_exclude(&path, exclude_dirs) {
-                        continue;
-                    }
-                    let project_name = name.to_string_lossy().to_string();
-                    let verus_files = find_verus_files_in_project(&path, exclude_dirs)?;
-                    
-                    if !verus_files.is_empty() {
-                        projects.insert(project_name, verus_files);
-                    }
-                }
-            }
-        }
+/// Lines around a hole for interactive display: `before` lines before, `after` lines after.
+fn context_lines_around(content: &str, line: usize, before: usize, after: usize) -> Vec<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let total = lines.len();
+    let from = line.saturating_sub(before).saturating_sub(1).max(0);
+    let to = (line + after).min(total);
+    let mut out = Vec::new();
+    for n in from..to {
+        let line_num = n + 1;
+        let marker = if line_num == line { ">" } else { " " };
+        let text = lines.get(n).copied().unwrap_or("").trim_end();
+        out.push(format!("  {} {:>5} | {}", marker, line_num, text));
     }
-    
-    Ok(projects)
+    out
 }
 
-/// Find all Verus files in a project directory
-fn find_verus_files_in_project(project_dir: &Path, exclude_dirs: &[PathBuf]) -> Result<Vec<PathBuf>> {
-    let mut verus_files = Vec::new();
-    
-    // Find all .rs files
-    for entry in WalkDir::new(project_dir).into_iter().filter_map(|e| e.ok()) {
-        let path = entry.path();
-        // Skip excluded directories
-        if should_exclude(path, exclude_dirs) {
-            continue;
-        }
-        if path.is_file() && path.extension().map_or(false, |ext| ext == "rs") {
-            // Check if it contains verus! macro
-            if contains_verus_macro(path)? {
-                verus_files.push(path.to_path_buf());
+/// Build context lines to display after the main hole line.
+/// For attribute holes: show all subsequent attributes plus the declaration they annotate.
+/// For assume/admit holes: show 2 lines before and 2 lines after.
+fn build_context_lines(content: &str, hole: &DetectedHole) -> Vec<String> {
+    let total_lines = content.lines().count();
+    let is_attribute_hole = hole.kind.is_external()
+        || matches!(hole.kind, HoleKind::Opaque | HoleKind::UnsafeFn | HoleKind::UnsafeImpl);
+
+    if matches!(
+        hole.kind,
+        HoleKind::StructOutsideVerus
+            | HoleKind::EnumOutsideVerus
+            | HoleKind::CloneDerivedOutside
+            | HoleKind::DebugDisplayInsideVerus
+    ) {
+        let mut lines = Vec::new();
+        let to = (hole.line + 2).min(total_lines);
+        for n in hole.line..=to {
+            if let Some(line) = get_line(content, n) {
+                lines.push(format!("     {:>5} | {}", n, line.trim_end()));
             }
         }
+        return lines;
     }
-    
-    Ok(verus_files)
-}
 
-/// Check if a file contains verus! or verus_! macro
-fn contains_verus_macro(path: &Path) -> Result<bool> {
-    let content = fs::read_to_string(path)?;
-    let parsed = ra_ap_syntax::SourceFile::parse(&content, ra_ap_syntax::Edition::Edition2021);
-    let tree = parsed.tree();
-    let root = tree.syntax();
-    
-    for node in root.descendants() {
-        if node.kind() == SyntaxKind::MACRO_CALL {
-            if let Some(macro_call) = ast::MacroCall::cast(node) {
-                if let Some(macro_path) = macro_call.path() {
-                    let path_str = macro_path.to_string();
-                    if path_str == "verus" || path_str == "verus_" {
-                        return Ok(true);
-                    }
-                }
-            }
-        }
-    }
-    Ok(false)
-}
-
-/// Compute byte offset of the start of a given line (1-based)
-fn offset_from_line(content: &str, line: usize) -> usize {
-    content
-        .lines()
-        .take(line.saturating_sub(1))
-        .map(|l| l.len() + 1)
-        .sum()
-}
-
-/// Compute line number from byte offset
-fn line_from_offset(content: &str, offset: usize) -> usize {
-    content[..offset.min(content.len())]
-        .chars()
-        .filter(|&c| c == '\n')
-        .count() + 1
-}
-
-/// Check if lines around attr_line contain "accept hole" (flexible on whitespace/punctuation).
-fn has_accept_hole_comment(content: &str, attr_line: usize) -> bool {
-    has_accept_hole_comment_in_range(content, attr_line, 1, 2)
-}
-
-/// Check if "accept hole" appears in a line range [attr_line - before, attr_line + after).
-/// Used for unsafe blocks which may span multiple lines.
-fn has_accept_hole_comment_in_range(content: &str, attr_line: usize, before: usize, after: usize) -> bool {
-    let lines: Vec<&str> = content.lines().collect();
-    let start = attr_line.saturating_sub(before);
-    let end = (attr_line + after).min(lines.len());
-    for line in lines.get(start..end).unwrap_or(&[]) {
-        let s = line.to_lowercase();
-        let normalized: String = s
-            .chars()
-            .map(|c| if c.is_ascii_punctuation() { ' ' } else { c })
-            .collect::<String>()
-            .split_whitespace()
-            .collect::<Vec<_>>()
-            .join("");
-        if normalized.contains("accepthole") {
-            return true;
-        }
-    }
-    false
-}
-
-
-/// Get a trimmed context snippet from around a byte offset
-fn get_context(content: &str, offset: usize) -> String {
-    // Find the start and end of the line containing the offset
-    let start = content[..offset.min(content.len())]
-        .rfind('\n')
-        .map(|p| p + 1)
-        .unwrap_or(0);
-    let end = content[offset.min(content.len())..]
-        .find('\n')
-        .map(|p| p + offset)
-        .unwrap_or(content.len());
-    
-    let line = &content[start..end];
-    // Trim and truncate for display
-    let trimmed = line.trim();
-    if trimmed.len() > 80 {
-        format!("{}...", &trimmed[..77])
-    } else {
-        trimmed.to_string()
-    }
-}
-
-/// Search backwards from `from_line` to find the enclosing `fn` signature line.
-/// Matches lines whose trimmed content contains ` fn ` or starts with `fn `.
-fn find_enclosing_fn_line(content: &str, from_line: usize) -> Option<usize> {
-    let lines: Vec<&str> = content.lines().collect();
-    let start = from_line.saturating_sub(1); // 0-indexed
-    for idx in (0..start).rev() {
-        let trimmed = lines[idx].trim();
-        if trimmed.contains(" fn ") || trimmed.starts_with("fn ") {
-            return Some(idx + 1); // back to 1-indexed
-        }
-    }
-    None
-}
-
-/// Get a specific 1-indexed line from content, trimmed.
-fn get_line(content: &str, line_num: usize) -> Option<String> {
-    content.lines().nth(line_num.saturating_sub(1)).map(|l| l.to_string())
-}
-
-/// Lines around a hole for interactive display: `before` lines before, `after` lines after.
-fn context_lines_around(content: &str, line: usize, before: usize, after: usize) -> Vec<String> {
-    let lines: Vec<&str> = content.lines().collect();
-    let total = lines.len();
-    let from = line.saturating_sub(before).saturating_sub(1).max(0);
-    let to = (line + after).min(total);
-    let mut out = Vec::new();
-    for n in from..to {
-        let line_num = n + 1;
-        let marker = if line_num == line { ">" } else { " " };
-        let text = lines.get(n).map(|s| *s).unwrap_or("").trim_end();
-        out.push(format!("  {} {:>5} | {}", marker, line_num, text));
-    }
-    out
-}
-
-/// Build context lines to display after the main hole line.
-/// For attribute holes: show all subsequent attributes plus the declaration they annotate.
-/// For assume/admit holes: show 2 lines before and 2 lines after.
-fn build_context_lines(content: &str, hole: &DetectedHole) -> Vec<String> {
-    let total_lines = content.lines().count();
-    let is_attribute_hole = hole.hole_type.starts_with("external")
-        || hole.hole_type == "opaque"
-        || hole.hole_type == "unsafe fn"
-        || hole.hole_type == "unsafe impl";
-
-    if hole.hole_type == "struct_outside_verus"
-        || hole.hole_type == "enum_outside_verus"
-        || hole.hole_type == "clone_derived_outside"
-        || hole.hole_type == "debug_display_inside_verus"
-    {
-        let mut lines = Vec::new();
-        let to = (hole.line + 2).min(total_lines);
-        for n in hole.line..=to {
-            if let Some(line) = get_line(content, n) {
-                lines.push(format!("     {:>5} | {}", n, line.trim_end()));
-            }
-        }
-        return lines;
-    }
-
-    if hole.hole_type == "bare_impl" {
+    if hole.kind == HoleKind::BareImpl {
         // Show the impl line and 2 lines after it for context
         let mut lines = Vec::new();
         let to = (hole.line + 2).min(total_lines);
@@ -1393,2490 +1332,93 @@ fn build_context_lines(content: &str, hole: &DetectedHole) -> Vec<String> {
     }
 }
 
-const STANDARD_TRAITS: &[&str] = &[
-    "Clone", "Copy", "Debug", "Display", "Default",
-    "PartialEq", "Eq", "PartialOrd", "Ord", "Hash",
-    "Iterator", "IntoIterator", "Send", "Sync",
-    "View", "DeepView", "ForLoopGhostIteratorNew", "ForLoopGhostIterator",
-    "PartialEqSpecImpl", "Sized", "Drop",
-    "Add", "Sub", "Mul", "Div", "Rem", "Neg",
-    "From", "Into", "TryFrom", "TryInto",
-    "AsRef", "AsMut", "Deref", "DerefMut",
-    "Fn", "FnMut", "FnOnce",
-];
-
-/// Check if an AST bare impl should be ignored (iter_mut, iter_*, only proof fns, etc.)
-fn should_ignore_bare_impl_ast(impl_block: &ast::Impl, content: &str, base_name: &str) -> bool {
-    if base_name.contains("Iter") {
-        return true;
-    }
-
-    // Text-based fallback: fn iter_mut, fn iter, fn iter_*, fn into_iter
-    let impl_text = impl_block.syntax().text().to_string();
-    if impl_text.contains("fn iter_mut") || impl_text.contains("fn iter_") || impl_text.contains("fn iter(") || impl_text.contains("fn into_iter") {
-        return true;
-    }
-
-    // #[verifier::external] on impl — check preceding lines
-    let offset: usize = impl_block.syntax().text_range().start().into();
-    let line = line_from_offset(content, offset);
-    let lines: Vec<&str> = content.lines().collect();
-    for i in (0..line.saturating_sub(1)).rev().take(5) {
-        if i < lines.len() {
-            let l = lines[i];
-            if l.contains("verifier") && l.contains("external") {
-                return true;
-            }
-            if !l.trim().starts_with("#[") && !l.trim().is_empty() {
-                break;
-            }
-        }
-    }
-
-    let mut has_any_fn = false;
-    let mut all_proof_or_spec_fn = true;
-    let mut has_iter_method = false;
-
-    if let Some(item_list) = impl_block.assoc_item_list() {
-        for assoc in item_list.assoc_items() {
-            if let ast::AssocItem::Fn(fn_def) = assoc {
-                has_any_fn = true;
-                if let Some(name) = fn_def.name() {
-                    let fn_name = name.text();
-                    if fn_name.starts_with("iter") || fn_name == "into_iter" {
-                        has_iter_method = true;
-                    }
-                }
-                let fn_text = fn_def.syntax().text().to_string();
-                let is_proof_or_spec = fn_text.contains(" proof fn ")
-                    || fn_text.starts_with("proof fn ")
-                    || fn_text.contains(" spec fn ")
-                    || fn_text.starts_with("spec fn ")
-                    || fn_text.contains(" open spec fn ")
-                    || fn_text.contains(" closed spec fn ");
-                if !is_proof_or_spec {
-                    all_proof_or_spec_fn = false;
-                }
-            }
-        }
-    }
-
-    if has_iter_method {
-        return true;
-    }
-    if has_any_fn && all_proof_or_spec_fn {
-        return true;
-    }
-    false
-}
-
-/// Collect derive names from #[derive(...)] attributes on the lines before a given offset.
-fn get_derives_before_offset(content: &str, offset: usize) -> Vec<String> {
-    let mut derives = Vec::new();
-    let content_len = content.len();
-    let offset = offset.min(content_len);
-    // Line ending before offset (exclusive end of previous line)
-    let mut line_end = offset;
-    while line_end > 0 {
-        let prev_newline = content[..line_end].rfind('\n');
-        let Some(newline_pos) = prev_newline else {
-            break;
-        };
-        let line_start = content[..newline_pos].rfind('\n').map(|p| p + 1).unwrap_or(0);
-        let line = content[line_start..newline_pos].trim();
-        if !line.is_empty() && !line.starts_with("#[derive(") {
-            break;
-        }
-        if line.starts_with("#[derive(") {
-            if let Some(inner) = line
-                .strip_prefix("#[derive(")
-                .and_then(|s| s.strip_suffix(")]"))
-            {
-                for part in inner.split(',') {
-                    derives.push(part.trim().to_string());
-                }
-            }
-        }
-        line_end = newline_pos;
-    }
-    derives
-}
-
-/// Find structs and enums defined outside verus! (only meaningful when file has verus!).
-/// Also flags structs with #[derive(Clone)] — Clone should be implemented inside verus!.
-/// Find the offset of the "struct" keyword within a struct/enum node (skips doc comments).
-fn struct_keyword_offset(node: &SyntaxNode) -> usize {
-    for token in node.descendants_with_tokens().filter_map(|n| n.into_token()) {
-        if token.kind() == SyntaxKind::STRUCT_KW || token.kind() == SyntaxKind::ENUM_KW {
-            return token.text_range().start().into();
-        }
-    }
-    node.text_range().start().into()
-}
-
-fn detect_structs_outside_verus(root: &SyntaxNode, content: &str, stats: &mut FileStats) {
-    for node in root.descendants() {
-        if node.kind() == SyntaxKind::STRUCT {
-            if let Some(struct_def) = ast::Struct::cast(node.clone()) {
-                let name = struct_def.name().map(|n| n.text().to_string()).unwrap_or_else(|| "?".to_string());
-                let offset: usize = struct_keyword_offset(struct_def.syntax());
-                let line = line_from_offset(content, offset);
-                if has_accept_hole_comment(content, line) {
-                    stats.infos.push(DetectedHole {
-                        line,
-                        hole_type: "struct_outside_verus_accept_hole".to_string(),
-                        context: format!("struct {} — outside verus! with accept hole comment", name),
-                    });
-                } else {
-                    stats.warnings.push(DetectedHole {
-                        line,
-                        hole_type: "struct_outside_verus".to_string(),
-                        context: format!("struct {} — should be inside verus!", name),
-                    });
-                }
-                let derives = get_derives_before_offset(content, offset);
-                if derives.iter().any(|d| d == "Clone") && !has_accept_hole_comment(content, line) {
-                    stats.warnings.push(DetectedHole {
-                        line,
-                        hole_type: "clone_derived_outside".to_string(),
-                        context: format!("struct {} — Clone should be implemented inside verus!, not derived outside", name),
-                    });
-                }
-            }
-        }
-        if node.kind() == SyntaxKind::ENUM {
-            if let Some(enum_def) = ast::Enum::cast(node.clone()) {
-                let name = enum_def.name().map(|n| n.text().to_string()).unwrap_or_else(|| "?".to_string());
-                let offset: usize = struct_keyword_offset(enum_def.syntax());
-                let line = line_from_offset(content, offset);
-                if has_accept_hole_comment(content, line) {
-                    stats.infos.push(DetectedHole {
-                        line,
-                        hole_type: "enum_outside_verus_accept_hole".to_string(),
-                        context: format!("enum {} — outside verus! with accept hole comment", name),
-                    });
-                } else {
-                    stats.warnings.push(DetectedHole {
-                        line,
-                        hole_type: "enum_outside_verus".to_string(),
-                        context: format!("enum {} — should be inside verus!", name),
-                    });
-                }
-                let derives = get_derives_before_offset(content, offset);
-                if derives.iter().any(|d| d == "Clone") && !has_accept_hole_comment(content, line) {
-                    stats.warnings.push(DetectedHole {
-                        line,
-                        hole_type: "clone_derived_outside".to_string(),
-                        context: format!("enum {} — Clone should be implemented inside verus!, not derived outside", name),
-                    });
-                }
-            }
-        }
-    }
-}
-
-fn detect_bare_impl_warnings(root: &SyntaxNode, content: &str) -> Vec<DetectedHole> {
-    let mut user_traits: Vec<String> = Vec::new();
-    let mut bare_impls: Vec<(String, usize, usize)> = Vec::new(); // (type_name, line, offset)
-
-    // AST pass for code outside verus! macros
-    for node in root.descendants() {
-        if node.kind() == SyntaxKind::TRAIT {
-            if let Some(trait_def) = ast::Trait::cast(node.clone()) {
-                if let Some(name) = trait_def.name() {
-                    let name_str = name.text().to_string();
-                    if !STANDARD_TRAITS.contains(&name_str.as_str()) {
-                        user_traits.push(name_str);
-                    }
-                }
-            }
-        }
-        if node.kind() == SyntaxKind::IMPL {
-            if let Some(impl_block) = ast::Impl::cast(node.clone()) {
-                if impl_block.trait_().is_none() {
-                    if let Some(self_ty) = impl_block.self_ty() {
-                        let type_str = self_ty.to_string();
-                        let base_name = type_str.split('<').next()
-                            .unwrap_or(&type_str).trim().to_string();
-                        let offset: usize = node.text_range().start().into();
-                        let line = line_from_offset(content, offset);
-                        if !should_ignore_bare_impl_ast(&impl_block, content, &base_name) {
-                            bare_impls.push((base_name, line, offset));
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    // Token pass inside verus! / verus_! macros
-    for node in root.descendants() {
-        if node.kind() == SyntaxKind::MACRO_CALL {
-            if let Some(macro_call) = ast::MacroCall::cast(node) {
-                if let Some(macro_path) = macro_call.path() {
-                    let path_str = macro_path.to_string();
-                    if path_str == "verus" || path_str == "verus_" {
-                        if let Some(token_tree) = macro_call.token_tree() {
-                            detect_traits_and_bare_impls_in_tokens(
-                                token_tree.syntax(), content,
-                                &mut user_traits, &mut bare_impls,
-                            );
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    if user_traits.is_empty() {
-        return Vec::new();
-    }
-
-    bare_impls.iter().map(|(bare_type, line, offset)| {
-        let context_line = get_context(content, *offset);
-        DetectedHole {
-            line: *line,
-            hole_type: "bare_impl".to_string(),
-            context: format!("{} — `impl {}` without trait; file defines [{}]",
-                context_line, bare_type, user_traits.join(", ")),
-        }
-    }).collect()
-}
-
-/// Check if a bare impl should be ignored based on its contents.
-/// Returns true if the impl contains only proof fns, or has iter/into_iter methods,
-/// or the type is an iterator type, or the impl has #[verifier::external].
-fn should_ignore_bare_impl(
-    tokens: &[ra_ap_syntax::SyntaxToken],
-    impl_idx: usize,
-    body_start: usize,
-    type_name: &str,
-) -> bool {
-    // Rule: iterator types (name contains "Iter")
-    if type_name.contains("Iter") {
-        return true;
-    }
-
-    // Rule: #[verifier::external] on the impl — scan backwards for it
-    {
-        let mut k = impl_idx.saturating_sub(1);
-        // skip whitespace/comments backwards
-        while k > 0 && matches!(tokens[k].kind(),
-            SyntaxKind::WHITESPACE | SyntaxKind::COMMENT) {
-            k -= 1;
-        }
-        // Check for ] which would close an attribute
-        if k > 0 && tokens[k].kind() == SyntaxKind::R_BRACK {
-            // Walk back to find the attribute contents
-            let mut depth = 1;
-            let mut kk = k - 1;
-            while kk > 0 && depth > 0 {
-                if tokens[kk].kind() == SyntaxKind::R_BRACK { depth += 1; }
-                if tokens[kk].kind() == SyntaxKind::L_BRACK { depth -= 1; }
-                if depth > 0 { kk -= 1; }
-            }
-            // Collect text between [ and ] to check for verifier::external
-            let attr_text: String = tokens[kk..=k].iter()
-                .map(|t| t.text().to_string()).collect();
-            if attr_text.contains("verifier") && attr_text.contains("external") {
-                return true;
-            }
-        }
-    }
-
-    // Scan the impl body to classify its functions
-    // body_start points to L_CURLY of the impl body
-    let mut j = body_start + 1;
-    let mut inner_brace: i32 = 1;
-    let mut has_any_fn = false;
-    let mut all_proof_or_spec_fn = true;
-    let mut has_iter_method = false;
-
-    while j < tokens.len() && inner_brace > 0 {
-        match tokens[j].kind() {
-            SyntaxKind::L_CURLY => inner_brace += 1,
-            SyntaxKind::R_CURLY => inner_brace -= 1,
-            SyntaxKind::FN_KW if inner_brace == 1 => {
-                has_any_fn = true;
-
-                // Check function name
-                let mut n = j + 1;
-                while n < tokens.len() && tokens[n].kind() == SyntaxKind::WHITESPACE {
-                    n += 1;
-                }
-                if n < tokens.len() && tokens[n].kind() == SyntaxKind::IDENT {
-                    let fn_name = tokens[n].text();
-                    if fn_name.starts_with("iter") || fn_name == "into_iter" {
-                        has_iter_method = true;
-                    }
-                }
-
-                // proof fn or spec fn (open/closed spec fn) — OK in bare impl
-                let mut is_proof_or_spec_fn = false;
-                let lookback = j.saturating_sub(15);
-                for p in lookback..j {
-                    if tokens[p].kind() == SyntaxKind::IDENT {
-                        match tokens[p].text() {
-                            "proof" | "spec" => { is_proof_or_spec_fn = true; break; }
-                            _ => {}
-                        }
-                    }
-                }
-                if !is_proof_or_spec_fn {
-                    all_proof_or_spec_fn = false;
-                }
-            }
-            _ => {}
-        }
-        j += 1;
-    }
-
-    // Rule: contains iter/into_iter method
-    if has_iter_method {
-        return true;
-    }
-
-    // Rule: all functions are proof fn or spec fn (and there is at least one)
-    if has_any_fn && all_proof_or_spec_fn {
-        return true;
-    }
-
-    false
-}
-
-fn detect_traits_and_bare_impls_in_tokens(
-    tree: &SyntaxNode,
-    content: &str,
-    user_traits: &mut Vec<String>,
-    bare_impls: &mut Vec<(String, usize, usize)>,
-) {
-    let tokens: Vec<_> = tree.descendants_with_tokens()
-        .filter_map(|n| n.into_token())
-        .collect();
-
-    let mut brace_depth: i32 = 0;
-    let mut i = 0;
-
-    while i < tokens.len() {
-        match tokens[i].kind() {
-            SyntaxKind::L_CURLY => brace_depth += 1,
-            SyntaxKind::R_CURLY => brace_depth -= 1,
-            SyntaxKind::TRAIT_KW if brace_depth == 1 => {
-                let mut j = i + 1;
-                while j < tokens.len() && tokens[j].kind() == SyntaxKind::WHITESPACE {
-                    j += 1;
-                }
-                if j < tokens.len() && tokens[j].kind() == SyntaxKind::IDENT {
-                    let name = tokens[j].text().to_string();
-                    if !STANDARD_TRAITS.contains(&name.as_str()) {
-                        user_traits.push(name);
-                    }
-                }
-            }
-            SyntaxKind::IMPL_KW if brace_depth == 1 => {
-                let impl_offset: usize = tokens[i].text_range().start().into();
-                let impl_line = line_from_offset(content, impl_offset);
-
-                let mut j = i + 1;
-                let mut angle_depth: i32 = 0;
-                let mut found_for = false;
-                let mut type_name = String::new();
-
-                while j < tokens.len() {
-                    let kind = tokens[j].kind();
-                    match kind {
-                        SyntaxKind::L_ANGLE => angle_depth += 1,
-                        SyntaxKind::R_ANGLE => angle_depth = (angle_depth - 1).max(0),
-                        SyntaxKind::L_CURLY if angle_depth == 0 => break,
-                        SyntaxKind::FOR_KW if angle_depth == 0 => {
-                            found_for = true;
-                            break;
-                        }
-                        SyntaxKind::IDENT if angle_depth == 0 && type_name.is_empty() => {
-                            type_name = tokens[j].text().to_string();
-                        }
-                        _ => {}
-                    }
-                    j += 1;
-                }
-
-                if !found_for && !type_name.is_empty() {
-                    // j points to L_CURLY (body start)
-                    if !should_ignore_bare_impl(&tokens, i, j, &type_name) {
-                        bare_impls.push((type_name, impl_line, impl_offset));
-                    }
-                }
-            }
-            _ => {}
-        }
-        i += 1;
-    }
-}
-
-fn analyze_file(path: &Path) -> Result<FileStats> {
-    let content = fs::read_to_string(path)?;
-    let mut stats = FileStats::default();
 
-    // Use ra_ap_syntax for token-based attribute detection
-    // This catches ALL attributes regardless of Verus syntax
-    let parsed = ra_ap_syntax::SourceFile::parse(&content, ra_ap_syntax::Edition::Edition2021);
-    let source_file = parsed.tree();
-    let root = source_file.syntax();
-    
-    let mut found_verus_macro = false;
-    
-    // Scan for assume/admit calls and attributes in verus! and verus_! macros
-    for node in root.descendants() {
-        if node.kind() == SyntaxKind::MACRO_CALL {
-            if let Some(macro_call) = ast::MacroCall::cast(node.clone()) {
-                if let Some(macro_path) = macro_call.path() {
-                    let path_str = macro_path.to_string();
-                    if path_str == "verus" || path_str == "verus_" {
-                        if let Some(token_tree) = macro_call.token_tree() {
-                            found_verus_macro = true;
-                            analyze_verus_block(token_tree.syntax(), &content, &mut stats);
-                        }
-                    }
-                }
-            }
-        }
-    }
-    
-    // If no verus! macro found, scan for attributes at the file level (for non-Verus Rust files)
-    if !found_verus_macro {
-        analyze_attributes_with_ra_syntax(&root, &content, &mut stats);
-        stats.warnings.push(DetectedHole {
-            line: 1,
-            hole_type: "not_verusified".to_string(),
-            context: "File has no verus! block — not verusified.".to_string(),
-        });
-    }
+fn print_file_report(path: &str, stats: &FileHoles) {
+    let has_holes = stats.holes.total_holes > 0;
     
-    // Always scan the entire file for unsafe patterns (they can appear outside verus! blocks)
-    analyze_unsafe_patterns(&root, &content, &mut stats);
-
-    stats.warnings.extend(detect_bare_impl_warnings(&root, &content));
-
-    if found_verus_macro {
-        detect_structs_outside_verus(&root, &content, &mut stats);
-    }
-
-    detect_rust_rwlock(&content, &mut stats);
-
-    extract_crate_deps(&root, &content, &mut stats);
-
-    Ok(stats)
-}
-
-/// Extract crate:: module dependencies from use statements (excluding accept).
-fn extract_crate_deps(root: &SyntaxNode, content: &str, stats: &mut FileStats) {
-    for node in root.descendants() {
-        if let Some(use_item) = ast::Use::cast(node.clone()) {
-            extract_crate_paths_from_use_tree(use_item.use_tree(), stats);
-        }
-    }
-    // Also scan verus! macro bodies and broadcast use for crate:: paths
-    for node in root.descendants() {
-        if node.kind() == SyntaxKind::MACRO_CALL {
-            if let Some(macro_call) = ast::MacroCall::cast(node.clone()) {
-                if let Some(path) = macro_call.path() {
-                    let name = path.to_string();
-                    if name == "verus" || name == "verus_" {
-                        if let Some(tt) = macro_call.token_tree() {
-                            let range = tt.syntax().text_range();
-                            let start: usize = range.start().into();
-                            let end: usize = range.end().into();
-                            if start + 1 < content.len() && end <= content.len() {
-                                let inner = &content[start + 1..end - 1];
-                                extract_crate_deps_from_text(inner, stats);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-}
-
-fn extract_crate_paths_from_use_tree(use_tree: Option<ast::UseTree>, stats: &mut FileStats) {
-    let Some(tree) = use_tree else { return };
-    if let Some(path) = tree.path() {
-        let segments: Vec<String> = path.segments()
-            .filter_map(|s| s.name_ref())
-            .map(|n| n.text().to_string())
-            .collect();
-        if let Some(first) = segments.first() {
-            if first == "crate" && segments.len() >= 2 {
-                let path_str = segments[1..].join("::");
-                if !path_contains_accept(&path_str) {
-                    let module = path_to_module(&segments[1..]);
-                    stats.crate_deps.insert(module);
-                }
-            }
+    if has_holes {
+        log!("❌ {}", path);
+        log!("   Holes: {} total", stats.holes.total_holes);
+        
+        if stats.holes.assume_false_count > 0 {
+            log!("      {} × assume(false)", stats.holes.assume_false_count);
         }
-    }
-    if let Some(list) = tree.use_tree_list() {
-        for nested in list.use_trees() {
-            extract_crate_paths_from_use_tree(Some(nested), stats);
+        if stats.holes.assume_count > 0 {
+            log!("      {} × assume()", stats.holes.assume_count);
         }
-    }
-}
-
-fn path_contains_accept(path: &str) -> bool {
-    path.split("::").any(|s| s == "accept")
-}
-
-/// Convert path segments to the module we depend on.
-/// e.g. ["Chap02","SetStEph","Foo"] -> "Chap02::SetStEph" (module containing Foo)
-/// e.g. ["Chap02","SetStEph"] -> "Chap02::SetStEph" (the module itself)
-fn path_to_module(segments: &[String]) -> String {
-    if segments.len() <= 2 {
-        segments.join("::")
-    } else {
-        segments[..segments.len() - 1].join("::")
-    }
-}
-
-fn extract_crate_deps_from_text(text: &str, stats: &mut FileStats) {
-    // Find crate:: paths in text (e.g. inside verus! or broadcast use)
-    let mut i = 0;
-    while let Some(pos) = text[i..].find("crate::") {
-        let start = i + pos + "crate::".len();
-        let mut end = start;
-        while end < text.len() {
-            let c = text.as_bytes().get(end).copied();
-            match c {
-                Some(b'a'..=b'z') | Some(b'A'..=b'Z') | Some(b'0'..=b'9') | Some(b'_') => end += 1,
-                Some(b':') if end + 1 < text.len() && text.as_bytes()[end + 1] == b':' => end += 2,
-                _ => break,
-            }
+        if stats.holes.assume_new_count > 0 {
+            log!("      {} × Tracked::assume_new()", stats.holes.assume_new_count);
         }
-        if end > start {
-            let path = &text[start..end];
-            if !path_contains_accept(path) {
-                let segs: Vec<&str> = path.split("::").collect();
-                let module = if segs.len() <= 1 {
-                    path.to_string()
-                } else {
-                    segs[..segs.len() - 1].join("::")
-                };
-                stats.crate_deps.insert(module);
-            }
+        if stats.holes.assume_specification_count > 0 {
+            log!("      {} × assume_specification", stats.holes.assume_specification_count);
         }
-        i = end;
-    }
-}
-
-fn detect_rust_rwlock(content: &str, stats: &mut FileStats) {
-    for (line_no, line) in content.lines().enumerate() {
-        if line.contains("std::sync::RwLock") {
-            stats.warnings.push(DetectedHole {
-                line: line_no + 1,
-                hole_type: "rust_rwlock".to_string(),
-                context: "Use Verus RwLock (vstd::rwlock::RwLock), not std::sync::RwLock.".to_string(),
-            });
-        }
-    }
-}
-
-/// Analyze unsafe patterns across the entire file (including outside verus! blocks)
-/// This catches unsafe fn, unsafe impl, unsafe blocks that may be in regular Rust code
-fn analyze_unsafe_patterns(root: &SyntaxNode, content: &str, stats: &mut FileStats) {
-    let tokens: Vec<_> = root.descendants_with_tokens()
-        .filter_map(|n| n.into_token())
-        .collect();
-    
-    for i in 0..tokens.len() {
-        let token = &tokens[i];
-        
-        // Look for unsafe keyword (as UNSAFE_KW - regular Rust syntax)
-        if token.kind() == SyntaxKind::UNSAFE_KW {
-            let offset: usize = token.text_range().start().into();
-            let line = line_from_offset(content, offset);
-            let context = get_context(content, offset);
-            
-            // Look ahead to see what follows
-            let mut j = i + 1;
-            // Skip whitespace
-            while j < tokens.len() && tokens[j].kind() == SyntaxKind::WHITESPACE {
-                j += 1;
-            }
-            if j < tokens.len() {
-                match tokens[j].kind() {
-                    SyntaxKind::FN_KW => {
-                        if has_accept_hole_comment(content, line) {
-                            stats.infos.push(DetectedHole {
-                                line,
-                                hole_type: "unsafe_fn_accept_hole".to_string(),
-                                context: "unsafe fn with accept hole comment".to_string(),
-                            });
-                        } else {
-                            stats.holes.unsafe_fn_count += 1;
-                            stats.holes.total_holes += 1;
-                            stats.holes.holes.push(DetectedHole {
-                                line,
-                                hole_type: "unsafe fn".to_string(),
-                                context,
-                            });
-                        }
-                    }
-                    SyntaxKind::IMPL_KW => {
-                        if has_accept_hole_comment(content, line) {
-                            stats.infos.push(DetectedHole {
-                                line,
-                                hole_type: "unsafe_impl_accept_hole".to_string(),
-                                context: "unsafe impl with accept hole comment".to_string(),
-                            });
-                        } else {
-                            stats.holes.unsafe_impl_count += 1;
-                            stats.holes.total_holes += 1;
-                            stats.holes.holes.push(DetectedHole {
-                                line,
-                                hole_type: "unsafe impl".to_string(),
-                                context,
-                            });
-                        }
-                    }
-                    SyntaxKind::L_CURLY => {
-                        // Unsafe blocks may span multiple lines; check wider range for // accept hole
-                        if has_accept_hole_comment_in_range(content, line, 1, 6) {
-                            stats.infos.push(DetectedHole {
-                                line,
-                                hole_type: "unsafe_block_accept_hole".to_string(),
-                                context: "unsafe {{}} with accept hole comment".to_string(),
-                            });
-                        } else {
-                            stats.holes.unsafe_block_count += 1;
-                            stats.holes.total_holes += 1;
-                            stats.holes.holes.push(DetectedHole {
-                                line,
-                                hole_type: "unsafe {}".to_string(),
-                                context,
-                            });
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-        // Note: assume_new is handled in analyze_verus_macro() for verus! blocks
-    }
-}
-
-// Analyze attributes using ra_ap_syntax token walking
-// This is the most reliable method for Verus files as it catches all attributes
-// regardless of whether the Rust parser can fully understand Verus syntax
-fn analyze_attributes_with_ra_syntax(root: &SyntaxNode, content: &str, stats: &mut FileStats) {
-    let all_tokens: Vec<_> = root.descendants_with_tokens()
-        .filter_map(|n| n.into_token())
-        .collect();
-    
-    for (i, token) in all_tokens.iter().enumerate() {
-        if token.kind() == SyntaxKind::POUND {
-            if let Some(attr) = detect_verifier_attribute(&all_tokens, i) {
-                let offset: usize = token.text_range().start().into();
-                let line = line_from_offset(content, offset);
-                let context = get_context(content, offset);
-                
-                match attr {
-                    VerifierAttribute::ExecAllowsNoDecreasesClause => {
-                        // No hole — used for diverge() etc., skip fn_missing_requires
-                    }
-                    VerifierAttribute::ExternalBody => {
-                        if has_accept_hole_comment(content, line) {
-                            stats.infos.push(DetectedHole {
-                                line,
-                                hole_type: "external_body_accept_hole".to_string(),
-                                context: "external_body with accept hole comment".to_string(),
-                            });
-                        } else {
-                            stats.holes.external_body_count += 1;
-                            stats.holes.total_holes += 1;
-                            stats.holes.holes.push(DetectedHole {
-                                line,
-                                hole_type: "external_body".to_string(),
-                                context,
-                            });
-                        }
-                    }
-                    VerifierAttribute::ExternalFnSpec => {
-                        if has_accept_hole_comment(content, line) {
-                            stats.infos.push(DetectedHole {
-                                line,
-                                hole_type: "external_fn_specification_accept_hole".to_string(),
-                                context: "external_fn_specification with accept hole comment".to_string(),
-                            });
-                        } else {
-                            stats.holes.external_fn_spec_count += 1;
-                            stats.holes.total_holes += 1;
-                            stats.holes.holes.push(DetectedHole {
-                                line,
-                                hole_type: "external_fn_specification".to_string(),
-                                context,
-                            });
-                        }
-                    }
-                    VerifierAttribute::ExternalTraitSpec => {
-                        if has_accept_hole_comment(content, line) {
-                            stats.infos.push(DetectedHole {
-                                line,
-                                hole_type: "external_trait_specification_accept_hole".to_string(),
-                                context: "external_trait_specification with accept hole comment".to_string(),
-                            });
-                        } else {
-                            stats.holes.external_trait_spec_count += 1;
-                            stats.holes.total_holes += 1;
-                            stats.holes.holes.push(DetectedHole {
-                                line,
-                                hole_type: "external_trait_specification".to_string(),
-                                context,
-                            });
-                        }
-                    }
-                    VerifierAttribute::ExternalTypeSpec => {
-                        if has_accept_hole_comment(content, line) {
-                            stats.infos.push(DetectedHole {
-                                line,
-                                hole_type: "external_type_specification_accept_hole".to_string(),
-                                context: "external_type_specification with accept hole comment".to_string(),
-                            });
-                        } else {
-                            stats.holes.external_type_spec_count += 1;
-                            stats.holes.total_holes += 1;
-                            stats.holes.holes.push(DetectedHole {
-                                line,
-                                hole_type: "external_type_specification".to_string(),
-                                context,
-                            });
-                        }
-                    }
-                    VerifierAttribute::ExternalTraitExt => {
-                        if has_accept_hole_comment(content, line) {
-                            stats.infos.push(DetectedHole {
-                                line,
-                                hole_type: "external_trait_extension_accept_hole".to_string(),
-                                context: "external_trait_extension with accept hole comment".to_string(),
-                            });
-                        } else {
-                            stats.holes.external_trait_ext_count += 1;
-                            stats.holes.total_holes += 1;
-                            stats.holes.holes.push(DetectedHole {
-                                line,
-                                hole_type: "external_trait_extension".to_string(),
-                                context,
-                            });
-                        }
-                    }
-                    VerifierAttribute::External => {
-                        if has_accept_hole_comment(content, line) {
-                            stats.infos.push(DetectedHole {
-                                line,
-                                hole_type: "external_accept_hole".to_string(),
-                                context: "external with accept hole comment".to_string(),
-                            });
-                        } else {
-                            stats.holes.external_count += 1;
-                            stats.holes.total_holes += 1;
-                            stats.holes.holes.push(DetectedHole {
-                                line,
-                                hole_type: "external".to_string(),
-                                context,
-                            });
-                        }
-                    }
-                    VerifierAttribute::Opaque => {
-                        stats.holes.opaque_count += 1;
-                        stats.holes.total_holes += 1;
-                        stats.holes.holes.push(DetectedHole {
-                            line,
-                            hole_type: "opaque".to_string(),
-                            context,
-                        });
-                    }
-                    VerifierAttribute::Axiom => {
-                        // #[verifier::axiom] attribute - tracked separately as axiom
-                        stats.axioms.axiom_fn_count += 1;
-                        stats.axioms.total_axioms += 1;
-                    }
-                }
-            }
-        }
-    }
-}
-
-/// Check whether `diverge()` follows after the `false` token in `assume(false); diverge()`.
-/// `start` should point to the token after `false` (i.e., the `)` of `assume(false)`).
-fn has_diverge_after(tokens: &[ra_ap_syntax::SyntaxToken], start: usize) -> bool {
-    let mut j = start;
-    while j < tokens.len() {
-        match tokens[j].kind() {
-            SyntaxKind::R_PAREN | SyntaxKind::SEMICOLON | SyntaxKind::WHITESPACE => j += 1,
-            _ => break,
-        }
-    }
-    if j < tokens.len() && tokens[j].kind() == SyntaxKind::IDENT && tokens[j].text() == "diverge" {
-        j += 1;
-        while j < tokens.len() && tokens[j].kind() == SyntaxKind::WHITESPACE {
-            j += 1;
-        }
-        if j < tokens.len() && tokens[j].kind() == SyntaxKind::L_PAREN {
-            return true;
-        }
-    }
-    false
-}
-
-/// Analyze verus! block content using verus_syn (Verus parser).
-/// Falls back to token-based analysis if verus_syn fails to parse.
-fn analyze_verus_block(
-    token_tree_syntax: &SyntaxNode,
-    content: &str,
-    stats: &mut FileStats,
-) {
-    let range = token_tree_syntax.text_range();
-    let start: usize = range.start().into();
-    let end: usize = range.end().into();
-    // Token tree is { ... } — inner content is between the braces
-    if start + 2 > content.len() || end > content.len() {
-        analyze_verus_macro_tokens(token_tree_syntax, content, stats);
-        return;
-    }
-    let inner = &content[start + 1..end - 1];
-    let brace_line = content[..=start].lines().count();
-    let line_offset = brace_line.saturating_sub(1);
-
-    match verus_syn::parse_file(inner) {
-        Ok(file) => {
-            let mut visitor = ProofHoleVisitor::new(content, line_offset, stats);
-            visitor.visit_file(&file);
-        }
-        Err(_) => {
-            // Fallback: token-based analysis when verus_syn can't parse
-            analyze_verus_macro_tokens(token_tree_syntax, content, stats);
-        }
-    }
-}
-
-/// Visitor that walks the Verus AST to detect proof holes, assume/admit, verifier attrs, etc.
-struct ProofHoleVisitor<'a> {
-    content: &'a str,
-    line_offset: usize,
-    stats: &'a mut FileStats,
-    /// Trait being implemented (e.g. "Eq", "PartialEq", "Clone") when inside an impl block
-    current_impl_trait: Option<String>,
-    /// Impl self type (e.g. "ArraySeqMtEphS<T>") when inside an impl block
-    current_impl_type: Option<String>,
-    /// Name of the function we're visiting (e.g. "eq", "clone")
-    current_fn_name: Option<String>,
-    /// When true, external_body on current fn is a Verus RwLock constructor — add warning, not hole
-    suppress_external_body_hole: bool,
-}
-
-impl<'a> ProofHoleVisitor<'a> {
-    fn new(content: &'a str, line_offset: usize, stats: &'a mut FileStats) -> Self {
-        Self {
-            content,
-            line_offset,
-            stats,
-            current_impl_trait: None,
-            current_impl_type: None,
-            current_fn_name: None,
-            suppress_external_body_hole: false,
-        }
-    }
-
-    fn return_type_contains_verus_rwlock(output: &verus_syn::ReturnType) -> bool {
-        use verus_syn::ReturnType;
-        let ty_str = match output {
-            ReturnType::Default => return false,
-            ReturnType::Type(_, _, _, ty) => ty.to_token_stream().to_string(),
-        };
-        ty_str.contains("RwLock") && !ty_str.contains("std::sync::RwLock")
-    }
-
-    fn return_type_is_bool(output: &verus_syn::ReturnType) -> bool {
-        use verus_syn::ReturnType;
-        let ty_str = match output {
-            ReturnType::Default => return false,
-            ReturnType::Type(_, _, _, ty) => ty.to_token_stream().to_string(),
-        };
-        ty_str.trim() == "bool"
-    }
-
-    fn is_in_eq_or_clone_context(&self) -> bool {
-        let fn_ok = self
-            .current_fn_name
-            .as_deref()
-            .map_or(false, |n| n == "eq" || n == "clone" || n == "clone_tree" || n == "clone_link");
-        let trait_ok = self
-            .current_impl_trait
-            .as_deref()
-            .map_or(false, |t| t == "Eq" || t == "PartialEq" || t == "Clone");
-        // eq/clone in impl Eq/PartialEq/Clone, or standalone clone_tree/clone_link helper
-        (fn_ok && trait_ok)
-            || matches!(
-                self.current_fn_name.as_deref(),
-                Some("clone_tree") | Some("clone_link")
-            )
-    }
-
-    /// fn X() with no parameters has no pre-state to precondition — no requires needed.
-    fn fn_has_no_params(sig: &verus_syn::Signature) -> bool {
-        sig.inputs.is_empty()
-    }
-
-    /// Warn for each requires clause that is just `true` (vacuous precondition).
-    fn check_requires_true(&mut self, sig: &verus_syn::Signature) {
-        if let Some(ref r) = sig.spec.requires {
-            for expr in &r.exprs.exprs {
-                let s = expr.to_token_stream().to_string();
-                if s.trim() == "true" {
-                    self.stats.warnings.push(DetectedHole {
-                        line: self.file_line(expr.span()),
-                        hole_type: "requires_true".to_string(),
-                        context: "requires true — vacuous precondition".to_string(),
-                    });
-                }
-            }
-        }
-    }
-
-    fn file_line(&self, span: Span) -> usize {
-        let line = span.start().line;
-        line.saturating_add(self.line_offset).max(1)
-    }
-
-    fn context_at(&self, line: usize) -> String {
-        let offset = offset_from_line(self.content, line);
-        get_context(self.content, offset)
-            .chars()
-            .take(60)
-            .collect::<String>()
-    }
-}
-
-impl<'a> Visit<'a> for ProofHoleVisitor<'a> {
-    fn visit_item_fn(&mut self, i: &'a verus_syn::ItemFn) {
-        use verus_syn::FnMode;
-        let line = self.file_line(i.sig.ident.span());
-        let name = i.sig.ident.to_string();
-        let prev_fn = self.current_fn_name.replace(name.clone());
-        self.check_requires_true(&i.sig);
-
-        let has_external_body = i.attrs.iter().any(|a| {
-            detect_verifier_attr_verus_syn(a) == Some(VerifierAttribute::ExternalBody)
-        });
-        let has_exec_allows_no_decreases = i.attrs.iter().any(|a| {
-            detect_verifier_attr_verus_syn(a) == Some(VerifierAttribute::ExecAllowsNoDecreasesClause)
-        });
-        if has_external_body && Self::return_type_contains_verus_rwlock(&i.sig.output) {
-            self.suppress_external_body_hole = true;
-        }
-
-        let has_accept_hole = has_accept_hole_comment(self.content, line);
-
-        match &i.sig.mode {
-            FnMode::Exec(_) | FnMode::Default => {
-                self.stats.fn_spec.total_fns += 1;
-                // external_body, exec_allows_no_decreases_clause (diverge), or accept hole — skip fn_missing_requires/ensures
-                if !has_external_body && !has_exec_allows_no_decreases && !has_accept_hole {
-                    let has_requires = i.sig.spec.requires.is_some();
-                    let has_ensures = i.sig.spec.ensures.is_some()
-                        || i.sig.spec.default_ensures.is_some();
-                    let no_params_exempt = Self::fn_has_no_params(&i.sig);
-                    if has_requires && has_ensures {
-                        self.stats.fn_spec.exec_fns_complete += 1;
-                    } else {
-                        self.stats.fn_spec.exec_fns_missing_spec += 1;
-                        if !has_requires && !has_ensures {
-                            if no_params_exempt {
-                                self.stats.warnings.push(DetectedHole {
-                                    line,
-                                    hole_type: "fn_missing_ensures".to_string(),
-                                    context: format!("fn {} — exec fn should have ensures", name),
-                                });
-                            } else {
-                                self.stats.warnings.push(DetectedHole {
-                                    line,
-                                    hole_type: "fn_missing_requires_ensures".to_string(),
-                                    context: format!("fn {} — exec fn should have requires and ensures", name),
-                                });
-                            }
-                        } else if !has_requires && !no_params_exempt {
-                            self.stats.warnings.push(DetectedHole {
-                                line,
-                                hole_type: "fn_missing_requires".to_string(),
-                                context: format!("fn {} — exec fn should have requires", name),
-                            });
-                        } else if !has_ensures {
-                            self.stats.warnings.push(DetectedHole {
-                                line,
-                                hole_type: "fn_missing_ensures".to_string(),
-                                context: format!("fn {} — exec fn should have ensures", name),
-                            });
-                        }
-                    }
-                } else {
-                    self.stats.fn_spec.exec_fns_complete += 1;
-                }
-            }
-            FnMode::Spec(_) | FnMode::SpecChecked(_) => {
-                self.stats.fn_spec.total_fns += 1;
-                let holes = count_holes_in_verus_block(&i.block);
-                if holes > 0 && !has_accept_hole {
-                    self.stats.fn_spec.proof_spec_fns_with_holes += 1;
-                    self.stats.warnings.push(DetectedHole {
-                        line,
-                        hole_type: "spec_fn_with_holes".to_string(),
-                        context: format!("spec fn {} — contains assume/external_body/admit, needs proof", name),
-                    });
-                } else {
-                    self.stats.fn_spec.proof_spec_fns_clean += 1;
-                }
-                if name.contains("wf") && Self::return_type_is_bool(&i.sig.output) && block_returns_only_true(&i.block) {
-                    let item = DetectedHole {
-                        line,
-                        hole_type: "trivial_spec_wf".to_string(),
-                        context: format!("spec fn {} — trivial body {{ true }} or {{ true; }}, needs // accept hole", name),
-                    };
-                    if has_accept_hole {
-                        self.stats.infos.push(item);
-                    } else {
-                        self.stats.holes.trivial_spec_wf_count += 1;
-                        self.stats.holes.total_holes += 1;
-                        self.stats.holes.holes.push(item);
-                    }
-                }
-            }
-            FnMode::Proof(_) => {
-                self.stats.proof_functions += 1;
-                self.stats.fn_spec.total_fns += 1;
-                let holes = count_holes_in_verus_block(&i.block);
-                if holes > 0 {
-                    self.stats.holed_proof_functions += 1;
-                    if !self.is_in_eq_or_clone_context() && !has_accept_hole {
-                        self.stats.fn_spec.proof_spec_fns_with_holes += 1;
-                        self.stats.warnings.push(DetectedHole {
-                            line,
-                            hole_type: "proof_fn_with_holes".to_string(),
-                            context: format!("proof fn {} — contains assume/external_body/admit, needs proof", name),
-                        });
-                    }
-                } else {
-                    self.stats.clean_proof_functions += 1;
-                    self.stats.fn_spec.proof_spec_fns_clean += 1;
-                }
-            }
-            FnMode::ProofAxiom(_) => {
-                self.stats.fn_spec.total_fns += 1;
-                let hole = DetectedHole {
-                    line,
-                    hole_type: "axiom".to_string(),
-                    context: format!("axiom fn {} — axiom is a hole", name),
-                };
-                self.stats.holes.axiom_count += 1;
-                self.stats.holes.total_holes += 1;
-                self.stats.holes.holes.push(hole);
-            }
-        }
-        visit::visit_item_fn(self, i);
-        self.suppress_external_body_hole = false;
-        self.current_fn_name = prev_fn;
-    }
-
-    fn visit_impl_item_fn(&mut self, i: &'a verus_syn::ImplItemFn) {
-        use verus_syn::FnMode;
-        let line = self.file_line(i.sig.ident.span());
-        let name = i.sig.ident.to_string();
-        let prev_fn = self.current_fn_name.replace(name.clone());
-        self.check_requires_true(&i.sig);
-
-        let has_external_body = i.attrs.iter().any(|a| {
-            detect_verifier_attr_verus_syn(a) == Some(VerifierAttribute::ExternalBody)
-        });
-        let has_exec_allows_no_decreases = i.attrs.iter().any(|a| {
-            detect_verifier_attr_verus_syn(a) == Some(VerifierAttribute::ExecAllowsNoDecreasesClause)
-        });
-        if has_external_body && Self::return_type_contains_verus_rwlock(&i.sig.output) {
-            self.suppress_external_body_hole = true;
-        }
-
-        let has_accept_hole = has_accept_hole_comment(self.content, line);
-
-        match &i.sig.mode {
-            FnMode::Exec(_) | FnMode::Default => {
-                self.stats.fn_spec.total_fns += 1;
-                // Trait impl methods inherit requires/ensures from the trait — no need to repeat
-                // external_body, exec_allows_no_decreases_clause, or accept hole — skip fn_missing_requires/ensures
-                let in_trait_impl = self.current_impl_trait.is_some();
-                let iter_spec_exempt = name == "iter" || name == "iter_mut" || name == "into_iter";
-                let no_params_exempt = Self::fn_has_no_params(&i.sig);
-                if in_trait_impl || has_external_body || has_exec_allows_no_decreases || has_accept_hole || iter_spec_exempt {
-                    self.stats.fn_spec.exec_fns_complete += 1;
-                } else {
-                    let has_requires = i.sig.spec.requires.is_some();
-                    let has_ensures = i.sig.spec.ensures.is_some()
-                        || i.sig.spec.default_ensures.is_some();
-                    if has_requires && has_ensures {
-                        self.stats.fn_spec.exec_fns_complete += 1;
-                    } else {
-                        self.stats.fn_spec.exec_fns_missing_spec += 1;
-                        if !has_requires && !has_ensures {
-                            if no_params_exempt {
-                                self.stats.warnings.push(DetectedHole {
-                                    line,
-                                    hole_type: "fn_missing_ensures".to_string(),
-                                    context: format!("fn {} — exec fn should have ensures", name),
-                                });
-                            } else {
-                                self.stats.warnings.push(DetectedHole {
-                                    line,
-                                    hole_type: "fn_missing_requires_ensures".to_string(),
-                                    context: format!("fn {} — exec fn should have requires and ensures", name),
-                                });
-                            }
-                        } else if !has_requires && !no_params_exempt {
-                            self.stats.warnings.push(DetectedHole {
-                                line,
-                                hole_type: "fn_missing_requires".to_string(),
-                                context: format!("fn {} — exec fn should have requires", name),
-                            });
-                        } else if !has_ensures {
-                            self.stats.warnings.push(DetectedHole {
-                                line,
-                                hole_type: "fn_missing_ensures".to_string(),
-                                context: format!("fn {} — exec fn should have ensures", name),
-                            });
-                        }
-                    }
-                }
-            }
-            FnMode::Spec(_) | FnMode::SpecChecked(_) => {
-                self.stats.fn_spec.total_fns += 1;
-                let holes = count_holes_in_verus_block(&i.block);
-                if holes > 0 && !has_accept_hole {
-                    self.stats.fn_spec.proof_spec_fns_with_holes += 1;
-                    self.stats.warnings.push(DetectedHole {
-                        line,
-                        hole_type: "spec_fn_with_holes".to_string(),
-                        context: format!("spec fn {} — contains assume/external_body/admit, needs proof", name),
-                    });
-                } else {
-                    self.stats.fn_spec.proof_spec_fns_clean += 1;
-                }
-                if name.contains("wf") && Self::return_type_is_bool(&i.sig.output) && block_returns_only_true(&i.block) {
-                    let item = DetectedHole {
-                        line,
-                        hole_type: "trivial_spec_wf".to_string(),
-                        context: format!("spec fn {} — trivial body {{ true }} or {{ true; }}, needs // accept hole", name),
-                    };
-                    if has_accept_hole {
-                        self.stats.infos.push(item);
-                    } else {
-                        self.stats.holes.trivial_spec_wf_count += 1;
-                        self.stats.holes.total_holes += 1;
-                        self.stats.holes.holes.push(item);
-                    }
-                }
-            }
-            FnMode::Proof(_) => {
-                self.stats.proof_functions += 1;
-                self.stats.fn_spec.total_fns += 1;
-                let holes = count_holes_in_verus_block(&i.block);
-                if holes > 0 {
-                    self.stats.holed_proof_functions += 1;
-                    if !self.is_in_eq_or_clone_context() && !has_accept_hole {
-                        self.stats.fn_spec.proof_spec_fns_with_holes += 1;
-                        self.stats.warnings.push(DetectedHole {
-                            line,
-                            hole_type: "proof_fn_with_holes".to_string(),
-                            context: format!("proof fn {} — contains assume/external_body/admit, needs proof", name),
-                        });
-                    }
-                } else {
-                    self.stats.clean_proof_functions += 1;
-                    self.stats.fn_spec.proof_spec_fns_clean += 1;
-                }
-            }
-            FnMode::ProofAxiom(_) => {
-                self.stats.fn_spec.total_fns += 1;
-                let hole = DetectedHole {
-                    line,
-                    hole_type: "axiom".to_string(),
-                    context: format!("axiom fn {} — axiom is a hole", name),
-                };
-                self.stats.holes.axiom_count += 1;
-                self.stats.holes.total_holes += 1;
-                self.stats.holes.holes.push(hole);
-            }
-        }
-
-        visit::visit_impl_item_fn(self, i);
-        self.suppress_external_body_hole = false;
-        self.current_fn_name = prev_fn;
-    }
-
-    fn visit_trait_item_fn(&mut self, i: &'a verus_syn::TraitItemFn) {
-        use verus_syn::FnMode;
-        let line = self.file_line(i.sig.ident.span());
-        let name = i.sig.ident.to_string();
-        let prev_fn = self.current_fn_name.replace(name.clone());
-        self.check_requires_true(&i.sig);
-
-        let has_external_body = i.attrs.iter().any(|a| {
-            detect_verifier_attr_verus_syn(a) == Some(VerifierAttribute::ExternalBody)
-        });
-        let has_accept_hole = has_accept_hole_comment(self.content, line);
-
-        match &i.sig.mode {
-            FnMode::Exec(_) | FnMode::Default => {
-                self.stats.fn_spec.total_fns += 1;
-                // Abstract trait methods (no default body) get spec from impl — skip fn_missing_requires
-                // external_body or accept hole — skip fn_missing_requires/ensures
-                let is_abstract = i.default.is_none();
-                let no_params_exempt = Self::fn_has_no_params(&i.sig);
-                if is_abstract || has_external_body || has_accept_hole {
-                    self.stats.fn_spec.exec_fns_complete += 1;
-                } else {
-                    let has_requires = i.sig.spec.requires.is_some();
-                    let has_ensures = i.sig.spec.ensures.is_some()
-                        || i.sig.spec.default_ensures.is_some();
-                    if has_requires && has_ensures {
-                        self.stats.fn_spec.exec_fns_complete += 1;
-                    } else {
-                        self.stats.fn_spec.exec_fns_missing_spec += 1;
-                        if !has_requires && !has_ensures {
-                            if no_params_exempt {
-                                self.stats.warnings.push(DetectedHole {
-                                    line,
-                                    hole_type: "fn_missing_ensures".to_string(),
-                                    context: format!("fn {} — exec fn should have ensures", name),
-                                });
-                            } else {
-                                self.stats.warnings.push(DetectedHole {
-                                    line,
-                                    hole_type: "fn_missing_requires_ensures".to_string(),
-                                    context: format!("fn {} — exec fn should have requires and ensures", name),
-                                });
-                            }
-                        } else if !has_requires && !no_params_exempt {
-                            self.stats.warnings.push(DetectedHole {
-                                line,
-                                hole_type: "fn_missing_requires".to_string(),
-                                context: format!("fn {} — exec fn should have requires", name),
-                            });
-                        } else if !has_ensures {
-                            self.stats.warnings.push(DetectedHole {
-                                line,
-                                hole_type: "fn_missing_ensures".to_string(),
-                                context: format!("fn {} — exec fn should have ensures", name),
-                            });
-                        }
-                    }
-                }
-            }
-            FnMode::Spec(_) | FnMode::SpecChecked(_) => {
-                self.stats.fn_spec.total_fns += 1;
-                let holes = i.default.as_ref().map_or(0, |b| count_holes_in_verus_block(b));
-                if holes > 0 && !has_accept_hole {
-                    self.stats.fn_spec.proof_spec_fns_with_holes += 1;
-                    self.stats.warnings.push(DetectedHole {
-                        line,
-                        hole_type: "spec_fn_with_holes".to_string(),
-                        context: format!("spec fn {} — contains assume/external_body/admit, needs proof", name),
-                    });
-                } else {
-                    self.stats.fn_spec.proof_spec_fns_clean += 1;
-                }
-                if name.contains("wf") && Self::return_type_is_bool(&i.sig.output) && i.default.as_ref().map_or(false, block_returns_only_true) {
-                    let item = DetectedHole {
-                        line,
-                        hole_type: "trivial_spec_wf".to_string(),
-                        context: format!("spec fn {} — trivial body {{ true }} or {{ true; }}, needs // accept hole", name),
-                    };
-                    if has_accept_hole {
-                        self.stats.infos.push(item);
-                    } else {
-                        self.stats.holes.trivial_spec_wf_count += 1;
-                        self.stats.holes.total_holes += 1;
-                        self.stats.holes.holes.push(item);
-                    }
-                }
-            }
-            FnMode::Proof(_) => {
-                self.stats.proof_functions += 1;
-                self.stats.fn_spec.total_fns += 1;
-                let holes = i.default.as_ref().map_or(0, |b| count_holes_in_verus_block(b));
-                if holes > 0 {
-                    self.stats.holed_proof_functions += 1;
-                    if !self.is_in_eq_or_clone_context() && !has_accept_hole {
-                        self.stats.fn_spec.proof_spec_fns_with_holes += 1;
-                        self.stats.warnings.push(DetectedHole {
-                            line,
-                            hole_type: "proof_fn_with_holes".to_string(),
-                            context: format!("proof fn {} — contains assume/external_body/admit, needs proof", name),
-                        });
-                    }
-                } else {
-                    self.stats.clean_proof_functions += 1;
-                    self.stats.fn_spec.proof_spec_fns_clean += 1;
-                }
-            }
-            FnMode::ProofAxiom(_) => {
-                self.stats.fn_spec.total_fns += 1;
-                let hole = DetectedHole {
-                    line,
-                    hole_type: "axiom".to_string(),
-                    context: format!("axiom fn {} — axiom is a hole", name),
-                };
-                self.stats.holes.axiom_count += 1;
-                self.stats.holes.total_holes += 1;
-                self.stats.holes.holes.push(hole);
-            }
-        }
-
-        visit::visit_trait_item_fn(self, i);
-        self.current_fn_name = prev_fn;
-    }
-
-    fn visit_assume(&mut self, i: &'a verus_syn::Assume) {
-        let line = self.file_line(i.assume_token.span());
-        let context = self.context_at(line);
-        // Check for assume(false) — need to inspect the expr
-        let is_false = is_assume_false(i);
-        if is_false {
-            if has_diverge_after_in_block(i) {
-                self.stats.infos.push(DetectedHole {
-                    line,
-                    hole_type: "assume(false); diverge()".to_string(),
-                    context: format!("{} — valid non-termination idiom", context),
-                });
-            } else {
-                self.stats.holes.assume_false_count += 1;
-                self.stats.holes.total_holes += 1;
-                self.stats.holes.holes.push(DetectedHole {
-                    line,
-                    hole_type: "assume(false)".to_string(),
-                    context: format!("{} — needs diverge(); use `assume(false); diverge()`", context),
-                });
-            }
-        } else if self.is_in_eq_or_clone_context() {
-            self.stats.warnings.push(DetectedHole {
-                line,
-                hole_type: "assume_eq_clone_workaround".to_string(),
-                context: "at this point in Verus, clones may have to assume they work on generic types".to_string(),
-            });
-        } else {
-            self.stats.holes.assume_count += 1;
-            self.stats.holes.total_holes += 1;
-            self.stats.holes.holes.push(DetectedHole {
-                line,
-                hole_type: "assume()".to_string(),
-                context: self.context_at(line),
-            });
-        }
-        visit::visit_assume(self, i);
-    }
-
-    fn visit_assume_specification(&mut self, i: &'a verus_syn::AssumeSpecification) {
-        let line = self.file_line(i.assume_specification.span());
-        let context = self.context_at(line);
-        self.stats.holes.assume_specification_count += 1;
-        self.stats.holes.total_holes += 1;
-        self.stats.holes.holes.push(DetectedHole {
-            line,
-            hole_type: "assume_specification".to_string(),
-            context,
-        });
-        visit::visit_assume_specification(self, i);
-    }
-
-    fn visit_expr_call(&mut self, i: &'a verus_syn::ExprCall) {
-        if let verus_syn::Expr::Path(path) = &*i.func {
-            if let Some(seg) = path.path.segments.last() {
-                let name = seg.ident.to_string();
-                if name == "admit" {
-                    let line = self.file_line(seg.ident.span());
-                    self.stats.holes.admit_count += 1;
-                    self.stats.holes.total_holes += 1;
-                    self.stats.holes.holes.push(DetectedHole {
-                        line,
-                        hole_type: "admit()".to_string(),
-                        context: self.context_at(line),
-                    });
-                } else if name == "assume_new" {
-                    let line = self.file_line(seg.ident.span());
-                    self.stats.holes.assume_new_count += 1;
-                    self.stats.holes.total_holes += 1;
-                    self.stats.holes.holes.push(DetectedHole {
-                        line,
-                        hole_type: "assume_new()".to_string(),
-                        context: self.context_at(line),
-                    });
-                } else if name == "accept" {
-                    // All accept() treated uniformly; no special case for accept(true)
-                    let line = self.file_line(seg.ident.span());
-                    self.stats.infos.push(DetectedHole {
-                        line,
-                        hole_type: "accept()".to_string(),
-                        context: "accept hole".to_string(),
-                    });
-                }
-            }
-        }
-        visit::visit_expr_call(self, i);
-    }
-
-    fn visit_item_impl(&mut self, i: &'a verus_syn::ItemImpl) {
-        let line = self.file_line(i.impl_token.span());
-        let prev_trait = self.current_impl_trait.take();
-        let prev_impl_type = self.current_impl_type.take();
-        self.current_impl_type = Some(i.self_ty.to_token_stream().to_string());
-        if let Some((_, path, _)) = &i.trait_ {
-            if let Some(seg) = path.segments.last() {
-                let name = seg.ident.to_string();
-                self.current_impl_trait = Some(name.clone());
-                if name == "Debug" || name == "Display" {
-                    self.stats.warnings.push(DetectedHole {
-                        line,
-                        hole_type: "debug_display_inside_verus".to_string(),
-                        context: format!("impl {} for ... — Debug/Display must be implemented outside verus!", name),
-                    });
-                }
-                if name == "RwLockPredicate" {
-                    for item in &i.items {
-                        if let verus_syn::ImplItem::Fn(impl_fn) = item {
-                            if impl_fn.sig.ident == "inv" && block_returns_only_true(&impl_fn.block) {
-                                let line = self.file_line(impl_fn.sig.ident.span());
-                                self.stats.warnings.push(DetectedHole {
-                                    line,
-                                    hole_type: "dummy_rwlock_predicate".to_string(),
-                                    context: "RwLockPredicate inv returning true is grossly underspecified.".to_string(),
-                                });
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        visit::visit_item_impl(self, i);
-        self.current_impl_trait = prev_trait;
-        self.current_impl_type = prev_impl_type;
-    }
-
-    fn visit_attribute(&mut self, i: &'a verus_syn::Attribute) {
-        if let Some(attr) = detect_verifier_attr_verus_syn(i) {
-            let line = self.file_line(i.pound_token.span());
-            let context = self.context_at(line);
-            match attr {
-                VerifierAttribute::ExecAllowsNoDecreasesClause => {
-                    // No hole — used for diverge() etc., skip fn_missing_requires
-                }
-                VerifierAttribute::ExternalBody => {
-                    if self.suppress_external_body_hole {
-                        self.stats.infos.push(DetectedHole {
-                            line,
-                            hole_type: "verus_rwlock_external_body".to_string(),
-                            context: "Verus RwLock new requires an external body at this point.".to_string(),
-                        });
-                    } else if has_accept_hole_comment(self.content, line) {
-                        self.stats.infos.push(DetectedHole {
-                            line,
-                            hole_type: "external_body_accept_hole".to_string(),
-                            context: "external_body with accept hole comment".to_string(),
-                        });
-                    } else {
-                        self.stats.holes.external_body_count += 1;
-                        self.stats.holes.total_holes += 1;
-                        self.stats.holes.holes.push(DetectedHole { line, hole_type: "external_body".to_string(), context });
-                    }
-                }
-                VerifierAttribute::ExternalFnSpec => {
-                    if has_accept_hole_comment(self.content, line) {
-                        self.stats.infos.push(DetectedHole {
-                            line,
-                            hole_type: "external_fn_specification_accept_hole".to_string(),
-                            context: "external_fn_specification with accept hole comment".to_string(),
-                        });
-                    } else {
-                        self.stats.holes.external_fn_spec_count += 1;
-                        self.stats.holes.total_holes += 1;
-                        self.stats.holes.holes.push(DetectedHole { line, hole_type: "external_fn_specification".to_string(), context });
-                    }
-                }
-                VerifierAttribute::ExternalTraitSpec => {
-                    if has_accept_hole_comment(self.content, line) {
-                        self.stats.infos.push(DetectedHole {
-                            line,
-                            hole_type: "external_trait_specification_accept_hole".to_string(),
-                            context: "external_trait_specification with accept hole comment".to_string(),
-                        });
-                    } else {
-                        self.stats.holes.external_trait_spec_count += 1;
-                        self.stats.holes.total_holes += 1;
-                        self.stats.holes.holes.push(DetectedHole { line, hole_type: "external_trait_specification".to_string(), context });
-                    }
-                }
-                VerifierAttribute::ExternalTypeSpec => {
-                    if has_accept_hole_comment(self.content, line) {
-                        self.stats.infos.push(DetectedHole {
-                            line,
-                            hole_type: "external_type_specification_accept_hole".to_string(),
-                            context: "external_type_specification with accept hole comment".to_string(),
-                        });
-                    } else {
-                        self.stats.holes.external_type_spec_count += 1;
-                        self.stats.holes.total_holes += 1;
-                        self.stats.holes.holes.push(DetectedHole { line, hole_type: "external_type_specification".to_string(), context });
-                    }
-                }
-                VerifierAttribute::ExternalTraitExt => {
-                    if has_accept_hole_comment(self.content, line) {
-                        self.stats.infos.push(DetectedHole {
-                            line,
-                            hole_type: "external_trait_extension_accept_hole".to_string(),
-                            context: "external_trait_extension with accept hole comment".to_string(),
-                        });
-                    } else {
-                        self.stats.holes.external_trait_ext_count += 1;
-                        self.stats.holes.total_holes += 1;
-                        self.stats.holes.holes.push(DetectedHole { line, hole_type: "external_trait_extension".to_string(), context });
-                    }
-                }
-                VerifierAttribute::External => {
-                    if has_accept_hole_comment(self.content, line) {
-                        self.stats.infos.push(DetectedHole {
-                            line,
-                            hole_type: "external_accept_hole".to_string(),
-                            context: "external with accept hole comment".to_string(),
-                        });
-                    } else {
-                        self.stats.holes.external_count += 1;
-                        self.stats.holes.total_holes += 1;
-                        self.stats.holes.holes.push(DetectedHole { line, hole_type: "external".to_string(), context });
-                    }
-                }
-                VerifierAttribute::Opaque => {
-                    self.stats.holes.opaque_count += 1;
-                    self.stats.holes.total_holes += 1;
-                    self.stats.holes.holes.push(DetectedHole { line, hole_type: "opaque".to_string(), context });
-                }
-                VerifierAttribute::Axiom => {
-                    self.stats.holes.axiom_count += 1;
-                    self.stats.holes.total_holes += 1;
-                    self.stats.holes.holes.push(DetectedHole { line, hole_type: "axiom".to_string(), context });
-                }
-            }
-        }
-        visit::visit_attribute(self, i);
-    }
-}
-
-fn block_returns_only_true(block: &verus_syn::Block) -> bool {
-    use verus_syn::{Expr, Lit, Stmt};
-    if block.stmts.len() != 1 {
-        return false;
-    }
-    match &block.stmts[0] {
-        Stmt::Expr(expr, _) => matches!(
-            expr,
-            Expr::Lit(expr_lit) if matches!(&expr_lit.lit, Lit::Bool(lb) if lb.value)
-        ),
-        _ => false,
-    }
-}
-
-/// Heuristic: assume(equal == (*self == *other)), assume(cloned == *self), assume(c == *t), etc.
-fn looks_like_eq_clone_workaround(context: &str) -> bool {
-    let s = context.trim();
-    let has_self = s.contains("self");
-    let has_other = s.contains("other");
-    let has_equal = s.contains("equal") || s.contains("r ==");
-    let has_cloned = s.contains("cloned");
-    let has_c_t = (s.contains("c ==") && s.contains("*t")) || (s.contains("c ==") && s.contains("*link"));
-    (has_equal && has_self && has_other)
-        || (has_cloned && has_self)
-        || has_c_t
-}
-
-fn is_assume_false(assume: &verus_syn::Assume) -> bool {
-    use verus_syn::{Expr, ExprLit, Lit};
-    matches!(
-        &*assume.expr,
-        Expr::Lit(ExprLit { lit: Lit::Bool(lb), .. }) if !lb.value
-    )
-}
-
-fn has_diverge_after_in_block(_assume: &verus_syn::Assume) -> bool {
-    // Verus AST doesn't give us easy access to "next statement". For now, conservatively false.
-    false
-}
-
-fn count_holes_in_verus_block(block: &verus_syn::Block) -> usize {
-    struct HoleCounter(usize);
-    impl<'a> Visit<'a> for HoleCounter {
-        fn visit_assume(&mut self, i: &'a verus_syn::Assume) {
-            self.0 += 1;
-            visit::visit_assume(self, i);
-        }
-        fn visit_expr_call(&mut self, i: &'a verus_syn::ExprCall) {
-            if let verus_syn::Expr::Path(p) = &*i.func {
-                if let Some(seg) = p.path.segments.last() {
-                    if seg.ident == "admit" || seg.ident == "assume_new" {
-                        self.0 += 1;
-                    }
-                }
-            }
-            visit::visit_expr_call(self, i);
-        }
-        fn visit_attribute(&mut self, i: &'a verus_syn::Attribute) {
-            if detect_verifier_attr_verus_syn(i).is_some() {
-                self.0 += 1;
-            }
-            visit::visit_attribute(self, i);
-        }
-    }
-    let mut counter = HoleCounter(0);
-    counter.visit_block(block);
-    counter.0
-}
-
-fn detect_verifier_attr_verus_syn(attr: &verus_syn::Attribute) -> Option<VerifierAttribute> {
-    let path = attr.path();
-    let segs: Vec<_> = path.segments.iter().map(|s| s.ident.to_string()).collect();
-    if segs.first()?.as_str() != "verifier" {
-        return None;
-    }
-    let name = segs.get(1)?.as_str();
-    match name {
-        "external_body" => Some(VerifierAttribute::ExternalBody),
-        "exec_allows_no_decreases_clause" => Some(VerifierAttribute::ExecAllowsNoDecreasesClause),
-        "external_fn_specification" => Some(VerifierAttribute::ExternalFnSpec),
-        "external_trait_specification" => Some(VerifierAttribute::ExternalTraitSpec),
-        "external_type_specification" => Some(VerifierAttribute::ExternalTypeSpec),
-        "external_trait_extension" => Some(VerifierAttribute::ExternalTraitExt),
-        "external" => Some(VerifierAttribute::External),
-        "opaque" => Some(VerifierAttribute::Opaque),
-        "axiom" => Some(VerifierAttribute::Axiom),
-        _ => None,
-    }
-}
-
-/// Token-based fallback when verus_syn fails to parse.
-fn analyze_verus_macro_tokens(tree: &SyntaxNode, content: &str, stats: &mut FileStats) {
-    // Walk the token tree looking for:
-    // 1. Functions with proof modifier
-    // 2. Function calls to assume/admit
-    // 3. Verifier attributes (which are often inside verus! macros)
-
-    let tokens: Vec<_> = tree.descendants_with_tokens()
-        .filter_map(|n| n.into_token())
-        .collect();
-
-    let mut i = 0;
-    while i < tokens.len() {
-        let token = &tokens[i];
-        
-        // Look for "fn" keyword to find proof functions and axiom functions
-        if token.kind() == SyntaxKind::FN_KW {
-            let is_axiom = is_axiom_function(&tokens, i);
-            if is_axiom {
-                let offset: usize = token.text_range().start().into();
-                let line = line_from_offset(content, offset);
-                let context = get_context(content, offset);
-                stats.holes.axiom_count += 1;
-                stats.holes.total_holes += 1;
-                stats.holes.holes.push(DetectedHole {
-                    line,
-                    hole_type: "axiom".to_string(),
-                    context,
-                });
-            }
-            
-            let is_proof = is_proof_function(&tokens, i);
-            
-            if is_proof {
-                stats.proof_functions += 1;
-                
-                // Check if this proof function has holes
-                let holes_in_function = count_holes_in_function(&tokens, i);
-                if holes_in_function > 0 {
-                    stats.holed_proof_functions += 1;
-                } else {
-                    stats.clean_proof_functions += 1;
-                }
-            }
-        }
-        
-        // Look for assume/admit function calls  
-        // Also check for "broadcast" which might not be an IDENT
-        if token.kind() == SyntaxKind::IDENT || token.text() == "broadcast" {
-            let text = token.text();
-            
-            // Check for assume_specification (followed by < for generics)
-            if text == "assume_specification" {
-                let offset: usize = token.text_range().start().into();
-                let line = line_from_offset(content, offset);
-                let context = get_context(content, offset);
-                stats.holes.assume_specification_count += 1;
-                stats.holes.total_holes += 1;
-                stats.holes.holes.push(DetectedHole {
-                    line,
-                    hole_type: "assume_specification".to_string(),
-                    context,
-                });
-            }
-            
-            if text == "assume" || text == "admit" || text == "assume_new" {
-                // Check if it's followed by (
-                if i + 1 < tokens.len() && tokens[i + 1].kind() == SyntaxKind::L_PAREN {
-                    let offset: usize = token.text_range().start().into();
-                    let line = line_from_offset(content, offset);
-                    let context = get_context(content, offset);
-                    
-                    if text == "assume" {
-                        if i + 2 < tokens.len() && tokens[i + 2].text() == "false" {
-                            // assume(false) — check for diverge() after it
-                            if has_diverge_after(&tokens, i + 3) {
-                                // Valid non-termination idiom: assume(false); diverge()
-                                stats.infos.push(DetectedHole {
-                                    line,
-                                    hole_type: "assume(false); diverge()".to_string(),
-                                    context: format!("{} — valid non-termination idiom", context),
-                                });
-                            } else {
-                                // assume(false) without diverge() — still a hole
-                                stats.holes.assume_false_count += 1;
-                                stats.holes.total_holes += 1;
-                                stats.holes.holes.push(DetectedHole {
-                                    line,
-                                    hole_type: "assume(false)".to_string(),
-                                    context: format!("{} — needs diverge(); use `assume(false); diverge()`", context),
-                                });
-                            }
-                        } else if looks_like_eq_clone_workaround(&context) {
-                            stats.warnings.push(DetectedHole {
-                                line,
-                                hole_type: "assume_eq_clone_workaround".to_string(),
-                                context: "at this point in Verus, clones may have to assume they work on generic types".to_string(),
-                            });
-                        } else {
-                            stats.holes.assume_count += 1;
-                            stats.holes.total_holes += 1;
-                            stats.holes.holes.push(DetectedHole {
-                                line,
-                                hole_type: "assume()".to_string(),
-                                context,
-                            });
-                        }
-                    } else if text == "admit" {
-                        stats.holes.admit_count += 1;
-                        stats.holes.total_holes += 1;
-                        stats.holes.holes.push(DetectedHole {
-                            line,
-                            hole_type: "admit()".to_string(),
-                            context,
-                        });
-                    } else if text == "accept" {
-                        // All accept() treated uniformly; no special case for accept(true)
-                        stats.infos.push(DetectedHole {
-                            line,
-                            hole_type: "accept()".to_string(),
-                            context: "accept hole".to_string(),
-                        });
-                    } else if text == "assume_new" {
-                        // Tracked::assume_new() - a sneaky assume!
-                        stats.holes.assume_new_count += 1;
-                        stats.holes.total_holes += 1;
-                        stats.holes.holes.push(DetectedHole {
-                            line,
-                            hole_type: "assume_new()".to_string(),
-                            context,
-                        });
-                    }
-                }
-            }
-            
-            // Note: We no longer count "broadcast use" statements
-            // broadcast use just imports axioms - it doesn't define them
-            // The axioms themselves are counted when we find axiom fn with holes
-        }
-        
-        // Note: unsafe fn/impl/blocks are detected by analyze_unsafe_patterns() on the whole file
-        
-        // Look for impl Debug/Display inside verus! — Debug/Display must be outside verus!
-        if token.kind() == SyntaxKind::IMPL_KW {
-            let mut j = i + 1;
-            while j < tokens.len() && tokens[j].kind() == SyntaxKind::WHITESPACE {
-                j += 1;
-            }
-            if j < tokens.len() && tokens[j].kind() == SyntaxKind::IDENT {
-                let trait_name = tokens[j].text().to_string();
-                if trait_name == "Debug" || trait_name == "Display" {
-                    let offset: usize = token.text_range().start().into();
-                    let line = line_from_offset(content, offset);
-                    let context = get_context(content, offset);
-                    stats.warnings.push(DetectedHole {
-                        line,
-                        hole_type: "debug_display_inside_verus".to_string(),
-                        context: format!("{} — Debug/Display must be implemented outside verus!", context),
-                    });
-                }
-            }
-        }
-
-        // Look for verifier attributes inside the verus! macro
-        if token.kind() == SyntaxKind::POUND {
-            if let Some(attr) = detect_verifier_attribute(&tokens, i) {
-                let offset: usize = token.text_range().start().into();
-                let line = line_from_offset(content, offset);
-                let context = get_context(content, offset);
-                
-                match attr {
-                    VerifierAttribute::ExecAllowsNoDecreasesClause => {
-                        // No hole — used for diverge() etc., skip fn_missing_requires
-                    }
-                    VerifierAttribute::ExternalBody => {
-                        if has_accept_hole_comment(content, line) {
-                            stats.infos.push(DetectedHole {
-                                line,
-                                hole_type: "external_body_accept_hole".to_string(),
-                                context: "external_body with accept hole comment".to_string(),
-                            });
-                        } else {
-                            stats.holes.external_body_count += 1;
-                            stats.holes.total_holes += 1;
-                            stats.holes.holes.push(DetectedHole {
-                                line,
-                                hole_type: "external_body".to_string(),
-                                context,
-                            });
-                        }
-                    }
-                    VerifierAttribute::ExternalFnSpec => {
-                        if has_accept_hole_comment(content, line) {
-                            stats.infos.push(DetectedHole {
-                                line,
-                                hole_type: "external_fn_specification_accept_hole".to_string(),
-                                context: "external_fn_specification with accept hole comment".to_string(),
-                            });
-                        } else {
-                            stats.holes.external_fn_spec_count += 1;
-                            stats.holes.total_holes += 1;
-                            stats.holes.holes.push(DetectedHole {
-                                line,
-                                hole_type: "external_fn_specification".to_string(),
-                                context,
-                            });
-                        }
-                    }
-                    VerifierAttribute::ExternalTraitSpec => {
-                        if has_accept_hole_comment(content, line) {
-                            stats.infos.push(DetectedHole {
-                                line,
-                                hole_type: "external_trait_specification_accept_hole".to_string(),
-                                context: "external_trait_specification with accept hole comment".to_string(),
-                            });
-                        } else {
-                            stats.holes.external_trait_spec_count += 1;
-                            stats.holes.total_holes += 1;
-                            stats.holes.holes.push(DetectedHole {
-                                line,
-                                hole_type: "external_trait_specification".to_string(),
-                                context,
-                            });
-                        }
-                    }
-                    VerifierAttribute::ExternalTypeSpec => {
-                        if has_accept_hole_comment(content, line) {
-                            stats.infos.push(DetectedHole {
-                                line,
-                                hole_type: "external_type_specification_accept_hole".to_string(),
-                                context: "external_type_specification with accept hole comment".to_string(),
-                            });
-                        } else {
-                            stats.holes.external_type_spec_count += 1;
-                            stats.holes.total_holes += 1;
-                            stats.holes.holes.push(DetectedHole {
-                                line,
-                                hole_type: "external_type_specification".to_string(),
-                                context,
-                            });
-                        }
-                    }
-                    VerifierAttribute::ExternalTraitExt => {
-                        if has_accept_hole_comment(content, line) {
-                            stats.infos.push(DetectedHole {
-                                line,
-                                hole_type: "external_trait_extension_accept_hole".to_string(),
-                                context: "external_trait_extension with accept hole comment".to_string(),
-                            });
-                        } else {
-                            stats.holes.external_trait_ext_count += 1;
-                            stats.holes.total_holes += 1;
-                            stats.holes.holes.push(DetectedHole {
-                                line,
-                                hole_type: "external_trait_extension".to_string(),
-                                context,
-                            });
-                        }
-                    }
-                    VerifierAttribute::External => {
-                        if has_accept_hole_comment(content, line) {
-                            stats.infos.push(DetectedHole {
-                                line,
-                                hole_type: "external_accept_hole".to_string(),
-                                context: "external with accept hole comment".to_string(),
-                            });
-                        } else {
-                            stats.holes.external_count += 1;
-                            stats.holes.total_holes += 1;
-                            stats.holes.holes.push(DetectedHole {
-                                line,
-                                hole_type: "external".to_string(),
-                                context,
-                            });
-                        }
-                    }
-                    VerifierAttribute::Opaque => {
-                        stats.holes.opaque_count += 1;
-                        stats.holes.total_holes += 1;
-                        stats.holes.holes.push(DetectedHole {
-                            line,
-                            hole_type: "opaque".to_string(),
-                            context,
-                        });
-                    }
-                    VerifierAttribute::Axiom => {
-                        // #[verifier::axiom] attribute - tracked separately as axiom
-                        stats.axioms.axiom_fn_count += 1;
-                        stats.axioms.total_axioms += 1;
-                    }
-                }
-            }
-        }
-        
-        i += 1;
-    }
-}
-
-fn detect_verifier_attribute(tokens: &[ra_ap_syntax::SyntaxToken], start_idx: usize) -> Option<VerifierAttribute> {
-    // Look for patterns:
-    // #[verifier::external_body]
-    // #[verifier(external_body)]
-    // #[verifier::opaque]
-    // #[verifier(opaque)]
-    // etc.
-    
-    let mut i = start_idx;
-    
-    if i >= tokens.len() || tokens[i].kind() != SyntaxKind::POUND {
-        return None;
-    }
-    i += 1;
-    
-    // Skip whitespace
-    while i < tokens.len() && tokens[i].kind() == SyntaxKind::WHITESPACE {
-        i += 1;
-    }
-    
-    if i >= tokens.len() || tokens[i].kind() != SyntaxKind::L_BRACK {
-        return None;
-    }
-    i += 1;
-    
-    // Skip whitespace
-    while i < tokens.len() && tokens[i].kind() == SyntaxKind::WHITESPACE {
-        i += 1;
-    }
-    
-    // Look for "verifier"
-    if i >= tokens.len() || tokens[i].kind() != SyntaxKind::IDENT || tokens[i].text() != "verifier" {
-        return None;
-    }
-    i += 1;
-    
-    // Skip whitespace
-    while i < tokens.len() && tokens[i].kind() == SyntaxKind::WHITESPACE {
-        i += 1;
-    }
-    
-    if i >= tokens.len() {
-        return None;
-    }
-    
-    // Check for :: (path) or ( (call syntax)
-    // Note: Inside macros, :: might be tokenized as two COLON tokens
-    let use_path_syntax = tokens[i].kind() == SyntaxKind::COLON2 || 
-                          (tokens[i].kind() == SyntaxKind::COLON && 
-                           i + 1 < tokens.len() && tokens[i + 1].kind() == SyntaxKind::COLON);
-    let use_call_syntax = tokens[i].kind() == SyntaxKind::L_PAREN;
-    
-    if !use_path_syntax && !use_call_syntax {
-        return None;
-    }
-    
-    // Skip past :: (might be COLON2 or two COLON tokens)
-    if tokens[i].kind() == SyntaxKind::COLON2 {
-        i += 1;
-    } else if tokens[i].kind() == SyntaxKind::COLON {
-        i += 2; // Skip both colons
-    } else {
-        i += 1; // L_PAREN case
-    }
-    
-    // Skip whitespace
-    while i < tokens.len() && tokens[i].kind() == SyntaxKind::WHITESPACE {
-        i += 1;
-    }
-    
-    // Get the attribute name
-    if i >= tokens.len() || tokens[i].kind() != SyntaxKind::IDENT {
-        return None;
-    }
-    
-    let attr_name = tokens[i].text();
-    
-    match attr_name {
-        "external_body" => Some(VerifierAttribute::ExternalBody),
-        "exec_allows_no_decreases_clause" => Some(VerifierAttribute::ExecAllowsNoDecreasesClause),
-        "external_fn_specification" => Some(VerifierAttribute::ExternalFnSpec),
-        "external_trait_specification" => Some(VerifierAttribute::ExternalTraitSpec),
-        "external_type_specification" => Some(VerifierAttribute::ExternalTypeSpec),
-        "external_trait_extension" => Some(VerifierAttribute::ExternalTraitExt),
-        "external" => Some(VerifierAttribute::External),
-        "opaque" => Some(VerifierAttribute::Opaque),
-        "axiom" => Some(VerifierAttribute::Axiom),
-        _ => None,
-    }
-}
-
-fn is_proof_function(tokens: &[ra_ap_syntax::SyntaxToken], fn_idx: usize) -> bool {
-    // Look backwards for "proof" modifier
-    let start_idx = if fn_idx >= 10 { fn_idx - 10 } else { 0 };
-    for j in start_idx..fn_idx {
-        if tokens[j].kind() == SyntaxKind::IDENT && tokens[j].text() == "proof" {
-            return true;
-        }
-    }
-    false
-}
-
-fn is_axiom_function(tokens: &[ra_ap_syntax::SyntaxToken], fn_idx: usize) -> bool {
-    // Look backwards for "axiom" modifier
-    let start_idx = if fn_idx >= 10 { fn_idx - 10 } else { 0 };
-    for j in start_idx..fn_idx {
-        if tokens[j].kind() == SyntaxKind::IDENT && tokens[j].text() == "axiom" {
-            return true;
-        }
-    }
-    false
-}
-
-/// Extract the function name after the fn keyword
-fn get_function_name(tokens: &[ra_ap_syntax::SyntaxToken], fn_idx: usize) -> Option<String> {
-    // Look forward from fn for the next IDENT token (the function name)
-    for i in (fn_idx + 1)..(fn_idx + 5).min(tokens.len()) {
-        if tokens[i].kind() == SyntaxKind::IDENT {
-            return Some(tokens[i].text().to_string());
-        }
-    }
-    None
-}
-
-fn count_holes_in_function(tokens: &[ra_ap_syntax::SyntaxToken], fn_idx: usize) -> usize {
-    // Find the function body (from fn to its closing brace)
-    let mut i = fn_idx + 1;
-    
-    // Find opening brace
-    while i < tokens.len() && tokens[i].kind() != SyntaxKind::L_CURLY {
-        i += 1;
-    }
-    
-    if i >= tokens.len() {
-        return 0;
-    }
-    
-    let start = i;
-    let mut brace_depth = 1;
-    i += 1;
-    
-    // Find matching closing brace
-    while i < tokens.len() && brace_depth > 0 {
-        match tokens[i].kind() {
-            SyntaxKind::L_CURLY => brace_depth += 1,
-            SyntaxKind::R_CURLY => brace_depth -= 1,
-            _ => {}
-        }
-        i += 1;
-    }
-    
-    let end = i;
-    
-    // Count holes in this range (accept() is intentional, not a hole)
-    let mut holes = 0;
-    for j in start..end {
-        if tokens[j].kind() == SyntaxKind::IDENT {
-            let text = tokens[j].text();
-            if (text == "assume" || text == "admit")
-                && j + 1 < end
-                && tokens[j + 1].kind() == SyntaxKind::L_PAREN {
-                holes += 1;
-            }
-        }
-        
-        // Check for #[verifier::*] attributes
-        if tokens[j].kind() == SyntaxKind::POUND {
-            if detect_verifier_attribute(tokens, j).is_some() {
-                holes += 1;
-            }
-        }
-    }
-    
-    holes
-}
-
-fn print_file_report(path: &str, stats: &FileStats) {
-    let has_holes = stats.holes.total_holes > 0;
-    
-    if has_holes {
-        log!("❌ {}", path);
-        log!("   Holes: {} total", stats.holes.total_holes);
-        
-        if stats.holes.assume_false_count > 0 {
-            log!("      {} × assume(false)", stats.holes.assume_false_count);
-        }
-        if stats.holes.assume_count > 0 {
-            log!("      {} × assume()", stats.holes.assume_count);
-        }
-        if stats.holes.assume_new_count > 0 {
-            log!("      {} × Tracked::assume_new()", stats.holes.assume_new_count);
-        }
-        if stats.holes.assume_specification_count > 0 {
-            log!("      {} × assume_specification", stats.holes.assume_specification_count);
-        }
-        if stats.holes.admit_count > 0 {
-            log!("      {} × admit()", stats.holes.admit_count);
+        if stats.holes.admit_count > 0 {
+            log!("      {} × admit()", stats.holes.admit_count);
         }
         if stats.holes.unsafe_fn_count > 0 {
             log!("      {} × unsafe fn", stats.holes.unsafe_fn_count);
         }
-        if stats.holes.unsafe_impl_count > 0 {
-            log!("      {} × unsafe impl", stats.holes.unsafe_impl_count);
-        }
-        if stats.holes.unsafe_block_count > 0 {
-            log!("      {} × unsafe {{}}", stats.holes.unsafe_block_count);
-        }
-        if stats.holes.external_body_count > 0 {
-            log!("      {} × external_body", stats.holes.external_body_count);
-        }
-        if stats.holes.external_fn_spec_count > 0 {
-            log!("      {} × external_fn_specification", stats.holes.external_fn_spec_count);
-        }
-        if stats.holes.external_trait_spec_count > 0 {
-            log!("      {} × external_trait_specification", stats.holes.external_trait_spec_count);
-        }
-        if stats.holes.external_type_spec_count > 0 {
-            log!("      {} × external_type_specification", stats.holes.external_type_spec_count);
-        }
-        if stats.holes.external_trait_ext_count > 0 {
-            log!("      {} × external_trait_extension", stats.holes.external_trait_ext_count);
-        }
-        if stats.holes.external_count > 0 {
-            log!("      {} × external", stats.holes.external_count);
-        }
-        if stats.holes.opaque_count > 0 {
-            log!("      {} × opaque", stats.holes.opaque_count);
-        }
-        
-        if stats.proof_functions > 0 {
-            log!("   Proof functions: {} total ({} clean, {} holed)", 
-                 stats.proof_functions, 
-                 stats.clean_proof_functions, 
-                 stats.holed_proof_functions);
-        }
-    } else {
-        log!("✓ {}", path);
-        if stats.proof_functions > 0 {
-            log!("   {} clean proof function{}", 
-                 stats.proof_functions,
-                 if stats.proof_functions == 1 { "" } else { "s" });
-        }
-    }
-}
-
-/// Only fn_missing_requires, fn_missing_ensures, fn_missing_requires_ensures are "errors" (spec/style).
-/// Everything else (trivial_spec_wf, proof_fn_with_holes, spec_fn_with_holes, etc.) is a hole.
-fn is_spec_style_error(hole_type: &str) -> bool {
-    matches!(
-        hole_type,
-        "fn_missing_requires" | "fn_missing_ensures" | "fn_missing_requires_ensures"
-    )
-}
-
-fn compute_summary(file_stats_map: &HashMap<String, FileStats>, base_dir: &Path) -> SummaryStats {
-    let mut summary = SummaryStats::default();
-    summary.has_subdir_paths = file_stats_map.keys().any(|p| p.contains('/'));
-    let is_warning_level = |t: &str| matches!(t, "assume_eq_clone_workaround" | "requires_true");
-    
-    for (path_str, stats) in file_stats_map {
-        let full_path = base_dir.join(path_str).canonicalize()
-            .unwrap_or_else(|_| base_dir.join(path_str))
-            .display()
-            .to_string();
-        summary.total_files += 1;
-        
-        let has_errors = stats.warnings.iter().any(|w| !is_warning_level(&w.hole_type))
-            || stats.holes.trivial_spec_wf_count > 0;
-        if stats.holes.total_holes > 0 || has_errors {
-            summary.holed_modules += 1;
-        } else {
-            summary.clean_modules += 1;
-        }
-        
-        summary.total_proof_functions += stats.proof_functions;
-        summary.clean_proof_functions += stats.clean_proof_functions;
-        summary.holed_proof_functions += stats.holed_proof_functions;
-        
-        summary.holes.assume_false_count += stats.holes.assume_false_count;
-        summary.holes.assume_count += stats.holes.assume_count;
-        summary.holes.assume_new_count += stats.holes.assume_new_count;
-        summary.holes.assume_specification_count += stats.holes.assume_specification_count;
-        summary.holes.admit_count += stats.holes.admit_count;
-        summary.holes.unsafe_fn_count += stats.holes.unsafe_fn_count;
-        summary.holes.unsafe_impl_count += stats.holes.unsafe_impl_count;
-        summary.holes.unsafe_block_count += stats.holes.unsafe_block_count;
-        summary.holes.external_body_count += stats.holes.external_body_count;
-        summary.holes.external_fn_spec_count += stats.holes.external_fn_spec_count;
-        summary.holes.external_trait_spec_count += stats.holes.external_trait_spec_count;
-        summary.holes.external_type_spec_count += stats.holes.external_type_spec_count;
-        summary.holes.external_trait_ext_count += stats.holes.external_trait_ext_count;
-        summary.holes.external_count += stats.holes.external_count;
-        summary.holes.opaque_count += stats.holes.opaque_count;
-        summary.holes.trivial_spec_wf_count += stats.holes.trivial_spec_wf_count;
-        summary.holes.total_holes += stats.holes.total_holes;
-        
-        summary.axioms.axiom_fn_count += stats.axioms.axiom_fn_count;
-        summary.axioms.broadcast_use_axiom_count += stats.axioms.broadcast_use_axiom_count;
-        summary.axioms.total_axioms += stats.axioms.total_axioms;
-
-        summary.fn_spec.total_fns += stats.fn_spec.total_fns;
-        summary.fn_spec.exec_fns_complete += stats.fn_spec.exec_fns_complete;
-        summary.fn_spec.exec_fns_missing_spec += stats.fn_spec.exec_fns_missing_spec;
-        summary.fn_spec.proof_spec_fns_clean += stats.fn_spec.proof_spec_fns_clean;
-        summary.fn_spec.proof_spec_fns_with_holes += stats.fn_spec.proof_spec_fns_with_holes;
-
-        summary.total_warnings += stats.warnings.len() + stats.holes.trivial_spec_wf_count;
-        summary.total_infos += stats.infos.len();
-        let is_warning_level = |t: &str| matches!(t, "assume_eq_clone_workaround" | "requires_true");
-        for w in &stats.warnings {
-            *summary.warning_type_counts.entry(w.hole_type.clone()).or_insert(0) += 1;
-            let entry = (full_path.clone(), w.line, w.hole_type.clone());
-            if is_warning_level(&w.hole_type) {
-                summary.all_warnings.push(entry);
-            } else {
-                summary.all_errors.push(entry);
-            }
-        }
-        // trivial_spec_wf is in holes only (not warnings) to avoid double-print; add to all_errors here
-        for h in &stats.holes.holes {
-            if h.hole_type == "trivial_spec_wf" {
-                *summary.warning_type_counts.entry("trivial_spec_wf".to_string()).or_insert(0) += 1;
-                summary.all_errors.push((full_path.clone(), h.line, h.hole_type.clone()));
-            }
-        }
-        for info in &stats.infos {
-            summary.all_infos.push((
-                full_path.clone(),
-                info.line,
-                info.hole_type.clone(),
-                info.context.clone(),
-            ));
-        }
-
-        // Aggregate for Proof Targets (root/* and root/*/*), only for paths with subdirs
-        // All issues (holes + fn_missing_*) count as holes for display
-        if path_str.contains('/') {
-            let file_errors = stats.warnings.iter().filter(|w| is_spec_style_error(&w.hole_type)).count();
-            let file_holes = stats.holes.total_holes + file_errors;
-            let path_no_ext = path_str.strip_suffix(".rs").unwrap_or(path_str);
-            let parts: Vec<&str> = path_no_ext.split('/').collect();
-            let root = parts[0].to_string();
-            let top_level = if parts.len() >= 2 { parts[1].to_string() } else { continue };
-            let e = summary.by_root_top.entry(root.clone()).or_default()
-                .entry(top_level.clone()).or_insert((0, 0, 0));
-            e.1 += file_holes;
-            e.2 += 1;
-        }
-    }
-
-    // Next Target Files and Directories: worst among those that depend only on clean modules
-    let all_modules: HashSet<String> = file_stats_map.keys()
-        .map(|p| path_str_to_module(p))
-        .collect();
-    let mut module_to_holed: HashMap<String, bool> = HashMap::new();
-    for (path_str, stats) in file_stats_map.iter() {
-        let module = path_str_to_module(path_str);
-        let has_errors = stats.warnings.iter().any(|w| !is_warning_level(&w.hole_type))
-            || stats.holes.trivial_spec_wf_count > 0;
-        module_to_holed.insert(module, stats.holes.total_holes > 0 || has_errors);
-    }
-    for (path_str, stats) in file_stats_map.iter() {
-        if !path_str.starts_with("src/") {
-            continue;
-        }
-        let mut has_holed_dep = false;
-        for dep in &stats.crate_deps {
-            for m in &all_modules {
-                if (*m == *dep || m.starts_with(&format!("{}::", dep)))
-                    && *module_to_holed.get(m).unwrap_or(&false)
-                {
-                    has_holed_dep = true;
-                    break;
-                }
-            }
-            if has_holed_dep {
-                break;
-            }
+        if stats.holes.unsafe_impl_count > 0 {
+            log!("      {} × unsafe impl", stats.holes.unsafe_impl_count);
         }
-        if !has_holed_dep {
-            let file_errors = stats.warnings.iter().filter(|w| is_spec_style_error(&w.hole_type)).count();
-            let file_holes = stats.holes.total_holes + file_errors;
-            if file_holes > 0 {
-                summary.next_target_files.push((path_str.clone(), file_holes));
-            }
+        if stats.holes.unsafe_block_count > 0 {
+            log!("      {} × unsafe {{}}", stats.holes.unsafe_block_count);
         }
-    }
-    summary.next_target_files.sort_by(|a, b| b.1.cmp(&a.1)); // sort by holes descending
-
-    // Next Target Directories: dirs where ALL files depend only on clean
-    let mut dir_files: HashMap<String, Vec<(usize, bool)>> = HashMap::new();
-    for (path_str, stats) in file_stats_map.iter() {
-        if !path_str.starts_with("src/") {
-            continue;
+        if stats.holes.external_body_count > 0 {
+            log!("      {} × external_body", stats.holes.external_body_count);
         }
-        let dir = path_str.rsplit_once('/').map(|(d, _)| d.to_string()).unwrap_or_else(|| path_str.clone());
-        let mut has_holed_dep = false;
-        for dep in &stats.crate_deps {
-            for m in &all_modules {
-                if (*m == *dep || m.starts_with(&format!("{}::", dep)))
-                    && *module_to_holed.get(m).unwrap_or(&false)
-                {
-                    has_holed_dep = true;
-                    break;
-                }
-            }
-            if has_holed_dep {
-                break;
-            }
+        if stats.holes.external_fn_spec_count > 0 {
+            log!("      {} × external_fn_specification", stats.holes.external_fn_spec_count);
         }
-        let file_errors = stats.warnings.iter().filter(|w| is_spec_style_error(&w.hole_type)).count();
-        let file_holes = stats.holes.total_holes + file_errors;
-        dir_files.entry(dir).or_default().push((file_holes, has_holed_dep));
-    }
-    for (dir, files) in dir_files {
-        if dir == "src" {
-            continue; // skip root; only show subdirs like src/Chap05
+        if stats.holes.external_trait_spec_count > 0 {
+            log!("      {} × external_trait_specification", stats.holes.external_trait_spec_count);
         }
-        if files.iter().any(|(_, h)| *h) {
-            continue;
+        if stats.holes.external_type_spec_count > 0 {
+            log!("      {} × external_type_specification", stats.holes.external_type_spec_count);
         }
-        let holes: usize = files.iter().map(|(h, _)| h).sum();
-        if holes > 0 {
-            summary.next_target_dirs.push((dir, holes, files.len()));
+        if stats.holes.external_trait_ext_count > 0 {
+            log!("      {} × external_trait_extension", stats.holes.external_trait_ext_count);
         }
-    }
-    summary.next_target_dirs.sort_by(|a, b| b.1.cmp(&a.1)); // sort by holes descending
-
-    // Not verusified: files with no verus! block
-    for (path_str, stats) in file_stats_map.iter() {
-        if !path_str.starts_with("src/") {
-            continue;
+        if stats.holes.external_count > 0 {
+            log!("      {} × external", stats.holes.external_count);
         }
-        let is_not_verusified = stats.warnings.iter().any(|w| w.hole_type == "not_verusified");
-        if is_not_verusified {
-            summary.not_verusified_files.push(path_str.clone());
+        if stats.holes.opaque_count > 0 {
+            log!("      {} × opaque", stats.holes.opaque_count);
         }
-    }
-    summary.not_verusified_files.sort();
-
-    // Not verusified with clean deps: same but only if depends only on clean modules
-    for (path_str, stats) in file_stats_map.iter() {
-        if !path_str.starts_with("src/") {
-            continue;
+        if stats.holes.verification_failure_count > 0 {
+            log!("      {} × verification failure", stats.holes.verification_failure_count);
         }
-        let is_not_verusified = stats.warnings.iter().any(|w| w.hole_type == "not_verusified");
-        if !is_not_verusified {
-            continue;
+        
+        if stats.proof_functions > 0 {
+            log!("   Proof functions: {} total ({} clean, {} holed)", 
+                 stats.proof_functions, 
+                 stats.clean_proof_functions, 
+                 stats.holed_proof_functions);
         }
-        let mut has_holed_dep = false;
-        for dep in &stats.crate_deps {
-            for m in &all_modules {
-                if (*m == *dep || m.starts_with(&format!("{}::", dep)))
-                    && *module_to_holed.get(m).unwrap_or(&false)
-                {
-                    has_holed_dep = true;
-                    break;
-                }
-            }
-            if has_holed_dep {
-                break;
-            }
+    } else {
+        log!("✓ {}", path);
+        if stats.proof_functions > 0 {
+            log!("   {} clean proof function{}", 
+                 stats.proof_functions,
+                 if stats.proof_functions == 1 { "" } else { "s" });
         }
-        if !has_holed_dep {
-            summary.not_verusified_clean_deps.push(path_str.clone());
+    }
+    if !stats.soft_holes.is_empty() {
+        log!("   Soft holes: {} total", stats.soft_holes.len());
+        for (kind, n) in stats.soft_hole_counts() {
+            log!("      {} × {}", n, kind);
         }
     }
-    summary.not_verusified_clean_deps.sort();
-    
-    summary
-}
-
-/// path_str "src/Chap05/SetStEph.rs" -> "Chap05::SetStEph" (strip src/ for crate module path)
-fn path_str_to_module(path_str: &str) -> String {
-    let s = path_str.strip_suffix(".rs").unwrap_or(path_str);
-    let s = s.strip_prefix("src/").unwrap_or(s);
-    s.replace('/', "::")
 }
 
-fn print_depends_upon(file_stats_map: &HashMap<String, FileStats>) {
-    let is_warning_level = |t: &str| matches!(t, "assume_eq_clone_workaround" | "requires_true");
-    let mut module_to_holed: HashMap<String, bool> = HashMap::new();
-    for (path_str, stats) in file_stats_map {
-        let module = path_str_to_module(path_str);
-        let has_errors = stats.warnings.iter().any(|w| !is_warning_level(&w.hole_type))
-            || stats.holes.trivial_spec_wf_count > 0;
-        let holed = stats.holes.total_holes > 0 || has_errors;
-        module_to_holed.insert(module.clone(), holed);
-    }
-    let all_modules: HashSet<String> = module_to_holed.keys().cloned().collect();
-
+fn print_depends_upon(file_stats_map: &HashMap<String, FileHoles>) {
     log!("");
     log!("=================================================================");
     log!("2. Depends Upon");
     log!("=================================================================");
     log!("");
 
-    // Build (module, path_str, holed_deps) for each file
-    let mut entries: Vec<(String, String, Vec<String>)> = Vec::new();
-    for path_str in file_stats_map.keys() {
-        let stats = file_stats_map.get(path_str).unwrap();
-        let module = path_str_to_module(path_str);
-        let mut holed_deps: Vec<String> = Vec::new();
-        for dep in &stats.crate_deps {
-            for m in &all_modules {
-                if (*m == *dep || m.starts_with(&format!("{}::", dep)))
-                    && *module_to_holed.get(m).unwrap_or(&false)
-                {
-                    holed_deps.push(m.clone());
-                }
-            }
-        }
-        holed_deps.sort();
-        holed_deps.dedup();
-        entries.push((module, path_str.clone(), holed_deps));
-    }
-    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    let entries = compute_depends_upon(file_stats_map);
 
     log!("=================================================================");
     log!("2.1. By Module");
@@ -3921,6 +1463,11 @@ fn print_summary(summary: &SummaryStats) {
     log!("   {} clean ({}%)", summary.clean_proof_functions, pct(summary.clean_proof_functions, summary.total_proof_functions));
     log!("   {} holed ({}%)", summary.holed_proof_functions, pct(summary.holed_proof_functions, summary.total_proof_functions));
     log!("   {} total", summary.total_proof_functions);
+    let proof_trust: Vec<&FnTrust> = summary.trust.iter().filter(|t| t.mode == "proof").collect();
+    if !proof_trust.is_empty() {
+        let truly_clean = proof_trust.iter().filter(|t| t.trusted_base.is_empty()).count();
+        log!("   {} truly clean, no holes reachable through calls ({}%; see 4.7)", truly_clean, pct(truly_clean, proof_trust.len()));
+    }
     log!("");
     if summary.fn_spec.total_fns > 0 {
         log!("Function Specs:");
@@ -3982,6 +1529,21 @@ fn print_summary(summary: &SummaryStats) {
     if summary.holes.trivial_spec_wf_count > 0 {
         log!("   {} × trivial spec*wf {{ true }} ({}%)", summary.holes.trivial_spec_wf_count, pct(summary.holes.trivial_spec_wf_count, total_holes));
     }
+    if summary.holes.verification_failure_count > 0 {
+        log!("   {} × verification failure ({}%)", summary.holes.verification_failure_count, pct(summary.holes.verification_failure_count, total_holes));
+    }
+    if !summary.all_soft_holes.is_empty() {
+        log!("");
+        log!("Soft Holes (proof fragility, not counted as holes): {} total", summary.all_soft_holes.len());
+        for (kind, n) in &summary.soft_hole_counts {
+            log!("   {} × {}", n, kind);
+        }
+        let mut soft_holes: Vec<&(String, usize, HoleKind)> = summary.all_soft_holes.iter().collect();
+        soft_holes.sort();
+        for (path, line, kind) in soft_holes {
+            log!("   {}:{}: {}", path, line, kind);
+        }
+    }
 
     if summary.holes.total_holes == 0 && summary.total_warnings == 0 {
         log!("");
@@ -4084,82 +1646,25 @@ fn print_summary(summary: &SummaryStats) {
     }
 }
 
-/// Extract chapter from path: src/Chap43/File.rs -> Chap43, src/Concurrency.rs -> Concurrency.
-fn path_str_to_chapter(path_str: &str) -> Option<String> {
-    if !path_str.starts_with("src/") {
-        return None;
-    }
-    let s = path_str.strip_suffix(".rs").unwrap_or(path_str);
-    let parts: Vec<&str> = s.split('/').collect();
-    if parts.len() < 2 {
-        return None;
-    }
-    Some(parts[1].to_string())
-}
-
-/// Extract chapter from module path: Chap37::AVLTreeSeqStEph -> Chap37.
-fn module_to_chapter(module: &str) -> Option<String> {
-    module.split("::").next().map(|s| s.to_string())
-}
-
-/// Chapter sort: Chap02 < Chap03 < ... < Chap66 < Concurrency < ParaPairs.
-fn chapter_sort_key(ch: &str) -> (bool, u32, &str) {
-    if let Some(num_str) = ch.strip_prefix("Chap") {
-        if let Ok(n) = num_str.parse::<u32>() {
-            return (false, n, "");
-        }
-    }
-    (true, u32::MAX, ch)
-}
-
-/// Print section 4.6: Chapter by Chapter Proof Targeting (same analysis as chapter-cleanliness-status.sh).
+/// Print section 4.6: Chapter by Chapter Proof Targeting (same analysis as chapter-cleanliness-status.sh),
+/// over the groups of the configured hierarchy.
 fn print_chapter_by_chapter_proof_targeting(
-    file_stats_map: &HashMap<String, FileStats>,
+    file_stats_map: &HashMap<String, FileHoles>,
     summary: &SummaryStats,
+    base_dir: &Path,
+    grouping: &Grouping,
 ) {
-    let top_map = match summary.by_root_top.get("src") {
-        Some(m) if !m.is_empty() => m,
-        _ => return,
-    };
-
-    let is_warning_level = |t: &str| matches!(t, "assume_eq_clone_workaround" | "requires_true");
-    let mut module_to_holed: HashMap<String, bool> = HashMap::new();
-    for (path_str, stats) in file_stats_map {
-        let module = path_str_to_module(path_str);
-        let has_errors = stats.warnings.iter().any(|w| !is_warning_level(&w.hole_type))
-            || stats.holes.trivial_spec_wf_count > 0;
-        let holed = stats.holes.total_holes > 0 || has_errors;
-        module_to_holed.insert(module.clone(), holed);
-    }
-    let all_modules: HashSet<String> = module_to_holed.keys().cloned().collect();
-
-    // Build (module, path_str, holed_deps) for each file
-    let mut entries: Vec<(String, String, Vec<String>)> = Vec::new();
-    for path_str in file_stats_map.keys() {
-        let stats = file_stats_map.get(path_str).unwrap();
-        let module = path_str_to_module(path_str);
-        let mut holed_deps: Vec<String> = Vec::new();
-        for dep in &stats.crate_deps {
-            for m in &all_modules {
-                if (*m == *dep || m.starts_with(&format!("{}::", dep)))
-                    && *module_to_holed.get(m).unwrap_or(&false)
-                {
-                    holed_deps.push(m.clone());
-                }
-            }
-        }
-        holed_deps.sort();
-        holed_deps.dedup();
-        entries.push((module, path_str.clone(), holed_deps));
+    let group_of: HashMap<&str, String> = file_stats_map.keys()
+        .filter_map(|p| grouping.group_of_path(&base_dir.join(p)).map(|g| (p.as_str(), g)))
+        .collect();
+    if group_of.is_empty() {
+        return;
     }
+    let module_group: HashMap<String, &String> = group_of.iter()
+        .map(|(p, g)| (path_str_to_module(p), g))
+        .collect();
 
-    // Collect chapters from top_map and from file paths (for dep-only chapters)
-    let mut chapters: HashSet<String> = top_map.keys().cloned().collect();
-    for path_str in file_stats_map.keys() {
-        if let Some(ch) = path_str_to_chapter(path_str) {
-            chapters.insert(ch);
-        }
-    }
+    let entries = compute_depends_upon(file_stats_map);
 
     // Per-chapter: holes, files, ext_deps, int_deps
     let mut ext_deps: HashMap<String, Vec<String>> = HashMap::new();
@@ -4168,64 +1673,53 @@ fn print_chapter_by_chapter_proof_targeting(
     let mut seen_int: HashSet<(String, String)> = HashSet::new();
 
     for (_, path_str, holed_deps) in &entries {
-        let Some(chap) = path_str_to_chapter(path_str) else { continue };
+        let Some(chap) = group_of.get(path_str.as_str()) else { continue };
         for dep in holed_deps {
-            let Some(dep_chap) = module_to_chapter(dep) else { continue };
+            let Some(dep_chap) = module_group.get(dep) else { continue };
             let key = (chap.clone(), dep.clone());
-            if dep_chap != chap {
+            if *dep_chap != chap {
                 if seen_ext.insert(key) {
                     ext_deps.entry(chap.clone()).or_default().push(dep.clone());
                 }
-            } else {
-                if seen_int.insert(key) {
-                    int_deps.entry(chap.clone()).or_default().push(dep.clone());
-                }
+            } else if seen_int.insert(key) {
+                int_deps.entry(chap.clone()).or_default().push(dep.clone());
             }
         }
     }
 
-    // Sort chapters
-    let mut chap_list: Vec<String> = chapters.into_iter().collect();
-    chap_list.sort_by(|a, b| {
-        let ka = chapter_sort_key(a);
-        let kb = chapter_sort_key(b);
-        ka.cmp(&kb)
-    });
-
-    // Holes and files per chapter
+    // Holes and files per chapter; all issues (holes + fn_missing_*) count as holes, as in 4.1
     let mut holes: HashMap<String, usize> = HashMap::new();
     let mut files: HashMap<String, usize> = HashMap::new();
-    for (ch, (_, h, f)) in top_map {
-        holes.insert(ch.clone(), *h);
-        files.insert(ch.clone(), *f);
-    }
-    for ch in &chap_list {
-        if !holes.contains_key(ch) {
-            holes.insert(ch.clone(), 0);
-        }
-        if !files.contains_key(ch) {
-            let count = file_stats_map.keys().filter(|p| path_str_to_chapter(p).as_deref() == Some(ch)).count();
-            files.insert(ch.clone(), count);
-        }
+    for (path_str, ch) in &group_of {
+        let stats = &file_stats_map[*path_str];
+        let file_errors = stats.warnings.iter().filter(|w| w.kind.is_missing_spec()).count();
+        *holes.entry(ch.clone()).or_default() += stats.holes.total_holes + file_errors;
+        *files.entry(ch.clone()).or_default() += 1;
     }
 
+    let mut chap_list: Vec<String> = files.keys().cloned().collect();
+    grouping.sort(&mut chap_list);
+
     let n_clean = chap_list.iter().filter(|c| *holes.get(*c).unwrap_or(&0) == 0).count();
     let n_holed = chap_list.len() - n_clean;
     let total_holes: usize = chap_list.iter().map(|c| holes.get(c).unwrap_or(&0)).sum();
     let total_f: usize = chap_list.iter().map(|c| *files.get(c).unwrap_or(&0)).sum();
     let global_holes = summary.holes.total_holes;
+    let label = grouping.label();
+    let lower = label.to_lowercase();
+    let upper = label.to_uppercase();
 
     log!("");
     log!("=================================================================");
-    log!("4.6. Chapter by Chapter Proof Targeting");
+    log!("4.6. {} by {} Proof Targeting", label, label);
     log!("=================================================================");
     log!("");
-    log!("Chapter Status — {} chapters, {} clean, {} holed, {} holes (global), {} modules",
-        chap_list.len(), n_clean, n_holed, global_holes, total_f);
+    log!("{} Status — {} {}s, {} clean, {} holed, {} holes (global), {} modules",
+        label, chap_list.len(), lower, n_clean, n_holed, global_holes, total_f);
     log!("");
 
-    log!("CLEAN CHAPTERS ({})", n_clean);
-    log!("  {:<14} {:>5}", "Chapter", "Files");
+    log!("CLEAN {}S ({})", upper, n_clean);
+    log!("  {:<14} {:>5}", label, "Files");
     log!("  {:<14} {:>5}", "--------------", "-----");
     for ch in &chap_list {
         if *holes.get(ch).unwrap_or(&0) == 0 {
@@ -4234,8 +1728,8 @@ fn print_chapter_by_chapter_proof_targeting(
     }
 
     log!("");
-    log!("HOLED CHAPTERS ({}) — {} holes", n_holed, total_holes);
-    log!("  {:<14} {:>5} {:>5}  {:<8}  {}", "Chapter", "Holes", "Files", "ClnDeps?", "Blocked by (external holed modules)");
+    log!("HOLED {}S ({}) — {} holes", upper, n_holed, total_holes);
+    log!("  {:<14} {:>5} {:>5}  {:<8}  {}", label, "Holes", "Files", "ClnDeps?", "Blocked by (external holed modules)");
     log!("  {:<14} {:>5} {:>5}  {:<8}  {}", "--------------", "-----", "-----", "--------", "-----------------------------------");
     for ch in &chap_list {
         let h = *holes.get(ch).unwrap_or(&0);
@@ -4253,19 +1747,16 @@ fn print_chapter_by_chapter_proof_targeting(
     }
 
     log!("");
-    log!("DEPENDENCY CHAIN (chapter-level, external only)");
-    log!("  {:<14}  {}", "Chapter", "Blocked by chapters");
+    log!("DEPENDENCY CHAIN ({}-level, external only)", lower);
+    log!("  {:<14}  Blocked by {}s", label, lower);
     log!("  {:<14}  {}", "--------------", "-------------------");
     for ch in &chap_list {
         if let Some(deps) = ext_deps.get(ch) {
-            let mut dep_chaps: HashSet<String> = HashSet::new();
-            for d in deps {
-                if let Some(dc) = module_to_chapter(d) {
-                    dep_chaps.insert(dc);
-                }
-            }
+            let dep_chaps: HashSet<String> = deps.iter()
+                .filter_map(|d| module_group.get(d).map(|g| (*g).clone()))
+                .collect();
             let mut dep_list: Vec<String> = dep_chaps.into_iter().collect();
-            dep_list.sort_by(|a, b| chapter_sort_key(a).cmp(&chapter_sort_key(b)));
+            grouping.sort(&mut dep_list);
             log!("  {:<14}  {}", ch, dep_list.join(", "));
         }
     }
@@ -4354,37 +1845,8 @@ fn print_global_summary(projects: &[ProjectStats]) {
     log!("");
     
     let mut global = GlobalSummaryStats::default();
-    global.total_projects = projects.len();
-    
-    // Aggregate stats across all projects
     for project in projects {
-        global.total_files += project.summary.total_files;
-        global.clean_modules += project.summary.clean_modules;
-        global.holed_modules += project.summary.holed_modules;
-        global.total_proof_functions += project.summary.total_proof_functions;
-        global.clean_proof_functions += project.summary.clean_proof_functions;
-        global.holed_proof_functions += project.summary.holed_proof_functions;
-        
-        global.holes.assume_false_count += project.summary.holes.assume_false_count;
-        global.holes.assume_count += project.summary.holes.assume_count;
-        global.holes.assume_new_count += project.summary.holes.assume_new_count;
-        global.holes.assume_specification_count += project.summary.holes.assume_specification_count;
-        global.holes.admit_count += project.summary.holes.admit_count;
-        global.holes.unsafe_fn_count += project.summary.holes.unsafe_fn_count;
-        global.holes.unsafe_impl_count += project.summary.holes.unsafe_impl_count;
-        global.holes.unsafe_block_count += project.summary.holes.unsafe_block_count;
-        global.holes.external_body_count += project.summary.holes.external_body_count;
-        global.holes.external_fn_spec_count += project.summary.holes.external_fn_spec_count;
-        global.holes.external_trait_spec_count += project.summary.holes.external_trait_spec_count;
-        global.holes.external_type_spec_count += project.summary.holes.external_type_spec_count;
-        global.holes.external_trait_ext_count += project.summary.holes.external_trait_ext_count;
-        global.holes.external_count += project.summary.holes.external_count;
-        global.holes.opaque_count += project.summary.holes.opaque_count;
-        global.holes.total_holes += project.summary.holes.total_holes;
-        
-        global.axioms.axiom_fn_count += project.summary.axioms.axiom_fn_count;
-        global.axioms.broadcast_use_axiom_count += project.summary.axioms.broadcast_use_axiom_count;
-        global.axioms.total_axioms += project.summary.axioms.total_axioms;
+        global.add(&project.summary);
     }
     
     log!("Projects Scanned: {}", global.total_projects);
@@ -4495,3 +1957,730 @@ fn print_global_summary(projects: &[ProjectStats]) {
     }
 }
 
+
+// ============================================================================
+// Machine-readable reports (--format json / --format sarif)
+// ============================================================================
+
+/// Analyze every file for the report formats, keyed by path relative to the returned base dir.
+fn analyze_for_report(args: &ProofHolesArgs) -> Result<(PathBuf, HashMap<String, FileHoles>)> {
+    let (base_dir, files) = if let Some(multi_base) = &args.standard.multi_codebase {
+        let projects = discover_verus_projects(multi_base, &args.exclude_dirs)?;
+        let mut files: Vec<PathBuf> = projects.into_values().flatten().collect();
+        files.sort();
+        (multi_base.clone(), files)
+    } else {
+        (args.standard.base_dir(), collect_files(&args.standard, &args.exclude_dirs))
+    };
+    
+    let mut file_stats_map: HashMap<String, FileHoles> = HashMap::new();
+    let mut analyzer = Analyzer::new(&args.standard, !args.no_cache).with_blame(args.blame);
+    if args.verify {
+        // Keep stdout a single document: Verus progress goes to stderr
+        analyzer = analyzer.with_verification(run_verification(&base_dir, &|msg: &str| eprintln!("{}", msg))?);
+    }
+    for file in &files {
+        if let Ok(stats) = analyzer.analyze(file) {
+            let path_str = if let Ok(rel_path) = file.strip_prefix(&base_dir) {
+                rel_path.display().to_string()
+            } else {
+                file.display().to_string()
+            };
+            file_stats_map.insert(path_str, stats);
+        }
+    }
+    analyzer.finish();
+    Ok((base_dir, file_stats_map))
+}
+
+/// --format json|sarif: analyze, print one document to stdout and save it under analyses/.
+fn run_report_mode(args: &ProofHolesArgs) -> Result<()> {
+    let (base_dir, file_stats_map) = analyze_for_report(args)?;
+    let summary = compute_summary(&file_stats_map, &base_dir);
+    let mut report = build_json_report(&base_dir, &file_stats_map, &summary);
+    if args.deps {
+        report.dependencies = analyze_dependencies(&args.standard.base_dir())?;
+    }
+    if args.blame {
+        report.attribution = Some(HoleAttribution::new(&file_stats_map));
+    }
+    if args.tcb {
+        report.tcb = Some(TcbReport::new(&base_dir, file_stats_map.keys().cloned()));
+    }
+    report.accepted = build_accepted_inventory(&args.standard.config, &base_dir, &file_stats_map);
+    if args.graph {
+        write_module_graph(&summary.module_graph, &args.standard.base_dir(), &|msg: &str| eprintln!("{}", msg))?;
+    }
+    
+    let (text, file_name) = match args.format {
+        OutputFormat::Sarif => (
+            serde_json::to_string_pretty(&build_sarif_report(&report))?,
+            "veracity-review-verus-proof-holes.sarif",
+        ),
+        _ => (
+            serde_json::to_string_pretty(&report)?,
+            "veracity-review-verus-proof-holes.json",
+        ),
+    };
+    
+    let analyses_dir = args.standard.base_dir().join("analyses");
+    let _ = fs::create_dir_all(&analyses_dir);
+    let _ = fs::write(analyses_dir.join(file_name), &text);
+    
+    println!("{}", text);
+    let _ = std::io::stdout().flush();
+    
+    // Keep stdout a single document: baseline results go to stderr
+    let new_holes = run_baseline(args, &base_dir, &file_stats_map, &|msg: &str| eprintln!("{}", msg))?;
+    let over_budget = run_budgets(&args.standard.config, &base_dir, &file_stats_map, &|msg: &str| eprintln!("{}", msg));
+    if new_holes > 0 || over_budget > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Dependency trust (--deps)
+// ============================================================================
+
+fn print_dependency_trust(deps: &[DepTrust], emit: &dyn Fn(&str)) {
+    emit("");
+    emit("=================================================================");
+    emit("D. Dependency Trust");
+    emit("=================================================================");
+    emit("");
+    emit("   Not included in the numbers above.");
+    emit("");
+    if deps.is_empty() {
+        emit("   No path or vendored dependencies");
+        return;
+    }
+    emit(&format!("   {:<24} {:<8} {:>6} {:>6} {:>6} {:>11} {:>13}",
+        "crate", "source", "files", "holes", "axioms", "assume_spec", "external_body"));
+    for d in deps {
+        let source = match d.source {
+            DepSource::Path => "path",
+            DepSource::Vendored => "vendored",
+        };
+        emit(&format!("   {:<24} {:<8} {:>6} {:>6} {:>6} {:>11} {:>13}  (via {}) {}",
+            d.name, source, d.files, d.holes, d.axioms, d.assume_specification, d.external_body,
+            d.required_by, d.dir));
+    }
+    let holed = deps.iter().filter(|d| d.holes > 0).count();
+    let holes: usize = deps.iter().map(|d| d.holes).sum();
+    emit(&format!("{} of {} dependencies have holes ({} holes)", holed, deps.len(), holes));
+}
+
+// ============================================================================
+// Trusted computing base (--tcb)
+// ============================================================================
+
+fn print_tcb(report: &TcbReport, base_dir: &Path, deps: &[DepTrust], emit: &dyn Fn(&str)) {
+    emit("");
+    emit("=================================================================");
+    emit("T. Trusted Computing Base");
+    emit("=================================================================");
+    emit("");
+    for (path_str, items) in &report.files {
+        let abs_path = base_dir.join(path_str);
+        let abs_path = abs_path.canonicalize().unwrap_or(abs_path);
+        for item in items {
+            let spec = if item.spec.is_empty() { "(no spec)".to_string() } else { truncate_spec(&item.spec) };
+            emit(&format!("{}:{}: info: {} {} ({} lines, {} tokens) trusts: {}",
+                abs_path.display(), item.line, item.kind, item.name, item.lines, item.tokens, spec));
+        }
+    }
+    emit("");
+    emit("By module:");
+    emit(&format!("   {:>6} {:>7} {:>8}  module", "items", "lines", "tokens"));
+    let mut modules: Vec<(&String, &TcbTotals)> = report.modules.iter().collect();
+    modules.sort_by(|a, b| b.1.tokens.cmp(&a.1.tokens).then_with(|| a.0.cmp(b.0)));
+    for (module, t) in modules {
+        emit(&format!("   {:>6} {:>7} {:>8}  {}", t.items, t.lines, t.tokens, module));
+    }
+    emit("");
+    emit("By kind:");
+    for kind in TrustKind::ALL {
+        if let Some(t) = report.by_kind.get(kind.as_str()) {
+            emit(&format!("   {:>6} {:>7} {:>8}  {}", t.items, t.lines, t.tokens, kind));
+        }
+    }
+    emit("");
+    emit("By crate:");
+    emit(&format!("   {:>6} {:>7} {:>8}  (this crate)", report.total.items, report.total.lines, report.total.tokens));
+    for dep in deps {
+        emit(&format!("   {:>6} {:>7} {:>8}  {}", dep.tcb.items, dep.tcb.lines, dep.tcb.tokens, dep.name));
+    }
+    emit("");
+    emit(&format!("Trusted: {} items, {} lines, {} tokens", report.total.items, report.total.lines, report.total.tokens));
+}
+
+/// Spec text for a one-line diagnostic
+fn truncate_spec(spec: &str) -> String {
+    const MAX: usize = 120;
+    match spec.char_indices().nth(MAX) {
+        Some((i, _)) => format!("{}...", &spec[..i]),
+        None => spec.to_string(),
+    }
+}
+
+// ============================================================================
+// Hole attribution (--blame)
+// ============================================================================
+
+/// " [author date abcdef1]" for the Emacs line of a blamed hole
+fn blame_suffix(hole: &DetectedHole) -> String {
+    match &hole.blame {
+        Some(b) => format!(" [{} {} {}]", b.author, b.date, &b.commit[..b.commit.len().min(7)]),
+        None => String::new(),
+    }
+}
+
+fn print_hole_attribution(file_stats_map: &HashMap<String, FileHoles>, emit: &dyn Fn(&str)) {
+    let attribution = HoleAttribution::new(file_stats_map);
+    emit("");
+    emit("=================================================================");
+    emit("A. Hole Attribution");
+    emit("=================================================================");
+    emit("");
+    emit("   By author:");
+    let mut authors: Vec<(&String, &usize)> = attribution.by_author.iter().collect();
+    authors.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    for (author, holes) in authors {
+        emit(&format!("   {:>6}  {}", holes, author));
+    }
+    emit("");
+    emit("   By age:");
+    for (label, _) in AGE_BUCKETS {
+        emit(&format!("   {:>6}  {}", attribution.by_age[label], label));
+    }
+    if attribution.unattributed > 0 {
+        emit("");
+        emit(&format!("   {} holes not attributed (git blame failed)", attribution.unattributed));
+    }
+}
+
+// ============================================================================
+// Attestation manifest (--attest / --check)
+// ============================================================================
+
+/// --attest FILE: analyze, then write the manifest
+fn run_attest(args: &ProofHolesArgs, path: &Path) -> Result<()> {
+    let (base_dir, file_stats_map) = analyze_for_report(args)?;
+    let files = collect_files(&args.standard, &args.exclude_dirs);
+    let attestation = build_attestation(&args.standard.config.justification, &base_dir, &file_stats_map, files)?;
+    let json = serde_json::to_string_pretty(&attestation)?;
+    fs::write(path, json).with_context(|| format!("Failed to write attestation: {}", path.display()))?;
+    println!("Wrote attestation: {}", path.display());
+    println!("   {} files hashed", attestation.files.len());
+    println!("   {} holes, {} accepted, {} trusted items", attestation.holes.len(), attestation.accepted.len(), attestation.trusted.len());
+    println!("   veracity {}, {}, vstd {}", attestation.veracity_version, attestation.verus_version, attestation.vstd_commit);
+    Ok(())
+}
+
+/// --check FILE: re-hash the tree and report drift from the manifest; exit 1 on any drift
+fn run_check(args: &ProofHolesArgs, path: &Path) -> Result<()> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read attestation: {}", path.display()))?;
+    let attestation: Attestation = serde_json::from_str(&text)
+        .with_context(|| format!("Failed to parse attestation: {}", path.display()))?;
+    let base_dir = args.standard.base_dir();
+    let current = hash_tree(collect_files(&args.standard, &args.exclude_dirs), &base_dir)?;
+
+    println!("Attestation: {} (generated {})", path.display(), attestation.generated);
+    let drift = attestation.drift(&current);
+    for d in &drift {
+        match d {
+            Drift::Tool(what, recorded, now) => println!("{}: {} -> {}", what, recorded, now),
+            Drift::Removed(file) => println!("{}: removed", base_dir.join(file).display()),
+            Drift::Changed(file) => println!("{}:1: changed since attestation", base_dir.join(file).display()),
+            Drift::Added(file) => println!("{}:1: added since attestation", base_dir.join(file).display()),
+        }
+    }
+
+    if drift.is_empty() {
+        println!("✓ No drift: {} files match the attestation.", current.len());
+        return Ok(());
+    }
+    println!("{} differences from the attestation.", drift.len());
+    let _ = std::io::stdout().flush();
+    std::process::exit(1);
+}
+
+// ============================================================================
+// History mode (--history)
+// ============================================================================
+
+/// --history RANGE: analyze base_dir at each commit and write the time series under analyses/.
+fn run_history_mode(args: &ProofHolesArgs, range: &str) -> Result<()> {
+    let mut history = HistoryAnalyzer::new(&args.standard, &args.exclude_dirs, !args.no_cache)?;
+    let commits = history.commits(range)?;
+    let json = args.format == OutputFormat::Json;
+    if !json {
+        println!("History of {} over {} ({} commits)", history.base_dir.display(), range, commits.len());
+        println!();
+        println!("   {:<10} {:<25} {:>6} {:>6} {:>6} {:>6}", "commit", "date", "holes", "clean", "holed", "files");
+    }
+
+    let mut points = Vec::new();
+    for (commit, date, subject) in &commits {
+        let file_stats_map = history.analyze_commit(commit)?;
+        let summary = compute_summary(&file_stats_map, &history.base_dir);
+        let point = history_point(commit, date, subject, &file_stats_map, &summary);
+        if !json {
+            println!("   {:<10} {:<25} {:>6} {:>6} {:>6} {:>6}",
+                &commit[..commit.len().min(10)], date, point.total_holes,
+                point.clean_modules, point.holed_modules, point.total_files);
+        }
+        points.push(point);
+    }
+    let (reused, analyzed) = history.finish();
+
+    let report = HistoryReport {
+        tool: "veracity-review-proof-holes".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        generated: Local::now().format("%Y-%m-%d %H:%M:%S %Z").to_string(),
+        base_dir: history.base_dir.display().to_string(),
+        range: range.to_string(),
+        commits: points,
+    };
+    let text = serde_json::to_string_pretty(&report)?;
+    let analyses_dir = history.base_dir.join("analyses");
+    let _ = fs::create_dir_all(&analyses_dir);
+    let json_path = analyses_dir.join("veracity-review-verus-proof-holes-history.json");
+    let csv_path = analyses_dir.join("veracity-review-verus-proof-holes-history.csv");
+    fs::write(&json_path, &text).with_context(|| format!("Failed to write {}", json_path.display()))?;
+    fs::write(&csv_path, history_csv(&report.commits)).with_context(|| format!("Failed to write {}", csv_path.display()))?;
+
+    if json {
+        println!("{}", text);
+    } else {
+        println!();
+        println!("Cache: {} unchanged files reused, {} analyzed", reused, analyzed);
+        println!("Wrote {}", csv_path.display());
+        println!("Wrote {}", json_path.display());
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Baseline / ratchet mode (--baseline / --write-baseline)
+// ============================================================================
+
+/// Write and/or compare against a baseline. Returns the number of new holes (0 if no --baseline).
+fn run_baseline(
+    args: &ProofHolesArgs,
+    base_dir: &Path,
+    file_stats_map: &HashMap<String, FileHoles>,
+    emit: &dyn Fn(&str),
+) -> Result<usize> {
+    if args.baseline.is_none() && args.write_baseline.is_none() {
+        return Ok(0);
+    }
+    let current = collect_hole_keys(base_dir, file_stats_map);
+
+    emit("");
+    emit("=================================================================");
+    emit("B. Baseline Comparison");
+    emit("=================================================================");
+    emit("");
+
+    // Load before writing so the same file can be compared and then ratcheted
+    let recorded = match &args.baseline {
+        Some(path) => Some(load_baseline(path)?),
+        None => None,
+    };
+
+    if let Some(path) = &args.write_baseline {
+        write_baseline(path, &current)?;
+        let total: usize = current.values().map(|lines| lines.len()).sum();
+        emit(&format!("Wrote baseline: {} ({} holes, {} keys)", path.display(), total, current.len()));
+        emit("");
+    }
+
+    let (Some(path), Some(recorded)) = (&args.baseline, recorded) else { return Ok(0) };
+
+    let diff = compare(&current, &recorded);
+
+    emit(&format!("Baseline: {}", path.display()));
+    emit(&format!("   {} new, {} fixed", diff.new.len(), diff.fixed.iter().map(|(_, n)| n).sum::<usize>()));
+    emit("");
+    if !diff.new.is_empty() {
+        emit("New holes (not in baseline):");
+        for (key, line) in &diff.new {
+            let abs_path = base_dir.join(&key.file);
+            let abs_path = abs_path.canonicalize().unwrap_or(abs_path);
+            let function = if key.function.is_empty() { "<module>" } else { key.function.as_str() };
+            emit(&format!("{}:{}: error: new {} in {} - {}", abs_path.display(), line, key.hole_type, function, key.context));
+        }
+        emit("");
+    }
+    if !diff.fixed.is_empty() {
+        emit("Fixed holes (in baseline, now gone):");
+        for (key, n) in &diff.fixed {
+            let function = if key.function.is_empty() { "<module>" } else { key.function.as_str() };
+            let times = if *n > 1 { format!(" (×{})", n) } else { String::new() };
+            emit(&format!("   {}: {} in {}{} - {}", key.file, key.hole_type, function, times, key.context));
+        }
+        emit("");
+        emit("Re-run with --write-baseline to ratchet the baseline down.");
+    }
+    if diff.new.is_empty() {
+        emit("✓ No new holes relative to baseline.");
+    }
+
+    Ok(diff.new.len())
+}
+
+// ============================================================================
+// Transitive trust analysis (crate-wide call graph)
+// ============================================================================
+
+/// Print section 4.7: locally clean functions whose proofs rest on holes elsewhere.
+fn print_transitive_trust(summary: &SummaryStats) {
+    fn pct(n: usize, total: usize) -> usize {
+        if total > 0 { (n * 100) / total } else { 0 }
+    }
+    if summary.trust.is_empty() {
+        return;
+    }
+    let total = summary.trust.len();
+    let locally_clean = summary.trust.iter().filter(|t| t.own_holes == 0).count();
+    let truly_clean = summary.trust.iter().filter(|t| t.trusted_base.is_empty()).count();
+    let mut resting: Vec<&FnTrust> = summary.trust.iter()
+        .filter(|t| t.own_holes == 0 && !t.trusted_base.is_empty())
+        .collect();
+    resting.sort_by(|a, b| b.trusted_base.len().cmp(&a.trusted_base.len())
+        .then_with(|| a.file.cmp(&b.file))
+        .then_with(|| a.line.cmp(&b.line)));
+
+    log!("");
+    log!("=================================================================");
+    log!("4.7. Transitive Trust");
+    log!("=================================================================");
+    log!("");
+    log!("   A function is truly clean only if no hole is reachable through its calls.");
+    log!("   Calls resolve by name, so shared method names may over-approximate.");
+    log!("");
+    log!("Functions: {} total", total);
+    log!("   {} locally clean ({}%)", locally_clean, pct(locally_clean, total));
+    log!("   {} truly clean ({}%)", truly_clean, pct(truly_clean, total));
+    log!("   {} locally clean but resting on holes", resting.len());
+    log!("");
+    for t in resting {
+        log!("   {}:{}: {} fn {}::{} rests on {} hole{}",
+            t.file, t.line, t.mode, t.module, t.function,
+            t.trusted_base.len(), if t.trusted_base.len() == 1 { "" } else { "s" });
+        for h in t.trusted_base.iter().take(5) {
+            log!("      {}:{}: {} in {}", h.file, h.line, h.hole_type, h.function);
+        }
+        if t.trusted_base.len() > 5 {
+            log!("      ... and {} more", t.trusted_base.len() - 5);
+        }
+    }
+}
+
+/// Print section 4.8: modules that depend on each other through `use crate::`.
+fn print_dependency_cycles(summary: &SummaryStats) {
+    let graph = &summary.module_graph;
+    log!("");
+    log!("=================================================================");
+    log!("4.8. Dependency Cycles");
+    log!("=================================================================");
+    log!("");
+    if graph.cycles.is_empty() {
+        log!("   No cycles among crate:: module dependencies");
+        return;
+    }
+    log!("   Modules on a cycle cannot be cleaned one at a time; 4.2-4.5 treat each cycle as a unit.");
+    for (n, cycle) in graph.cycles.iter().enumerate() {
+        let members: Vec<&ModuleNode> = graph.nodes.iter().filter(|m| m.cycle == Some(n)).collect();
+        let holes: usize = members.iter().map(|m| m.holes).sum();
+        log!("");
+        log!("   Cycle {}: {} modules, {} holes", n + 1, cycle.len(), holes);
+        for m in members {
+            log!("      {}  ({} holes)  {}", m.module, m.holes, m.path);
+        }
+    }
+}
+
+/// --graph: write the module graph as analyses/veracity-review-verus-proof-holes-graph.{dot,json}.
+fn write_module_graph(graph: &ModuleGraph, base_dir: &Path, emit: &dyn Fn(&str)) -> Result<()> {
+    let analyses_dir = base_dir.join("analyses");
+    fs::create_dir_all(&analyses_dir)?;
+    let dot_path = analyses_dir.join("veracity-review-verus-proof-holes-graph.dot");
+    let json_path = analyses_dir.join("veracity-review-verus-proof-holes-graph.json");
+    fs::write(&dot_path, graph.to_dot())
+        .with_context(|| format!("Failed to write {}", dot_path.display()))?;
+    fs::write(&json_path, serde_json::to_string_pretty(graph)?)
+        .with_context(|| format!("Failed to write {}", json_path.display()))?;
+    emit("");
+    emit(&format!("Module graph: {} ({} modules, {} edges, {} cycles)",
+        dot_path.display(), graph.nodes.len(), graph.edges.len(), graph.cycles.len()));
+    emit(&format!("              {}", json_path.display()));
+    Ok(())
+}
+
+// ============================================================================
+// Hole budgets (veracity.toml [[budget]])
+// ============================================================================
+
+/// Print the "P. Hole Budgets" section; returns the number of budgets exceeded.
+fn run_budgets(config: &VeracityConfig, base_dir: &Path, file_stats_map: &HashMap<String, FileHoles>, emit: &dyn Fn(&str)) -> usize {
+    if config.budget.is_empty() {
+        return 0;
+    }
+    let usage = compute_budgets(config, base_dir, file_stats_map);
+    let config_path = config.source.as_ref()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "veracity.toml".to_string());
+    
+    emit("");
+    emit("=================================================================");
+    emit("P. Hole Budgets");
+    emit("=================================================================");
+    emit("");
+    emit(&format!("Policy: {}", config_path));
+    for b in &usage {
+        let status = if b.exceeded() { "OVER" } else { "ok" };
+        emit(&format!("   {:>4}  {} holes / {} budget  {}", status, b.holes, b.max_holes, b.dir));
+    }
+    let exceeded: Vec<&BudgetUsage> = usage.iter().filter(|b| b.exceeded()).collect();
+    for b in &exceeded {
+        emit(&format!("{}: error: hole budget exceeded for {} - {} holes, budget {}",
+            config_path, b.dir, b.holes, b.max_holes));
+    }
+    emit(&format!("{} of {} budgets exceeded", exceeded.len(), usage.len()));
+    exceeded.len()
+}
+
+// ============================================================================
+// Accepted-hole justifications
+// ============================================================================
+
+/// Print the "J. Accepted Holes" section: accepted holes by category, then a warning per bad justification
+fn print_accepted_inventory(inventory: &AcceptedInventory, config: &VeracityConfig, base_dir: &Path, emit: &dyn Fn(&str)) {
+    emit("");
+    emit("=================================================================");
+    emit("J. Accepted Holes");
+    emit("=================================================================");
+    emit("");
+    if inventory.is_empty() {
+        emit("No accepted holes.");
+        return;
+    }
+    if config.justification.categories.is_empty() {
+        emit("Categories: any (veracity.toml has no [justification] categories)");
+    } else {
+        emit(&format!("Categories: {}", config.justification.categories.join(", ")));
+    }
+    let groups = inventory.by_category.iter()
+        .map(|(category, entries)| (category.as_str(), entries))
+        .chain((!inventory.unjustified.is_empty()).then_some(("(unjustified)", &inventory.unjustified)));
+    for (category, entries) in groups {
+        emit("");
+        emit(&format!("{} ({})", category, entries.len()));
+        for e in entries {
+            let mut line = format!("   {}:{}  {}  {}", e.file, e.line, e.hole_type, e.item);
+            if let Some(ticket) = &e.ticket {
+                line.push_str(&format!("  [{}]", ticket));
+            }
+            if !e.note.is_empty() {
+                line.push_str(&format!("  {}", e.note));
+            }
+            emit(line.trim_end());
+        }
+    }
+    emit("");
+    let problems: Vec<&AcceptedEntry> = inventory.problems().collect();
+    for e in &problems {
+        emit(&format!("{}:{}: warning: {} - {}", base_dir.join(&e.file).display(), e.line, e.hole_type,
+            e.problem.as_deref().unwrap_or_default()));
+    }
+    emit(&format!("{} accepted holes, {} without a valid justification", inventory.total(), problems.len()));
+}
+
+// ============================================================================
+// Triage TUI (-i)
+// ============================================================================
+
+/// Draw the triage view: groups and holes in the top half, the selected hole below
+fn draw_triage(triage: &Triage, out: &mut impl Write) -> Result<()> {
+    use crossterm::{cursor::MoveTo, queue, style::{Attribute, Print, SetAttribute}, terminal::{self, Clear, ClearType}};
+    let (width, height) = terminal::size()?;
+    let (width, height) = (width as usize, height as usize);
+    let fit = |s: &str, w: usize| -> String {
+        let s: String = s.chars().take(w).collect();
+        format!("{:<w$}", s)
+    };
+    queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+    let open = triage.holes.iter().filter(|h| h.wont_fix.is_none()).count();
+    let title = format!("veracity triage: {} holes ({} open), by {}   ? help", triage.holes.len(), open, triage.grouping.label(&triage.hierarchy));
+    queue!(out, SetAttribute(Attribute::Reverse), Print(fit(&title, width)), SetAttribute(Attribute::Reset))?;
+
+    if triage.help {
+        let help = [
+            "j/k, Up/Down   move within the group        h/l, Left/Right  previous/next group",
+            "n              next open hole               g                group by directory/chapter/hole type",
+            "a              accept (assume -> accept(), external -> // accept hole)",
+            "e              external_body -> #[accepted_external_body]",
+            "L              lift assume(P) into an admitted lemma",
+            "w              won't fix (asks for a reason)",
+            "u              undo the last edit           q, Esc           quit",
+        ];
+        for (row, line) in help.iter().enumerate() {
+            queue!(out, MoveTo(0, row as u16 + 2), Print(fit(line, width)))?;
+        }
+        out.flush()?;
+        return Ok(());
+    }
+
+    // Top half: groups on the left, the selected group's holes on the right
+    let list_rows = (height.saturating_sub(4) / 2).max(3);
+    let left = (width / 3).clamp(12, 40);
+    let right = width.saturating_sub(left + 1);
+    let window = |selected: usize, len: usize| selected.saturating_sub(list_rows - 1).min(len.saturating_sub(list_rows));
+    let group_top = window(triage.group, triage.groups.len());
+    for (row, (g, (name, members))) in triage.groups.iter().enumerate().skip(group_top).take(list_rows).enumerate() {
+        let text = fit(&format!(" {} ({})", name, members.len()), left);
+        queue!(out, MoveTo(0, row as u16 + 1))?;
+        if g == triage.group {
+            queue!(out, SetAttribute(Attribute::Reverse), Print(text), SetAttribute(Attribute::Reset))?;
+        } else {
+            queue!(out, Print(text))?;
+        }
+    }
+    if let Some((_, members)) = triage.groups.get(triage.group) {
+        let hole_top = window(triage.selected, members.len());
+        for (row, (s, &i)) in members.iter().enumerate().skip(hole_top).take(list_rows).enumerate() {
+            let h = &triage.holes[i];
+            let mark = if h.wont_fix.is_some() { " [won't fix]" } else { "" };
+            let text = fit(&format!(" {}:{}: {}{}", h.rel, h.hole.line, h.hole.kind, mark), right);
+            queue!(out, MoveTo(left as u16 + 1, row as u16 + 1))?;
+            if s == triage.selected {
+                queue!(out, SetAttribute(Attribute::Reverse), Print(text), SetAttribute(Attribute::Reset))?;
+            } else {
+                queue!(out, Print(text))?;
+            }
+        }
+    }
+
+    // Bottom half: the selected hole in context
+    let mut row = list_rows + 1;
+    queue!(out, MoveTo(0, row as u16), Print("─".repeat(width)))?;
+    row += 1;
+    if let Some(h) = triage.current() {
+        let content = fs::read_to_string(&h.path).unwrap_or_default();
+        let mut lines = vec![format!("{}:{}: {} - {}", h.rel, h.hole.line, h.hole.kind, h.hole.context)];
+        if let Some(reason) = &h.wont_fix {
+            lines.push(format!("won't fix: {}", reason));
+        }
+        if let Some(text) = get_line(&content, h.hole.line) {
+            lines.push(format!("   > {:>5} | {}", h.hole.line, text.trim_end()));
+        }
+        lines.extend(build_context_lines(&content, &h.hole));
+        for line in lines.iter().take(height.saturating_sub(row + 1)) {
+            queue!(out, MoveTo(0, row as u16), Print(fit(line, width)))?;
+            row += 1;
+        }
+    } else {
+        queue!(out, MoveTo(0, row as u16), Print("No holes."))?;
+    }
+
+    let status = match &triage.input {
+        Some(reason) => format!("Won't fix reason: {}", reason),
+        None => format!("{}   [{} edits]", triage.status, triage.log.len()),
+    };
+    queue!(out, MoveTo(0, height.saturating_sub(1) as u16), SetAttribute(Attribute::Reverse), Print(fit(&status, width)), SetAttribute(Attribute::Reset))?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Restores the terminal when the TUI exits, including on error
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> Result<Self> {
+        crossterm::terminal::enable_raw_mode()?;
+        crossterm::execute!(io::stdout(), crossterm::terminal::EnterAlternateScreen, crossterm::cursor::Hide)?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = crossterm::execute!(io::stdout(), crossterm::cursor::Show, crossterm::terminal::LeaveAlternateScreen);
+        let _ = crossterm::terminal::disable_raw_mode();
+    }
+}
+
+/// -i on a terminal: browse holes and fix them in a full-screen TUI
+fn run_triage_tui(args: &StandardArgs, files: &[PathBuf], accept_import: &str, use_cache: bool) -> Result<()> {
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+    let mut triage = Triage::new(args, files, accept_import, use_cache)?;
+    if triage.holes.is_empty() {
+        triage.analyzer.finish();
+        println!("No holes found.");
+        return Ok(());
+    }
+    triage.status = format!("{} holes in {} files", triage.holes.len(), triage.files.len());
+    {
+        let _guard = TerminalGuard::enter()?;
+        let mut stdout = io::stdout();
+        loop {
+            draw_triage(&triage, &mut stdout)?;
+            let Event::Key(key) = event::read()? else { continue };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if let Some(reason) = &mut triage.input {
+                match key.code {
+                    KeyCode::Enter if !reason.trim().is_empty() => {
+                        let reason = std::mem::take(reason);
+                        triage.input = None;
+                        triage.act('w', &reason);
+                    }
+                    KeyCode::Esc => triage.input = None,
+                    KeyCode::Backspace => {
+                        reason.pop();
+                    }
+                    KeyCode::Char(c) => reason.push(c),
+                    _ => {}
+                }
+                continue;
+            }
+            if triage.help {
+                triage.help = false;
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+                KeyCode::Char('?') => triage.help = true,
+                KeyCode::Down | KeyCode::Char('j') => triage.move_selection(1),
+                KeyCode::Up | KeyCode::Char('k') => triage.move_selection(-1),
+                KeyCode::PageDown => triage.move_selection(10),
+                KeyCode::PageUp => triage.move_selection(-10),
+                KeyCode::Right | KeyCode::Char('l') => triage.move_group(1),
+                KeyCode::Left | KeyCode::Char('h') => triage.move_group(-1),
+                KeyCode::Char('n') => triage.next_open_hole(),
+                KeyCode::Char('g') => {
+                    triage.grouping = triage.grouping.next();
+                    triage.rebuild();
+                }
+                KeyCode::Char('a') => triage.act('a', ""),
+                KeyCode::Char('e') => triage.act('e', ""),
+                KeyCode::Char('L') => triage.act('l', ""),
+                KeyCode::Char('w') => triage.input = Some(String::new()),
+                KeyCode::Char('u') => triage.undo(),
+                _ => {}
+            }
+        }
+    }
+    triage.analyzer.finish();
+    for label in triage.log.history() {
+        println!("{}", label);
+    }
+    println!("{} edits applied.", triage.log.len());
+    Ok(())
+}
@@ -20,7 +20,10 @@ use anyhow::Result;
 use ra_ap_syntax::{ast::{self, HasName}, AstNode, SyntaxKind, SyntaxToken};
 use std::cell::RefCell;
 use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
+use veracity::VeracityConfig;
+use veracity::cache::AnalysisCache;
 use walkdir::WalkDir;
 
 thread_local! {
@@ -101,6 +104,7 @@ struct StyleArgs {
     reorder: bool,              // -r/--reorder: reorder items and insert ToC
     allow_dirty: bool,          // --allow-dirty: skip git clean check
     dry_run: bool,              // -n/--dry-run: show what reorder would do, don't write
+    no_cache: bool,             // --no-cache: ignore and do not update analyses/.cache/
 }
 
 impl StyleArgs {
@@ -119,6 +123,7 @@ impl StyleArgs {
         let mut reorder = false;
         let mut allow_dirty = false;
         let mut dry_run = false;
+        let mut no_cache = false;
         
         let mut i = 1;
         while i < args.len() {
@@ -139,6 +144,10 @@ impl StyleArgs {
                     dry_run = true;
                     i += 1;
                 }
+                "--no-cache" => {
+                    no_cache = true;
+                    i += 1;
+                }
                 "-c" | "--codebase" => {
                     i += 1;
                     if i < args.len() {
@@ -212,6 +221,7 @@ impl StyleArgs {
             reorder,
             allow_dirty,
             dry_run,
+            no_cache,
         })
     }
     
@@ -234,6 +244,7 @@ impl StyleArgs {
         eprintln!("  -r, --reorder         Reorder items inside verus! to match Rule 18 and insert ToC");
         eprintln!("  -n, --dry-run         Show what reorder would do without writing files");
         eprintln!("      --allow-dirty     Allow reorder on files with uncommitted git changes");
+        eprintln!("      --no-cache        Re-check every file (ignore analyses/.cache/)");
         eprintln!("  -h, --help            Show this help message");
         eprintln!();
         eprintln!("Checks performed (always):");
@@ -1738,7 +1749,7 @@ fn check_order_violations(ordered: &[OrderedItem]) -> Vec<(usize, String)> {
 }
 
 /// Result of checking a file - tracks both passed and failed checks
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 struct CheckResult {
     passed: Vec<(usize, String)>,  // (rule_num, description)
     failed: Vec<(usize, usize, String)>,  // (rule_num, line, message)
@@ -1754,6 +1765,21 @@ impl CheckResult {
     }
 }
 
+/// Runtime and proof test files expected for a collection module:
+/// tests/<Chapter>/Test<Name>.rs and rust_verify_test/tests/<Chapter>/Prove<Name>.rs
+fn collection_test_paths(file_path: &Path, args: &StyleArgs) -> Option<(PathBuf, PathBuf)> {
+    let codebase = args.codebase.as_ref()?;
+    // Module path relative to src/
+    let mod_dir = file_path.parent()
+        .and_then(|p| p.strip_prefix(codebase).ok())
+        .and_then(|p| p.strip_prefix("src").ok().or_else(|| p.strip_prefix("source").ok()))?;
+    let fname = file_path.file_stem().and_then(|s| s.to_str())?;
+    Some((
+        codebase.join("tests").join(mod_dir).join(format!("Test{}.rs", fname)),
+        codebase.join("rust_verify_test/tests").join(mod_dir).join(format!("Prove{}.rs", fname)),
+    ))
+}
+
 /// Run style checks on a file
 fn check_file(file_path: &Path, content: &str, args: &StyleArgs) -> CheckResult {
     let mut result = CheckResult::default();
@@ -2076,15 +2102,7 @@ fn check_file(file_path: &Path, content: &str, args: &StyleArgs) -> CheckResult
         s.split('<').next().unwrap_or(s).to_string()
     };
     
-    // Get module path relative to src/ for test file checking
-    let module_dir = file_path.parent()
-        .and_then(|p| p.strip_prefix(args.codebase.as_ref().unwrap_or(&PathBuf::from("."))).ok())
-        .and_then(|p| p.strip_prefix("src").ok().or_else(|| p.strip_prefix("source").ok()))
-        .map(|p| p.to_path_buf());
-    
-    let collection_name = file_path.file_stem()
-        .and_then(|s| s.to_str())
-        .map(|s| s.to_string());
+    let test_paths = collection_test_paths(file_path, args);
     
     if !structure.collection_structs.is_empty() {
         for (_coll_line, coll_name) in &structure.collection_structs {
@@ -2115,17 +2133,13 @@ fn check_file(file_path: &Path, content: &str, args: &StyleArgs) -> CheckResult
         }
         
         // Check for test files using the filename as collection name
-        if let (Some(ref codebase), Some(ref mod_dir), Some(ref fname)) = (&args.codebase, &module_dir, &collection_name) {
-            // Runtime test: tests/<Chapter>/Test<CollectionName>.rs
-            let runtime_test = codebase.join("tests").join(mod_dir).join(format!("Test{}.rs", fname));
+        if let Some((runtime_test, proof_test)) = &test_paths {
             if !runtime_test.exists() {
                 check17_issues.push(format!("collection should have runtime test: {}", runtime_test.display()));
             } else {
                 check17_ok.push(format!("runtime test exists"));
             }
             
-            // Proof test: rust_verify_test/tests/<Chapter>/Prove<CollectionName>.rs
-            let proof_test = codebase.join("rust_verify_test/tests").join(mod_dir).join(format!("Prove{}.rs", fname));
             if !proof_test.exists() {
                 check17_issues.push(format!("collection should have proof test: {}", proof_test.display()));
             } else {
@@ -2936,11 +2950,15 @@ fn main() -> Result<()> {
     log!("Logging to: {}", log_path.display());
     log!();
     
-    // Find files to check
+    // Find files to check (honoring veracity.toml excludes)
+    let config = VeracityConfig::discover(&args.path)?;
     let files = if args.path.is_file() {
         vec![args.path.clone()]
     } else {
         find_rust_files(&args.path, &args.exclude_dirs)
+            .into_iter()
+            .filter(|f| !config.is_excluded(f))
+            .collect()
     };
     
     log!("Checking {} files...", files.len());
@@ -2950,16 +2968,33 @@ fn main() -> Result<()> {
     let mut total_passed = 0;
     let mut files_with_issues = 0;
     
+    // Results depend on -av and on which collection test files exist
+    let mut cache: AnalysisCache<CheckResult> = if args.no_cache {
+        AnalysisCache::disabled()
+    } else {
+        let salt = format!("av={} codebase={:?}", args.all_verbose, args.codebase);
+        AnalysisCache::load(&base_dir, "veracity-review-verus-style", &salt)
+    };
+    
     for file in &files {
-        let content = match std::fs::read_to_string(file) {
-            Ok(c) => c,
-            Err(e) => {
-                log!("Error reading {}: {}", file.display(), e);
-                continue;
+        let result = match cache.get(file) {
+            Some(result) => result,
+            None => {
+                let content = match std::fs::read_to_string(file) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        log!("Error reading {}: {}", file.display(), e);
+                        continue;
+                    }
+                };
+                let result = check_file(file, &content, &args);
+                let deps: Vec<PathBuf> = collection_test_paths(file, &args)
+                    .map(|(runtime_test, proof_test)| vec![runtime_test, proof_test])
+                    .unwrap_or_default();
+                cache.insert(file, &deps, result.clone());
+                result
             }
         };
-        
-        let result = check_file(file, &content, &args);
         let file_str = file.display().to_string();
 
         // Always print file header
@@ -2989,6 +3024,12 @@ fn main() -> Result<()> {
         log!();
     }
     
+    if let Err(e) = cache.save() {
+        log!("warning: could not save analysis cache: {:#}", e);
+    }
+    if cache.hits > 0 {
+        log!("Cache: {} unchanged files reused, {} checked", cache.hits, cache.misses);
+    }
     log!("════════════════════════════════════════════════════════════════");
     log!("Summary: {} passed, {} warnings in {} files (checked {} files)", 
         total_passed, total_issues, files_with_issues, files.len());
@@ -8,6 +8,7 @@
 
 use anyhow::Result;
 use std::{collections::HashMap, fs, path::{Path, PathBuf}};
+use veracity::VeracityConfig;
 use walkdir::WalkDir;
 use regex::Regex;
 
@@ -141,7 +142,7 @@ fn find_rust_files_quick(dir: &Path) -> bool {
     false
 }
 
-fn find_rust_files(dir: &Path) -> Vec<PathBuf> {
+fn find_rust_files(dir: &Path, config: &VeracityConfig) -> Vec<PathBuf> {
     let mut files = Vec::new();
     
     for entry in WalkDir::new(dir)
@@ -151,9 +152,9 @@ fn find_rust_files(dir: &Path) -> Vec<PathBuf> {
     {
         let path = entry.path();
         if path.extension().map_or(false, |ext| ext == "rs") {
-            // Skip test files and backup files
+            // Skip test files, backup files and veracity.toml excludes
             let path_str = path.to_string_lossy();
-            if !path_str.contains("/tests/") && !path_str.ends_with("#") {
+            if !path_str.contains("/tests/") && !path_str.ends_with("#") && !config.is_excluded(path) {
                 files.push(path.to_path_buf());
             }
         }
@@ -335,7 +336,8 @@ fn analyze_library(path: &Path) -> Result<LibraryStats> {
         ..Default::default()
     };
     
-    let files = find_rust_files(path);
+    let config = VeracityConfig::discover(path)?;
+    let files = find_rust_files(path, &config);
     
     for file in files {
         match analyze_file(&file) {
@@ -7,6 +7,9 @@
 //! chapter/module directory: how many files are verusified, cargo-only,
 //! gated behind feature flags, and how many runtime tests exist.
 //!
+//! Files are grouped by veracity.toml's `[grouping]` (chapters by default); in
+//! the chapters preset, groups other than `ChapNN` are foundation modules.
+//!
 //! Usage:
 //!   veracity-verusification --project ~/projects/APAS-VERUS
 //!   veracity-verusification -d src/Chap18
@@ -19,15 +22,17 @@ use ra_ap_syntax::{ast, ast::AstNode, SyntaxKind, SourceFile, Edition};
 use std::collections::{BTreeMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use veracity::config::GroupBy;
+use veracity::grouping::Grouping;
 use veracity::{find_rust_files, StandardArgs};
 
 // ── Data structures ─────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Default)]
 struct ModuleStats {
-    /// Directory name (e.g., "Chap18", "vstdplus")
-    directory: String,
-    /// Total .rs files in src/<dir>/
+    /// Group name (e.g., "Chap18", "vstdplus")
+    group: String,
+    /// Total .rs files in the group
     total_files: usize,
     /// Files containing verus! macro
     verusified: usize,
@@ -84,8 +89,14 @@ fn main() -> Result<()> {
     // Find project root (where Cargo.toml and src/ live)
     let project_root = find_project_root(&base_dir);
 
+    // Without a veracity.toml, group paths relative to the project root
+    let grouping = match args.config.source {
+        Some(_) => Grouping::new(&args.config)?,
+        None => Grouping::chapters(&project_root),
+    };
+
     let src_dir = project_root.join("src");
-    if !src_dir.is_dir() {
+    if grouping.by() == GroupBy::Chapters && !src_dir.is_dir() {
         anyhow::bail!("No src/ directory found at {}", project_root.display());
     }
 
@@ -99,90 +110,40 @@ fn main() -> Result<()> {
         GatingInfo::default()
     };
 
-    // Discover directories under src/
-    let mut directories: Vec<String> = Vec::new();
-    for entry in fs::read_dir(&src_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_dir() {
-            if let Some(name) = path.file_name() {
-                let name_str = name.to_string_lossy().to_string();
-                // Skip hidden dirs, analyses, target
-                if !name_str.starts_with('.') && name_str != "analyses" && name_str != "target" {
-                    directories.push(name_str);
-                }
-            }
+    // Source files are the .rs files under a src/ directory, other than crate roots
+    let scan_dir = if grouping.by() == GroupBy::Chapters { &src_dir } else { &project_root };
+    let mut groups: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    for file in args.without_excluded(find_rust_files(&[scan_dir.clone()])) {
+        let rel = file.strip_prefix(&project_root).unwrap_or(&file);
+        let in_src = rel.components().any(|c| c.as_os_str() == "src");
+        let skipped = rel.components().any(|c| c.as_os_str() == "analyses")
+            || file.file_name().is_some_and(|n| n == "lib.rs" || n == "main.rs");
+        if !in_src || skipped {
+            continue;
         }
-    }
-    directories.sort();
-
-    // Also find standalone .rs files (foundation modules like Types.rs, Concurrency.rs)
-    let mut standalone_files: Vec<String> = Vec::new();
-    for entry in fs::read_dir(&src_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_file()
-            && path.extension().map_or(false, |e| e == "rs")
-            && path.file_name().map_or(false, |n| n != "lib.rs" && n != "main.rs")
-        {
-            if let Some(name) = path.file_stem() {
-                standalone_files.push(name.to_string_lossy().to_string());
-            }
+        if let Some(group) = grouping.group_of_path(&file) {
+            groups.entry(group).or_default().push(file);
         }
     }
-    standalone_files.sort();
 
-    // Analyze each directory
+    // Analyze each group
     let mut foundation_stats: Vec<ModuleStats> = Vec::new();
     let mut chapter_stats: Vec<ModuleStats> = Vec::new();
 
-    // Standalone files as foundation modules
-    for name in &standalone_files {
-        let file_path = src_dir.join(format!("{}.rs", name));
-        let has_verus = file_contains_verus_macro(&file_path)?;
-        let is_gated = gating.chapter_gated.contains(name);
-
-        let (verusified, cargo_only, gated_count) = if is_gated {
-            (0, 0, 1)
-        } else if has_verus {
-            (1, 0, 0)
-        } else {
-            (0, 1, 0)
-        };
-
-        let state = derive_state(verusified, cargo_only, gated_count);
-
-        foundation_stats.push(ModuleStats {
-            directory: name.clone(),
-            total_files: 1,
-            verusified,
-            cargo_only,
-            gated: gated_count,
-            rtts: None,
-            ptts: None,
-            ptt_details: Vec::new(),
-            state,
-        });
-    }
-
-    // Directory-based modules
-    for dir_name in &directories {
-        let dir_path = src_dir.join(dir_name);
-        let rs_files = find_rust_files(&[dir_path.clone()]);
-
-        let entirely_gated = gating.chapter_gated.contains(dir_name);
+    for (group, rs_files) in &groups {
+        let entirely_gated = gating.chapter_gated.contains(group);
 
         let mut verusified = 0usize;
         let mut cargo_only = 0usize;
         let mut gated_count = 0usize;
 
-        for file in &rs_files {
+        for file in rs_files {
             let file_stem = file
                 .file_stem()
                 .map(|s| s.to_string_lossy().to_string())
                 .unwrap_or_default();
 
-            let module_key = format!("{}::{}", dir_name, file_stem);
+            let module_key = format!("{}::{}", group, file_stem);
             let is_module_gated =
                 entirely_gated || gating.module_gated.contains(&module_key);
 
@@ -195,16 +156,16 @@ fn main() -> Result<()> {
             }
         }
 
-        // Count RTTs from tests/<dir>/
-        let test_dir = tests_dir.join(dir_name);
+        // Count RTTs from tests/<group>/
+        let test_dir = tests_dir.join(group);
         let rtts = if test_dir.is_dir() {
             Some(count_test_functions(&test_dir)?)
         } else {
             None
         };
 
-        // Count PTTs from rust_verify_test/tests/<dir>/
-        let ptt_dir = project_root.join("rust_verify_test").join("tests").join(dir_name);
+        // Count PTTs from rust_verify_test/tests/<group>/
+        let ptt_dir = project_root.join("rust_verify_test").join("tests").join(group);
         let (ptts, ptt_details) = if ptt_dir.is_dir() {
             (
                 Some(count_ptt_invocations(&ptt_dir)?),
@@ -216,9 +177,8 @@ fn main() -> Result<()> {
 
         let state = derive_state(verusified, cargo_only, gated_count);
 
-        let is_chapter = dir_name.starts_with("Chap");
         let stats = ModuleStats {
-            directory: dir_name.clone(),
+            group: group.clone(),
             total_files: rs_files.len(),
             verusified,
             cargo_only,
@@ -229,18 +189,18 @@ fn main() -> Result<()> {
             state,
         };
 
-        if is_chapter {
-            chapter_stats.push(stats);
-        } else {
+        if grouping.is_foundation(group) {
             foundation_stats.push(stats);
+        } else {
+            chapter_stats.push(stats);
         }
     }
 
-    // Sort chapters numerically
-    chapter_stats.sort_by(|a, b| chapter_sort_key(&a.directory).cmp(&chapter_sort_key(&b.directory)));
+    // Chapters in numeric order, other hierarchies in natural order
+    chapter_stats.sort_by(|a, b| grouping.compare(&a.group, &b.group));
 
     // Generate markdown
-    let markdown = generate_markdown(&foundation_stats, &chapter_stats);
+    let markdown = generate_markdown(&foundation_stats, &chapter_stats, grouping.label());
 
     // Write to analyses/ at the base_dir level
     let analyses_dir = base_dir.join("analyses");
@@ -525,7 +485,7 @@ fn derive_state(verusified: usize, cargo_only: usize, gated: usize) -> String {
 
 // ── Markdown generation ─────────────────────────────────────────────────
 
-fn generate_markdown(foundation: &[ModuleStats], chapters: &[ModuleStats]) -> String {
+fn generate_markdown(foundation: &[ModuleStats], chapters: &[ModuleStats], label: &str) -> String {
     let mut md = String::new();
 
     md.push_str("<style>\n");
@@ -570,7 +530,7 @@ fn generate_markdown(foundation: &[ModuleStats], chapters: &[ModuleStats]) -> St
             md.push_str(&format!(
                 "| {} | {} | {} | {} | {} | {} | {} | {} |\n",
                 i + 1,
-                s.directory,
+                s.group,
                 s.verusified,
                 s.cargo_only,
                 s.gated,
@@ -584,14 +544,14 @@ fn generate_markdown(foundation: &[ModuleStats], chapters: &[ModuleStats]) -> St
 
     // Chapter table
     if !chapters.is_empty() {
-        md.push_str("## Chapter Modules\n\n");
-        md.push_str("| # | Chapter | Verusified | Cargo-only | Gated | PTTs | RTTs | State |\n");
-        md.push_str("|---|---------|------------|------------|-------|------|------|-------|\n");
+        md.push_str(&format!("## {} Modules\n\n", label));
+        md.push_str(&format!("| # | {} | Verusified | Cargo-only | Gated | PTTs | RTTs | State |\n", label));
+        md.push_str(&format!("|---|{}|------------|------------|-------|------|------|-------|\n", "-".repeat(label.len() + 2)));
         for (i, s) in chapters.iter().enumerate() {
             md.push_str(&format!(
                 "| {} | {} | {} | {} | {} | {} | {} | {} |\n",
                 i + 1,
-                s.directory,
+                s.group,
                 s.verusified,
                 s.cargo_only,
                 s.gated,
@@ -605,8 +565,8 @@ fn generate_markdown(foundation: &[ModuleStats], chapters: &[ModuleStats]) -> St
 
     // Summary table
     md.push_str("## Summary\n\n");
-    md.push_str("| State | Chapters | Modules | PTTs | RTTs |\n");
-    md.push_str("|-------|----------|---------|------|------|\n");
+    md.push_str(&format!("| State | {}s | Modules | PTTs | RTTs |\n", label));
+    md.push_str(&format!("|-------|{}|---------|------|------|\n", "-".repeat(label.len() + 3)));
 
     let mut by_state: BTreeMap<String, (usize, usize, usize, usize)> = BTreeMap::new();
     for s in chapters.iter() {
@@ -651,7 +611,7 @@ fn generate_markdown(foundation: &[ModuleStats], chapters: &[ModuleStats]) -> St
         md.push_str("Canonical loop patterns: `loop-loop`, `loop-borrow-iter`, `loop-borrow-into`, `loop-consume`, `for-iter`, `for-borrow-iter`, `for-borrow-into`, `for-consume`\n\n");
 
         for stats in &all_with_ptts {
-            md.push_str(&format!("### {}\n\n", stats.directory));
+            md.push_str(&format!("### {}\n\n", stats.group));
 
             // Header: # | Module | each canonical pattern | Other
             md.push_str("| # | Module | ll | lbi | lbn | lc | fi | fbi | fbn | fc | Other |\n");
@@ -716,12 +676,6 @@ fn chrono_date() -> String {
     }
 }
 
-fn chapter_sort_key(name: &str) -> usize {
-    name.strip_prefix("Chap")
-        .and_then(|n| n.parse::<usize>().ok())
-        .unwrap_or(usize::MAX)
-}
-
 fn find_project_root(start: &Path) -> PathBuf {
     let mut dir = if start.is_file() {
         start.parent().unwrap_or(start).to_path_buf()
@@ -0,0 +1,263 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Incremental analysis cache
+//!
+//! Per-file analysis results stored under `analyses/.cache/<tool>.json`, keyed by
+//! the file's content hash. The cache as a whole is keyed by tool name, veracity
+//! version and a tool-chosen salt (options that change results), so upgrading
+//! veracity or changing those options starts fresh.
+//!
+//! Each entry also records the hashes of the files it depends on (the files
+//! behind its `use crate::` imports, or any other input the tool names); the
+//! entry is stale as soon as any of them changes, appears or disappears.
+//!
+//! A file's hash is read once per run and then remembered, on the assumption that
+//! no file changes while a run is in progress. A tool that edits files mid-run
+//! calls `forget` on each file it changed.
+//!
+//! Results for content that is not a file on disk (a blob at some git revision)
+//! are stored by content id instead, with `get_content` / `insert_content`.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Hash recorded for a dependency that does not exist
+const ABSENT: &str = "absent";
+
+/// 64-bit FNV-1a of `bytes` as 16 hex digits; stable across runs and platforms
+pub fn content_hash(bytes: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in bytes {
+        hash ^= *b as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Hash of a file's content, or None if it cannot be read
+pub fn file_hash(path: &Path) -> Option<String> {
+    std::fs::read(path).ok().map(|bytes| content_hash(&bytes))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry<T> {
+    hash: String,
+    /// Dependency path -> its content hash (or "absent") when the entry was made
+    deps: BTreeMap<String, String>,
+    value: T,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheFile<T> {
+    tool: String,
+    version: String,
+    salt: String,
+    entries: BTreeMap<String, CacheEntry<T>>,
+}
+
+/// On-disk cache of per-file results of type `T` for one tool
+pub struct AnalysisCache<T> {
+    path: PathBuf,
+    tool: String,
+    salt: String,
+    enabled: bool,
+    entries: BTreeMap<String, CacheEntry<T>>,
+    /// Entries looked up or inserted this run
+    touched: HashSet<String>,
+    /// Current hashes, read once per run (see `forget`)
+    hashes: HashMap<PathBuf, String>,
+    pub hits: usize,
+    pub misses: usize,
+}
+
+impl<T: Clone + Serialize + DeserializeOwned> AnalysisCache<T> {
+    /// Load `<base_dir>/analyses/.cache/<tool>.json`.
+    ///
+    /// A missing, unreadable or out-of-date cache file gives an empty cache.
+    pub fn load(base_dir: &Path, tool: &str, salt: &str) -> Self {
+        let path = base_dir.join("analyses").join(".cache").join(format!("{}.json", tool));
+        let entries = std::fs::read_to_string(&path).ok()
+            .and_then(|text| serde_json::from_str::<CacheFile<T>>(&text).ok())
+            .filter(|f| f.tool == tool && f.version == env!("CARGO_PKG_VERSION") && f.salt == salt)
+            .map(|f| f.entries)
+            .unwrap_or_default();
+        AnalysisCache {
+            path,
+            tool: tool.to_string(),
+            salt: salt.to_string(),
+            enabled: true,
+            entries,
+            touched: HashSet::new(),
+            hashes: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// A cache that never hits and never writes (--no-cache)
+    pub fn disabled() -> Self {
+        AnalysisCache {
+            path: PathBuf::new(),
+            tool: String::new(),
+            salt: String::new(),
+            enabled: false,
+            entries: BTreeMap::new(),
+            touched: HashSet::new(),
+            hashes: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    fn key(path: &Path) -> String {
+        path.canonicalize().unwrap_or_else(|_| path.to_path_buf()).display().to_string()
+    }
+
+    fn current_hash(&mut self, path: &Path) -> String {
+        if let Some(hash) = self.hashes.get(path) {
+            return hash.clone();
+        }
+        let hash = file_hash(path).unwrap_or_else(|| ABSENT.to_string());
+        self.hashes.insert(path.to_path_buf(), hash.clone());
+        hash
+    }
+
+    /// Drop the remembered hash of `path`, which has changed since it was read this run
+    pub fn forget(&mut self, path: &Path) {
+        self.hashes.remove(path);
+        self.hashes.remove(Path::new(&Self::key(path)));
+    }
+
+    /// The cached result for `path` if neither it nor any recorded dependency changed
+    pub fn get(&mut self, path: &Path) -> Option<T> {
+        if !self.enabled {
+            return None;
+        }
+        let key = Self::key(path);
+        self.touched.insert(key.clone());
+        let hash = self.current_hash(path);
+        let deps: Vec<(String, String)> = match self.entries.get(&key) {
+            Some(entry) if entry.hash == hash => entry.deps.iter()
+                .map(|(p, h)| (p.clone(), h.clone()))
+                .collect(),
+            _ => {
+                self.misses += 1;
+                return None;
+            }
+        };
+        if deps.iter().any(|(p, h)| self.current_hash(Path::new(p)) != *h) {
+            self.misses += 1;
+            return None;
+        }
+        self.hits += 1;
+        self.entries.get(&key).map(|entry| entry.value.clone())
+    }
+
+    /// Record the result for `path`, which was computed from it and `deps`
+    pub fn insert(&mut self, path: &Path, deps: &[PathBuf], value: T) {
+        if !self.enabled {
+            return;
+        }
+        let key = Self::key(path);
+        let hash = self.current_hash(path);
+        let deps = deps.iter()
+            .map(|d| {
+                let dep_key = Self::key(d);
+                let dep_hash = self.current_hash(Path::new(&dep_key));
+                (dep_key, dep_hash)
+            })
+            .collect();
+        self.touched.insert(key.clone());
+        self.entries.insert(key, CacheEntry { hash, deps, value });
+    }
+
+    /// The cached result for the content named `id` (e.g. a git blob id), wherever it lives.
+    ///
+    /// For results that depend on nothing but that content; `id` must change with it.
+    pub fn get_content(&mut self, id: &str) -> Option<T> {
+        if !self.enabled {
+            return None;
+        }
+        let key = format!("content:{}", id);
+        self.touched.insert(key.clone());
+        match self.entries.get(&key) {
+            Some(entry) => {
+                self.hits += 1;
+                Some(entry.value.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Record the result for the content named `id`
+    pub fn insert_content(&mut self, id: &str, value: T) {
+        if !self.enabled {
+            return;
+        }
+        let key = format!("content:{}", id);
+        self.touched.insert(key.clone());
+        self.entries.insert(key, CacheEntry { hash: id.to_string(), deps: BTreeMap::new(), value });
+    }
+
+    /// Write the cache, dropping entries for files that no longer exist.
+    ///
+    /// A run over some directories keeps the entries of the others; content
+    /// entries are kept only if this run used them.
+    pub fn save(&mut self) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let touched = &self.touched;
+        self.entries.retain(|key, _| {
+            touched.contains(key) || (!key.starts_with("content:") && Path::new(key).is_file())
+        });
+        let file = CacheFile {
+            tool: self.tool.clone(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            salt: self.salt.clone(),
+            entries: std::mem::take(&mut self.entries),
+        };
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let text = serde_json::to_string(&file)?;
+        self.entries = file.entries;
+        std::fs::write(&self.path, text)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
+/// Source files behind `use crate::` modules (e.g. "Chap05::SetStEph"), for cache deps.
+///
+/// A module maps to `<src_root>/Chap05/SetStEph.rs` or `.../SetStEph/mod.rs`; when the
+/// path names an item inside a module, the longest prefix that is a file is used.
+pub fn crate_module_files<'a>(src_root: &Path, modules: impl IntoIterator<Item = &'a String>) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for module in modules {
+        let segs: Vec<&str> = module.split("::").collect();
+        for n in (1..=segs.len()).rev() {
+            let rel: PathBuf = segs[..n].iter().collect();
+            let file = src_root.join(&rel).with_extension("rs");
+            let mod_file = src_root.join(&rel).join("mod.rs");
+            if file.is_file() {
+                files.push(file);
+                break;
+            }
+            if mod_file.is_file() {
+                files.push(mod_file);
+                break;
+            }
+        }
+    }
+    files.sort();
+    files.dedup();
+    files
+}
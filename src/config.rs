@@ -0,0 +1,345 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Project policy file (veracity.toml)
+//!
+//! A `veracity.toml` at the project root sets policy shared by all review tools:
+//! directories to exclude, the `accept` function path, hole severities per hole
+//! type and per path glob, per-directory hole budgets, the categories allowed
+//! in accepted-hole justifications, and how reports group files.
+//!
+//! ```toml
+//! exclude = ["src/experiments", "attic/**"]
+//! accept = "crate::vstdplus::accept::accept"
+//!
+//! [grouping]
+//! by = "regex"
+//! pattern = "^crates/([^/]+)/src/([^/]+)/"
+//! label = "Component"
+//!
+//! [justification]
+//! categories = ["verus-limitation", "eq-clone-workaround", "external-crate"]
+//! require_ticket = false
+//!
+//! [severity]
+//! assume_eq_clone_workaround = "off"
+//!
+//! [[severity.paths]]
+//! glob = "src/vstdplus/**"
+//! external_body = "info"
+//!
+//! [[budget]]
+//! dir = "src/Chap05"
+//! max_holes = 10
+//! ```
+//!
+//! All paths and globs are relative to the directory holding `veracity.toml`.
+
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// File name searched for from the analyzed directory upward
+pub const CONFIG_FILE_NAME: &str = "veracity.toml";
+
+/// Reporting level for a hole type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+    /// Not reported at all
+    #[serde(alias = "ignore")]
+    Off,
+}
+
+impl Severity {
+    /// Level name as printed in Emacs-style diagnostics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+            Severity::Off => "off",
+        }
+    }
+}
+
+/// Severity overrides for files matching a glob
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PathSeverity {
+    pub glob: String,
+    /// hole type -> severity
+    #[serde(flatten)]
+    pub types: BTreeMap<String, Severity>,
+}
+
+/// The `[severity]` table
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SeverityPolicy {
+    /// Per-glob overrides; the last matching entry wins
+    pub paths: Vec<PathSeverity>,
+    /// hole type -> severity, for every file
+    #[serde(flatten)]
+    pub types: BTreeMap<String, Severity>,
+}
+
+/// A `[[budget]]` entry: at most `max_holes` holes under `dir`
+#[derive(Debug, Clone, Deserialize)]
+pub struct HoleBudget {
+    pub dir: String,
+    pub max_holes: usize,
+}
+
+/// Holes counted against one budget
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetUsage {
+    pub dir: String,
+    pub max_holes: usize,
+    pub holes: usize,
+}
+
+impl BudgetUsage {
+    pub fn exceeded(&self) -> bool {
+        self.holes > self.max_holes
+    }
+}
+
+/// The `[justification]` table: what a `// veracity-accept:` comment must give
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct JustificationPolicy {
+    /// Allowed `reason=` categories; empty allows any
+    pub categories: Vec<String>,
+    /// Every justification must carry `ticket=`
+    pub require_ticket: bool,
+}
+
+impl JustificationPolicy {
+    pub fn allows(&self, category: &str) -> bool {
+        self.categories.is_empty() || self.categories.iter().any(|c| c == category)
+    }
+}
+
+/// What the `[grouping]` table groups files by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    /// `src/ChapNN/` directories, ChapNN in numeric order first
+    #[default]
+    Chapters,
+    /// The captures of `pattern` matched against the file path
+    Regex,
+    /// The Cargo workspace member holding the file
+    Workspace,
+    /// The first `depth` segments of the file's module path
+    Module,
+}
+
+/// The `[grouping]` table: the hierarchy reports group files by
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct GroupingConfig {
+    pub by: GroupBy,
+    /// Regex for `by = "regex"`; its captures, joined with '/', name the group
+    pub pattern: Option<String>,
+    /// Module path depth for `by = "module"` (default 1)
+    pub depth: Option<usize>,
+    /// What a group is called in reports, e.g. "Component"
+    pub label: Option<String>,
+}
+
+/// Contents of veracity.toml
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct VeracityConfig {
+    /// Directories or globs excluded from every tool
+    pub exclude: Vec<String>,
+    /// Path of the accept proof fn (e.g. "crate::vstdplus::accept::accept")
+    pub accept: Option<String>,
+    pub severity: SeverityPolicy,
+    pub budget: Vec<HoleBudget>,
+    pub justification: JustificationPolicy,
+    pub grouping: GroupingConfig,
+    /// Directory holding veracity.toml; paths in the file are relative to it
+    #[serde(skip)]
+    pub root: PathBuf,
+    /// The file this was loaded from, if any
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
+}
+
+impl VeracityConfig {
+    /// Find veracity.toml in `start` or the nearest ancestor and load it.
+    ///
+    /// Returns the default (empty) policy rooted at `start` if there is none.
+    pub fn discover(start: &Path) -> Result<Self> {
+        let start = start.canonicalize().unwrap_or_else(|_| start.to_path_buf());
+        let start_dir = if start.is_file() {
+            start.parent().map(Path::to_path_buf).unwrap_or_else(|| start.clone())
+        } else {
+            start.clone()
+        };
+        for dir in start_dir.ancestors() {
+            let candidate = dir.join(CONFIG_FILE_NAME);
+            if candidate.is_file() {
+                return Self::from_file(&candidate);
+            }
+        }
+        Ok(VeracityConfig { root: start_dir, ..Default::default() })
+    }
+
+    /// Load a veracity.toml file; its directory becomes the policy root
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut config = Self::from_str(&content)
+            .with_context(|| format!("Invalid {}", path.display()))?;
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        config.root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        config.source = Some(path);
+        Ok(config)
+    }
+
+    /// Parse veracity.toml content (root is left empty)
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(content: &str) -> Result<Self> {
+        let config: VeracityConfig = toml::from_str(content)?;
+        for entry in &config.severity.paths {
+            glob_to_regex(&entry.glob)?;
+        }
+        for pattern in &config.exclude {
+            glob_to_regex(pattern)?;
+        }
+        match (&config.grouping.by, &config.grouping.pattern) {
+            (GroupBy::Regex, None) => bail!("[grouping] by = \"regex\" needs a pattern"),
+            (_, Some(pattern)) => {
+                Regex::new(pattern).with_context(|| format!("Invalid [grouping] pattern: {}", pattern))?;
+            }
+            _ => {}
+        }
+        if config.grouping.depth == Some(0) {
+            bail!("[grouping] depth must be at least 1");
+        }
+        Ok(config)
+    }
+
+    /// `path` relative to the policy root, with '/' separators.
+    ///
+    /// Paths that do not exist on disk are taken as already relative to the root.
+    pub fn relative_path(&self, path: &Path) -> String {
+        relative_to(&self.root, path)
+    }
+
+    /// True if `path` falls under an `exclude` entry
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        let rel = self.relative_path(path);
+        self.exclude.iter().any(|pattern| path_matches(pattern, &rel))
+    }
+
+    /// Import line for the accept fn, if the config names one
+    pub fn accept_import(&self) -> Option<String> {
+        self.accept.as_ref().map(|p| {
+            let p = p.trim().trim_start_matches("use ").trim_end_matches(';').trim();
+            format!("use {};", p)
+        })
+    }
+
+    /// Severity of `hole_type` in `path`: the last matching glob, then `[severity]`, then `default`.
+    ///
+    /// Keys match with or without a trailing "()", so `admit` covers "admit()".
+    pub fn severity_for(&self, hole_type: &str, path: &Path, default: Severity) -> Severity {
+        let lookup = |types: &BTreeMap<String, Severity>| {
+            types.get(hole_type)
+                .or_else(|| hole_type.strip_suffix("()").and_then(|t| types.get(t)))
+                .copied()
+        };
+        let rel = self.relative_path(path);
+        for entry in self.severity.paths.iter().rev() {
+            if path_matches(&entry.glob, &rel) {
+                if let Some(severity) = lookup(&entry.types) {
+                    return severity;
+                }
+            }
+        }
+        lookup(&self.severity.types).unwrap_or(default)
+    }
+
+    /// True if the config changes any severity
+    pub fn has_severity_overrides(&self) -> bool {
+        !self.severity.types.is_empty() || self.severity.paths.iter().any(|p| !p.types.is_empty())
+    }
+
+    /// Sum `(path, holes)` per budget directory, in config order
+    pub fn budget_usage(&self, file_holes: &[(PathBuf, usize)]) -> Vec<BudgetUsage> {
+        let rel_holes: Vec<(String, usize)> = file_holes.iter()
+            .map(|(path, holes)| (self.relative_path(path), *holes))
+            .collect();
+        self.budget.iter()
+            .map(|b| BudgetUsage {
+                dir: b.dir.clone(),
+                max_holes: b.max_holes,
+                holes: rel_holes.iter()
+                    .filter(|(rel, _)| path_matches(&b.dir, rel))
+                    .map(|(_, holes)| holes)
+                    .sum(),
+            })
+            .collect()
+    }
+}
+
+/// `path` relative to `root`, with '/' separators; see [`VeracityConfig::relative_path`]
+pub fn relative_to(root: &Path, path: &Path) -> String {
+    let canonical = if root.as_os_str().is_empty() { None } else { path.canonicalize().ok() };
+    let rel = match canonical {
+        Some(canonical) => canonical.strip_prefix(root)
+            .map(Path::to_path_buf)
+            .unwrap_or(canonical),
+        None => path.to_path_buf(),
+    };
+    rel.components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .filter(|c| c != ".")
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Match a relative path against a glob, or against a directory prefix if `pattern` has no glob chars
+pub fn path_matches(pattern: &str, rel_path: &str) -> bool {
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+    if !pattern.contains(['*', '?']) {
+        return rel_path == pattern || rel_path.starts_with(&format!("{}/", pattern));
+    }
+    glob_to_regex(pattern).map(|re| re.is_match(rel_path)).unwrap_or(false)
+}
+
+/// `**` spans directories, `*` and `?` stay within one path segment
+fn glob_to_regex(pattern: &str) -> Result<Regex> {
+    let mut re = String::from("^");
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                if chars.get(i + 2) == Some(&'/') {
+                    re.push_str("(?:.*/)?");
+                    i += 3;
+                } else {
+                    re.push_str(".*");
+                    i += 2;
+                }
+                continue;
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    re.push('$');
+    Regex::new(&re).with_context(|| format!("Invalid glob: {}", pattern))
+}
@@ -0,0 +1,193 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Local dependency crates
+//!
+//! Finds the crates a project depends on whose source is on disk, by reading
+//! `Cargo.toml` files only (no cargo, no network):
+//! - `path = "..."` dependencies;
+//! - registry dependencies vendored by `cargo vendor`, found through the
+//!   `directory` of a `[source.*]` entry in `.cargo/config.toml`, or `vendor/`.
+//!
+//! Dependencies of those crates are followed too. `workspace = true` entries are
+//! resolved against `[workspace.dependencies]` of the enclosing workspace.
+//! Only `[dependencies]` and `[target.*.dependencies]` count; dev- and
+//! build-dependencies are not part of what the crate's proofs rely on.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use toml::Value;
+
+/// How a dependency's source got onto disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DepSource {
+    Path,
+    Vendored,
+}
+
+/// A dependency crate with source on disk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LocalDep {
+    /// Package name
+    pub name: String,
+    /// Directory holding its Cargo.toml
+    pub dir: PathBuf,
+    pub source: DepSource,
+    /// Package that first pulled it in
+    pub required_by: String,
+}
+
+impl LocalDep {
+    /// Directory to analyze: `src/` if there is one, else the crate directory
+    pub fn source_dir(&self) -> PathBuf {
+        let src = self.dir.join("src");
+        if src.is_dir() { src } else { self.dir.clone() }
+    }
+}
+
+/// Nearest `Cargo.toml` at or above `start`
+pub fn find_manifest(start: &Path) -> Option<PathBuf> {
+    let start = start.canonicalize().ok()?;
+    let dir = if start.is_file() { start.parent()?.to_path_buf() } else { start };
+    dir.ancestors().map(|d| d.join("Cargo.toml")).find(|m| m.is_file())
+}
+
+/// Every path or vendored crate reachable from the package at `manifest`, breadth first.
+///
+/// Registry and git dependencies that are not vendored are skipped.
+pub fn local_dependencies(manifest: &Path) -> Result<Vec<LocalDep>> {
+    let manifest = manifest.canonicalize()
+        .with_context(|| format!("Cannot find {}", manifest.display()))?;
+    let root_dir = manifest.parent().map(Path::to_path_buf).unwrap_or_default();
+    let vendored = vendored_crates(&root_dir);
+
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+    seen.insert(root_dir);
+    let mut deps = Vec::new();
+    let mut queue = VecDeque::from([manifest]);
+
+    while let Some(manifest) = queue.pop_front() {
+        let package = read_manifest(&manifest)?;
+        let dir = manifest.parent().map(Path::to_path_buf).unwrap_or_default();
+        let package_name = package.get("package")
+            .and_then(|p| p.get("name"))
+            .and_then(Value::as_str)
+            .unwrap_or("?")
+            .to_string();
+        let workspace_deps = workspace_dependencies(&dir);
+
+        for (key, spec) in dependency_entries(&package) {
+            let spec = match spec.get("workspace").and_then(Value::as_bool) {
+                Some(true) => match workspace_deps.as_ref().and_then(|(ws_dir, t)| t.get(&key).map(|s| (ws_dir, s))) {
+                    Some((ws_dir, ws_spec)) => resolve_paths(ws_spec, ws_dir),
+                    None => continue,
+                },
+                _ => resolve_paths(&spec, &dir),
+            };
+            let name = spec.get("package").and_then(Value::as_str).unwrap_or(&key).to_string();
+            let found = match spec.get("path").and_then(Value::as_str) {
+                Some(path) => Some((PathBuf::from(path), DepSource::Path)),
+                None if spec.get("git").is_none() => vendored.get(&name).map(|d| (d.clone(), DepSource::Vendored)),
+                None => None,
+            };
+            let Some((dep_dir, source)) = found else { continue };
+            let Ok(dep_dir) = dep_dir.canonicalize() else { continue };
+            if !dep_dir.join("Cargo.toml").is_file() || !seen.insert(dep_dir.clone()) {
+                continue;
+            }
+            queue.push_back(dep_dir.join("Cargo.toml"));
+            deps.push(LocalDep { name, dir: dep_dir, source, required_by: package_name.clone() });
+        }
+    }
+    Ok(deps)
+}
+
+fn read_manifest(path: &Path) -> Result<Value> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    text.parse::<Value>()
+        .with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// (key, spec table) of `[dependencies]` and `[target.*.dependencies]`;
+/// a bare version string becomes an empty table
+fn dependency_entries(package: &Value) -> Vec<(String, Value)> {
+    let mut tables: Vec<&Value> = package.get("dependencies").into_iter().collect();
+    if let Some(targets) = package.get("target").and_then(Value::as_table) {
+        tables.extend(targets.values().filter_map(|t| t.get("dependencies")));
+    }
+    let mut entries = Vec::new();
+    for table in tables.iter().filter_map(|t| t.as_table()) {
+        for (key, spec) in table {
+            let spec = if spec.is_table() { spec.clone() } else { Value::Table(Default::default()) };
+            entries.push((key.clone(), spec));
+        }
+    }
+    entries
+}
+
+/// `spec` with a relative `path` made relative to `dir`
+fn resolve_paths(spec: &Value, dir: &Path) -> Value {
+    let mut spec = spec.clone();
+    if let Some(table) = spec.as_table_mut() {
+        if let Some(path) = table.get("path").and_then(Value::as_str) {
+            let joined = dir.join(path).display().to_string();
+            table.insert("path".to_string(), Value::String(joined));
+        }
+    }
+    spec
+}
+
+/// Directory and `[workspace.dependencies]` of the nearest workspace root at or above `dir`
+fn workspace_dependencies(dir: &Path) -> Option<(PathBuf, toml::Table)> {
+    for ancestor in dir.ancestors() {
+        let manifest = ancestor.join("Cargo.toml");
+        if !manifest.is_file() {
+            continue;
+        }
+        let Ok(value) = read_manifest(&manifest) else { continue };
+        if let Some(workspace) = value.get("workspace") {
+            let deps = workspace.get("dependencies").and_then(Value::as_table).cloned().unwrap_or_default();
+            return Some((ancestor.to_path_buf(), deps));
+        }
+    }
+    None
+}
+
+/// Package name -> directory of each crate in the vendor directory
+fn vendored_crates(root_dir: &Path) -> BTreeMap<String, PathBuf> {
+    let mut crates = BTreeMap::new();
+    let Some(vendor) = vendor_dir(root_dir) else { return crates };
+    let Ok(entries) = std::fs::read_dir(&vendor) else { return crates };
+    let mut dirs: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+    dirs.sort();
+    for dir in dirs {
+        let Ok(manifest) = read_manifest(&dir.join("Cargo.toml")) else { continue };
+        if let Some(name) = manifest.get("package").and_then(|p| p.get("name")).and_then(Value::as_str) {
+            crates.entry(name.to_string()).or_insert(dir);
+        }
+    }
+    crates
+}
+
+/// `directory` of the first `[source.*]` in `.cargo/config.toml` (or `.cargo/config`)
+/// at or above `root_dir`, else `root_dir/vendor` if it exists
+fn vendor_dir(root_dir: &Path) -> Option<PathBuf> {
+    for ancestor in root_dir.ancestors() {
+        for name in ["config.toml", "config"] {
+            let config = ancestor.join(".cargo").join(name);
+            let Ok(value) = read_manifest(&config) else { continue };
+            let directory = value.get("source")
+                .and_then(Value::as_table)
+                .and_then(|sources| sources.values().find_map(|s| s.get("directory").and_then(Value::as_str)));
+            if let Some(directory) = directory {
+                return Some(ancestor.join(directory));
+            }
+        }
+    }
+    let vendor = root_dir.join("vendor");
+    vendor.is_dir().then_some(vendor)
+}
@@ -0,0 +1,588 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Running Verus and reading its diagnostics
+//!
+//! [`run_verus`] runs a project's Verus build the way the tools always have:
+//! `cargo verus build` for cargo-verus projects, else `verus` on src/lib.rs. The
+//! output is parsed into [`Diagnostic`]s, from rustc's JSON diagnostics when the run
+//! asked for them and got them, else from the human-readable stderr. Each has:
+//!
+//! - a [`DiagnosticKind`] read from the message: assertion failed, precondition or
+//!   postcondition not satisfied, another proof obligation, rlimit exceeded,
+//!   trigger error, syntax error, panic, or other;
+//! - the primary span (`-->`) and the secondary spans (labelled underlines, `:::`);
+//! - the function holding the primary span, read from the source.
+//!
+//! A [`VerifyScope`] narrows a run to some modules (`--verify-module`) or one
+//! function (`--verify-function`).
+//!
+//! Lines of spans parsed from stderr are exact. Columns of underlines drawn beside a
+//! multi-line span may be off by the width of its gutter.
+
+use crate::holes::{analyze_source, FnNode};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+/// What went wrong, read from a diagnostic's message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    AssertionFailed,
+    PreconditionFailed,
+    PostconditionFailed,
+    /// Another failed proof obligation: an invariant, decreases, arithmetic overflow
+    OtherObligation,
+    RlimitExceeded,
+    Trigger,
+    Syntax,
+    Panic,
+    /// Anything else: type errors, unresolved names, notes
+    Other,
+}
+
+impl DiagnosticKind {
+    pub fn classify(message: &str) -> Self {
+        let m = message.to_lowercase();
+        if m.contains("panicked at") || m.starts_with("internal compiler error") {
+            DiagnosticKind::Panic
+        } else if m.contains("rlimit") || m.contains("resource limit") {
+            DiagnosticKind::RlimitExceeded
+        } else if m.contains("trigger") {
+            DiagnosticKind::Trigger
+        } else if m.contains("assertion failed") {
+            DiagnosticKind::AssertionFailed
+        } else if m.contains("precondition not satisfied") {
+            DiagnosticKind::PreconditionFailed
+        } else if m.contains("postcondition not satisfied") {
+            DiagnosticKind::PostconditionFailed
+        } else if m.contains("not satisfied") || m.contains("could not prove termination")
+            || m.starts_with("possible arithmetic") || m.starts_with("possible division by zero") {
+            DiagnosticKind::OtherObligation
+        } else if m.starts_with("expected ") || m.contains("unexpected token")
+            || m.contains("unexpected closing delimiter") || m.contains("unclosed delimiter")
+            || m.starts_with("unterminated ") {
+            DiagnosticKind::Syntax
+        } else {
+            DiagnosticKind::Other
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiagnosticKind::AssertionFailed => "assertion failed",
+            DiagnosticKind::PreconditionFailed => "precondition",
+            DiagnosticKind::PostconditionFailed => "postcondition",
+            DiagnosticKind::OtherObligation => "proof obligation",
+            DiagnosticKind::RlimitExceeded => "rlimit exceeded",
+            DiagnosticKind::Trigger => "trigger",
+            DiagnosticKind::Syntax => "syntax",
+            DiagnosticKind::Panic => "panic",
+            DiagnosticKind::Other => "other",
+        }
+    }
+
+    /// A proof that no longer goes through, as opposed to code that no longer compiles
+    pub fn is_verification_failure(self) -> bool {
+        matches!(self,
+            DiagnosticKind::AssertionFailed | DiagnosticKind::PreconditionFailed
+            | DiagnosticKind::PostconditionFailed | DiagnosticKind::OtherObligation
+            | DiagnosticKind::RlimitExceeded)
+    }
+}
+
+impl fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Level {
+    Error,
+    Warning,
+    Note,
+    Help,
+}
+
+impl Level {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            s if s.starts_with("error") => Some(Level::Error),
+            "warning" => Some(Level::Warning),
+            "note" | "failure-note" => Some(Level::Note),
+            "help" => Some(Level::Help),
+            _ => None,
+        }
+    }
+}
+
+/// A source range, 1-based; `end_column` is one past the last character
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    /// As Verus printed it, relative to the directory it ran in
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    /// Text beside the underline, e.g. "failed precondition"
+    pub label: Option<String>,
+}
+
+impl Span {
+    fn at(file: &str, line: usize, column: usize) -> Self {
+        Span { file: file.to_string(), line, column, end_line: line, end_column: column, label: None }
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// One error, warning or note from a Verus run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub kind: DiagnosticKind,
+    /// Error code, e.g. E0425
+    pub code: Option<String>,
+    pub message: String,
+    pub primary: Option<Span>,
+    pub secondary: Vec<Span>,
+    /// `= note:` and `= help:` lines, and child messages
+    pub notes: Vec<String>,
+    /// `Type::name` of the innermost function holding the primary span
+    pub enclosing_fn: Option<String>,
+}
+
+impl Diagnostic {
+    fn new(level: Level, code: Option<String>, message: &str) -> Self {
+        Diagnostic {
+            level,
+            kind: DiagnosticKind::classify(message),
+            code,
+            message: message.trim().to_string(),
+            primary: None,
+            secondary: Vec::new(),
+            notes: Vec::new(),
+            enclosing_fn: None,
+        }
+    }
+
+    /// One line: "precondition at src/a.rs:12:5 in Foo::bar: precondition not satisfied"
+    pub fn summary(&self) -> String {
+        let mut s = self.kind.to_string();
+        if let Some(span) = &self.primary {
+            s.push_str(&format!(" at {}", span));
+        }
+        if let Some(name) = &self.enclosing_fn {
+            s.push_str(&format!(" in {}", name));
+        }
+        format!("{}: {}", s, self.message)
+    }
+
+    /// Compiler summaries that are not diagnostics of their own: "aborting due to 2 previous errors"
+    fn is_summary(&self) -> bool {
+        let m = &self.message;
+        m.starts_with("aborting due to") || m.starts_with("could not compile")
+            || (self.level == Level::Warning && (m.ends_with("emitted") || m.contains(" generated ")))
+    }
+}
+
+/// How Verus is invoked in a codebase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerusCommand {
+    /// `cargo verus build`, for projects whose Cargo.toml has Verus metadata or vstd
+    CargoVerus,
+    /// `verus --crate-type=lib src/lib.rs`
+    Direct,
+}
+
+impl VerusCommand {
+    pub fn detect(codebase: &Path) -> Self {
+        let content = fs::read_to_string(codebase.join("Cargo.toml")).unwrap_or_default();
+        if content.contains("[package.metadata.verus]") || content.contains("vstd") {
+            VerusCommand::CargoVerus
+        } else {
+            VerusCommand::Direct
+        }
+    }
+
+    fn command(self, codebase: &Path, json: bool, scope: &VerifyScope) -> Command {
+        let mut cmd = match self {
+            VerusCommand::CargoVerus => {
+                let mut cmd = Command::new("cargo");
+                cmd.args(["verus", "build"]);
+                if json {
+                    cmd.arg("--message-format=json");
+                }
+                let scope_args = scope.args();
+                if !scope_args.is_empty() {
+                    cmd.arg("--").args(scope_args);
+                }
+                cmd
+            }
+            VerusCommand::Direct => {
+                let mut cmd = Command::new("verus");
+                cmd.args(["--crate-type=lib", "src/lib.rs", "--multiple-errors", "20", "--expand-errors"]);
+                if json {
+                    cmd.arg("--error-format=json");
+                }
+                cmd.args(scope.args());
+                cmd
+            }
+        };
+        cmd.current_dir(codebase);
+        cmd
+    }
+}
+
+/// What a run verifies. The whole crate is still compiled and type-checked either way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyScope {
+    Crate,
+    /// These modules (`Chap05::SetStEph`; "" is the crate root), via `--verify-module`
+    Modules(Vec<String>),
+    /// One function of one module, via `--verify-function`: `name` or `Type::name`
+    Function { module: String, function: String },
+}
+
+impl VerifyScope {
+    /// Verus arguments selecting the scope
+    pub fn args(&self) -> Vec<String> {
+        let module_args = |module: &str| match module {
+            "" => vec!["--verify-root".to_string()],
+            m => vec!["--verify-module".to_string(), m.to_string()],
+        };
+        match self {
+            VerifyScope::Crate => Vec::new(),
+            VerifyScope::Modules(modules) => modules.iter().flat_map(|m| module_args(m)).collect(),
+            VerifyScope::Function { module, function } => {
+                let mut args = module_args(module);
+                args.extend(["--verify-function".to_string(), function.clone()]);
+                args
+            }
+        }
+    }
+
+    /// Modules verified; None for the whole crate
+    pub fn module_count(&self) -> Option<usize> {
+        match self {
+            VerifyScope::Crate => None,
+            VerifyScope::Modules(modules) => Some(modules.len()),
+            VerifyScope::Function { .. } => Some(1),
+        }
+    }
+}
+
+/// How Verus reports diagnostics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Rendered text on stderr, as a user sees it
+    Human,
+    /// rustc's JSON diagnostics, falling back to stderr if there are none
+    Json,
+}
+
+/// The outcome of one Verus run
+#[derive(Debug, Clone)]
+pub struct VerusRun {
+    pub success: bool,
+    pub stderr: String,
+    pub diagnostics: Vec<Diagnostic>,
+    pub duration: Duration,
+}
+
+impl VerusRun {
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter().filter(|d| d.level == Level::Error)
+    }
+
+    /// Failed without any failed proof: a syntax or type error, an unresolved name, a panic.
+    ///
+    /// A minimizer that removed something and got this broke the code; it did not
+    /// learn that the proof needed what it removed.
+    pub fn is_breakage(&self) -> bool {
+        !self.success && !self.errors().any(|d| d.kind.is_verification_failure())
+    }
+}
+
+/// Run Verus on the whole crate in `codebase` the usual way and parse its stderr
+pub fn run_verus(codebase: &Path) -> Result<VerusRun> {
+    run_verus_with(codebase, VerusCommand::detect(codebase), OutputFormat::Human, &VerifyScope::Crate)
+}
+
+/// Run Verus in `codebase` and parse its diagnostics, resolving their functions against the source there
+pub fn run_verus_with(codebase: &Path, command: VerusCommand, format: OutputFormat, scope: &VerifyScope) -> Result<VerusRun> {
+    let start = Instant::now();
+    let output = command.command(codebase, format == OutputFormat::Json, scope)
+        .output()
+        .with_context(|| match command {
+            VerusCommand::CargoVerus => "Failed to run cargo verus build",
+            VerusCommand::Direct => "Failed to run verus",
+        })?;
+    let duration = start.elapsed();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    let mut diagnostics = parse_output(&stdout, &stderr);
+    resolve_enclosing_fns(&mut diagnostics, codebase);
+    Ok(VerusRun { success: output.status.success(), stderr, diagnostics, duration })
+}
+
+/// Diagnostics in a run's output: JSON diagnostics on either stream if there are any
+/// (with panics from stderr, which are never JSON), else the rendered stderr
+pub fn parse_output(stdout: &str, stderr: &str) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = stdout.lines().chain(stderr.lines())
+        .filter_map(parse_json_line)
+        .filter(|d| !d.is_summary())
+        .collect();
+    if diagnostics.is_empty() {
+        return parse_stderr(stderr);
+    }
+    diagnostics.extend(parse_stderr(stderr).into_iter().filter(|d| d.kind == DiagnosticKind::Panic));
+    diagnostics
+}
+
+/// A rustc JSON diagnostic, bare or in a cargo `compiler-message`
+pub fn parse_json_line(line: &str) -> Option<Diagnostic> {
+    let line = line.trim();
+    if !line.starts_with('{') {
+        return None;
+    }
+    let value: Value = serde_json::from_str(line).ok()?;
+    let message = match value.get("reason").and_then(Value::as_str) {
+        Some("compiler-message") => value.get("message")?,
+        Some(_) => return None,
+        None => &value,
+    };
+    json_diagnostic(message)
+}
+
+fn json_diagnostic(value: &Value) -> Option<Diagnostic> {
+    let level = Level::parse(value.get("level")?.as_str()?)?;
+    let code = value.get("code")
+        .and_then(|c| c.get("code"))
+        .and_then(Value::as_str)
+        .map(String::from);
+    let mut diagnostic = Diagnostic::new(level, code, value.get("message")?.as_str()?);
+    for span in value.get("spans").and_then(Value::as_array).into_iter().flatten() {
+        let Some((is_primary, span)) = json_span(span) else { continue };
+        if is_primary && diagnostic.primary.is_none() {
+            diagnostic.primary = Some(span);
+        } else {
+            diagnostic.secondary.push(span);
+        }
+    }
+    for child in value.get("children").and_then(Value::as_array).into_iter().flatten() {
+        if let Some(child) = json_diagnostic(child) {
+            let level = match child.level {
+                Level::Help => "help",
+                _ => "note",
+            };
+            diagnostic.notes.push(format!("{}: {}", level, child.message));
+            diagnostic.secondary.extend(child.primary);
+            diagnostic.secondary.extend(child.secondary);
+        }
+    }
+    Some(diagnostic)
+}
+
+fn json_span(value: &Value) -> Option<(bool, Span)> {
+    let num = |key: &str| value.get(key).and_then(Value::as_u64).map(|n| n as usize);
+    let span = Span {
+        file: value.get("file_name")?.as_str()?.to_string(),
+        line: num("line_start")?,
+        column: num("column_start")?,
+        end_line: num("line_end")?,
+        end_column: num("column_end")?,
+        label: value.get("label").and_then(Value::as_str).map(String::from),
+    };
+    Some((value.get("is_primary").and_then(Value::as_bool).unwrap_or(false), span))
+}
+
+/// Diagnostics in rendered rustc/Verus stderr.
+///
+/// A diagnostic starts at an `error:`, `error[E0425]:`, `warning:`, `note:` or `help:`
+/// line, or a `thread '...' panicked at` line. Its primary span is the `-->` after it;
+/// underlines in its snippets give the primary span's extent and label and the
+/// secondary spans; `::: file:line:col` moves the snippets to another file.
+pub fn parse_stderr(stderr: &str) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let mut current: Option<Diagnostic> = None;
+    let mut file = String::new();
+    let mut line_no = 0;
+    // Start of a multi-line span whose closing underline has not been seen
+    let mut open: Option<(usize, usize)> = None;
+    let mut panic_detail = false;
+
+    for line in stderr.lines() {
+        if let Some(d) = parse_header(line) {
+            diagnostics.extend(current.take());
+            panic_detail = d.kind == DiagnosticKind::Panic;
+            current = Some(d);
+            file.clear();
+            open = None;
+            continue;
+        }
+        let Some(d) = current.as_mut() else { continue };
+        let trimmed = line.trim_start();
+        if std::mem::take(&mut panic_detail) && !trimmed.is_empty() {
+            // Newer rustc prints the panic message on the line after "panicked at"
+            d.notes.push(trimmed.to_string());
+        } else if let Some(loc) = trimmed.strip_prefix("--> ") {
+            if let Some(span) = parse_location(loc) {
+                file = span.file.clone();
+                line_no = span.line;
+                if d.primary.is_none() {
+                    d.primary = Some(span);
+                }
+            }
+        } else if let Some(loc) = trimmed.strip_prefix("::: ") {
+            if let Some(span) = parse_location(loc) {
+                file = span.file;
+                line_no = span.line;
+            }
+        } else if let Some(note) = trimmed.strip_prefix("= ") {
+            d.notes.push(note.to_string());
+        } else if let Some((number, after_bar)) = split_gutter(line) {
+            match number {
+                Some(n) => {
+                    line_no = n;
+                    let code = after_bar.trim_start_matches(' ');
+                    if let Some(rest) = code.strip_prefix('/') {
+                        let index = after_bar.chars().count() - rest.trim_start().chars().count();
+                        open = Some((n, index.saturating_sub(2).max(1)));
+                    }
+                }
+                None => add_underlines(d, &file, line_no, after_bar, &mut open),
+            }
+        }
+    }
+    diagnostics.extend(current);
+    diagnostics.retain(|d| !d.is_summary());
+    diagnostics
+}
+
+fn parse_header(line: &str) -> Option<Diagnostic> {
+    if line.starts_with("thread '") && line.contains("panicked at") {
+        return Some(Diagnostic::new(Level::Error, None, line));
+    }
+    let (head, message) = line.split_once(": ")?;
+    let (level, code) = match head.split_once('[') {
+        Some((level, code)) => (level, Some(code.strip_suffix(']')?.to_string())),
+        None => (head, None),
+    };
+    if !matches!(level, "error" | "warning" | "note" | "help") {
+        return None;
+    }
+    Some(Diagnostic::new(Level::parse(level)?, code, message))
+}
+
+/// `file:line:col`
+fn parse_location(loc: &str) -> Option<Span> {
+    let mut parts = loc.trim().rsplitn(3, ':');
+    let column = parts.next()?.parse().ok()?;
+    let line = parts.next()?.parse().ok()?;
+    Some(Span::at(parts.next()?, line, column))
+}
+
+/// A snippet line split at its `|` gutter: the line number (None on underline
+/// lines) and the text after the bar, whose first character is column 1 - 1
+fn split_gutter(line: &str) -> Option<(Option<usize>, &str)> {
+    let (gutter, after) = line.split_once('|')?;
+    let gutter = gutter.trim();
+    if gutter.is_empty() {
+        Some((None, after))
+    } else {
+        Some((Some(gutter.parse().ok()?), after))
+    }
+}
+
+/// Spans from an underline line: runs of `^` (primary) or `-` (secondary), after
+/// any `|`, `_` and `/` of multi-line spans; the text after the last run is its label
+fn add_underlines(d: &mut Diagnostic, file: &str, line_no: usize, after_bar: &str, open: &mut Option<(usize, usize)>) {
+    let chars: Vec<char> = after_bar.chars().collect();
+    let mut i = 0;
+    let mut saw_underscore = false;
+    while i < chars.len() && matches!(chars[i], ' ' | '|' | '_' | '/') {
+        saw_underscore |= chars[i] == '_';
+        i += 1;
+    }
+    let gutter = if open.is_some() || saw_underscore { 2 } else { 0 };
+    let mut runs: Vec<(usize, usize, char)> = Vec::new();
+    while i < chars.len() && matches!(chars[i], '^' | '-') {
+        let start = i;
+        while i < chars.len() && chars[i] == chars[start] {
+            i += 1;
+        }
+        runs.push((start, i, chars[start]));
+        while i < chars.len() && chars[i] == ' ' {
+            i += 1;
+        }
+    }
+    if runs.is_empty() {
+        return;
+    }
+    let label: String = chars[i..].iter().collect();
+    let label = Some(label.trim().to_string()).filter(|l| !l.is_empty());
+    let count = runs.len();
+    for (k, (start, end, mark)) in runs.into_iter().enumerate() {
+        let column = start.saturating_sub(gutter).max(1);
+        let end_column = end.saturating_sub(gutter).max(column);
+        let label = if k + 1 == count { label.clone() } else { None };
+        let span = if saw_underscore && k == 0 {
+            // `|____^` closes an open span; `____^` on its own opens one at the mark
+            match open.take() {
+                Some((line, col)) if after_bar.trim_start().starts_with('|') => Span {
+                    file: file.to_string(), line, column: col.max(1), end_line: line_no, end_column, label,
+                },
+                _ => {
+                    *open = Some((line_no, column));
+                    continue;
+                }
+            }
+        } else {
+            Span { file: file.to_string(), line: line_no, column, end_line: line_no, end_column, label }
+        };
+        match d.primary.as_mut() {
+            Some(p) if mark == '^' && p.file == span.file && p.line == span.line => {
+                p.column = span.column;
+                p.end_line = span.end_line;
+                p.end_column = span.end_column;
+                p.label = span.label;
+            }
+            _ => d.secondary.push(span),
+        }
+    }
+}
+
+/// Fill in each diagnostic's enclosing function, reading its file under `root`
+pub fn resolve_enclosing_fns(diagnostics: &mut [Diagnostic], root: &Path) {
+    let mut fns: HashMap<String, Vec<FnNode>> = HashMap::new();
+    for d in diagnostics.iter_mut() {
+        let Some(span) = &d.primary else { continue };
+        let file_fns = fns.entry(span.file.clone()).or_insert_with(|| {
+            let path = root.join(&span.file);
+            fs::read_to_string(&path).map(|c| analyze_source(&c, &path).fns).unwrap_or_default()
+        });
+        d.enclosing_fn = innermost_fn(file_fns, span.line).map(FnNode::qualified_name);
+    }
+}
+
+/// The innermost function whose span holds `line`
+pub fn innermost_fn(fns: &[FnNode], line: usize) -> Option<&FnNode> {
+    fns.iter()
+        .filter(|f| f.start_line <= line && line <= f.end_line)
+        .min_by_key(|f| f.end_line - f.start_line)
+}
@@ -0,0 +1,275 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Single-parse analysis driver
+//!
+//! Review tools used to each read and parse every file themselves, so running the
+//! whole suite parsed each file once per tool. The driver reads and parses each file
+//! once and hands the result to every registered [`Analyzer`]:
+//!
+//! - the ra_ap_syntax tree of the whole file, built up front;
+//! - the `verus!` / `verus_!` blocks in it, with their tokens;
+//! - the verus_syn AST of each block, built on first request and then shared.
+//!
+//! Files are processed in parallel with rayon. Syntax trees are not `Send`, so each
+//! file is parsed and analyzed on one worker; analyzers only need to be `Sync`.
+//!
+//! Analyzers give each finding a default severity. The driver then looks the
+//! finding up under its analyzer's name in the veracity.toml `[severity]` table,
+//! so `termination = "info"` or a `[[severity.paths]]` glob can change it, and
+//! `off` drops the finding.
+
+use crate::config::{Severity, VeracityConfig};
+use anyhow::{Context, Result};
+use ra_ap_syntax::{ast, AstNode, Edition, SourceFile, SyntaxKind, SyntaxNode, SyntaxToken};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::cell::OnceCell;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// One `verus! { ... }` block of a parsed file
+pub struct VerusBlock {
+    /// The `{ ... }` token tree of the macro call
+    pub token_tree: SyntaxNode,
+    /// Byte range of the text between the braces
+    pub inner_start: usize,
+    pub inner_end: usize,
+    /// Added to verus_syn (block-relative) line numbers to get file line numbers
+    pub line_offset: usize,
+    syn_file: OnceCell<Option<verus_syn::File>>,
+}
+
+impl VerusBlock {
+    /// All tokens of the block, including whitespace and comments
+    pub fn tokens(&self) -> Vec<SyntaxToken> {
+        self.token_tree.descendants_with_tokens()
+            .filter_map(|n| n.into_token())
+            .collect()
+    }
+}
+
+/// A file read and parsed once, shared by all analyzers
+pub struct ParsedFile {
+    pub path: PathBuf,
+    pub content: String,
+    pub source: SourceFile,
+    pub verus_blocks: Vec<VerusBlock>,
+    line_starts: Vec<usize>,
+}
+
+impl ParsedFile {
+    /// Read and parse `path`
+    pub fn read(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(Self::parse(path, content))
+    }
+
+    /// Parse `content` as the text of `path`
+    pub fn parse(path: &Path, content: String) -> Self {
+        let source = SourceFile::parse(&content, Edition::Edition2021).tree();
+        let mut line_starts = vec![0];
+        line_starts.extend(content.match_indices('\n').map(|(i, _)| i + 1));
+
+        let mut verus_blocks = Vec::new();
+        for node in source.syntax().descendants() {
+            if node.kind() != SyntaxKind::MACRO_CALL {
+                continue;
+            }
+            let Some(macro_call) = ast::MacroCall::cast(node) else { continue };
+            let is_verus = macro_call.path()
+                .map(|p| { let p = p.to_string(); p == "verus" || p == "verus_" })
+                .unwrap_or(false);
+            if !is_verus {
+                continue;
+            }
+            let Some(token_tree) = macro_call.token_tree() else { continue };
+            let range = token_tree.syntax().text_range();
+            let start: usize = range.start().into();
+            let end: usize = range.end().into();
+            if end < start + 2 || end > content.len() {
+                continue;
+            }
+            // Same convention as the proof-holes visitor: line of the opening brace, minus one
+            let line_offset = content[..=start].lines().count().saturating_sub(1);
+            verus_blocks.push(VerusBlock {
+                token_tree: token_tree.syntax().clone(),
+                inner_start: start + 1,
+                inner_end: end - 1,
+                line_offset,
+                syn_file: OnceCell::new(),
+            });
+        }
+
+        ParsedFile { path: path.to_path_buf(), content, source, verus_blocks, line_starts }
+    }
+
+    /// 1-based line number of a byte offset in the file
+    pub fn line_at(&self, offset: usize) -> usize {
+        match self.line_starts.binary_search(&offset) {
+            Ok(i) => i + 1,
+            Err(i) => i,
+        }
+    }
+
+    /// 1-based line number of a token
+    pub fn token_line(&self, token: &SyntaxToken) -> usize {
+        self.line_at(token.text_range().start().into())
+    }
+
+    /// Source text between the braces of a verus! block
+    pub fn verus_text(&self, block: &VerusBlock) -> &str {
+        &self.content[block.inner_start..block.inner_end]
+    }
+
+    /// The verus_syn AST of a block, parsed on first use; None if verus_syn rejects it
+    pub fn verus_syn_file<'a>(&'a self, block: &'a VerusBlock) -> Option<&'a verus_syn::File> {
+        block.syn_file
+            .get_or_init(|| verus_syn::parse_file(self.verus_text(block)).ok())
+            .as_ref()
+    }
+
+    /// Does the file contain any verus! block
+    pub fn has_verus(&self) -> bool {
+        !self.verus_blocks.is_empty()
+    }
+}
+
+/// One diagnostic from an analyzer
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    pub path: PathBuf,
+    pub line: usize,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// What an analyzer reports for one file: diagnostics plus named counters
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AnalyzerOutput {
+    pub findings: Vec<Finding>,
+    pub counts: BTreeMap<String, usize>,
+}
+
+impl AnalyzerOutput {
+    /// Add `n` to counter `name`
+    pub fn count(&mut self, name: &str, n: usize) {
+        *self.counts.entry(name.to_string()).or_insert(0) += n;
+    }
+
+    /// Add a diagnostic at `line` of `file`
+    pub fn finding(&mut self, file: &ParsedFile, line: usize, severity: Severity, message: String) {
+        self.findings.push(Finding { path: file.path.clone(), line, severity, message });
+    }
+}
+
+/// A review pass run by the driver on each parsed file
+pub trait Analyzer: Sync {
+    /// Short name used as the report section title, e.g. "termination"
+    fn name(&self) -> &'static str;
+
+    /// Analyze one file. Called from rayon workers, possibly on many files at once.
+    fn analyze(&self, file: &ParsedFile) -> AnalyzerOutput;
+}
+
+/// Totals for one analyzer over all files
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AnalyzerReport {
+    pub name: String,
+    pub counts: BTreeMap<String, usize>,
+    pub findings: Vec<Finding>,
+}
+
+/// Combined result of a driver run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DriverReport {
+    pub files: usize,
+    pub verus_files: usize,
+    /// Files that could not be read
+    pub errors: Vec<(PathBuf, String)>,
+    pub analyzers: Vec<AnalyzerReport>,
+}
+
+/// Runs registered analyzers over a set of files, parsing each file once
+#[derive(Default)]
+pub struct Driver {
+    analyzers: Vec<Box<dyn Analyzer>>,
+    /// Severity overrides from veracity.toml
+    config: VeracityConfig,
+}
+
+impl Driver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an analyzer; reports list analyzers in registration order
+    pub fn register(&mut self, analyzer: Box<dyn Analyzer>) -> &mut Self {
+        self.analyzers.push(analyzer);
+        self
+    }
+
+    /// Apply `config`'s `[severity]` table to every finding, keyed by analyzer name
+    pub fn with_config(mut self, config: VeracityConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn analyzer_names(&self) -> Vec<&'static str> {
+        self.analyzers.iter().map(|a| a.name()).collect()
+    }
+
+    /// Run every analyzer on one already parsed file, in registration order
+    pub fn analyze_file(&self, file: &ParsedFile) -> Vec<AnalyzerOutput> {
+        self.analyzers.iter()
+            .map(|a| {
+                let mut output = a.analyze(file);
+                if self.config.has_severity_overrides() {
+                    for finding in &mut output.findings {
+                        finding.severity = self.config.severity_for(a.name(), &finding.path, finding.severity);
+                    }
+                    output.findings.retain(|f| f.severity != Severity::Off);
+                }
+                output
+            })
+            .collect()
+    }
+
+    /// Parse each file once and run every analyzer on it, in parallel over files.
+    ///
+    /// Findings come out in the order of `files`, whatever order the workers finish in.
+    pub fn run(&self, files: &[PathBuf]) -> DriverReport {
+        let per_file: Vec<Result<(bool, Vec<AnalyzerOutput>)>> = files.par_iter()
+            .map(|path| {
+                let parsed = ParsedFile::read(path)?;
+                Ok((parsed.has_verus(), self.analyze_file(&parsed)))
+            })
+            .collect();
+
+        let mut report = DriverReport {
+            files: files.len(),
+            analyzers: self.analyzers.iter()
+                .map(|a| AnalyzerReport { name: a.name().to_string(), ..Default::default() })
+                .collect(),
+            ..Default::default()
+        };
+        for (path, result) in files.iter().zip(per_file) {
+            match result {
+                Ok((has_verus, outputs)) => {
+                    if has_verus {
+                        report.verus_files += 1;
+                    }
+                    for (total, output) in report.analyzers.iter_mut().zip(outputs) {
+                        for (name, n) in output.counts {
+                            *total.counts.entry(name).or_insert(0) += n;
+                        }
+                        total.findings.extend(output.findings);
+                    }
+                }
+                Err(e) => report.errors.push((path.clone(), format!("{:#}", e))),
+            }
+        }
+        report
+    }
+}
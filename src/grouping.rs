@@ -0,0 +1,270 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Grouping files into a reporting hierarchy
+//!
+//! Reports that summarize by "chapter" group files with a [`Grouping`] built from
+//! veracity.toml's `[grouping]` table:
+//! - `chapters` (the default): `src/<top>/...` and `src/<top>.rs` group under
+//!   `<top>`, with `ChapNN` in numeric order before everything else;
+//! - `regex`: the captures of `pattern`, matched against the path relative to the
+//!   veracity.toml directory and joined with '/' (the whole match if it has none);
+//! - `workspace`: the Cargo workspace member holding the file, by package name;
+//! - `module`: the first `depth` segments of the file's module path, taken from
+//!   below its nearest `src/` directory.
+//!
+//! Files that fall in no group are left out of grouped sections. Groups other
+//! than chapters sort naturally: digit runs compare by value, so `part2` comes
+//! before `part10`.
+
+use crate::config::{path_matches, relative_to, GroupBy, VeracityConfig};
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use std::cmp::Ordering;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A configured grouping of files
+#[derive(Debug, Clone)]
+pub struct Grouping {
+    by: GroupBy,
+    pattern: Option<Regex>,
+    depth: usize,
+    label: String,
+    /// Directory paths are relative to
+    root: PathBuf,
+    /// Workspace members as (directory relative to `root`, package name), deepest first
+    members: Vec<(String, String)>,
+}
+
+impl Grouping {
+    /// The APAS-style `src/ChapNN/` preset, for paths relative to `root`
+    pub fn chapters(root: &Path) -> Self {
+        Grouping {
+            by: GroupBy::Chapters,
+            pattern: None,
+            depth: 1,
+            label: "Chapter".to_string(),
+            root: root.canonicalize().unwrap_or_else(|_| root.to_path_buf()),
+            members: Vec::new(),
+        }
+    }
+
+    /// The grouping `config` asks for, rooted at its directory
+    pub fn new(config: &VeracityConfig) -> Result<Self> {
+        let grouping = &config.grouping;
+        let pattern = match &grouping.pattern {
+            Some(p) => Some(Regex::new(p).with_context(|| format!("Invalid [grouping] pattern: {}", p))?),
+            None => None,
+        };
+        if grouping.by == GroupBy::Regex && pattern.is_none() {
+            bail!("[grouping] by = \"regex\" needs a pattern");
+        }
+        let members = if grouping.by == GroupBy::Workspace {
+            workspace_members(&config.root)?
+        } else {
+            Vec::new()
+        };
+        let default_label = match grouping.by {
+            GroupBy::Chapters => "Chapter",
+            GroupBy::Regex | GroupBy::Module => "Group",
+            GroupBy::Workspace => "Crate",
+        };
+        Ok(Grouping {
+            by: grouping.by,
+            pattern,
+            depth: grouping.depth.unwrap_or(1).max(1),
+            label: grouping.label.clone().unwrap_or_else(|| default_label.to_string()),
+            root: config.root.clone(),
+            members,
+        })
+    }
+
+    pub fn by(&self) -> GroupBy {
+        self.by
+    }
+
+    /// What a group is called, e.g. "Chapter"
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Group of a path relative to the grouping root, '/'-separated
+    pub fn group_of(&self, rel_path: &str) -> Option<String> {
+        match self.by {
+            GroupBy::Chapters => {
+                let s = rel_path.strip_prefix("src/")?;
+                let top = s.split('/').next()?;
+                let top = top.strip_suffix(".rs").unwrap_or(top);
+                (!top.is_empty()).then(|| top.to_string())
+            }
+            GroupBy::Regex => {
+                let caps = self.pattern.as_ref()?.captures(rel_path)?;
+                let parts: Vec<&str> = caps.iter().skip(1).flatten().map(|m| m.as_str()).collect();
+                let group = if parts.is_empty() { caps.get(0)?.as_str().to_string() } else { parts.join("/") };
+                (!group.is_empty()).then_some(group)
+            }
+            GroupBy::Workspace => self.members.iter()
+                .find(|(dir, _)| dir.is_empty() || path_matches(dir, rel_path))
+                .map(|(_, name)| name.clone()),
+            GroupBy::Module => {
+                let segments = module_segments(rel_path);
+                (!segments.is_empty()).then(|| {
+                    segments.iter().take(self.depth).copied().collect::<Vec<_>>().join("::")
+                })
+            }
+        }
+    }
+
+    /// Group of a file on disk
+    pub fn group_of_path(&self, path: &Path) -> Option<String> {
+        self.group_of(&relative_to(&self.root, path))
+    }
+
+    /// Report order of two groups: the chapter sort for the chapters preset, else natural order
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        if self.by == GroupBy::Chapters {
+            chapter_sort_key(a).cmp(&chapter_sort_key(b)).then_with(|| natural_cmp(a, b))
+        } else {
+            natural_cmp(a, b)
+        }
+    }
+
+    /// Sort group names into report order
+    pub fn sort(&self, groups: &mut [String]) {
+        groups.sort_by(|a, b| self.compare(a, b));
+    }
+
+    /// Groups reported apart from the hierarchy: in the chapters preset, the
+    /// top-level modules and directories that are not `ChapNN`
+    pub fn is_foundation(&self, group: &str) -> bool {
+        self.by == GroupBy::Chapters && chapter_sort_key(group).0
+    }
+}
+
+/// Chapter sort: Chap02 < Chap03 < ... < Chap66 < Concurrency < ParaPairs.
+pub fn chapter_sort_key(ch: &str) -> (bool, u32, &str) {
+    if let Some(num_str) = ch.strip_prefix("Chap") {
+        if let Ok(n) = num_str.parse::<u32>() {
+            return (false, n, "");
+        }
+    }
+    (true, u32::MAX, ch)
+}
+
+/// Compare with digit runs taken as numbers: "part2" < "part10"
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        match (a.chars().next(), b.chars().next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let (na, ra) = split_digits(a);
+                let (nb, rb) = split_digits(b);
+                let trimmed = |n: &str| n.trim_start_matches('0').to_string();
+                let (ta, tb) = (trimmed(na), trimmed(nb));
+                let ord = ta.len().cmp(&tb.len()).then_with(|| ta.cmp(&tb)).then_with(|| na.len().cmp(&nb.len()));
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+                a = ra;
+                b = rb;
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                a = &a[x.len_utf8()..];
+                b = &b[y.len_utf8()..];
+            }
+        }
+    }
+}
+
+fn split_digits(s: &str) -> (&str, &str) {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    s.split_at(end)
+}
+
+/// Module path segments of a file, below its nearest `src/`: src/Chap05/Set.rs -> [Chap05, Set]
+pub(crate) fn module_segments(rel_path: &str) -> Vec<&str> {
+    let s = rel_path.strip_suffix(".rs").unwrap_or(rel_path);
+    let s = match s.rfind("src/") {
+        Some(i) if i == 0 || s[..i].ends_with('/') => &s[i + 4..],
+        _ => s,
+    };
+    let mut segments: Vec<&str> = s.split('/').filter(|p| !p.is_empty()).collect();
+    if matches!(segments.last(), Some(&"mod") | Some(&"lib") | Some(&"main")) {
+        segments.pop();
+    }
+    segments
+}
+
+/// Members of the workspace whose manifest is at or above `root`, deepest directory first.
+///
+/// A member glob may use `*` in its last segment, as in `crates/*`. A root
+/// package is a member holding every file not in another member.
+fn workspace_members(root: &Path) -> Result<Vec<(String, String)>> {
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let manifest = root.ancestors()
+        .map(|d| d.join("Cargo.toml"))
+        .find(|m| read_manifest(m).is_some_and(|t| t.contains_key("workspace")))
+        .with_context(|| format!("[grouping] by = \"workspace\": no Cargo workspace at or above {}", root.display()))?;
+    let ws_dir = manifest.parent().map(Path::to_path_buf).unwrap_or_default();
+    let table = read_manifest(&manifest).unwrap_or_default();
+    let patterns: Vec<String> = table.get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .map(|a| a.iter().filter_map(|v| v.as_str()).map(String::from).collect())
+        .unwrap_or_default();
+
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for pattern in &patterns {
+        let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+        match pattern.rsplit_once('/') {
+            Some((parent, last)) if last.contains(['*', '?']) => {
+                let Ok(entries) = fs::read_dir(ws_dir.join(parent)) else { continue };
+                for entry in entries.flatten() {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if path_matches(pattern, &format!("{}/{}", parent, name)) && entry.path().join("Cargo.toml").is_file() {
+                        dirs.push(entry.path());
+                    }
+                }
+            }
+            _ => dirs.push(ws_dir.join(pattern)),
+        }
+    }
+
+    if table.contains_key("package") {
+        dirs.push(ws_dir.clone());
+    }
+    let mut members: Vec<(String, String)> = Vec::new();
+    for dir in &dirs {
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.clone());
+        if root.starts_with(&dir) {
+            // The root is inside this member; the member holds everything a deeper one does not
+            members.push((String::new(), package_name(&dir)));
+        } else if let Ok(rel) = dir.strip_prefix(&root) {
+            members.push((relative_to(Path::new(""), rel), package_name(&dir)));
+        }
+    }
+    members.sort_by(|a, b| b.0.matches('/').count().cmp(&a.0.matches('/').count())
+        .then_with(|| b.0.len().cmp(&a.0.len())));
+    if members.is_empty() {
+        bail!("[grouping] by = \"workspace\": {} lists no members under {}", manifest.display(), root.display());
+    }
+    Ok(members)
+}
+
+fn read_manifest(path: &Path) -> Option<toml::Table> {
+    fs::read_to_string(path).ok()?.parse::<toml::Table>().ok()
+}
+
+/// `[package] name` of the crate in `dir`, else the directory name
+fn package_name(dir: &Path) -> String {
+    read_manifest(&dir.join("Cargo.toml"))
+        .and_then(|t| t.get("package")?.get("name")?.as_str().map(String::from))
+        .unwrap_or_else(|| dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default())
+}
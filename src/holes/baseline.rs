@@ -0,0 +1,191 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Baseline keys (--baseline / --write-baseline)
+//!
+//! A baseline records each hole by a [`HoleKey`] that leaves out the line number, so
+//! a baseline survives edits that only move holes. Identical holes in one item share
+//! a key, so a baseline is a multiset: [`compare`] reports the instances beyond each
+//! key's recorded count as new.
+
+use crate::config::Severity;
+use crate::holes::{DetectedHole, FileHoles, HoleKind};
+use anyhow::{Context, Result};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// Stable identity of a hole that survives line shifts: the line number is deliberately absent.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct HoleKey {
+    pub file: String,
+    /// Enclosing fn (or annotated item for attribute holes); empty at module level
+    pub function: String,
+    pub hole_type: String,
+    /// Context with whitespace collapsed and truncation marker removed
+    pub context: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    #[serde(flatten)]
+    pub key: HoleKey,
+    /// Identical holes in the same fn share a key, so the baseline is a multiset
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Baseline {
+    pub tool: String,
+    pub version: String,
+    pub generated: String,
+    pub holes: Vec<BaselineEntry>,
+}
+
+fn normalize_context(context: &str) -> String {
+    let collapsed = context.split_whitespace().collect::<Vec<_>>().join(" ");
+    collapsed.trim_end_matches("...").trim_end().to_string()
+}
+
+/// Item name declared on a line: `pub proof fn lemma_foo<T>(...)` -> `lemma_foo`.
+/// Impl headers are kept whole (`impl View for Foo`) since they have no single name.
+fn item_name_from_line(line: &str) -> Option<String> {
+    let trimmed = line.trim();
+    let words: Vec<&str> = trimmed
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|w| !w.is_empty())
+        .collect();
+    for (i, word) in words.iter().enumerate() {
+        match *word {
+            "impl" => {
+                let header = trimmed.trim_end_matches('{').trim();
+                return Some(header.split_whitespace().collect::<Vec<_>>().join(" "));
+            }
+            "fn" | "struct" | "enum" | "trait" | "type" | "const" | "static" | "union" => {
+                return words.get(i + 1).map(|w| w.to_string());
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Name of the item a hole belongs to, for baseline keys.
+/// Attribute holes annotate the following declaration; body holes belong to the enclosing fn.
+pub fn hole_item_name(content: &str, hole: &DetectedHole) -> String {
+    let is_attribute_hole = hole.kind.is_external()
+        || hole.kind == HoleKind::Opaque
+        || hole.kind.is_accept_hole();
+
+    let decl_line = if is_attribute_hole {
+        content.lines().enumerate()
+            .skip(hole.line)
+            .find(|(_, l)| {
+                let t = l.trim();
+                !t.is_empty() && !t.starts_with("#[") && !t.starts_with("//")
+            })
+            .map(|(idx, _)| idx + 1)
+    } else if get_line(content, hole.line).and_then(|l| item_name_from_line(&l)).is_some() {
+        Some(hole.line)
+    } else {
+        find_enclosing_fn_line(content, hole.line)
+    };
+
+    decl_line
+        .and_then(|n| get_line(content, n))
+        .and_then(|l| item_name_from_line(&l))
+        .unwrap_or_default()
+}
+
+/// Search backwards from `from_line` to find the enclosing `fn` signature line.
+/// Matches lines whose trimmed content contains ` fn ` or starts with `fn `.
+pub fn find_enclosing_fn_line(content: &str, from_line: usize) -> Option<usize> {
+    let lines: Vec<&str> = content.lines().collect();
+    let start = from_line.saturating_sub(1); // 0-indexed
+    for idx in (0..start).rev() {
+        let trimmed = lines[idx].trim();
+        if trimmed.contains(" fn ") || trimmed.starts_with("fn ") {
+            return Some(idx + 1); // back to 1-indexed
+        }
+    }
+    None
+}
+
+/// Get a specific 1-indexed line from content, trimmed.
+pub fn get_line(content: &str, line_num: usize) -> Option<String> {
+    content.lines().nth(line_num.saturating_sub(1)).map(|l| l.to_string())
+}
+
+/// Every hole and error-level warning keyed for the baseline, with its current lines.
+pub fn collect_hole_keys(base_dir: &Path, file_stats_map: &HashMap<String, FileHoles>) -> BTreeMap<HoleKey, Vec<usize>> {
+    let mut keys: BTreeMap<HoleKey, Vec<usize>> = BTreeMap::new();
+    for (path_str, stats) in file_stats_map {
+        let content = fs::read_to_string(base_dir.join(path_str)).unwrap_or_default();
+        let errors = stats.warnings.iter().filter(|w| stats.warning_level(w.kind) == Severity::Error);
+        for hole in stats.holes.holes.iter().chain(errors) {
+            let key = HoleKey {
+                file: path_str.clone(),
+                function: hole_item_name(&content, hole),
+                hole_type: hole.kind.to_string(),
+                context: normalize_context(&hole.context),
+            };
+            keys.entry(key).or_default().push(hole.line);
+        }
+    }
+    keys
+}
+
+pub fn write_baseline(path: &Path, keys: &BTreeMap<HoleKey, Vec<usize>>) -> Result<()> {
+    let baseline = Baseline {
+        tool: "veracity-review-proof-holes".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        generated: Local::now().format("%Y-%m-%d %H:%M:%S %Z").to_string(),
+        holes: keys.iter()
+            .map(|(key, lines)| BaselineEntry { key: key.clone(), count: lines.len() })
+            .collect(),
+    };
+    let json = serde_json::to_string_pretty(&baseline)?;
+    fs::write(path, json).with_context(|| format!("Failed to write baseline: {}", path.display()))?;
+    Ok(())
+}
+
+pub fn load_baseline(path: &Path) -> Result<BTreeMap<HoleKey, usize>> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read baseline: {}", path.display()))?;
+    let baseline: Baseline = serde_json::from_str(&text)
+        .with_context(|| format!("Failed to parse baseline: {}", path.display()))?;
+    let mut counts: BTreeMap<HoleKey, usize> = BTreeMap::new();
+    for entry in baseline.holes {
+        *counts.entry(entry.key).or_insert(0) += entry.count;
+    }
+    Ok(counts)
+}
+
+/// Current holes measured against a recorded baseline
+#[derive(Debug, Default)]
+pub struct BaselineDiff<'a> {
+    /// Current instances beyond the recorded count for their key: (key, line)
+    pub new: Vec<(&'a HoleKey, usize)>,
+    /// Recorded instances no longer present: (key, instances gone)
+    pub fixed: Vec<(&'a HoleKey, usize)>,
+}
+
+/// Split `current` against `recorded` into new and fixed instances
+pub fn compare<'a>(current: &'a BTreeMap<HoleKey, Vec<usize>>, recorded: &'a BTreeMap<HoleKey, usize>) -> BaselineDiff<'a> {
+    let mut diff = BaselineDiff::default();
+    for (key, lines) in current {
+        let allowed = recorded.get(key).copied().unwrap_or(0);
+        for line in lines.iter().skip(allowed) {
+            diff.new.push((key, *line));
+        }
+    }
+    for (key, count) in recorded {
+        let now = current.get(key).map_or(0, |lines| lines.len());
+        if *count > now {
+            diff.fixed.push((key, count - now));
+        }
+    }
+    diff
+}
@@ -0,0 +1,643 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Hole collection for a run
+//!
+//! Which files a run analyzes ([`collect_files`], [`discover_verus_projects`]), and
+//! the [`Analyzer`] that analyzes them: it reuses cached results, records Verus
+//! errors as `verification_failure` holes, applies veracity.toml and runs git blame.
+//! [`HistoryAnalyzer`] does the same for the tree at each commit, read from git.
+
+use crate::accepted::{check_accept_site, is_accept_site, AcceptedInventory};
+use crate::args::StandardArgs;
+use crate::cache::{crate_module_files, AnalysisCache};
+use crate::config::{BudgetUsage, VeracityConfig};
+use crate::deps::find_manifest;
+use crate::diagnostics::{innermost_fn, run_verus, VerusRun};
+use crate::find_rust_files;
+use crate::holes::baseline::hole_item_name;
+use crate::holes::{
+    analyze_file_with_macros, analyze_source_with_macros, find_macro_definitions, DetectedHole, FileHoles, HoleBlame,
+    HoleKind, HoleMacro, MacroTable,
+};
+use anyhow::{Context, Result};
+use ra_ap_syntax::{ast::{self, AstNode}, SyntaxKind};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+// ============================================================================
+// Files to analyze
+// ============================================================================
+
+/// Check if a path should be excluded based on exclude_dirs
+pub fn should_exclude(path: &Path, exclude_dirs: &[PathBuf]) -> bool {
+    // Always exclude docs, path (legacy), src/lib.rs (crate root), and src/Types.rs
+    if path.file_name().is_some_and(|f| f == "lib.rs") && path.parent().is_some_and(|p| p.ends_with("src")) {
+        return true;
+    }
+    if path.file_name().is_some_and(|f| f == "Types.rs") && path.parent().is_some_and(|p| p.ends_with("src")) {
+        return true;
+    }
+    if path.components().any(|c| {
+        let s = c.as_os_str();
+        s == "docs" || s == "path"
+    }) {
+        return true;
+    }
+    for exclude in exclude_dirs {
+        // Check if the path starts with the exclude directory
+        if let Ok(canonical_path) = path.canonicalize() {
+            if let Ok(canonical_exclude) = exclude.canonicalize() {
+                if canonical_path.starts_with(&canonical_exclude) {
+                    return true;
+                }
+            }
+        }
+        // Also check without canonicalization for relative paths
+        if path.starts_with(exclude) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Collect the .rs files named by args.paths (files or directories), minus exclusions
+pub fn collect_files(args: &StandardArgs, exclude_dirs: &[PathBuf]) -> Vec<PathBuf> {
+    let mut all_files: Vec<PathBuf> = Vec::new();
+    
+    // Handle both file and directory modes
+    for path in &args.paths {
+        if path.is_file() && path.extension().is_some_and(|e| e == "rs") {
+            if !should_exclude(path, exclude_dirs) {
+                all_files.push(path.clone());
+            }
+        } else if path.is_dir() {
+            let files = find_rust_files(std::slice::from_ref(path));
+            for file in files {
+                if !should_exclude(&file, exclude_dirs) {
+                    all_files.push(file);
+                }
+            }
+        }
+    }
+    args.without_excluded(all_files)
+}
+
+/// Discover all projects containing Verus files in a directory
+pub fn discover_verus_projects(base_dir: &Path, exclude_dirs: &[PathBuf]) -> Result<HashMap<String, Vec<PathBuf>>> {
+    let mut projects: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    
+    // Find all subdirectories (potential projects)
+    for entry in fs::read_dir(base_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            // Skip hidden directories and common non-project dirs
+            if let Some(name) = path.file_name() {
+                let name_str = name.to_string_lossy();
+                if !name_str.starts_with('.') && name_str != "target" {
+                    // Skip excluded directories
+                    if should_exclude(&path, exclude_dirs) {
+                        continue;
+                    }
+                    let project_name = name.to_string_lossy().to_string();
+                    let verus_files = find_verus_files_in_project(&path, exclude_dirs)?;
+                    
+                    if !verus_files.is_empty() {
+                        projects.insert(project_name, verus_files);
+                    }
+                }
+            }
+        }
+    }
+    
+    Ok(projects)
+}
+
+/// Find all Verus files in a project directory
+fn find_verus_files_in_project(project_dir: &Path, exclude_dirs: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut verus_files = Vec::new();
+    
+    // Find all .rs files
+    for entry in WalkDir::new(project_dir).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        // Skip excluded directories
+        if should_exclude(path, exclude_dirs) {
+            continue;
+        }
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "rs") {
+            // Check if it contains verus! macro
+            if contains_verus_macro(path)? {
+                verus_files.push(path.to_path_buf());
+            }
+        }
+    }
+    
+    Ok(verus_files)
+}
+
+/// Check if a file contains verus! or verus_! macro
+fn contains_verus_macro(path: &Path) -> Result<bool> {
+    let content = fs::read_to_string(path)?;
+    let parsed = ra_ap_syntax::SourceFile::parse(&content, ra_ap_syntax::Edition::Edition2021);
+    let tree = parsed.tree();
+    let root = tree.syntax();
+    
+    for node in root.descendants() {
+        if node.kind() == SyntaxKind::MACRO_CALL {
+            if let Some(macro_call) = ast::MacroCall::cast(node) {
+                if let Some(macro_path) = macro_call.path() {
+                    let path_str = macro_path.to_string();
+                    if path_str == "verus" || path_str == "verus_" {
+                        return Ok(true);
+                    }
+                }
+            }
+        }
+    }
+    Ok(false)
+}
+
+// ============================================================================
+// Incremental cache (analyses/.cache/) and project policy (veracity.toml)
+// ============================================================================
+
+/// FileHoles as cached; the hole list travels beside it because reports skip it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedFileStats {
+    pub stats: FileHoles,
+    pub holes: Vec<DetectedHole>,
+}
+
+/// Analyzes files for one run: reuses cached FileHoles, then applies veracity.toml.
+pub struct Analyzer<'a> {
+    config: &'a VeracityConfig,
+    cache: AnalysisCache<CachedFileStats>,
+    /// Directory that `use crate::` module paths are relative to
+    src_root: PathBuf,
+    /// The crate's macro_rules!, expanded at each use to find holes
+    macros: MacroTable,
+    /// Run git blame on each hole line (never cached: blame changes without the file changing)
+    blame: bool,
+    /// --verify: Verus errors by canonical file path, recorded as holes after the cache
+    verification: HashMap<PathBuf, Vec<VerusError>>,
+}
+
+impl<'a> Analyzer<'a> {
+    pub fn new(args: &'a StandardArgs, use_cache: bool) -> Self {
+        let base_dir = args.base_dir();
+        let src_root = if base_dir.join("src").is_dir() { base_dir.join("src") } else { base_dir.clone() };
+        let macros = MacroTable::from_files(&args.without_excluded(find_rust_files(std::slice::from_ref(&src_root))));
+        // Severities are applied after the cache, so veracity.toml is not part of the key;
+        // a file's holes depend on the crate's holed macros, so they are
+        let cache = if use_cache {
+            AnalysisCache::load(&base_dir, "veracity-review-verus-proof-holes", &macros.fingerprint())
+        } else {
+            AnalysisCache::disabled()
+        };
+        Analyzer { config: &args.config, cache, src_root, macros, blame: false, verification: HashMap::new() }
+    }
+
+    pub fn with_blame(mut self, blame: bool) -> Self {
+        self.blame = blame;
+        self
+    }
+
+    pub fn with_verification(mut self, verification: HashMap<PathBuf, Vec<VerusError>>) -> Self {
+        self.verification = verification;
+        self
+    }
+
+    /// FileHoles for `path` with veracity.toml severities applied.
+    ///
+    /// A cache entry is reused while the file and the files behind its `use crate::` deps are unchanged.
+    pub fn analyze(&mut self, path: &Path) -> Result<FileHoles> {
+        let mut stats = match self.cache.get(path) {
+            Some(cached) => {
+                let mut stats = cached.stats;
+                stats.holes.holes = cached.holes;
+                stats
+            }
+            None => {
+                let stats = analyze_file_with_macros(path, &self.macros)?;
+                let deps = crate_module_files(&self.src_root, &stats.crate_deps);
+                let holes = stats.holes.holes.clone();
+                self.cache.insert(path, &deps, CachedFileStats { stats: stats.clone(), holes });
+                stats
+            }
+        };
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if let Some(errors) = self.verification.get(&canonical) {
+            record_verification_failures(&mut stats, errors);
+        }
+        if stats.infos.iter().any(|i| i.kind.is_accept_hole()) {
+            stats.apply_justification_policy(&fs::read_to_string(path)?, &self.config.justification);
+        }
+        if self.config.has_severity_overrides() {
+            stats.apply_policy(path, self.config);
+        }
+        if self.blame {
+            blame_holes(path, &mut stats.holes.holes);
+        }
+        Ok(stats)
+    }
+
+    /// Forget the hash read of `path` this run, so the next `analyze` sees an edit to it
+    pub fn invalidate(&mut self, path: &Path) {
+        self.cache.forget(path);
+    }
+
+    /// Save the cache; returns (files reused, files analyzed).
+    pub fn finish(&mut self) -> (usize, usize) {
+        if let Err(e) = self.cache.save() {
+            eprintln!("warning: could not save analysis cache: {:#}", e);
+        }
+        (self.cache.hits, self.cache.misses)
+    }
+}
+
+/// Holes per veracity.toml `[[budget]]` directory.
+pub fn compute_budgets(config: &VeracityConfig, base_dir: &Path, file_stats_map: &HashMap<String, FileHoles>) -> Vec<BudgetUsage> {
+    let file_holes: Vec<(PathBuf, usize)> = file_stats_map.iter()
+        .map(|(path_str, stats)| (base_dir.join(path_str), stats.holes.total_holes))
+        .collect();
+    config.budget_usage(&file_holes)
+}
+
+/// Every accept site, checked against veracity.toml's `[justification]` policy
+pub fn build_accepted_inventory(config: &VeracityConfig, base_dir: &Path, file_stats_map: &HashMap<String, FileHoles>) -> AcceptedInventory {
+    let mut inventory = AcceptedInventory::default();
+    let mut paths: Vec<&String> = file_stats_map.keys().collect();
+    paths.sort();
+    for path_str in paths {
+        let stats = &file_stats_map[path_str];
+        if !stats.infos.iter().any(|i| is_accept_site(i.kind)) {
+            continue;
+        }
+        let content = fs::read_to_string(base_dir.join(path_str)).unwrap_or_default();
+        for info in stats.infos.iter().filter(|i| is_accept_site(i.kind)) {
+            let site = check_accept_site(&content, info, &config.justification);
+            inventory.add(path_str, hole_item_name(&content, info), &site);
+        }
+    }
+    inventory
+}
+
+// ============================================================================
+// Verification failures (--verify)
+// ============================================================================
+
+/// One Verus error, at the line of its primary `-->` span
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerusError {
+    /// Canonical path of the file the span is in
+    pub path: PathBuf,
+    pub line: usize,
+    pub message: String,
+}
+
+/// Errors of a Verus run that have a primary span, with their paths resolved against `root`.
+///
+/// Errors with no span ("could not compile ...", a bare panic) are dropped.
+pub fn verus_errors(run: &VerusRun, root: &Path) -> Vec<VerusError> {
+    run.errors()
+        .filter_map(|d| {
+            let span = d.primary.as_ref()?;
+            let path = root.join(&span.file);
+            Some(VerusError {
+                path: path.canonicalize().unwrap_or(path),
+                line: span.line,
+                message: d.message.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Record one verification_failure hole per function with an error, at the function's line.
+///
+/// The innermost function whose span holds the error gets it; an error outside every
+/// function (a struct invariant, a broadcast group) is reported at its own line.
+pub fn record_verification_failures(stats: &mut FileHoles, errors: &[VerusError]) {
+    let mut failed: BTreeMap<usize, (Option<String>, Vec<&str>)> = BTreeMap::new();
+    for error in errors {
+        let (line, name) = match innermost_fn(&stats.fns, error.line) {
+            Some(f) => (f.line, Some(f.qualified_name())),
+            None => (error.line, None),
+        };
+        let entry = failed.entry(line).or_insert_with(|| (name, Vec::new()));
+        if !entry.1.contains(&error.message.as_str()) {
+            entry.1.push(&error.message);
+        }
+    }
+    for (line, (name, messages)) in failed {
+        let context = match name {
+            Some(name) => format!("fn {}: {}", name, messages.join("; ")),
+            None => messages.join("; "),
+        };
+        stats.holes.record(DetectedHole { line, kind: HoleKind::VerificationFailure, context, blame: None });
+    }
+    stats.holes.holes.sort_by_key(|h| h.line);
+}
+
+/// --verify: run Verus on the crate holding `base_dir` and return its errors by file.
+pub fn run_verification(base_dir: &Path, emit: &dyn Fn(&str)) -> Result<HashMap<PathBuf, Vec<VerusError>>> {
+    let root = find_manifest(base_dir)
+        .and_then(|manifest| manifest.parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| base_dir.to_path_buf());
+    emit(&format!("Verifying {} with Verus...", root.display()));
+    let run = run_verus(&root)?;
+    let errors = verus_errors(&run, &root);
+    let elapsed = run.duration.as_secs_f64();
+    if run.success {
+        emit(&format!("Verus: verified in {:.1}s", elapsed));
+    } else {
+        emit(&format!("Verus: {} error{} in {:.1}s", errors.len(), if errors.len() == 1 { "" } else { "s" }, elapsed));
+        if errors.is_empty() {
+            emit("warning: Verus failed without an error span; the end of its output:");
+            let lines: Vec<&str> = run.stderr.lines().collect();
+            for line in &lines[lines.len().saturating_sub(10)..] {
+                emit(&format!("   {}", line));
+            }
+        }
+    }
+    emit("");
+    let mut by_file: HashMap<PathBuf, Vec<VerusError>> = HashMap::new();
+    for error in errors {
+        by_file.entry(error.path.clone()).or_default().push(error);
+    }
+    Ok(by_file)
+}
+
+// ============================================================================
+// Hole attribution (--blame)
+// ============================================================================
+
+/// Set `blame` on each hole with one `git blame --porcelain` over just the hole lines.
+/// Leaves the holes unattributed if git fails (not a repository, untracked file).
+pub fn blame_holes(path: &Path, holes: &mut [DetectedHole]) {
+    let mut lines: Vec<usize> = holes.iter().map(|h| h.line).filter(|&l| l > 0).collect();
+    lines.sort_unstable();
+    lines.dedup();
+    if lines.is_empty() {
+        return;
+    }
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else { return };
+    let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+    let ranges: Vec<String> = lines.iter().map(|l| format!("{},{}", l, l)).collect();
+    let name = name.to_string_lossy();
+    let mut args = vec!["blame", "--porcelain"];
+    for range in &ranges {
+        args.extend(["-L", range.as_str()]);
+    }
+    args.extend(["--", name.as_ref()]);
+    let Ok(porcelain) = git(dir, &args) else { return };
+    let by_line = parse_blame_porcelain(&porcelain);
+    for hole in holes.iter_mut() {
+        hole.blame = by_line.get(&hole.line).cloned();
+    }
+}
+
+/// Final line number -> blame, from `git blame --porcelain`.
+///
+/// Each line starts with "<sha> <orig line> <final line> [<group size>]"; the author
+/// headers follow only the first line from each commit, and the content line starts with a tab.
+pub fn parse_blame_porcelain(porcelain: &str) -> HashMap<usize, HoleBlame> {
+    let mut authors: HashMap<&str, (String, i64)> = HashMap::new();
+    let mut lines: Vec<(usize, &str)> = Vec::new();
+    let mut commit = "";
+    for line in porcelain.lines() {
+        if line.starts_with('\t') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(' ').collect();
+        let is_header = fields.len() >= 3
+            && matches!(fields[0].len(), 40 | 64)
+            && fields[0].bytes().all(|b| b.is_ascii_hexdigit());
+        if is_header {
+            commit = fields[0];
+            if let Ok(final_line) = fields[2].parse() {
+                lines.push((final_line, commit));
+            }
+        } else if let Some(author) = line.strip_prefix("author ") {
+            authors.entry(commit).or_default().0 = author.to_string();
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            authors.entry(commit).or_default().1 = time.parse().unwrap_or(0);
+        }
+    }
+    lines.into_iter()
+        .filter_map(|(line, commit)| {
+            let (author, time) = authors.get(commit)?.clone();
+            let date = chrono::DateTime::from_timestamp(time, 0)
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+            Some((line, HoleBlame { author, commit: commit.to_string(), date, time }))
+        })
+        .collect()
+}
+
+// ============================================================================
+// History (--history)
+// ============================================================================
+
+/// Run git in `dir` and return its stdout
+pub fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = std::process::Command::new("git")
+        .arg("-C").arg(dir)
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Contents of blobs by id, read with one `git cat-file --batch`
+fn read_blobs(repo: &Path, ids: &[String]) -> Result<HashMap<String, String>> {
+    use std::io::Read;
+    use std::process::{Command, Stdio};
+    let mut blobs = HashMap::new();
+    if ids.is_empty() {
+        return Ok(blobs);
+    }
+    let mut child = Command::new("git")
+        .arg("-C").arg(repo)
+        .args(["cat-file", "--batch"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .context("Failed to run git cat-file")?;
+    let mut stdin = child.stdin.take().context("git cat-file has no stdin")?;
+    let request: String = ids.iter().map(|id| format!("{}\n", id)).collect();
+    // Write from another thread so a full stdout pipe cannot block both sides
+    let writer = std::thread::spawn(move || stdin.write_all(request.as_bytes()));
+    let mut out = Vec::new();
+    child.stdout.take().context("git cat-file has no stdout")?.read_to_end(&mut out)?;
+    let _ = writer.join();
+    child.wait()?;
+
+    // Each blob is "<id> blob <size>\n<content>\n"; unknown ids are "<id> missing\n"
+    let mut pos = 0;
+    while let Some(nl) = out[pos..].iter().position(|&b| b == b'\n') {
+        let header = String::from_utf8_lossy(&out[pos..pos + nl]).into_owned();
+        pos += nl + 1;
+        let parts: Vec<&str> = header.split(' ').collect();
+        if parts.len() != 3 {
+            continue;
+        }
+        let size: usize = parts[2].parse().with_context(|| format!("Bad git cat-file header: {}", header))?;
+        let end = (pos + size).min(out.len());
+        blobs.insert(parts[0].to_string(), String::from_utf8_lossy(&out[pos..end]).into_owned());
+        pos = end + 1;
+        if pos >= out.len() {
+            break;
+        }
+    }
+    Ok(blobs)
+}
+
+/// Analyzes the tree at each commit from git blobs; results are cached per blob id
+pub struct HistoryAnalyzer<'a> {
+    args: &'a StandardArgs,
+    exclude_dirs: &'a [PathBuf],
+    pub base_dir: PathBuf,
+    repo: PathBuf,
+    /// base_dir relative to the repository root, "" or ending in '/'
+    prefix: String,
+    macros: AnalysisCache<Vec<HoleMacro>>,
+    stats: AnalysisCache<CachedFileStats>,
+}
+
+impl<'a> HistoryAnalyzer<'a> {
+    pub fn new(args: &'a StandardArgs, exclude_dirs: &'a [PathBuf], use_cache: bool) -> Result<Self> {
+        let base_dir = args.base_dir();
+        let repo = PathBuf::from(git(&base_dir, &["rev-parse", "--show-toplevel"])?.trim());
+        let prefix = git(&base_dir, &["rev-parse", "--show-prefix"])?.trim().to_string();
+        let (macros, stats) = if use_cache {
+            (
+                AnalysisCache::load(&base_dir, "veracity-review-verus-proof-holes-history-macros", ""),
+                AnalysisCache::load(&base_dir, "veracity-review-verus-proof-holes-history", ""),
+            )
+        } else {
+            (AnalysisCache::disabled(), AnalysisCache::disabled())
+        };
+        Ok(HistoryAnalyzer { args, exclude_dirs, base_dir, repo, prefix, macros, stats })
+    }
+
+    /// (commit, date, subject) for each first-parent commit in `range` touching base_dir, oldest first
+    pub fn commits(&self, range: &str) -> Result<Vec<(String, String, String)>> {
+        let mut log_args = vec!["log", "--reverse", "--first-parent", "--format=%H%x1f%cI%x1f%s", range, "--"];
+        log_args.push(if self.prefix.is_empty() { "." } else { self.prefix.as_str() });
+        let text = git(&self.repo, &log_args)?;
+        Ok(text.lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, '\u{1f}');
+                Some((fields.next()?.to_string(), fields.next()?.to_string(), fields.next().unwrap_or("").to_string()))
+            })
+            .collect())
+    }
+
+    /// (path relative to base_dir, blob id) of each analyzed .rs file at `commit`
+    pub fn files_at(&self, commit: &str) -> Result<Vec<(String, String)>> {
+        let mut ls_args = vec!["ls-tree", "-r", "-z", commit];
+        if !self.prefix.is_empty() {
+            ls_args.push("--");
+            ls_args.push(self.prefix.as_str());
+        }
+        let text = git(&self.repo, &ls_args)?;
+        let mut files = Vec::new();
+        for entry in text.split('\0') {
+            // "<mode> blob <id>\t<path>"
+            let Some((meta, path)) = entry.split_once('\t') else { continue };
+            let meta: Vec<&str> = meta.split(' ').collect();
+            if meta.len() != 3 || meta[1] != "blob" || !path.ends_with(".rs") {
+                continue;
+            }
+            let rel = path.strip_prefix(self.prefix.as_str()).unwrap_or(path).to_string();
+            let full = self.base_dir.join(&rel);
+            if should_exclude(&full, self.exclude_dirs) || self.args.config.is_excluded(&full) {
+                continue;
+            }
+            files.push((rel, meta[2].to_string()));
+        }
+        Ok(files)
+    }
+
+    /// FileHoles of every analyzed file at `commit`, keyed by path relative to base_dir
+    pub fn analyze_commit(&mut self, commit: &str) -> Result<HashMap<String, FileHoles>> {
+        let files = self.files_at(commit)?;
+
+        // The crate's macros first: a file's holes depend on them
+        let cached_defs: Vec<Option<Vec<HoleMacro>>> = files.iter()
+            .map(|(_, id)| self.macros.get_content(id))
+            .collect();
+        let missing: Vec<String> = files.iter().zip(&cached_defs)
+            .filter(|(_, defs)| defs.is_none())
+            .map(|((_, id), _)| id.clone())
+            .collect();
+        let mut contents = read_blobs(&self.repo, &missing)?;
+        let mut definitions = Vec::new();
+        for ((rel, id), defs) in files.iter().zip(cached_defs) {
+            let defs = defs.unwrap_or_else(|| {
+                let content = contents.get(id).map(String::as_str).unwrap_or("");
+                let defs = find_macro_definitions(content, &self.base_dir.join(rel));
+                self.macros.insert_content(id, defs.clone());
+                defs
+            });
+            definitions.extend(defs);
+        }
+        let macros = MacroTable::from_definitions(definitions);
+        let fingerprint = macros.fingerprint();
+        let key = |id: &str| if fingerprint.is_empty() { id.to_string() } else { format!("{}:{}", id, fingerprint) };
+
+        let cached_stats: Vec<Option<CachedFileStats>> = files.iter()
+            .map(|(_, id)| self.stats.get_content(&key(id)))
+            .collect();
+        // Cached files are read only to check accept-hole justifications
+        let missing: Vec<String> = files.iter().zip(&cached_stats)
+            .filter(|((_, id), cached)| !contents.contains_key(id) && cached.as_ref()
+                .is_none_or(|c| c.stats.infos.iter().any(|i| i.kind.is_accept_hole())))
+            .map(|((_, id), _)| id.clone())
+            .collect();
+        contents.extend(read_blobs(&self.repo, &missing)?);
+
+        let mut file_stats_map = HashMap::new();
+        for ((rel, id), cached) in files.into_iter().zip(cached_stats) {
+            let path = self.base_dir.join(&rel);
+            let mut stats = match cached {
+                Some(cached) => {
+                    let mut stats = cached.stats;
+                    stats.holes.holes = cached.holes;
+                    stats
+                }
+                None => {
+                    let content = contents.get(&id).map(String::as_str).unwrap_or("");
+                    let stats = analyze_source_with_macros(content, &path, &macros);
+                    let holes = stats.holes.holes.clone();
+                    self.stats.insert_content(&key(&id), CachedFileStats { stats: stats.clone(), holes });
+                    stats
+                }
+            };
+            stats.path = path.clone();
+            let content = contents.get(&id).map(String::as_str).unwrap_or("");
+            stats.apply_justification_policy(content, &self.args.config.justification);
+            if self.args.config.has_severity_overrides() {
+                stats.apply_policy(&path, &self.args.config);
+            }
+            file_stats_map.insert(rel, stats);
+        }
+        Ok(file_stats_map)
+    }
+
+    /// Save both caches; returns (files reused, files analyzed) over all commits
+    pub fn finish(&mut self) -> (usize, usize) {
+        if let Err(e) = self.macros.save().and_then(|_| self.stats.save()) {
+            eprintln!("warning: could not save analysis cache: {:#}", e);
+        }
+        (self.stats.hits, self.stats.misses)
+    }
+}
@@ -0,0 +1,496 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Machine-readable reports
+//!
+//! The `--format json` document ([`JsonReport`], documented in
+//! schemas/proof_holes.schema.json) and its SARIF 2.1.0 conversion, plus the
+//! optional parts: trust per dependency crate (`--deps`), holes by author and age
+//! (`--blame`) and the summary at each commit (`--history`).
+
+use crate::accepted::AcceptedInventory;
+use crate::deps::{find_manifest, local_dependencies, DepSource};
+use crate::find_rust_files;
+use crate::holes::summary::{compute_depends_upon, path_str_to_module, SummaryStats};
+use crate::holes::{analyze_file_with_macros, AxiomStats, DetectedHole, FileHoles, FnSpecStats, HoleBlame, HoleKind, MacroTable, ProofHoleStats};
+use crate::tcb::{tcb_totals, FnTrust, TcbReport, TcbTotals};
+use anyhow::Result;
+use chrono::Local;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+
+// ============================================================================
+// JSON and SARIF (--format json / --format sarif)
+// ============================================================================
+
+const PROOF_HOLES_SCHEMA: &str = "https://github.com/veracity/schemas/proof_holes.schema.json";
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Root of the JSON report; documented in schemas/proof_holes.schema.json
+#[derive(Debug, Serialize)]
+pub struct JsonReport {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub tool: String,
+    pub version: String,
+    pub generated: String,
+    pub base_dir: String,
+    pub files: Vec<JsonFileReport>,
+    pub depends_upon: Vec<JsonDependsUpon>,
+    pub summary: JsonSummary,
+    pub proof_targets: JsonProofTargets,
+    pub transitive_trust: Vec<FnTrust>,
+    /// --deps: trust summary per dependency crate, not included in `summary`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<DepTrust>,
+    /// --blame: holes by author and by age
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribution: Option<HoleAttribution>,
+    /// --tcb: trusted items with line and token counts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tcb: Option<TcbReport>,
+    /// Accept sites grouped by `// veracity-accept: reason=` category
+    #[serde(skip_serializing_if = "AcceptedInventory::is_empty")]
+    pub accepted: AcceptedInventory,
+}
+
+/// One diagnostic: a hole (error), a warning/error-level finding, or an info
+#[derive(Debug, Serialize)]
+pub struct JsonDiagnostic {
+    pub file: String,
+    pub line: usize,
+    pub hole_type: String,
+    /// "error", "warning" or "info" (same levels as the Emacs output)
+    pub severity: String,
+    pub context: String,
+    /// --blame: who last changed the line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blame: Option<HoleBlame>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonFileReport {
+    pub path: String,
+    pub module: String,
+    pub verusified: bool,
+    pub clean: bool,
+    pub counts: ProofHoleStats,
+    pub axioms: AxiomStats,
+    pub fn_spec: FnSpecStats,
+    pub proof_functions: usize,
+    pub clean_proof_functions: usize,
+    pub holed_proof_functions: usize,
+    pub holes: Vec<JsonDiagnostic>,
+    pub warnings: Vec<JsonDiagnostic>,
+    pub infos: Vec<JsonDiagnostic>,
+    pub soft_holes: Vec<JsonDiagnostic>,
+    pub crate_deps: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonDependsUpon {
+    pub module: String,
+    pub path: String,
+    pub holed_deps: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonSummary {
+    pub total_files: usize,
+    pub clean_modules: usize,
+    pub holed_modules: usize,
+    pub total_proof_functions: usize,
+    pub clean_proof_functions: usize,
+    pub holed_proof_functions: usize,
+    pub holes: ProofHoleStats,
+    pub axioms: AxiomStats,
+    pub fn_spec: FnSpecStats,
+    pub total_warnings: usize,
+    pub total_infos: usize,
+    pub total_soft_holes: usize,
+    pub soft_hole_counts: BTreeMap<String, usize>,
+    pub warning_type_counts: BTreeMap<String, usize>,
+    /// Functions in the call graph with no holes of their own
+    pub locally_clean_fns: usize,
+    /// Functions with no holes reachable through calls
+    pub truly_clean_fns: usize,
+    pub truly_clean_proof_fns: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonDirTarget {
+    pub dir: String,
+    pub holes: usize,
+    pub files: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonFileTarget {
+    pub path: String,
+    pub holes: usize,
+}
+
+/// Sections 4.1-4.5 of the text report
+#[derive(Debug, Serialize)]
+pub struct JsonProofTargets {
+    pub worst_directories: Vec<JsonDirTarget>,
+    pub next_target_files: Vec<JsonFileTarget>,
+    pub next_target_dirs: Vec<JsonDirTarget>,
+    pub not_verusified: Vec<String>,
+    pub not_verusified_clean_deps: Vec<String>,
+    /// Modules on each crate:: dependency cycle (section 4.8)
+    pub dependency_cycles: Vec<Vec<String>>,
+}
+
+fn to_json_diagnostics(path_str: &str, holes: &[DetectedHole], severity: impl Fn(HoleKind) -> &'static str) -> Vec<JsonDiagnostic> {
+    holes.iter()
+        .map(|h| JsonDiagnostic {
+            file: path_str.to_string(),
+            line: h.line,
+            hole_type: h.kind.to_string(),
+            severity: severity(h.kind).to_string(),
+            context: h.context.clone(),
+            blame: h.blame.clone(),
+        })
+        .collect()
+}
+
+pub fn build_json_report(base_dir: &Path, file_stats_map: &HashMap<String, FileHoles>, summary: &SummaryStats) -> JsonReport {
+    let mut paths: Vec<&String> = file_stats_map.keys().collect();
+    paths.sort();
+    
+    let mut files = Vec::new();
+    for path_str in paths {
+        let stats = &file_stats_map[path_str];
+        let has_errors = stats.has_errors();
+        let mut crate_deps: Vec<String> = stats.crate_deps.iter().cloned().collect();
+        crate_deps.sort();
+        files.push(JsonFileReport {
+            path: path_str.clone(),
+            module: path_str_to_module(path_str),
+            verusified: !stats.is_not_verusified(),
+            clean: stats.holes.total_holes == 0 && !has_errors,
+            counts: stats.holes.clone(),
+            axioms: stats.axioms.clone(),
+            fn_spec: stats.fn_spec.clone(),
+            proof_functions: stats.proof_functions,
+            clean_proof_functions: stats.clean_proof_functions,
+            holed_proof_functions: stats.holed_proof_functions,
+            holes: to_json_diagnostics(path_str, &stats.holes.holes, |_| "error"),
+            warnings: to_json_diagnostics(path_str, &stats.warnings, |k| stats.warning_level(k).as_str()),
+            infos: to_json_diagnostics(path_str, &stats.infos, |_| "info"),
+            soft_holes: to_json_diagnostics(path_str, &stats.soft_holes, |k| stats.warning_level(k).as_str()),
+            crate_deps,
+        });
+    }
+    
+    let depends_upon = compute_depends_upon(file_stats_map).into_iter()
+        .map(|(module, path, holed_deps)| JsonDependsUpon { module, path, holed_deps })
+        .collect();
+    
+    let mut worst_directories: Vec<JsonDirTarget> = summary.by_root_top.get("src")
+        .map(|top_map| top_map.iter()
+            .map(|(dir, (_, holes, files))| JsonDirTarget { dir: dir.clone(), holes: *holes, files: *files })
+            .collect())
+        .unwrap_or_default();
+    worst_directories.sort_by(|a, b| b.holes.cmp(&a.holes).then_with(|| a.dir.cmp(&b.dir)));
+    
+    JsonReport {
+        schema: PROOF_HOLES_SCHEMA.to_string(),
+        tool: "veracity-review-proof-holes".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        generated: Local::now().format("%Y-%m-%d %H:%M:%S %Z").to_string(),
+        base_dir: base_dir.display().to_string(),
+        files,
+        depends_upon,
+        summary: JsonSummary {
+            total_files: summary.total_files,
+            clean_modules: summary.clean_modules,
+            holed_modules: summary.holed_modules,
+            total_proof_functions: summary.total_proof_functions,
+            clean_proof_functions: summary.clean_proof_functions,
+            holed_proof_functions: summary.holed_proof_functions,
+            holes: summary.holes.clone(),
+            axioms: summary.axioms.clone(),
+            fn_spec: summary.fn_spec.clone(),
+            total_warnings: summary.total_warnings,
+            total_infos: summary.total_infos,
+            total_soft_holes: summary.all_soft_holes.len(),
+            soft_hole_counts: summary.soft_hole_counts.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+            warning_type_counts: summary.warning_type_counts.iter().map(|(k, v)| (k.clone(), *v)).collect(),
+            locally_clean_fns: summary.trust.iter().filter(|t| t.own_holes == 0).count(),
+            truly_clean_fns: summary.trust.iter().filter(|t| t.trusted_base.is_empty()).count(),
+            truly_clean_proof_fns: summary.trust.iter()
+                .filter(|t| t.mode == "proof" && t.trusted_base.is_empty())
+                .count(),
+        },
+        proof_targets: JsonProofTargets {
+            worst_directories,
+            next_target_files: summary.next_target_files.iter()
+                .map(|(path, holes)| JsonFileTarget { path: path.clone(), holes: *holes })
+                .collect(),
+            next_target_dirs: summary.next_target_dirs.iter()
+                .map(|(dir, holes, files)| JsonDirTarget { dir: dir.clone(), holes: *holes, files: *files })
+                .collect(),
+            not_verusified: summary.not_verusified_files.clone(),
+            not_verusified_clean_deps: summary.not_verusified_clean_deps.clone(),
+            dependency_cycles: summary.module_graph.cycles.clone(),
+        },
+        transitive_trust: summary.trust.clone(),
+        dependencies: Vec::new(),
+        attribution: None,
+        tcb: None,
+        accepted: AcceptedInventory::default(),
+    }
+}
+
+/// Convert the JSON report into a SARIF 2.1.0 log (one result per hole, warning, info and soft hole).
+pub fn build_sarif_report(report: &JsonReport) -> serde_json::Value {
+    let diagnostics: Vec<&JsonDiagnostic> = report.files.iter()
+        .flat_map(|f| f.holes.iter().chain(f.warnings.iter()).chain(f.infos.iter()).chain(f.soft_holes.iter()))
+        .collect();
+    
+    let rule_ids: BTreeSet<&str> = diagnostics.iter().map(|d| d.hole_type.as_str()).collect();
+    let rules: Vec<serde_json::Value> = rule_ids.iter()
+        .map(|id| serde_json::json!({
+            "id": id,
+            "name": id,
+            "shortDescription": { "text": format!("Verus proof hole: {}", id) },
+            "helpUri": "https://github.com/briangmilnes/veracity/blob/main/docs/veracity-proof-holes.md",
+        }))
+        .collect();
+    
+    let results: Vec<serde_json::Value> = diagnostics.iter()
+        .map(|d| {
+            let level = match d.severity.as_str() {
+                "info" => "note",
+                other => other,
+            };
+            let text = if d.context.is_empty() {
+                d.hole_type.clone()
+            } else {
+                format!("{} - {}", d.hole_type, d.context)
+            };
+            serde_json::json!({
+                "ruleId": d.hole_type,
+                "level": level,
+                "message": { "text": text },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": d.file, "uriBaseId": "SRCROOT" },
+                        "region": { "startLine": d.line.max(1) },
+                    }
+                }],
+            })
+        })
+        .collect();
+    
+    serde_json::json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": report.tool,
+                    "version": report.version,
+                    "informationUri": "https://github.com/briangmilnes/veracity",
+                    "rules": rules,
+                }
+            },
+            "originalUriBaseIds": {
+                "SRCROOT": { "uri": format!("file://{}/", report.base_dir.trim_end_matches('/')) }
+            },
+            "results": results,
+        }],
+    })
+}
+
+// ============================================================================
+// Dependency trust (--deps)
+// ============================================================================
+
+/// Holes in one path or vendored dependency crate
+#[derive(Debug, Serialize)]
+pub struct DepTrust {
+    pub name: String,
+    pub source: DepSource,
+    pub dir: String,
+    pub required_by: String,
+    pub files: usize,
+    pub holed_files: usize,
+    pub holes: usize,
+    pub axioms: usize,
+    pub assume_specification: usize,
+    pub external_body: usize,
+    /// Trusted items, lines and tokens (see veracity::tcb)
+    pub tcb: TcbTotals,
+}
+
+/// Analyze each local dependency crate of the package holding `base_dir`.
+/// Each crate is analyzed on its own, with its own macros and no veracity.toml or cache.
+pub fn analyze_dependencies(base_dir: &Path) -> Result<Vec<DepTrust>> {
+    let manifest = find_manifest(base_dir)
+        .ok_or_else(|| anyhow::anyhow!("--deps: no Cargo.toml at or above {}", base_dir.display()))?;
+    let mut report = Vec::new();
+    for dep in local_dependencies(&manifest)? {
+        let files = find_rust_files(&[dep.source_dir()]);
+        let macros = MacroTable::from_files(&files);
+        let mut trust = DepTrust {
+            name: dep.name.clone(),
+            source: dep.source,
+            dir: dep.dir.display().to_string(),
+            required_by: dep.required_by.clone(),
+            files: 0,
+            holed_files: 0,
+            holes: 0,
+            axioms: 0,
+            assume_specification: 0,
+            external_body: 0,
+            tcb: tcb_totals(&files),
+        };
+        for file in &files {
+            let Ok(stats) = analyze_file_with_macros(file, &macros) else { continue };
+            trust.files += 1;
+            if stats.holes.total_holes > 0 {
+                trust.holed_files += 1;
+            }
+            trust.holes += stats.holes.total_holes;
+            trust.axioms += stats.axioms.total_axioms;
+            trust.assume_specification += stats.holes.assume_specification_count;
+            trust.external_body += stats.holes.external_body_count;
+        }
+        report.push(trust);
+    }
+    Ok(report)
+}
+
+// ============================================================================
+// Hole attribution (--blame)
+// ============================================================================
+
+/// Age buckets for the attribution summary, youngest first: (label, days under)
+pub const AGE_BUCKETS: [(&str, i64); 3] = [("under 1 week", 7), ("under 1 month", 30), ("older", i64::MAX)];
+
+/// Holes per author and per age bucket
+#[derive(Debug, Serialize)]
+pub struct HoleAttribution {
+    pub by_author: BTreeMap<String, usize>,
+    pub by_age: BTreeMap<String, usize>,
+    /// Holes git blame could not attribute (untracked file, not a repo)
+    pub unattributed: usize,
+}
+
+impl HoleAttribution {
+    pub fn new(file_stats_map: &HashMap<String, FileHoles>) -> Self {
+        let now = Local::now().timestamp();
+        let mut attribution = HoleAttribution { by_author: BTreeMap::new(), by_age: BTreeMap::new(), unattributed: 0 };
+        for (label, _) in AGE_BUCKETS {
+            attribution.by_age.insert(label.to_string(), 0);
+        }
+        for hole in file_stats_map.values().flat_map(|s| &s.holes.holes) {
+            let Some(blame) = &hole.blame else {
+                attribution.unattributed += 1;
+                continue;
+            };
+            *attribution.by_author.entry(blame.author.clone()).or_default() += 1;
+            *attribution.by_age.entry(age_bucket(blame.time, now).to_string()).or_default() += 1;
+        }
+        attribution
+    }
+}
+
+fn age_bucket(time: i64, now: i64) -> &'static str {
+    let days = (now - time) / 86_400;
+    AGE_BUCKETS.iter().find(|(_, under)| days < *under).map_or("older", |(label, _)| label)
+}
+
+// ============================================================================
+// History (--history)
+// ============================================================================
+
+/// The summary at one commit
+#[derive(Debug, Serialize)]
+pub struct HistoryPoint {
+    pub commit: String,
+    /// Committer date, ISO 8601
+    pub date: String,
+    pub subject: String,
+    pub total_files: usize,
+    pub clean_modules: usize,
+    pub holed_modules: usize,
+    pub total_proof_functions: usize,
+    pub clean_proof_functions: usize,
+    pub holed_proof_functions: usize,
+    pub total_holes: usize,
+    pub total_warnings: usize,
+    pub total_infos: usize,
+    pub total_soft_holes: usize,
+    /// hole_type -> holes
+    pub by_hole_type: BTreeMap<String, usize>,
+    /// Directory relative to base_dir -> hole_type -> holes
+    pub by_dir: BTreeMap<String, BTreeMap<String, usize>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HistoryReport {
+    pub tool: String,
+    pub version: String,
+    pub generated: String,
+    pub base_dir: String,
+    pub range: String,
+    pub commits: Vec<HistoryPoint>,
+}
+
+pub fn history_point(commit: &str, date: &str, subject: &str, file_stats_map: &HashMap<String, FileHoles>, summary: &SummaryStats) -> HistoryPoint {
+    let mut by_hole_type: BTreeMap<String, usize> = BTreeMap::new();
+    let mut by_dir: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+    for (path_str, stats) in file_stats_map {
+        let dir = Path::new(path_str).parent()
+            .map(|p| p.display().to_string())
+            .filter(|d| !d.is_empty())
+            .unwrap_or_else(|| ".".to_string());
+        for hole in &stats.holes.holes {
+            *by_hole_type.entry(hole.kind.to_string()).or_insert(0) += 1;
+            *by_dir.entry(dir.clone()).or_default().entry(hole.kind.to_string()).or_insert(0) += 1;
+        }
+    }
+    HistoryPoint {
+        commit: commit.to_string(),
+        date: date.to_string(),
+        subject: subject.to_string(),
+        total_files: summary.total_files,
+        clean_modules: summary.clean_modules,
+        holed_modules: summary.holed_modules,
+        total_proof_functions: summary.total_proof_functions,
+        clean_proof_functions: summary.clean_proof_functions,
+        holed_proof_functions: summary.holed_proof_functions,
+        total_holes: summary.holes.total_holes,
+        total_warnings: summary.total_warnings,
+        total_infos: summary.total_infos,
+        total_soft_holes: summary.all_soft_holes.len(),
+        by_hole_type,
+        by_dir,
+    }
+}
+
+/// Long-format CSV: one row per commit, directory and hole type. Directory "*" is the
+/// whole tree and hole type "total" all holes, so every commit has at least one row.
+pub fn history_csv(points: &[HistoryPoint]) -> String {
+    fn field(s: &str) -> String {
+        if s.contains([',', '"', '\n']) { format!("\"{}\"", s.replace('"', "\"\"")) } else { s.to_string() }
+    }
+    let mut csv = String::from("commit,date,dir,hole_type,holes\n");
+    for p in points {
+        csv.push_str(&format!("{},{},*,total,{}\n", p.commit, p.date, p.total_holes));
+        for (kind, n) in &p.by_hole_type {
+            csv.push_str(&format!("{},{},*,{},{}\n", p.commit, p.date, field(kind), n));
+        }
+        for (dir, kinds) in &p.by_dir {
+            for (kind, n) in kinds {
+                csv.push_str(&format!("{},{},{},{},{}\n", p.commit, p.date, field(dir), field(kind), n));
+            }
+        }
+    }
+    csv
+}
@@ -0,0 +1,346 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Project summary of per-file hole analyses
+//!
+//! [`compute_summary`] folds the [`FileHoles`] of every analyzed file, keyed by path
+//! relative to the analyzed directory, into the totals, proof targets, module graph
+//! and transitive trust that the text and JSON reports print.
+
+use crate::config::Severity;
+use crate::holes::{AxiomStats, FileHoles, FnSpecStats, HoleKind, ProofHoleStats};
+use crate::module_graph::{ModuleGraph, ModuleNode};
+use crate::tcb::{compute_trust, FnTrust};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+/// Totals and proof targets over every analyzed file
+#[derive(Debug, Default)]
+pub struct SummaryStats {
+    pub total_files: usize,
+    pub clean_modules: usize,
+    pub holed_modules: usize,
+    pub total_proof_functions: usize,
+    pub clean_proof_functions: usize,
+    pub holed_proof_functions: usize,
+    pub holes: ProofHoleStats,
+    pub axioms: AxiomStats,
+    pub fn_spec: FnSpecStats,
+    pub total_warnings: usize,
+    pub total_infos: usize,
+    /// Count per hole_type for accurate summary message
+    pub warning_type_counts: HashMap<String, usize>,
+    /// All infos with path:line for summary listing
+    pub all_infos: Vec<(String, usize, String, String)>,
+    /// All errors (path, line, hole_type) for single-line summary
+    pub all_errors: Vec<(String, usize, String)>,
+    /// All warnings (path, line, hole_type) for single-line summary
+    pub all_warnings: Vec<(String, usize, String)>,
+    /// Count per soft hole kind
+    pub soft_hole_counts: BTreeMap<HoleKind, usize>,
+    /// All soft holes (path, line, kind)
+    pub all_soft_holes: Vec<(String, usize, HoleKind)>,
+    /// Per root (src, path, tests): top-level dirs -> (unused, holes, file_count) for Proof Targets
+    pub by_root_top: HashMap<String, HashMap<String, (usize, usize, usize)>>,
+    /// True if any path has subdirs (e.g. Chap05/SetStEph.rs)
+    pub has_subdir_paths: bool,
+    /// Next Target Files: src files that depend only on clean modules, (path, holes)
+    pub next_target_files: Vec<(String, usize)>,
+    /// Next Target Directories: src dirs where all files depend only on clean, (dir, holes, file_count)
+    pub next_target_dirs: Vec<(String, usize, usize)>,
+    /// Not verusified: src files with no verus! block
+    pub not_verusified_files: Vec<String>,
+    /// Not verusified with clean deps: not_verusified files that depend only on clean modules
+    pub not_verusified_clean_deps: Vec<String>,
+    /// Per-function transitive trusted base (holes reachable through calls)
+    pub trust: Vec<FnTrust>,
+    /// Files, their crate:: dependencies and the cycles among them
+    pub module_graph: ModuleGraph,
+}
+
+/// Totals across the projects of a multi-codebase run
+#[derive(Debug, Default)]
+pub struct GlobalSummaryStats {
+    pub total_projects: usize,
+    pub total_files: usize,
+    pub clean_modules: usize,
+    pub holed_modules: usize,
+    pub total_proof_functions: usize,
+    pub clean_proof_functions: usize,
+    pub holed_proof_functions: usize,
+    pub holes: ProofHoleStats,
+    pub axioms: AxiomStats,
+}
+
+impl GlobalSummaryStats {
+    /// Add one project's summary to the totals
+    pub fn add(&mut self, summary: &SummaryStats) {
+        self.total_projects += 1;
+        self.total_files += summary.total_files;
+        self.clean_modules += summary.clean_modules;
+        self.holed_modules += summary.holed_modules;
+        self.total_proof_functions += summary.total_proof_functions;
+        self.clean_proof_functions += summary.clean_proof_functions;
+        self.holed_proof_functions += summary.holed_proof_functions;
+
+        self.holes.assume_false_count += summary.holes.assume_false_count;
+        self.holes.assume_count += summary.holes.assume_count;
+        self.holes.assume_new_count += summary.holes.assume_new_count;
+        self.holes.assume_specification_count += summary.holes.assume_specification_count;
+        self.holes.admit_count += summary.holes.admit_count;
+        self.holes.unsafe_fn_count += summary.holes.unsafe_fn_count;
+        self.holes.unsafe_impl_count += summary.holes.unsafe_impl_count;
+        self.holes.unsafe_block_count += summary.holes.unsafe_block_count;
+        self.holes.external_body_count += summary.holes.external_body_count;
+        self.holes.external_fn_spec_count += summary.holes.external_fn_spec_count;
+        self.holes.external_trait_spec_count += summary.holes.external_trait_spec_count;
+        self.holes.external_type_spec_count += summary.holes.external_type_spec_count;
+        self.holes.external_trait_ext_count += summary.holes.external_trait_ext_count;
+        self.holes.external_count += summary.holes.external_count;
+        self.holes.opaque_count += summary.holes.opaque_count;
+        self.holes.total_holes += summary.holes.total_holes;
+
+        self.axioms.axiom_fn_count += summary.axioms.axiom_fn_count;
+        self.axioms.broadcast_use_axiom_count += summary.axioms.broadcast_use_axiom_count;
+        self.axioms.total_axioms += summary.axioms.total_axioms;
+    }
+}
+
+/// Sum the files' counts and work out proof targets, the module graph and transitive trust
+pub fn compute_summary(file_stats_map: &HashMap<String, FileHoles>, base_dir: &Path) -> SummaryStats {
+    let mut summary = SummaryStats {
+        has_subdir_paths: file_stats_map.keys().any(|p| p.contains('/')),
+        ..Default::default()
+    };
+    
+    for (path_str, stats) in file_stats_map {
+        let full_path = base_dir.join(path_str).canonicalize()
+            .unwrap_or_else(|_| base_dir.join(path_str))
+            .display()
+            .to_string();
+        summary.total_files += 1;
+        
+        if stats.holes.total_holes > 0 || stats.has_errors() {
+            summary.holed_modules += 1;
+        } else {
+            summary.clean_modules += 1;
+        }
+        
+        summary.total_proof_functions += stats.proof_functions;
+        summary.clean_proof_functions += stats.clean_proof_functions;
+        summary.holed_proof_functions += stats.holed_proof_functions;
+        
+        summary.holes.assume_false_count += stats.holes.assume_false_count;
+        summary.holes.assume_count += stats.holes.assume_count;
+        summary.holes.assume_new_count += stats.holes.assume_new_count;
+        summary.holes.assume_specification_count += stats.holes.assume_specification_count;
+        summary.holes.admit_count += stats.holes.admit_count;
+        summary.holes.unsafe_fn_count += stats.holes.unsafe_fn_count;
+        summary.holes.unsafe_impl_count += stats.holes.unsafe_impl_count;
+        summary.holes.unsafe_block_count += stats.holes.unsafe_block_count;
+        summary.holes.external_body_count += stats.holes.external_body_count;
+        summary.holes.external_fn_spec_count += stats.holes.external_fn_spec_count;
+        summary.holes.external_trait_spec_count += stats.holes.external_trait_spec_count;
+        summary.holes.external_type_spec_count += stats.holes.external_type_spec_count;
+        summary.holes.external_trait_ext_count += stats.holes.external_trait_ext_count;
+        summary.holes.external_count += stats.holes.external_count;
+        summary.holes.opaque_count += stats.holes.opaque_count;
+        summary.holes.trivial_spec_wf_count += stats.holes.trivial_spec_wf_count;
+        summary.holes.verification_failure_count += stats.holes.verification_failure_count;
+        summary.holes.total_holes += stats.holes.total_holes;
+        
+        summary.axioms.axiom_fn_count += stats.axioms.axiom_fn_count;
+        summary.axioms.broadcast_use_axiom_count += stats.axioms.broadcast_use_axiom_count;
+        summary.axioms.total_axioms += stats.axioms.total_axioms;
+
+        summary.fn_spec.total_fns += stats.fn_spec.total_fns;
+        summary.fn_spec.exec_fns_complete += stats.fn_spec.exec_fns_complete;
+        summary.fn_spec.exec_fns_missing_spec += stats.fn_spec.exec_fns_missing_spec;
+        summary.fn_spec.proof_spec_fns_clean += stats.fn_spec.proof_spec_fns_clean;
+        summary.fn_spec.proof_spec_fns_with_holes += stats.fn_spec.proof_spec_fns_with_holes;
+
+        summary.total_warnings += stats.warnings.len() + stats.holes.trivial_spec_wf_count;
+        summary.total_infos += stats.infos.len();
+        for w in &stats.warnings {
+            *summary.warning_type_counts.entry(w.kind.to_string()).or_insert(0) += 1;
+            let entry = (full_path.clone(), w.line, w.kind.to_string());
+            if stats.warning_level(w.kind) == Severity::Warning {
+                summary.all_warnings.push(entry);
+            } else {
+                summary.all_errors.push(entry);
+            }
+        }
+        // trivial_spec_wf is in holes only (not warnings) to avoid double-print; add to all_errors here
+        for h in &stats.holes.holes {
+            if h.kind == HoleKind::TrivialSpecWf {
+                *summary.warning_type_counts.entry(h.kind.to_string()).or_insert(0) += 1;
+                summary.all_errors.push((full_path.clone(), h.line, h.kind.to_string()));
+            }
+        }
+        for soft in &stats.soft_holes {
+            *summary.soft_hole_counts.entry(soft.kind).or_insert(0) += 1;
+            summary.all_soft_holes.push((full_path.clone(), soft.line, soft.kind));
+        }
+        for info in &stats.infos {
+            summary.all_infos.push((
+                full_path.clone(),
+                info.line,
+                info.kind.to_string(),
+                info.context.clone(),
+            ));
+        }
+
+        // Aggregate for Proof Targets (root/* and root/*/*), only for paths with subdirs
+        // All issues (holes + fn_missing_*) count as holes for display
+        if path_str.contains('/') {
+            let file_errors = stats.warnings.iter().filter(|w| w.kind.is_missing_spec()).count();
+            let file_holes = stats.holes.total_holes + file_errors;
+            let path_no_ext = path_str.strip_suffix(".rs").unwrap_or(path_str);
+            let parts: Vec<&str> = path_no_ext.split('/').collect();
+            let root = parts[0].to_string();
+            let top_level = if parts.len() >= 2 { parts[1].to_string() } else { continue };
+            let e = summary.by_root_top.entry(root.clone()).or_default()
+                .entry(top_level.clone()).or_insert((0, 0, 0));
+            e.1 += file_holes;
+            e.2 += 1;
+        }
+    }
+
+    // Next Target Files and Directories: worst among those that depend only on clean modules.
+    // Holed modules on a cycle with the file do not count: the cycle is cleaned as a unit.
+    summary.module_graph = build_module_graph(file_stats_map);
+    let clean_deps: HashMap<&str, bool> = summary.module_graph.nodes.iter()
+        .map(|n| (n.path.as_str(), n.clean_deps))
+        .collect();
+    for (path_str, stats) in file_stats_map.iter() {
+        if !path_str.starts_with("src/") {
+            continue;
+        }
+        let has_holed_dep = !clean_deps.get(path_str.as_str()).copied().unwrap_or(true);
+        if !has_holed_dep {
+            let file_errors = stats.warnings.iter().filter(|w| w.kind.is_missing_spec()).count();
+            let file_holes = stats.holes.total_holes + file_errors;
+            if file_holes > 0 {
+                summary.next_target_files.push((path_str.clone(), file_holes));
+            }
+        }
+    }
+    summary.next_target_files.sort_by(|a, b| b.1.cmp(&a.1)); // sort by holes descending
+
+    // Next Target Directories: dirs where ALL files depend only on clean
+    let mut dir_files: HashMap<String, Vec<(usize, bool)>> = HashMap::new();
+    for (path_str, stats) in file_stats_map.iter() {
+        if !path_str.starts_with("src/") {
+            continue;
+        }
+        let dir = path_str.rsplit_once('/').map(|(d, _)| d.to_string()).unwrap_or_else(|| path_str.clone());
+        let has_holed_dep = !clean_deps.get(path_str.as_str()).copied().unwrap_or(true);
+        let file_errors = stats.warnings.iter().filter(|w| w.kind.is_missing_spec()).count();
+        let file_holes = stats.holes.total_holes + file_errors;
+        dir_files.entry(dir).or_default().push((file_holes, has_holed_dep));
+    }
+    for (dir, files) in dir_files {
+        if dir == "src" {
+            continue; // skip root; only show subdirs like src/Chap05
+        }
+        if files.iter().any(|(_, h)| *h) {
+            continue;
+        }
+        let holes: usize = files.iter().map(|(h, _)| h).sum();
+        if holes > 0 {
+            summary.next_target_dirs.push((dir, holes, files.len()));
+        }
+    }
+    summary.next_target_dirs.sort_by(|a, b| b.1.cmp(&a.1)); // sort by holes descending
+
+    // Not verusified: files with no verus! block
+    for (path_str, stats) in file_stats_map.iter() {
+        if !path_str.starts_with("src/") {
+            continue;
+        }
+        let is_not_verusified = stats.is_not_verusified();
+        if is_not_verusified {
+            summary.not_verusified_files.push(path_str.clone());
+        }
+    }
+    summary.not_verusified_files.sort();
+
+    // Not verusified with clean deps: same but only if depends only on clean modules
+    for (path_str, stats) in file_stats_map.iter() {
+        if !path_str.starts_with("src/") {
+            continue;
+        }
+        let is_not_verusified = stats.is_not_verusified();
+        if !is_not_verusified {
+            continue;
+        }
+        let has_holed_dep = !clean_deps.get(path_str.as_str()).copied().unwrap_or(true);
+        if !has_holed_dep {
+            summary.not_verusified_clean_deps.push(path_str.clone());
+        }
+    }
+    summary.not_verusified_clean_deps.sort();
+
+    summary.trust = compute_trust(file_stats_map, base_dir);
+    
+    summary
+}
+
+/// path_str "src/Chap05/SetStEph.rs" -> "Chap05::SetStEph" (strip src/ for crate module path)
+pub fn path_str_to_module(path_str: &str) -> String {
+    let s = path_str.strip_suffix(".rs").unwrap_or(path_str);
+    let s = s.strip_prefix("src/").unwrap_or(s);
+    s.replace('/', "::")
+}
+
+/// One node per file, with the `use crate::` dependencies that path_str_to_module names resolve
+pub fn build_module_graph(file_stats_map: &HashMap<String, FileHoles>) -> ModuleGraph {
+    let files = file_stats_map.iter()
+        .map(|(path_str, stats)| {
+            let file_errors = stats.warnings.iter().filter(|w| w.kind.is_missing_spec()).count();
+            let node = ModuleNode {
+                module: path_str_to_module(path_str),
+                path: path_str.clone(),
+                holes: stats.holes.total_holes + file_errors,
+                holed: stats.holes.total_holes > 0 || stats.has_errors(),
+                verusified: !stats.is_not_verusified(),
+                ..Default::default()
+            };
+            (node, stats.crate_deps.iter().cloned().collect())
+        })
+        .collect();
+    ModuleGraph::new(files)
+}
+
+/// For each file, (module, path_str, holed modules it depends upon), sorted by module.
+pub fn compute_depends_upon(file_stats_map: &HashMap<String, FileHoles>) -> Vec<(String, String, Vec<String>)> {
+    let mut module_to_holed: HashMap<String, bool> = HashMap::new();
+    for (path_str, stats) in file_stats_map {
+        let module = path_str_to_module(path_str);
+        let holed = stats.holes.total_holes > 0 || stats.has_errors();
+        module_to_holed.insert(module.clone(), holed);
+    }
+    let all_modules: HashSet<String> = module_to_holed.keys().cloned().collect();
+
+    // Build (module, path_str, holed_deps) for each file
+    let mut entries: Vec<(String, String, Vec<String>)> = Vec::new();
+    for path_str in file_stats_map.keys() {
+        let stats = file_stats_map.get(path_str).unwrap();
+        let module = path_str_to_module(path_str);
+        let mut holed_deps: Vec<String> = Vec::new();
+        for dep in &stats.crate_deps {
+            for m in &all_modules {
+                if (*m == *dep || m.starts_with(&format!("{}::", dep)))
+                    && *module_to_holed.get(m).unwrap_or(&false)
+                {
+                    holed_deps.push(m.clone());
+                }
+            }
+        }
+        holed_deps.sort();
+        holed_deps.dedup();
+        entries.push((module, path_str.clone(), holed_deps));
+    }
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries
+}
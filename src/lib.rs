@@ -6,9 +6,23 @@
 //! This library provides tools to analyze Verus code for proof holes,
 //! axiom dependencies, and lines of code metrics.
 
+pub mod accepted;
+pub mod analyzers;
 pub mod args;
+pub mod cache;
+pub mod config;
+pub mod deps;
+pub mod diagnostics;
+pub mod driver;
+pub mod grouping;
+pub mod holes;
+pub mod lift;
+pub mod minimize;
+pub mod module_graph;
 pub mod parser;
 pub mod search;
+pub mod tcb;
+pub mod triage;
 pub mod vstd_inventory;
 
 use anyhow::Result;
@@ -16,6 +30,7 @@ use ra_ap_syntax::SourceFile;
 
 // Re-export commonly used items
 pub use args::StandardArgs;
+pub use config::VeracityConfig;
 pub use parser::parse_file;
 
 // Re-export find_rust_files and format_number from args module
@@ -0,0 +1,663 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Lift `assume(P)` into named admitted lemmas
+//!
+//! An `assume(P)` buried in a function body cannot be searched for, counted per
+//! obligation, or shared. [`lift_assumes`] replaces it with a call to a new lemma
+//! and adds the lemma to the module:
+//!
+//! ```text
+//! proof fn lemma_<fn>_<n>(<free variables of P>)
+//!     ensures
+//!         P,
+//! {
+//!     admit();
+//! }
+//! ```
+//!
+//! The parameters are the variables P mentions, typed from the enclosing fn's
+//! signature and from `let x: T` bindings before the assume. The generics of the
+//! enclosing impl and fn that those types use come along. In exec code the call
+//! goes in a `proof { }` block.
+//!
+//! An assume is left alone, with a reason, when P mentions a variable whose type
+//! is not written down (an untyped `let`, a loop or match binding), `self` or
+//! `Self`, a `&mut` parameter, or `old(..)`.
+//!
+//! Lemmas go at the end of the module's proof fn section (the
+//! `//\t\tN. proof fns/broadcast groups` header written by
+//! veracity-review-verus-style), or else before the first trait, impl or exec fn.
+
+use crate::driver::ParsedFile;
+use quote::ToTokens;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use verus_syn::spanned::Spanned;
+use verus_syn::visit::{self, Visit};
+
+/// An assume replaced by a lemma call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiftedAssume {
+    pub line: usize,
+    pub lemma: String,
+}
+
+/// An assume that could not be lifted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedAssume {
+    pub line: usize,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LiftResult {
+    /// The rewritten file; equal to the input when nothing was lifted
+    pub content: String,
+    pub lifted: Vec<LiftedAssume>,
+    pub skipped: Vec<SkippedAssume>,
+}
+
+/// Lift every `assume(P)` on the given 1-based `lines` of `content`.
+pub fn lift_assumes(content: &str, lines: &[usize]) -> LiftResult {
+    let wanted: HashSet<usize> = lines.iter().copied().collect();
+    let parsed = ParsedFile::parse(Path::new(""), content.to_string());
+    let mut taken: HashSet<String> = existing_fn_names(content);
+    let mut edits: Vec<(usize, usize, String)> = Vec::new();
+    let mut result = LiftResult::default();
+    let mut found: HashSet<usize> = HashSet::new();
+
+    for block in &parsed.verus_blocks {
+        let Some(file) = parsed.verus_syn_file(block) else { continue };
+        let mut finder = AssumeFinder::new(block.line_offset, &wanted);
+        finder.visit_file(file);
+        if finder.assumes.is_empty() {
+            continue;
+        }
+
+        let mut lemmas = String::new();
+        let indent_line = insertion_line(&parsed, block, file);
+        let indent: String = line_text(content, indent_line).chars().take_while(|c| c.is_whitespace()).collect();
+        let mut per_line: HashMap<usize, usize> = HashMap::new();
+        let mut per_fn: HashMap<String, usize> = HashMap::new();
+        for assume in finder.assumes {
+            found.insert(assume.line);
+            let nth = per_line.entry(assume.line).or_insert(0);
+            let occurrence = *nth;
+            *nth += 1;
+            let params = match assume.params {
+                Ok(params) => params,
+                Err(reason) => {
+                    result.skipped.push(SkippedAssume { line: assume.line, reason });
+                    continue;
+                }
+            };
+            let Some((start, open, close)) = find_assume(content, assume.line, occurrence) else {
+                result.skipped.push(SkippedAssume { line: assume.line, reason: "cannot find assume( in the source".to_string() });
+                continue;
+            };
+
+            let counter = per_fn.entry(assume.fn_name.clone()).or_insert(0);
+            let lemma = loop {
+                *counter += 1;
+                let name = format!("lemma_{}_{}", assume.fn_name, counter);
+                if taken.insert(name.clone()) {
+                    break name;
+                }
+            };
+            let condition = &content[open + 1..close];
+            let args: Vec<&str> = params.iter().map(|(name, _)| name.as_str()).collect();
+            let call = format!("{}({});", lemma, args.join(", "));
+            let mut end = close + 1;
+            let replacement = if assume.in_proof {
+                call.trim_end_matches(';').to_string()
+            } else {
+                // The call replaces `assume(P);` whole, so the `;` goes inside the block
+                let rest = &content[end..];
+                if rest.trim_start().starts_with(';') {
+                    end += rest.len() - rest.trim_start().len() + 1;
+                }
+                format!("proof {{ {} }}", call)
+            };
+            edits.push((start, end, replacement));
+            lemmas.push_str(&lemma_text(&indent, &lemma, &assume.generics, &params, condition));
+            lemmas.push('\n');
+            result.lifted.push(LiftedAssume { line: assume.line, lemma });
+        }
+        if !lemmas.is_empty() {
+            let at = line_start(content, indent_line);
+            edits.push((at, at, lemmas));
+        }
+    }
+
+    for &line in lines {
+        if !found.contains(&line) {
+            result.skipped.push(SkippedAssume { line, reason: "no assume( inside a fn body here".to_string() });
+        }
+    }
+    result.lifted.sort_by_key(|l| l.line);
+    result.skipped.sort_by_key(|s| s.line);
+    result.skipped.dedup();
+
+    let mut out = content.to_string();
+    edits.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.cmp(&a.1)));
+    for (start, end, text) in edits {
+        out.replace_range(start..end, &text);
+    }
+    result.content = out;
+    result
+}
+
+/// One assume found in a fn body
+struct FoundAssume {
+    line: usize,
+    fn_name: String,
+    /// Already in ghost code (a proof fn or a proof block)
+    in_proof: bool,
+    /// Lemma generics, e.g. "<T: View>", or ""
+    generics: String,
+    /// (name, type) in order of first use in P, or why it cannot be lifted
+    params: Result<Vec<(String, String)>, String>,
+}
+
+/// The fn an assume is in
+struct FnScope {
+    name: String,
+    is_proof: bool,
+    /// Generic params of the impl, then the fn: (ident, text with bounds)
+    generics: Vec<(String, String)>,
+    where_predicates: Vec<String>,
+    /// Binding name -> type, None when not written down; later bindings shadow earlier ones
+    bindings: HashMap<String, Option<String>>,
+    mut_refs: HashSet<String>,
+}
+
+struct AssumeFinder<'w> {
+    line_offset: usize,
+    wanted: &'w HashSet<usize>,
+    impl_generics: Vec<(String, String)>,
+    impl_where: Vec<String>,
+    scope: Option<FnScope>,
+    proof_depth: usize,
+    assumes: Vec<FoundAssume>,
+}
+
+impl<'w> AssumeFinder<'w> {
+    fn new(line_offset: usize, wanted: &'w HashSet<usize>) -> Self {
+        AssumeFinder {
+            line_offset,
+            wanted,
+            impl_generics: Vec::new(),
+            impl_where: Vec::new(),
+            scope: None,
+            proof_depth: 0,
+            assumes: Vec::new(),
+        }
+    }
+
+    fn fn_scope(&self, sig: &verus_syn::Signature) -> FnScope {
+        use verus_syn::{FnArgKind, FnMode, Pat};
+        let mut generics = self.impl_generics.clone();
+        generics.extend(generic_params(&sig.generics));
+        let mut where_predicates = self.impl_where.clone();
+        where_predicates.extend(where_predicates_of(&sig.generics));
+        let mut scope = FnScope {
+            name: sig.ident.to_string(),
+            is_proof: matches!(sig.mode, FnMode::Proof(_) | FnMode::ProofAxiom(_)),
+            generics,
+            where_predicates,
+            bindings: HashMap::new(),
+            mut_refs: HashSet::new(),
+        };
+        for arg in &sig.inputs {
+            match &arg.kind {
+                FnArgKind::Receiver(_) => {
+                    scope.bindings.insert("self".to_string(), None);
+                }
+                FnArgKind::Typed(pt) => {
+                    let ty = tidy(&pt.ty.to_token_stream().to_string());
+                    if let Pat::Ident(id) = &*pt.pat {
+                        if ty.starts_with("&mut ") {
+                            scope.mut_refs.insert(id.ident.to_string());
+                        }
+                        scope.bindings.insert(id.ident.to_string(), Some(ty));
+                    } else {
+                        for name in pattern_names(&pt.pat) {
+                            scope.bindings.insert(name, None);
+                        }
+                    }
+                }
+            }
+        }
+        scope
+    }
+
+    /// Visit a fn body with the fn's scope, restoring the enclosing one after (fns can nest)
+    fn in_fn(&mut self, sig: &verus_syn::Signature, body: &verus_syn::Block) {
+        let scope = self.fn_scope(sig);
+        let prev_scope = self.scope.replace(scope);
+        let prev_depth = std::mem::replace(&mut self.proof_depth, 0);
+        self.visit_block(body);
+        self.scope = prev_scope;
+        self.proof_depth = prev_depth;
+    }
+
+    /// Run `f`, then drop the bindings it introduced (blocks, closures, match arms, loops)
+    fn scoped(&mut self, f: impl FnOnce(&mut Self)) {
+        let saved = self.scope.as_ref().map(|s| (s.bindings.clone(), s.mut_refs.clone()));
+        f(self);
+        if let (Some(scope), Some((bindings, mut_refs))) = (self.scope.as_mut(), saved) {
+            scope.bindings = bindings;
+            scope.mut_refs = mut_refs;
+        }
+    }
+}
+
+/// Lemma generics and parameters for `assume`, or why it cannot be lifted
+fn lift(assume: &verus_syn::Assume, scope: &FnScope) -> Result<(String, Vec<(String, String)>), String> {
+    let mut free = FreeVars::default();
+    free.visit_expr(&assume.expr);
+    if free.calls_old {
+        return Err("mentions old(..)".to_string());
+    }
+    let condition = assume.expr.to_token_stream().to_string();
+    if has_word(&condition, "Self") {
+        return Err("mentions Self".to_string());
+    }
+    let mut params = Vec::new();
+    for name in free.names {
+        let Some(ty) = scope.bindings.get(&name) else { continue };
+        if name == "self" {
+            return Err("mentions self".to_string());
+        }
+        if scope.mut_refs.contains(&name) {
+            return Err(format!("mentions &mut parameter `{}`", name));
+        }
+        let Some(ty) = ty else {
+            return Err(format!("the type of `{}` is not written down", name));
+        };
+        if has_word(ty, "Self") {
+            return Err(format!("the type of `{}` mentions Self", name));
+        }
+        params.push((name, ty.clone()));
+    }
+
+    // Keep the generics the parameter types use
+    let types: Vec<&str> = params.iter().map(|(_, ty)| ty.as_str()).collect();
+    let used: Vec<&(String, String)> = scope.generics.iter()
+        .filter(|(ident, _)| types.iter().any(|ty| has_word(ty, ident)))
+        .collect();
+    if used.is_empty() {
+        return Ok((String::new(), params));
+    }
+    let mut generics = format!("<{}>", used.iter().map(|(_, text)| text.as_str()).collect::<Vec<_>>().join(", "));
+    let predicates: Vec<&str> = scope.where_predicates.iter()
+        .filter(|p| used.iter().any(|(ident, _)| has_word(p, ident)))
+        .map(String::as_str)
+        .collect();
+    if !predicates.is_empty() {
+        generics = format!("{} where {}", generics, predicates.join(", "));
+    }
+    Ok((generics, params))
+}
+
+impl<'a> Visit<'a> for AssumeFinder<'_> {
+    fn visit_item_impl(&mut self, i: &'a verus_syn::ItemImpl) {
+        let prev_generics = std::mem::replace(&mut self.impl_generics, generic_params(&i.generics));
+        let prev_where = std::mem::replace(&mut self.impl_where, where_predicates_of(&i.generics));
+        visit::visit_item_impl(self, i);
+        self.impl_generics = prev_generics;
+        self.impl_where = prev_where;
+    }
+
+    fn visit_item_trait(&mut self, i: &'a verus_syn::ItemTrait) {
+        let prev_generics = std::mem::replace(&mut self.impl_generics, generic_params(&i.generics));
+        let prev_where = std::mem::replace(&mut self.impl_where, where_predicates_of(&i.generics));
+        visit::visit_item_trait(self, i);
+        self.impl_generics = prev_generics;
+        self.impl_where = prev_where;
+    }
+
+    fn visit_item_fn(&mut self, i: &'a verus_syn::ItemFn) {
+        self.in_fn(&i.sig, &i.block);
+    }
+
+    fn visit_impl_item_fn(&mut self, i: &'a verus_syn::ImplItemFn) {
+        self.in_fn(&i.sig, &i.block);
+    }
+
+    fn visit_trait_item_fn(&mut self, i: &'a verus_syn::TraitItemFn) {
+        if let Some(block) = &i.default {
+            self.in_fn(&i.sig, block);
+        }
+    }
+
+    fn visit_block(&mut self, i: &'a verus_syn::Block) {
+        self.scoped(|v| visit::visit_block(v, i));
+    }
+
+    fn visit_expr_closure(&mut self, i: &'a verus_syn::ExprClosure) {
+        self.scoped(|v| visit::visit_expr_closure(v, i));
+    }
+
+    fn visit_expr_for_loop(&mut self, i: &'a verus_syn::ExprForLoop) {
+        self.scoped(|v| visit::visit_expr_for_loop(v, i));
+    }
+
+    fn visit_arm(&mut self, i: &'a verus_syn::Arm) {
+        self.scoped(|v| visit::visit_arm(v, i));
+    }
+
+    fn visit_local(&mut self, i: &'a verus_syn::Local) {
+        use verus_syn::Pat;
+        if let Some(init) = &i.init {
+            self.visit_expr(&init.expr);
+            if let Some((_, diverge)) = &init.diverge {
+                self.visit_expr(diverge);
+            }
+        }
+        let Some(scope) = self.scope.as_mut() else { return };
+        match &i.pat {
+            Pat::Type(pt) => match &*pt.pat {
+                Pat::Ident(id) => {
+                    let ty = tidy(&pt.ty.to_token_stream().to_string());
+                    scope.mut_refs.remove(&id.ident.to_string());
+                    scope.bindings.insert(id.ident.to_string(), Some(ty));
+                }
+                other => bind_untyped(scope, other),
+            },
+            other => bind_untyped(scope, other),
+        }
+    }
+
+    fn visit_pat(&mut self, i: &'a verus_syn::Pat) {
+        // Loop, match and closure bindings: names with no written type
+        if let Some(scope) = self.scope.as_mut() {
+            bind_untyped(scope, i);
+        }
+    }
+
+    fn visit_expr_unary(&mut self, i: &'a verus_syn::ExprUnary) {
+        let is_proof = matches!(i.op, verus_syn::UnOp::Proof(_));
+        if is_proof {
+            self.proof_depth += 1;
+        }
+        visit::visit_expr_unary(self, i);
+        if is_proof {
+            self.proof_depth -= 1;
+        }
+    }
+
+    fn visit_assume(&mut self, i: &'a verus_syn::Assume) {
+        let line = i.assume_token.span().start().line.saturating_add(self.line_offset).max(1);
+        let Some(scope) = self.scope.as_ref() else { return };
+        if !self.wanted.contains(&line) {
+            return;
+        }
+        let (generics, params) = match lift(i, scope) {
+            Ok((generics, params)) => (generics, Ok(params)),
+            Err(reason) => (String::new(), Err(reason)),
+        };
+        self.assumes.push(FoundAssume {
+            line,
+            fn_name: scope.name.trim_start_matches("r#").to_string(),
+            in_proof: scope.is_proof || self.proof_depth > 0,
+            generics,
+            params,
+        });
+    }
+}
+
+/// Variables P mentions that it does not bind itself, in order of first use
+#[derive(Default)]
+struct FreeVars {
+    bound: Vec<String>,
+    names: Vec<String>,
+    calls_old: bool,
+}
+
+impl FreeVars {
+    fn mention(&mut self, name: String) {
+        if !self.bound.contains(&name) && !self.names.contains(&name) {
+            self.names.push(name);
+        }
+    }
+}
+
+impl<'a> Visit<'a> for FreeVars {
+    fn visit_expr_closure(&mut self, i: &'a verus_syn::ExprClosure) {
+        // forall|x: int| ... binds x
+        let depth = self.bound.len();
+        for input in &i.inputs {
+            self.bound.extend(pattern_names(&input.pat));
+        }
+        self.visit_expr(&i.body);
+        self.bound.truncate(depth);
+    }
+
+    fn visit_expr_path(&mut self, i: &'a verus_syn::ExprPath) {
+        if i.qself.is_none() && i.path.leading_colon.is_none() && i.path.segments.len() == 1 {
+            self.mention(i.path.segments[0].ident.to_string());
+        }
+        visit::visit_expr_path(self, i);
+    }
+
+    fn visit_expr_call(&mut self, i: &'a verus_syn::ExprCall) {
+        if let verus_syn::Expr::Path(p) = &*i.func {
+            if p.path.is_ident("old") {
+                self.calls_old = true;
+            }
+        }
+        visit::visit_expr_call(self, i);
+    }
+
+    fn visit_macro(&mut self, i: &'a verus_syn::Macro) {
+        // Macro arguments are not parsed; any identifier in them may be a variable
+        for token in i.tokens.clone() {
+            collect_idents(token, &mut |name| self.mention(name));
+        }
+    }
+}
+
+fn collect_idents(token: proc_macro2::TokenTree, f: &mut dyn FnMut(String)) {
+    match token {
+        proc_macro2::TokenTree::Ident(ident) => f(ident.to_string()),
+        proc_macro2::TokenTree::Group(group) => {
+            for t in group.stream() {
+                collect_idents(t, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Names bound by a pattern
+fn pattern_names(pat: &verus_syn::Pat) -> Vec<String> {
+    struct Names(Vec<String>);
+    impl<'a> Visit<'a> for Names {
+        fn visit_pat_ident(&mut self, i: &'a verus_syn::PatIdent) {
+            self.0.push(i.ident.to_string());
+            visit::visit_pat_ident(self, i);
+        }
+    }
+    let mut names = Names(Vec::new());
+    names.visit_pat(pat);
+    names.0
+}
+
+fn bind_untyped(scope: &mut FnScope, pat: &verus_syn::Pat) {
+    for name in pattern_names(pat) {
+        // `None`, `Ordering::Less` and friends are constants, not bindings
+        if !name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            scope.mut_refs.remove(&name);
+            scope.bindings.insert(name, None);
+        }
+    }
+}
+
+/// (ident, text with bounds) of each generic parameter
+fn generic_params(generics: &verus_syn::Generics) -> Vec<(String, String)> {
+    use verus_syn::GenericParam;
+    generics.params.iter()
+        .map(|p| {
+            let ident = match p {
+                GenericParam::Type(t) => t.ident.to_string(),
+                GenericParam::Lifetime(l) => l.lifetime.to_string(),
+                GenericParam::Const(c) => c.ident.to_string(),
+            };
+            (ident, tidy(&p.to_token_stream().to_string()))
+        })
+        .collect()
+}
+
+fn where_predicates_of(generics: &verus_syn::Generics) -> Vec<String> {
+    generics.where_clause.iter()
+        .flat_map(|w| w.predicates.iter())
+        .map(|p| tidy(&p.to_token_stream().to_string()))
+        .collect()
+}
+
+/// Token-stream text with the spaces rustfmt would not write removed
+pub(crate) fn tidy(tokens: &str) -> String {
+    let mut s = tokens.to_string();
+    for (from, to) in [(" :: ", "::"), (":: ", "::"), (" < ", "<"), ("< ", "<"), (" <", "<"), (" >", ">"),
+                       (" ,", ","), ("& ", "&"), ("( ", "("), (" )", ")"), ("[ ", "["), (" ]", "]"), (" ;", ";"),
+                       (" : ", ": ")] {
+        s = s.replace(from, to);
+    }
+    // `->` lost its spaces in " >"
+    s.replace("->", " -> ").replace("  ", " ")
+}
+
+/// `word` appears in `text` as a whole identifier
+fn has_word(text: &str, word: &str) -> bool {
+    text.match_indices(word).any(|(i, _)| {
+        let before = text[..i].chars().next_back();
+        let after = text[i + word.len()..].chars().next();
+        let is_ident = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+        !is_ident(before) && !is_ident(after)
+    })
+}
+
+fn lemma_text(indent: &str, name: &str, generics: &str, params: &[(String, String)], condition: &str) -> String {
+    let params: Vec<String> = params.iter().map(|(n, t)| format!("{}: {}", n, t)).collect();
+    format!(
+        "{i}proof fn {name}{generics}({params})\n{i}    ensures\n{i}        {cond},\n{i}{{\n{i}    admit();\n{i}}}\n",
+        i = indent,
+        name = name,
+        generics = generics,
+        params = params.join(", "),
+        cond = condition.trim(),
+    )
+}
+
+/// (start of `assume`, its `(`, matching `)`) for the `occurrence`-th assume on `line`
+fn find_assume(content: &str, line: usize, occurrence: usize) -> Option<(usize, usize, usize)> {
+    let start = line_start(content, line);
+    let text = line_text(content, line);
+    let mut seen = 0;
+    for (i, _) in text.match_indices("assume") {
+        let before = text[..i].chars().next_back();
+        if before.is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '.' || c == ':') {
+            continue;
+        }
+        let rest = &text[i + "assume".len()..];
+        if !rest.trim_start().starts_with('(') {
+            continue;
+        }
+        if seen < occurrence {
+            seen += 1;
+            continue;
+        }
+        let open = start + i + "assume".len() + (rest.len() - rest.trim_start().len());
+        let close = matching_paren(content, open)?;
+        return Some((start + i, open, close));
+    }
+    None
+}
+
+fn matching_paren(content: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in content[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Line that the lemmas go before: the end of the proof fn section, else the first
+/// trait, impl or exec fn, else the closing brace of the block
+fn insertion_line(parsed: &ParsedFile, block: &crate::driver::VerusBlock, file: &verus_syn::File) -> usize {
+    use verus_syn::{FnMode, Item};
+    let first_line = parsed.line_at(block.inner_start);
+    let last_line = parsed.line_at(block.inner_end);
+    let is_header = |line: &str| {
+        let t = line.trim_start();
+        t.starts_with("//\t") && t.trim_start_matches(['/', '\t']).starts_with(|c: char| c.is_ascii_digit())
+    };
+    let mut in_proof_section = false;
+    for line in first_line..=last_line {
+        let text = line_text(&parsed.content, line);
+        if in_proof_section && is_header(text) {
+            return line;
+        }
+        if is_header(text) && text.contains("proof fns") {
+            in_proof_section = true;
+        }
+    }
+
+    let mut items: BTreeMap<usize, bool> = BTreeMap::new();
+    for item in &file.items {
+        let after_proof_fns = match item {
+            Item::Fn(f) => matches!(f.sig.mode, FnMode::Exec(_) | FnMode::Default),
+            Item::Impl(_) | Item::Trait(_) => true,
+            _ => false,
+        };
+        let line = item.span().start().line.saturating_add(block.line_offset).max(1);
+        items.insert(line, after_proof_fns);
+    }
+    items.into_iter()
+        .find(|(_, after)| *after)
+        .map(|(line, _)| line)
+        .unwrap_or(last_line)
+}
+
+/// Names of fns already in the file, so generated lemmas do not clash
+fn existing_fn_names(content: &str) -> HashSet<String> {
+    let mut names = HashSet::new();
+    for (i, _) in content.match_indices("fn ") {
+        if i > 0 && content[..i].chars().next_back().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            continue;
+        }
+        let name: String = content[i + 3..].trim_start()
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        if !name.is_empty() {
+            names.insert(name);
+        }
+    }
+    names
+}
+
+/// Byte offset of the start of 1-based `line`
+fn line_start(content: &str, line: usize) -> usize {
+    if line <= 1 {
+        return 0;
+    }
+    content.match_indices('\n').nth(line - 2).map(|(i, _)| i + 1).unwrap_or(content.len())
+}
+
+fn line_text(content: &str, line: usize) -> &str {
+    let start = line_start(content, line);
+    let end = content[start..].find('\n').map(|i| start + i).unwrap_or(content.len());
+    &content[start..end]
+}
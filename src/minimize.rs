@@ -0,0 +1,311 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Group testing and resume checks for veracity-minimize-lib
+//!
+//! [`partition_search`] finds which removal candidates a codebase needs with as
+//! few Verus runs as it can. It edits the tree through a [`RemovalSet`] and runs
+//! Verus, checkpoints and logs through a [`SearchEnv`], so it can be driven
+//! without either. [`merge_verdicts`] brings verdicts found in scratch copies
+//! into the real tree the same way.
+//!
+//! A saved run records the tree it left behind in a [`ResumeGuard`]: the command
+//! line, the hash of every source file and any edit still in flight. A resume
+//! is refused if any of them no longer match.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use walkdir::WalkDir;
+
+/// One phase's removal candidates, as group testing sees them. Candidates are
+/// commented out in batches; the pending batch is then restored or kept.
+pub trait RemovalSet {
+    /// Shown in progress lines
+    fn label(&self, i: usize) -> &str;
+    /// Files that commenting out candidates `idx` edits
+    fn files(&self, idx: &[usize]) -> Vec<&Path>;
+    /// Comment out candidates `idx` with TESTING markers
+    fn comment_out(&mut self, idx: &[usize]) -> Result<()>;
+    /// Put back the pending batch
+    fn restore(&mut self) -> Result<()>;
+    /// Make the pending batch's removals permanent
+    fn keep(&mut self) -> Result<()>;
+    /// Record that candidate `i` is needed
+    fn mark_needed(&mut self, _i: usize) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// What a search runs against: Verus, the run's checkpoint and its log
+pub trait SearchEnv {
+    /// Verify the tree as it stands: (verified, verify_time)
+    fn verify(&mut self) -> Result<(bool, Duration)>;
+    /// A test is about to edit `files`
+    fn begin_edit(&mut self, _label: &str, _files: &[&Path]) -> Result<()> {
+        Ok(())
+    }
+    /// Candidate `i` is settled as (needed, verify_time)
+    fn settle(&mut self, _i: usize, _needed: bool, _verify_time: Duration) {}
+    /// The edit begun last has settled
+    fn end_edit(&mut self) -> Result<()> {
+        Ok(())
+    }
+    /// Candidate `i`'s verdict, found in a scratch copy, is now in the real tree
+    fn merged(&mut self, _i: usize) {}
+    /// A progress line, or the end of the one `log_start` began
+    fn log(&mut self, _line: &str) {}
+    /// Start a progress line
+    fn log_start(&mut self, _text: &str) {}
+}
+
+/// Binary-partition group testing: remove all of `items` in one Verus run; if
+/// that fails, split them in half and search each half, the second on top of
+/// whatever the first removed. When the first half goes entirely, the second
+/// is the failing set again, so it is split without being re-run.
+///
+/// Fills `verdicts` with (needed, verify_time); a batch's time saved over
+/// `baseline_time` is shared among its members. Returns whether every item was removed.
+pub fn partition_search(
+    set: &mut dyn RemovalSet,
+    env: &mut dyn SearchEnv,
+    items: &[usize],
+    known_failing: bool,
+    baseline_time: Duration,
+    verdicts: &mut [(bool, Duration)],
+) -> Result<bool> {
+    if items.is_empty() {
+        return Ok(true);
+    }
+    if !known_failing {
+        if items.len() == 1 {
+            env.log_start(&format!("  removing {}... ", set.label(items[0])));
+        } else {
+            env.log_start(&format!("  removing {} ({} .. {})... ", items.len(),
+                set.label(items[0]), set.label(items[items.len() - 1])));
+        }
+        env.begin_edit(&format!("a batch of {} starting at {}", items.len(), set.label(items[0])), &set.files(items))?;
+        set.comment_out(items)?;
+        let (success, verify_time) = env.verify()?;
+        if success {
+            set.keep()?;
+            env.log(&format!("PASSED → {} UNNEEDED (commented) [{}]", items.len(), format_duration(verify_time)));
+            let share = baseline_time.saturating_sub(verify_time) / items.len() as u32;
+            for &i in items {
+                verdicts[i] = (false, baseline_time - share);
+                env.settle(i, false, baseline_time - share);
+            }
+            env.end_edit()?;
+            return Ok(true);
+        }
+        set.restore()?;
+        env.end_edit()?;
+        if items.len() == 1 {
+            env.log(&format!("FAILED → NEEDED (restored) [{}]", format_duration(verify_time)));
+        } else {
+            env.log(&format!("FAILED → splitting [{}]", format_duration(verify_time)));
+        }
+    }
+    if items.len() == 1 {
+        if known_failing {
+            env.log(&format!("  {}... NEEDED (implied by its group)", set.label(items[0])));
+        }
+        env.begin_edit(set.label(items[0]), &set.files(items))?;
+        set.mark_needed(items[0])?;
+        verdicts[items[0]] = (true, Duration::ZERO);
+        env.settle(items[0], true, Duration::ZERO);
+        env.end_edit()?;
+        return Ok(false);
+    }
+    let (first, second) = items.split_at(items.len() / 2);
+    let first_removed = partition_search(set, env, first, false, baseline_time, verdicts)?;
+    partition_search(set, env, second, first_removed, baseline_time, verdicts)?;
+    Ok(false)
+}
+
+/// Group-test every candidate of `set` not already `settled` by a resumed run;
+/// returns (needed, verify_time) per candidate
+pub fn group_test(
+    set: &mut dyn RemovalSet,
+    env: &mut dyn SearchEnv,
+    settled: &[Option<(bool, Duration)>],
+    baseline_time: Duration,
+) -> Result<Vec<(bool, Duration)>> {
+    let items: Vec<usize> = (0..settled.len()).filter(|&i| settled[i].is_none()).collect();
+    env.log(&format!("Group testing {} candidates (ddmin)...", items.len()));
+    env.log("");
+    let mut verdicts: Vec<(bool, Duration)> = settled.iter().map(|v| v.unwrap_or((true, Duration::ZERO))).collect();
+    partition_search(set, env, &items, false, baseline_time, &mut verdicts)?;
+    Ok(verdicts)
+}
+
+/// Merge verdicts found in scratch copies into the real tree: comment out every
+/// candidate of `merging` that is not needed, then verify once.
+///
+/// Candidates tested alone in the copies may still fail together. If the merged
+/// tree fails, its removals are rolled back and each is re-tested on its own in
+/// the real tree, updating `verdicts`. Returns whether the merge verified as is.
+pub fn merge_verdicts(
+    set: &mut dyn RemovalSet,
+    env: &mut dyn SearchEnv,
+    merging: &[usize],
+    verdicts: &mut [(bool, Duration)],
+) -> Result<bool> {
+    env.begin_edit(&format!("a merge of {} candidates", merging.len()), &set.files(merging))?;
+    let mut removed = Vec::new();
+    for &i in merging {
+        if verdicts[i].0 {
+            set.mark_needed(i)?;
+        } else {
+            set.comment_out(&[i])?;
+            removed.push(i);
+        }
+    }
+    let confirmed = removed.is_empty() || {
+        env.log("");
+        env.log_start(&format!("Merged {} removals into the codebase; verifying... ", removed.len()));
+        let (success, verify_time) = env.verify()?;
+        if success {
+            env.log(&format!("PASSED [{}]", format_duration(verify_time)));
+        } else {
+            env.log(&format!("FAILED [{}]", format_duration(verify_time)));
+            env.log("Removals that verify alone fail together; re-testing them one at a time.");
+        }
+        env.log("");
+        success
+    };
+    if confirmed {
+        set.keep()?;
+        for &i in merging {
+            env.merged(i);
+        }
+        env.end_edit()?;
+        return Ok(true);
+    }
+    set.restore()?;
+    for &i in merging.iter().filter(|&&i| verdicts[i].0) {
+        env.merged(i);
+    }
+    env.end_edit()?;
+    for (k, &i) in removed.iter().enumerate() {
+        env.log_start(&format!("[{}/{}] {}... ", k + 1, removed.len(), set.label(i)));
+        env.begin_edit(set.label(i), &set.files(&[i]))?;
+        set.comment_out(&[i])?;
+        let (success, verify_time) = env.verify()?;
+        if success {
+            set.keep()?;
+            env.log(&format!("UNNEEDED (commented) [{}]", format_duration(verify_time)));
+        } else {
+            set.restore()?;
+            set.mark_needed(i)?;
+            env.log(&format!("NEEDED (restored) [{}]", format_duration(verify_time)));
+        }
+        verdicts[i] = (!success, verify_time);
+        env.settle(i, !success, verify_time);
+        env.merged(i);
+        env.end_edit()?;
+    }
+    Ok(false)
+}
+
+pub fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs < 60 {
+        format!("{:.1}s", d.as_secs_f64())
+    } else if secs < 3600 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h {}m {}s", secs / 3600, (secs % 3600) / 60, secs % 60)
+    }
+}
+
+/// Files being edited by a test that has not settled, with their content from before it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InFlightEdit {
+    /// What was being tested, e.g. "necessity of lemma_foo"
+    pub label: String,
+    pub originals: Vec<(PathBuf, String)>,
+}
+
+impl InFlightEdit {
+    /// Write back every file's content from before the edit
+    pub fn restore(&self) -> Result<()> {
+        for (file, original) in &self.originals {
+            std::fs::write(file, original)
+                .with_context(|| format!("Failed to restore {}", file.display()))?;
+        }
+        Ok(())
+    }
+}
+
+/// What a saved run must still match to be resumed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResumeGuard {
+    /// Command line, less --resume/--recover/--danger; a resume must repeat it
+    pub args: Vec<String>,
+    /// SHA-256 of every source file as of the last settled test
+    pub hashes: BTreeMap<PathBuf, String>,
+    pub in_flight: Option<InFlightEdit>,
+}
+
+impl ResumeGuard {
+    /// Hash the source files under `roots`, unless an edit is in flight and
+    /// the tree is between states
+    pub fn record(&mut self, roots: &[PathBuf]) -> Result<()> {
+        if self.in_flight.is_none() {
+            self.hashes = source_hashes(roots)?;
+        }
+        Ok(())
+    }
+
+    /// Why the run cannot be resumed with command line `args` over `roots`:
+    /// different options, an unrecovered edit, or source files that changed
+    /// after the last settled test. Empty if it can.
+    pub fn problems(&self, args: &[String], roots: &[PathBuf]) -> Result<Vec<String>> {
+        let mut problems = Vec::new();
+        if self.args != args {
+            problems.push(format!("The saved run used different options: {}", self.args.join(" ")));
+        }
+        if let Some(edit) = &self.in_flight {
+            problems.push(format!("The run stopped while testing {}; run with --recover first", edit.label));
+            return Ok(problems);
+        }
+        let now = source_hashes(roots)?;
+        for (file, hash) in &self.hashes {
+            match now.get(file) {
+                Some(h) if h == hash => {}
+                Some(_) => problems.push(format!("Changed since the run stopped: {}", file.display())),
+                None => problems.push(format!("Removed since the run stopped: {}", file.display())),
+            }
+        }
+        for file in now.keys().filter(|f| !self.hashes.contains_key(*f)) {
+            problems.push(format!("Added since the run stopped: {}", file.display()));
+        }
+        Ok(problems)
+    }
+}
+
+/// SHA-256 of every .rs file under `roots`, outside attic/ and target/
+fn source_hashes(roots: &[PathBuf]) -> Result<BTreeMap<PathBuf, String>> {
+    let mut hashes = BTreeMap::new();
+    for root in roots {
+        let files = WalkDir::new(root).into_iter()
+            .filter_map(|e| e.ok())
+            .map(|e| e.into_path())
+            .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext == "rs"))
+            .filter(|p| {
+                let s = p.to_string_lossy();
+                !s.contains("/attic/") && !s.contains("/target/")
+            });
+        for file in files {
+            if let std::collections::btree_map::Entry::Vacant(e) = hashes.entry(file.canonicalize().unwrap_or(file)) {
+                let digest = format!("{:x}", Sha256::digest(std::fs::read(e.key())?));
+                e.insert(digest);
+            }
+        }
+    }
+    Ok(hashes)
+}
@@ -0,0 +1,261 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Module dependency graph
+//!
+//! One node per source file, named by module path (`Chap05::SetStEph` for
+//! `src/Chap05/SetStEph.rs`). An edge `a -> b` means `a` has a `use crate::`
+//! path naming `b`, or a directory module that contains `b`.
+//!
+//! Modules on a dependency cycle depend on each other, so none of them can be
+//! cleaned first. Dependencies inside a module's own cycle therefore do not
+//! count against it when deciding whether it depends only on clean modules.
+
+use crate::grouping::module_segments;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+
+/// A source file in the graph
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ModuleNode {
+    pub module: String,
+    /// Path relative to the analyzed directory
+    pub path: String,
+    /// Holes plus missing-spec errors
+    pub holes: usize,
+    /// Has holes or error-level findings
+    pub holed: bool,
+    pub verusified: bool,
+    /// Every module it depends on outside its own cycle is clean (set by `ModuleGraph::new`)
+    pub clean_deps: bool,
+    /// Has holes and only clean dependencies: a module to work on next (set by `ModuleGraph::new`)
+    pub frontier: bool,
+    /// Index into `ModuleGraph::cycles` if the module is on a cycle (set by `ModuleGraph::new`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cycle: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModuleEdge {
+    pub from: String,
+    pub to: String,
+}
+
+/// Modules, their `use crate::` dependencies, and the cycles among them
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModuleGraph {
+    /// Sorted by module
+    pub nodes: Vec<ModuleNode>,
+    pub edges: Vec<ModuleEdge>,
+    /// Modules on each cycle, sorted; cycles sorted by their first module
+    pub cycles: Vec<Vec<String>>,
+}
+
+impl ModuleGraph {
+    /// Graph of `files`: each node with the `use crate::` module paths of its file
+    /// (without `crate::`, as in `FileHoles::crate_deps`).
+    pub fn new(mut files: Vec<(ModuleNode, Vec<String>)>) -> Self {
+        files.sort_by(|a, b| a.0.module.cmp(&b.0.module).then_with(|| a.0.path.cmp(&b.0.path)));
+        let mut nodes: Vec<ModuleNode> = Vec::with_capacity(files.len());
+        let mut uses: Vec<Vec<String>> = Vec::with_capacity(files.len());
+        for (node, node_uses) in files {
+            nodes.push(node);
+            uses.push(node_uses);
+        }
+
+        let mut targets: Vec<Vec<usize>> = Vec::with_capacity(nodes.len());
+        for (from, node_uses) in uses.iter().enumerate() {
+            let mut to: Vec<usize> = node_uses.iter()
+                .flat_map(|u| resolve(&nodes, u))
+                .filter(|&t| t != from)
+                .collect();
+            to.sort_unstable();
+            to.dedup();
+            targets.push(to);
+        }
+
+        let component = strongly_connected_components(&targets);
+        let mut members: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+        for (i, &c) in component.iter().enumerate() {
+            members[c].push(i);
+        }
+        // Members are in index order, and nodes are sorted by module, so cycles come out sorted
+        let mut cycles: Vec<Vec<usize>> = members.into_iter().filter(|m| m.len() > 1).collect();
+        cycles.sort();
+        for (n, cycle) in cycles.iter().enumerate() {
+            for &i in cycle {
+                nodes[i].cycle = Some(n);
+            }
+        }
+
+        for (i, to) in targets.iter().enumerate() {
+            let clean_deps = to.iter().all(|&t| !nodes[t].holed || component[t] == component[i]);
+            nodes[i].clean_deps = clean_deps;
+            nodes[i].frontier = clean_deps && nodes[i].holes > 0;
+        }
+
+        let edges = targets.iter().enumerate()
+            .flat_map(|(from, to)| to.iter().map(move |&t| (from, t)))
+            .map(|(from, to)| ModuleEdge { from: nodes[from].module.clone(), to: nodes[to].module.clone() })
+            .collect();
+        let cycles = cycles.into_iter()
+            .map(|c| c.into_iter().map(|i| nodes[i].module.clone()).collect())
+            .collect();
+        ModuleGraph { nodes, edges, cycles }
+    }
+
+    /// GraphViz DOT: nodes filled by hole count (gray when not verusified, green when clean),
+    /// frontier modules outlined in bold, edges on a cycle drawn in red.
+    pub fn to_dot(&self) -> String {
+        let cycle_of: HashMap<&str, usize> = self.nodes.iter()
+            .filter_map(|n| n.cycle.map(|c| (n.module.as_str(), c)))
+            .collect();
+        let mut dot = String::new();
+        dot.push_str("digraph modules {\n");
+        dot.push_str("    rankdir=LR;\n");
+        dot.push_str("    node [shape=box, style=filled, fontname=\"Helvetica\"];\n");
+        for node in &self.nodes {
+            let mut label = node.module.clone();
+            if !node.verusified {
+                label.push_str("\\nnot verusified");
+            } else if node.holes > 0 {
+                let _ = write!(label, "\\n{} hole{}", node.holes, if node.holes == 1 { "" } else { "s" });
+            }
+            if node.frontier {
+                label.push_str("\\nfrontier");
+            }
+            let _ = write!(dot, "    \"{}\" [label=\"{}\", fillcolor=\"{}\"", escape(&node.module), escape_label(&label), fill_color(node));
+            if node.frontier {
+                dot.push_str(", penwidth=3, color=\"#1d3557\"");
+            }
+            dot.push_str("];\n");
+        }
+        for edge in &self.edges {
+            let _ = write!(dot, "    \"{}\" -> \"{}\"", escape(&edge.from), escape(&edge.to));
+            let from_cycle = cycle_of.get(edge.from.as_str());
+            if from_cycle.is_some() && from_cycle == cycle_of.get(edge.to.as_str()) {
+                dot.push_str(" [color=\"#d62828\", penwidth=2]");
+            }
+            dot.push_str(";\n");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// `modules` and every module that depends on one of them, directly or
+    /// through others: what must be verified again after they change. Sorted.
+    pub fn dependents(&self, modules: &[&str]) -> Vec<String> {
+        let mut affected: BTreeSet<&str> = modules.iter().copied().collect();
+        let mut work: Vec<&str> = affected.iter().copied().collect();
+        while let Some(module) = work.pop() {
+            for edge in self.edges.iter().filter(|e| e.to == module) {
+                if affected.insert(edge.from.as_str()) {
+                    work.push(edge.from.as_str());
+                }
+            }
+        }
+        affected.into_iter().map(String::from).collect()
+    }
+}
+
+/// Module path of a source file from below its nearest `src/`, as Verus names it:
+/// src/Chap05/SetStEph.rs -> Chap05::SetStEph, src/Chap05/mod.rs -> Chap05, and
+/// the crate root (src/lib.rs, src/main.rs) -> "".
+pub fn module_path(rel_path: &str) -> String {
+    module_segments(rel_path).join("::")
+}
+
+/// Indices of the nodes a `use crate::` path names: the module itself, or every module under it
+fn resolve(nodes: &[ModuleNode], path: &str) -> Vec<usize> {
+    let prefix = format!("{}::", path);
+    let exact = nodes.partition_point(|n| n.module.as_str() < path);
+    let under = nodes.partition_point(|n| n.module.as_str() < prefix.as_str());
+    let mut found: Vec<usize> = (exact..nodes.len()).take_while(|&i| nodes[i].module == path).collect();
+    found.extend((under..nodes.len()).take_while(|&i| nodes[i].module.starts_with(&prefix)));
+    found
+}
+
+/// Component id per node (Tarjan); nodes share an id exactly when they are on a cycle together
+fn strongly_connected_components(targets: &[Vec<usize>]) -> Vec<usize> {
+    struct Tarjan<'a> {
+        targets: &'a [Vec<usize>],
+        index: Vec<Option<usize>>,
+        low: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        next_index: usize,
+        component: Vec<usize>,
+        components: usize,
+    }
+    impl Tarjan<'_> {
+        fn visit(&mut self, v: usize) {
+            self.index[v] = Some(self.next_index);
+            self.low[v] = self.next_index;
+            self.next_index += 1;
+            self.stack.push(v);
+            self.on_stack[v] = true;
+            for &w in &self.targets[v] {
+                match self.index[w] {
+                    None => {
+                        self.visit(w);
+                        self.low[v] = self.low[v].min(self.low[w]);
+                    }
+                    Some(w_index) if self.on_stack[w] => self.low[v] = self.low[v].min(w_index),
+                    Some(_) => {}
+                }
+            }
+            if Some(self.low[v]) == self.index[v] {
+                while let Some(w) = self.stack.pop() {
+                    self.on_stack[w] = false;
+                    self.component[w] = self.components;
+                    if w == v {
+                        break;
+                    }
+                }
+                self.components += 1;
+            }
+        }
+    }
+
+    let n = targets.len();
+    let mut tarjan = Tarjan {
+        targets,
+        index: vec![None; n],
+        low: vec![0; n],
+        on_stack: vec![false; n],
+        stack: Vec::new(),
+        next_index: 0,
+        component: vec![0; n],
+        components: 0,
+    };
+    for v in 0..n {
+        if tarjan.index[v].is_none() {
+            tarjan.visit(v);
+        }
+    }
+    tarjan.component
+}
+
+fn fill_color(node: &ModuleNode) -> &'static str {
+    if !node.verusified {
+        "#d9d9d9"
+    } else if !node.holed {
+        "#b7e4c7"
+    } else if node.holes < 5 {
+        "#ffe08a"
+    } else if node.holes < 20 {
+        "#f4a261"
+    } else {
+        "#e76f51"
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Like `escape`, but keeps the `\n` line breaks of a label
+fn escape_label(s: &str) -> String {
+    s.replace('"', "\\\"")
+}
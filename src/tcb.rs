@@ -0,0 +1,778 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Trusted computing base
+//!
+//! The code a Verus proof takes on trust instead of checking:
+//!
+//! - the body of every `#[verifier::external_body]` fn;
+//! - every `assume_specification` and `#[verifier::external_fn_specification]` fn;
+//! - every `#[verifier::external_type_specification]` type;
+//! - every axiom fn, counted apart from `broadcast` axioms (which include
+//!   `external_body` broadcast proof fns, the way vstd writes them);
+//! - every `accept(..)` site.
+//!
+//! [`trusted_items`] lists them for one file with their size in lines and tokens
+//! and the spec text each one trusts. Sizes are what Verus does not check: the body
+//! of an external_body fn, the whole item otherwise, the call for an accept.
+//! Tokens are proc-macro2 tokens, counting a delimiter pair as two.
+//!
+//! [`compute_trust`] follows calls across the crate to find each function's
+//! transitive trusted base: the holes its proof rests on, its own or its callees'.
+//! [`build_attestation`] records what a verified tree trusts, with the hash of every
+//! file and the tool versions, so `--check` can tell when any of them has changed.
+
+use crate::accepted::{check_accept_site, is_accept_site, AcceptSite, Justification};
+use crate::config::JustificationPolicy;
+use crate::driver::ParsedFile;
+use crate::holes::baseline::hole_item_name;
+use crate::holes::collect::git;
+use crate::holes::summary::path_str_to_module;
+use crate::holes::{base_type_name, DetectedHole, FileHoles, FnNode, HoleKind};
+use crate::lift::tidy;
+use anyhow::{Context, Result};
+use chrono::Local;
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::ToTokens;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use verus_syn::spanned::Spanned;
+use verus_syn::visit::{self, Visit};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrustKind {
+    ExternalBody,
+    AssumeSpecification,
+    ExternalFnSpecification,
+    ExternalTypeSpecification,
+    Axiom,
+    BroadcastAxiom,
+    Accept,
+}
+
+impl TrustKind {
+    pub const ALL: &'static [TrustKind] = &[
+        TrustKind::ExternalBody,
+        TrustKind::AssumeSpecification,
+        TrustKind::ExternalFnSpecification,
+        TrustKind::ExternalTypeSpecification,
+        TrustKind::Axiom,
+        TrustKind::BroadcastAxiom,
+        TrustKind::Accept,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TrustKind::ExternalBody => "external_body",
+            TrustKind::AssumeSpecification => "assume_specification",
+            TrustKind::ExternalFnSpecification => "external_fn_specification",
+            TrustKind::ExternalTypeSpecification => "external_type_specification",
+            TrustKind::Axiom => "axiom",
+            TrustKind::BroadcastAxiom => "broadcast_axiom",
+            TrustKind::Accept => "accept",
+        }
+    }
+}
+
+impl fmt::Display for TrustKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One trusted item
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TrustedItem {
+    pub kind: TrustKind,
+    /// `name` or `Type::name`; the wrapped path for assume_specification; the enclosing fn for accept
+    pub name: String,
+    /// Line of the item's name, or of the accept call
+    pub line: usize,
+    /// Lines of trusted code
+    pub lines: usize,
+    /// Tokens of trusted code
+    pub tokens: usize,
+    /// What it trusts: requires/ensures, the type definition, or the accepted condition
+    pub spec: String,
+}
+
+/// Item, line and token counts summed over trusted items
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TcbTotals {
+    pub items: usize,
+    pub lines: usize,
+    pub tokens: usize,
+}
+
+impl TcbTotals {
+    pub fn add(&mut self, item: &TrustedItem) {
+        self.items += 1;
+        self.lines += item.lines;
+        self.tokens += item.tokens;
+    }
+
+    pub fn merge(&mut self, other: &TcbTotals) {
+        self.items += other.items;
+        self.lines += other.lines;
+        self.tokens += other.tokens;
+    }
+}
+
+/// Trusted items in the `verus!` blocks of `content`, in source order.
+/// Blocks verus_syn cannot parse are skipped.
+pub fn trusted_items(content: &str) -> Vec<TrustedItem> {
+    let parsed = ParsedFile::parse(Path::new(""), content.to_string());
+    let mut items = Vec::new();
+    for block in &parsed.verus_blocks {
+        let Some(file) = parsed.verus_syn_file(block) else { continue };
+        let mut finder = TcbFinder { line_offset: block.line_offset, self_type: None, fn_name: None, items: Vec::new() };
+        finder.visit_file(file);
+        items.extend(finder.items);
+    }
+    items.sort_by_key(|item| item.line);
+    items
+}
+
+struct TcbFinder {
+    line_offset: usize,
+    /// Base name of the impl self type or trait we're inside
+    self_type: Option<String>,
+    /// Qualified name of the fn we're inside, for accept sites
+    fn_name: Option<String>,
+    items: Vec<TrustedItem>,
+}
+
+impl TcbFinder {
+    fn line(&self, span: proc_macro2::Span) -> usize {
+        span.start().line.saturating_add(self.line_offset).max(1)
+    }
+
+    fn lines(span: proc_macro2::Span) -> usize {
+        span.end().line.saturating_sub(span.start().line) + 1
+    }
+
+    fn qualified(&self, name: &str) -> String {
+        match &self.self_type {
+            Some(ty) => format!("{}::{}", ty, name),
+            None => name.to_string(),
+        }
+    }
+
+    fn push(&mut self, kind: TrustKind, name: String, line: usize, code: &dyn ToTokens, spec: String) {
+        let tokens = code.to_token_stream();
+        self.items.push(TrustedItem {
+            kind,
+            name,
+            line,
+            lines: Self::lines(code.span()),
+            tokens: count_tokens(tokens),
+            spec,
+        });
+    }
+
+    /// Record a fn if it is trusted; `item` is the whole fn, `body` its block if it has one
+    fn check_fn(&mut self, attrs: &[verus_syn::Attribute], sig: &verus_syn::Signature, item: &dyn ToTokens, body: Option<&verus_syn::Block>) {
+        use verus_syn::FnMode;
+        let has = |name: &str| attrs.iter().any(|a| verifier_attr(a).as_deref() == Some(name));
+        let is_axiom = matches!(sig.mode, FnMode::ProofAxiom(_)) || has("axiom");
+        let external_body = has("external_body");
+        let kind = if sig.broadcast.is_some() && (is_axiom || (external_body && matches!(sig.mode, FnMode::Proof(_)))) {
+            TrustKind::BroadcastAxiom
+        } else if is_axiom {
+            TrustKind::Axiom
+        } else if has("external_fn_specification") {
+            TrustKind::ExternalFnSpecification
+        } else if external_body {
+            TrustKind::ExternalBody
+        } else {
+            return;
+        };
+        let name = self.qualified(&sig.ident.to_string());
+        let line = self.line(sig.ident.span());
+        let spec = spec_text(sig.spec.requires.as_ref(), sig.spec.ensures.as_ref());
+        match (kind, body) {
+            // Only the body of an external_body fn goes unchecked; its signature is still type checked
+            (TrustKind::ExternalBody, Some(body)) => self.push(kind, name, line, body, spec),
+            _ => self.push(kind, name, line, item, spec),
+        }
+    }
+}
+
+impl<'a> Visit<'a> for TcbFinder {
+    fn visit_item_impl(&mut self, i: &'a verus_syn::ItemImpl) {
+        let prev = self.self_type.replace(base_type_name(&i.self_ty.to_token_stream().to_string()));
+        visit::visit_item_impl(self, i);
+        self.self_type = prev;
+    }
+
+    fn visit_item_trait(&mut self, i: &'a verus_syn::ItemTrait) {
+        let prev = self.self_type.replace(i.ident.to_string());
+        visit::visit_item_trait(self, i);
+        self.self_type = prev;
+    }
+
+    fn visit_item_fn(&mut self, i: &'a verus_syn::ItemFn) {
+        let prev_type = self.self_type.take();
+        self.check_fn(&i.attrs, &i.sig, i, Some(&i.block));
+        let prev_fn = self.fn_name.replace(i.sig.ident.to_string());
+        visit::visit_item_fn(self, i);
+        self.fn_name = prev_fn;
+        self.self_type = prev_type;
+    }
+
+    fn visit_impl_item_fn(&mut self, i: &'a verus_syn::ImplItemFn) {
+        self.check_fn(&i.attrs, &i.sig, i, Some(&i.block));
+        let prev_fn = self.fn_name.replace(self.qualified(&i.sig.ident.to_string()));
+        visit::visit_impl_item_fn(self, i);
+        self.fn_name = prev_fn;
+    }
+
+    fn visit_trait_item_fn(&mut self, i: &'a verus_syn::TraitItemFn) {
+        self.check_fn(&i.attrs, &i.sig, i, i.default.as_ref());
+        let prev_fn = self.fn_name.replace(self.qualified(&i.sig.ident.to_string()));
+        visit::visit_trait_item_fn(self, i);
+        self.fn_name = prev_fn;
+    }
+
+    fn visit_item_struct(&mut self, i: &'a verus_syn::ItemStruct) {
+        if i.attrs.iter().any(|a| verifier_attr(a).as_deref() == Some("external_type_specification")) {
+            let line = self.line(i.ident.span());
+            let mut def = i.clone();
+            def.attrs.clear();
+            self.push(TrustKind::ExternalTypeSpecification, i.ident.to_string(), line, i, tidy(&def.to_token_stream().to_string()));
+        }
+        visit::visit_item_struct(self, i);
+    }
+
+    fn visit_item_enum(&mut self, i: &'a verus_syn::ItemEnum) {
+        if i.attrs.iter().any(|a| verifier_attr(a).as_deref() == Some("external_type_specification")) {
+            let line = self.line(i.ident.span());
+            let mut def = i.clone();
+            def.attrs.clear();
+            self.push(TrustKind::ExternalTypeSpecification, i.ident.to_string(), line, i, tidy(&def.to_token_stream().to_string()));
+        }
+        visit::visit_item_enum(self, i);
+    }
+
+    fn visit_assume_specification(&mut self, i: &'a verus_syn::AssumeSpecification) {
+        let line = self.line(i.assume_specification.span());
+        let name = tidy(&i.path.to_token_stream().to_string());
+        let spec = spec_text(i.requires.as_ref(), i.ensures.as_ref());
+        self.push(TrustKind::AssumeSpecification, name, line, i, spec);
+        visit::visit_assume_specification(self, i);
+    }
+
+    fn visit_expr_call(&mut self, i: &'a verus_syn::ExprCall) {
+        if let verus_syn::Expr::Path(path) = &*i.func {
+            if path.path.segments.last().is_some_and(|seg| seg.ident == "accept") {
+                let line = self.line(i.span());
+                let name = self.fn_name.clone().unwrap_or_default();
+                let spec = expr_text(&i.args.to_token_stream().to_string());
+                self.push(TrustKind::Accept, name, line, i, spec);
+            }
+        }
+        visit::visit_expr_call(self, i);
+    }
+}
+
+/// `external_body` for `#[verifier::external_body]`
+fn verifier_attr(attr: &verus_syn::Attribute) -> Option<String> {
+    let segments: Vec<String> = attr.path().segments.iter().map(|s| s.ident.to_string()).collect();
+    match segments.as_slice() {
+        [verifier, name, ..] if verifier == "verifier" => Some(name.clone()),
+        _ => None,
+    }
+}
+
+/// "requires a, ensures b" from the clauses present
+fn spec_text(requires: Option<&verus_syn::Requires>, ensures: Option<&verus_syn::Ensures>) -> String {
+    let clauses: Vec<String> = [requires.map(|r| r.to_token_stream()), ensures.map(|e| e.to_token_stream())]
+        .into_iter()
+        .flatten()
+        .map(|tokens| expr_text(&tokens.to_string()).trim_end_matches(',').to_string())
+        .collect();
+    clauses.join(" ")
+}
+
+fn count_tokens(tokens: TokenStream) -> usize {
+    tokens.into_iter()
+        .map(|tree| match tree {
+            TokenTree::Group(group) => {
+                let delimiters = if group.delimiter() == Delimiter::None { 0 } else { 2 };
+                delimiters + count_tokens(group.stream())
+            }
+            _ => 1,
+        })
+        .sum()
+}
+
+/// Expression text close to how it is written. Unlike `tidy` (for types), spaces
+/// around `<` and `>` stay, since in expressions they are comparisons.
+fn expr_text(tokens: &str) -> String {
+    const KEYWORDS: [&str; 10] = ["requires", "ensures", "recommends", "if", "in", "return", "forall", "exists", "match", "else"];
+    let mut s = tokens.to_string();
+    for (from, to) in [(" :: ", "::"), (" ,", ","), ("( ", "("), (" )", ")"), ("[ ", "["), (" ]", "]"),
+                       (" ;", ";"), (" . ", "."), ("! ", "!"), (" ()", "()")] {
+        s = s.replace(from, to);
+    }
+    // No space before the `(` of a call: `f (x)` -> `f(x)`, but `requires (x)` stays
+    let mut out = String::with_capacity(s.len());
+    for (i, part) in s.split(" (").enumerate() {
+        if i > 0 {
+            let word: String = out.chars().rev().take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+            let is_call = !word.is_empty() && !KEYWORDS.iter().any(|k| k.chars().rev().eq(word.chars()));
+            out.push_str(if is_call { "(" } else { " (" });
+        }
+        out.push_str(part);
+    }
+    out
+}
+
+// ============================================================================
+// Transitive trust (crate-wide call graph)
+// ============================================================================
+
+/// A hole in some function's transitive trusted base
+#[derive(Debug, Clone, Serialize)]
+pub struct TrustedHole {
+    pub file: String,
+    pub function: String,
+    pub line: usize,
+    pub hole_type: String,
+}
+
+/// One function's transitive trusted base: its own holes plus those reachable through calls
+#[derive(Debug, Clone, Serialize)]
+pub struct FnTrust {
+    pub file: String,
+    pub module: String,
+    /// `Type::name` for methods, `name` for free fns
+    pub function: String,
+    pub line: usize,
+    pub mode: String,
+    pub own_holes: usize,
+    pub trusted_base: Vec<TrustedHole>,
+}
+
+/// Holes that make callers rely on something unproved. Style findings (trivial spec wf,
+/// unsafe, opaque) do not weaken a caller's proof.
+fn is_trust_hole(kind: HoleKind) -> bool {
+    matches!(
+        kind,
+        HoleKind::AssumeFalse
+            | HoleKind::Assume
+            | HoleKind::AssumeNew
+            | HoleKind::Admit
+            | HoleKind::AssumeSpecification
+            | HoleKind::Axiom
+            | HoleKind::ExternalBody
+            | HoleKind::ExternalFnSpecification
+            | HoleKind::ExternalTraitSpecification
+            | HoleKind::ExternalTypeSpecification
+            | HoleKind::ExternalTraitExtension
+            | HoleKind::External
+            | HoleKind::VerificationFailure
+    )
+}
+
+/// `pub assume_specification<T> [ Vec::<T>::len ](...)` -> (Some("Vec"), "len")
+fn assume_spec_target(text: &str) -> Option<(Option<String>, String)> {
+    let open = text.find('[')?;
+    let close = open + text[open..].find(']')?;
+    let path = text[open + 1..close].trim();
+    let (prefix, name) = match path.rfind("::") {
+        Some(idx) => (Some(&path[..idx]), &path[idx + 2..]),
+        None => (None, path),
+    };
+    let name = name.split('<').next().unwrap_or(name).trim();
+    if name.is_empty() {
+        return None;
+    }
+    let qualifier = prefix.map(|p| {
+        // "<Vec<T> as Clone>" and "Vec::<T>" both name Vec
+        let p = p.trim_start_matches('<');
+        let p = p.split(" as ").next().unwrap_or(p);
+        base_type_name(p.trim_end_matches("::"))
+    });
+    Some((qualifier, name.to_string()))
+}
+
+/// Build the crate-wide call graph from every file's FnNodes and compute each function's
+/// transitive trusted base. Calls resolve by name (and type/module qualifier when present),
+/// so an ambiguous method name links to every candidate: the base may over-approximate.
+pub fn compute_trust(file_stats_map: &HashMap<String, FileHoles>, base_dir: &Path) -> Vec<FnTrust> {
+    let mut paths: Vec<&String> = file_stats_map.keys().collect();
+    paths.sort();
+
+    // Nodes: real fns, plus stand-ins for assume_specification so calls to the spec'd fn reach it
+    let mut nodes: Vec<(&String, FnNode)> = Vec::new();
+    let mut holes: Vec<TrustedHole> = Vec::new();
+    let mut own: Vec<Vec<usize>> = Vec::new();
+    for path_str in &paths {
+        let stats = &file_stats_map[*path_str];
+        let first = nodes.len();
+        for f in &stats.fns {
+            nodes.push((*path_str, f.clone()));
+            own.push(Vec::new());
+        }
+        let mut content: Option<String> = None;
+        for hole in stats.holes.holes.iter().filter(|h| is_trust_hole(h.kind)) {
+            // Innermost fn whose span contains the hole
+            let owner = (first..nodes.len())
+                .filter(|&n| nodes[n].1.start_line <= hole.line && hole.line <= nodes[n].1.end_line)
+                .min_by_key(|&n| nodes[n].1.end_line - nodes[n].1.start_line);
+            let owner = match owner {
+                Some(n) => Some(n),
+                None if hole.kind == HoleKind::AssumeSpecification => {
+                    let text = content.get_or_insert_with(|| fs::read_to_string(base_dir.join(path_str)).unwrap_or_default());
+                    let lines: Vec<&str> = text.lines().skip(hole.line.saturating_sub(1)).take(3).collect();
+                    assume_spec_target(&lines.join(" ")).map(|(qualifier, name)| {
+                        nodes.push((*path_str, FnNode {
+                            name,
+                            self_type: qualifier,
+                            mode: "assume_specification".to_string(),
+                            line: hole.line,
+                            start_line: hole.line,
+                            end_line: hole.line,
+                            callees: HashSet::new(),
+                        }));
+                        own.push(Vec::new());
+                        nodes.len() - 1
+                    })
+                }
+                None => None,
+            };
+            if let Some(n) = owner {
+                let node = &nodes[n].1;
+                let function = node.qualified_name();
+                holes.push(TrustedHole {
+                    file: (*path_str).clone(),
+                    function,
+                    line: hole.line,
+                    hole_type: hole.kind.to_string(),
+                });
+                own[n].push(holes.len() - 1);
+            }
+        }
+    }
+
+    let mut by_name: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (n, (_, node)) in nodes.iter().enumerate() {
+        by_name.entry(node.name.as_str()).or_default().push(n);
+    }
+
+    // Resolve calls to edges
+    let mut edges: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    for (n, (_, node)) in nodes.iter().enumerate() {
+        let mut targets: BTreeSet<usize> = BTreeSet::new();
+        for callee in &node.callees {
+            let Some(candidates) = by_name.get(callee.name.as_str()) else { continue };
+            let is_free = |&&c: &&usize| nodes[c].1.self_type.is_none();
+            let resolved: Vec<usize> = if callee.is_method {
+                candidates.iter().filter(|&&c| nodes[c].1.self_type.is_some()).copied().collect()
+            } else if let Some(q) = &callee.qualifier {
+                let typed: Vec<usize> = candidates.iter()
+                    .filter(|&&c| nodes[c].1.self_type.as_deref() == Some(q.as_str()))
+                    .copied()
+                    .collect();
+                // Otherwise the qualifier is a module path: crate::Chap05::Foo::lemma
+                if typed.is_empty() { candidates.iter().filter(is_free).copied().collect() } else { typed }
+            } else {
+                let free: Vec<usize> = candidates.iter().filter(is_free).copied().collect();
+                if free.is_empty() { candidates.clone() } else { free }
+            };
+            targets.extend(resolved);
+        }
+        edges[n] = targets.into_iter().collect();
+    }
+
+    // Fixed point: base(n) = own(n) ∪ base(callees); recursion converges since sets only grow
+    let mut base: Vec<BTreeSet<usize>> = own.iter().map(|h| h.iter().copied().collect()).collect();
+    loop {
+        let mut changed = false;
+        for n in 0..nodes.len() {
+            let mut merged: BTreeSet<usize> = BTreeSet::new();
+            for &m in &edges[n] {
+                if m != n {
+                    merged.extend(base[m].iter().copied());
+                }
+            }
+            let before = base[n].len();
+            base[n].extend(merged);
+            if base[n].len() != before {
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    nodes.iter().enumerate()
+        .filter(|(_, (_, node))| node.mode != "assume_specification")
+        .map(|(n, (path_str, node))| FnTrust {
+            file: (*path_str).clone(),
+            module: path_str_to_module(path_str),
+            function: node.qualified_name(),
+            line: node.line,
+            mode: node.mode.clone(),
+            own_holes: own[n].len(),
+            trusted_base: base[n].iter().map(|&h| holes[h].clone()).collect(),
+        })
+        .collect()
+}
+
+// ============================================================================
+// Trusted computing base report (--tcb)
+// ============================================================================
+
+/// Trusted items of the analyzed crate, with totals per module, kind and crate
+#[derive(Debug, Default, Serialize)]
+pub struct TcbReport {
+    /// Path relative to the analyzed directory -> its trusted items
+    pub files: BTreeMap<String, Vec<TrustedItem>>,
+    pub modules: BTreeMap<String, TcbTotals>,
+    pub by_kind: BTreeMap<String, TcbTotals>,
+    pub total: TcbTotals,
+}
+
+impl TcbReport {
+    pub fn new(base_dir: &Path, paths: impl IntoIterator<Item = String>) -> Self {
+        let mut report = TcbReport::default();
+        for path_str in paths {
+            let content = fs::read_to_string(base_dir.join(&path_str)).unwrap_or_default();
+            let items = trusted_items(&content);
+            if items.is_empty() {
+                continue;
+            }
+            let module = report.modules.entry(path_str_to_module(&path_str)).or_default();
+            for item in &items {
+                module.add(item);
+                report.by_kind.entry(item.kind.to_string()).or_default().add(item);
+                report.total.add(item);
+            }
+            report.files.insert(path_str, items);
+        }
+        report
+    }
+}
+
+/// Trusted code in the files of one dependency crate
+pub fn tcb_totals(files: &[PathBuf]) -> TcbTotals {
+    let mut totals = TcbTotals::default();
+    for file in files {
+        let content = fs::read_to_string(file).unwrap_or_default();
+        for item in trusted_items(&content) {
+            totals.add(&item);
+        }
+    }
+    totals
+}
+
+// ============================================================================
+// Attestation manifest (--attest / --check)
+// ============================================================================
+
+/// A hole or accepted hole as recorded in the attestation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttestedHole {
+    pub file: String,
+    pub line: usize,
+    /// Enclosing or annotated item (see `hole_item_name`); empty at module level
+    pub item: String,
+    pub hole_type: String,
+    pub context: String,
+    /// Accepted holes: the `// veracity-accept:` justification, as in the accepted-holes inventory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub justification: Option<Justification>,
+    /// Accepted holes whose justification is missing or fails veracity.toml's policy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub problem: Option<String>,
+}
+
+/// A declaration whose spec is trusted rather than proved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttestedItem {
+    pub file: String,
+    pub line: usize,
+    pub item: String,
+    pub hole_type: String,
+    pub accepted: bool,
+}
+
+/// What was verified, with which tools, and what it trusts
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Attestation {
+    pub tool: String,
+    pub veracity_version: String,
+    pub verus_version: String,
+    pub vstd_commit: String,
+    pub generated: String,
+    /// Path relative to the analyzed directory -> SHA-256 (hex)
+    pub files: BTreeMap<String, String>,
+    pub holes: Vec<AttestedHole>,
+    pub accepted: Vec<AttestedHole>,
+    pub trusted: Vec<AttestedItem>,
+}
+
+/// One difference between an attestation and the tree as it is now
+#[derive(Debug, Clone, PartialEq)]
+pub enum Drift {
+    /// (tool, recorded version, version now)
+    Tool(&'static str, String, String),
+    Removed(String),
+    Changed(String),
+    Added(String),
+}
+
+impl Attestation {
+    /// How the tools in use and the `current` hashes (see [`hash_tree`]) differ from
+    /// what was attested; empty if nothing has changed
+    pub fn drift(&self, current: &BTreeMap<String, String>) -> Vec<Drift> {
+        let mut drift = Vec::new();
+        for (what, recorded, now) in [
+            ("veracity", &self.veracity_version, env!("CARGO_PKG_VERSION").to_string()),
+            ("verus", &self.verus_version, verus_version()),
+            ("vstd", &self.vstd_commit, vstd_commit()),
+        ] {
+            if *recorded != now {
+                drift.push(Drift::Tool(what, recorded.clone(), now));
+            }
+        }
+        for (file, hash) in &self.files {
+            match current.get(file) {
+                None => drift.push(Drift::Removed(file.clone())),
+                Some(now) if now != hash => drift.push(Drift::Changed(file.clone())),
+                Some(_) => {}
+            }
+        }
+        for file in current.keys().filter(|f| !self.files.contains_key(*f)) {
+            drift.push(Drift::Added(file.clone()));
+        }
+        drift
+    }
+}
+
+/// Build files hashed beside the sources: they change what Verus checks
+pub const ATTESTED_BUILD_FILES: [&str; 3] = ["Cargo.toml", "Cargo.lock", "veracity.toml"];
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(format!("{:x}", Sha256::digest(&bytes)))
+}
+
+/// SHA-256 of the analyzed source `files` and the build files, keyed by path relative to `base_dir`
+pub fn hash_tree(mut files: Vec<PathBuf>, base_dir: &Path) -> Result<BTreeMap<String, String>> {
+    files.extend(ATTESTED_BUILD_FILES.iter().map(|f| base_dir.join(f)).filter(|p| p.is_file()));
+    let mut hashes = BTreeMap::new();
+    for file in files {
+        let rel = file.strip_prefix(base_dir).unwrap_or(&file).display().to_string();
+        hashes.insert(rel, sha256_file(&file)?);
+    }
+    Ok(hashes)
+}
+
+/// First line of `verus --version`, or "unknown"
+pub fn verus_version() -> String {
+    std::process::Command::new("verus")
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8_lossy(&o.stdout).lines().next().map(|l| l.trim().to_string()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Commit of the Verus checkout whose vstd is in use, or "unknown".
+///
+/// As in analyze_libs: verus is at source/target-verus/release/verus and vstd at source/vstd.
+pub fn vstd_commit() -> String {
+    let Ok(output) = std::process::Command::new("which").arg("verus").output() else {
+        return "unknown".to_string();
+    };
+    let verus_path = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    let source_dir = verus_path.canonicalize().ok()
+        .and_then(|p| p.parent()?.parent()?.parent().map(Path::to_path_buf))
+        .filter(|dir| dir.join("vstd").is_dir());
+    source_dir
+        .and_then(|dir| git(&dir, &["rev-parse", "HEAD"]).ok())
+        .map(|sha| sha.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn is_accepted(kind: HoleKind) -> bool {
+    kind.is_accept_hole() || matches!(kind, HoleKind::Accept | HoleKind::AssumeFalseDiverge)
+}
+
+/// Declarations trusted by their spec: external specs and bodies, assume_specification, axioms
+fn is_trusted_item(kind: HoleKind) -> bool {
+    kind.is_external() || matches!(kind, HoleKind::AssumeSpecification | HoleKind::Axiom)
+}
+
+/// The manifest for `file_stats_map`: its holes, accepted holes and trusted items,
+/// with the hashes of the source `files` and the versions of the tools
+pub fn build_attestation(
+    justification: &JustificationPolicy,
+    base_dir: &Path,
+    file_stats_map: &HashMap<String, FileHoles>,
+    files: Vec<PathBuf>,
+) -> Result<Attestation> {
+    let mut holes = Vec::new();
+    let mut accepted = Vec::new();
+    let mut trusted = Vec::new();
+    let mut paths: Vec<&String> = file_stats_map.keys().collect();
+    paths.sort();
+    for path_str in paths {
+        let stats = &file_stats_map[path_str];
+        let content = fs::read_to_string(base_dir.join(path_str)).unwrap_or_default();
+        let attest = |hole: &DetectedHole, site: Option<AcceptSite>| {
+            let (justification, problem) = site
+                .map(|s| (s.justification, s.problem.map(|p| p.to_string())))
+                .unwrap_or_default();
+            AttestedHole {
+                file: path_str.clone(),
+                line: hole.line,
+                item: hole_item_name(&content, hole),
+                hole_type: hole.kind.to_string(),
+                context: hole.context.clone(),
+                justification,
+                problem,
+            }
+        };
+        for hole in &stats.holes.holes {
+            holes.push(attest(hole, None));
+        }
+        for info in stats.infos.iter().filter(|i| is_accepted(i.kind)) {
+            // assume(false) before a diverging loop is accepted without a justification
+            let site = is_accept_site(info.kind)
+                .then(|| check_accept_site(&content, info, justification));
+            accepted.push(attest(info, site));
+        }
+        for hole in stats.holes.holes.iter().chain(&stats.infos).filter(|h| is_trusted_item(h.kind)) {
+            trusted.push(AttestedItem {
+                file: path_str.clone(),
+                line: hole.line,
+                item: hole_item_name(&content, hole),
+                hole_type: hole.kind.to_string(),
+                accepted: hole.kind.is_accept_hole(),
+            });
+        }
+    }
+    trusted.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+    Ok(Attestation {
+        tool: "veracity-review-proof-holes".to_string(),
+        veracity_version: env!("CARGO_PKG_VERSION").to_string(),
+        verus_version: verus_version(),
+        vstd_commit: vstd_commit(),
+        generated: Local::now().format("%Y-%m-%d %H:%M:%S %Z").to_string(),
+        files: hash_tree(files, base_dir)?,
+        holes,
+        accepted,
+        trusted,
+    })
+}
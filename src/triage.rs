@@ -0,0 +1,530 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Source edits for hole triage, applied as transactions with undo
+//!
+//! Each triage action turns a file's content into new content. A [`Transaction`]
+//! holds the before and after text of every file an action touches, and an
+//! [`EditLog`] writes it only if every file is still as the action saw it, then
+//! keeps it so it can be undone. Undo likewise refuses to overwrite a file that
+//! has changed since the edit.
+//!
+//! A [`Triage`] is the state behind the -i view: the holes of a set of files,
+//! grouped and selected, with each action applied through the log and the file
+//! it edited analyzed again.
+
+use crate::args::StandardArgs;
+use crate::grouping::Grouping;
+use crate::holes::collect::Analyzer;
+use crate::holes::{has_accept_hole_comment, DetectedHole, HoleKind};
+use crate::lift::lift_assumes;
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Comment marker recording a hole that will not be fixed
+pub const WONT_FIX_MARKER: &str = "veracity-wontfix:";
+
+/// One file's content before and after an edit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEdit {
+    pub path: PathBuf,
+    pub before: String,
+    pub after: String,
+}
+
+/// Edits that are applied, and undone, together
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    /// Shown in the undo history, e.g. "accept src/a.rs:12"
+    pub label: String,
+    pub edits: Vec<FileEdit>,
+}
+
+impl Transaction {
+    pub fn new(label: impl Into<String>) -> Self {
+        Transaction { label: label.into(), edits: Vec::new() }
+    }
+
+    /// Replace `path`'s content `before` with `after`; no-op edits are dropped
+    pub fn edit(mut self, path: &Path, before: String, after: String) -> Self {
+        if before != after {
+            self.edits.push(FileEdit { path: path.to_path_buf(), before, after });
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+}
+
+/// Applied transactions, most recent last
+#[derive(Debug, Default)]
+pub struct EditLog {
+    done: Vec<Transaction>,
+}
+
+impl EditLog {
+    /// Write every file of `tx`, or none of them.
+    ///
+    /// Fails without writing if any file no longer holds its `before` content.
+    pub fn apply(&mut self, tx: Transaction) -> Result<()> {
+        write_all(&tx, |e| (&e.before, &e.after))?;
+        self.done.push(tx);
+        Ok(())
+    }
+
+    /// Restore the files of the last transaction and return it, or None if there is nothing to undo
+    pub fn undo(&mut self) -> Result<Option<Transaction>> {
+        let Some(tx) = self.done.pop() else {
+            return Ok(None);
+        };
+        if let Err(e) = write_all(&tx, |e| (&e.after, &e.before)) {
+            self.done.push(tx);
+            return Err(e);
+        }
+        Ok(Some(tx))
+    }
+
+    /// Labels of the applied transactions, oldest first
+    pub fn history(&self) -> impl Iterator<Item = &str> {
+        self.done.iter().map(|t| t.label.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.done.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.done.is_empty()
+    }
+}
+
+/// Check every file holds `from`, then write `to`; on a failed write, restore the files already written
+fn write_all(tx: &Transaction, pick: impl Fn(&FileEdit) -> (&String, &String)) -> Result<()> {
+    for edit in &tx.edits {
+        let (from, _) = pick(edit);
+        let current = fs::read_to_string(&edit.path)
+            .with_context(|| format!("Failed to read {}", edit.path.display()))?;
+        if current != *from {
+            bail!("{} changed on disk; not editing it", edit.path.display());
+        }
+    }
+    for (i, edit) in tx.edits.iter().enumerate() {
+        if let Err(e) = fs::write(&edit.path, pick(edit).1) {
+            for done in &tx.edits[..i] {
+                let _ = fs::write(&done.path, pick(done).0);
+            }
+            return Err(e).with_context(|| format!("Failed to write {}", edit.path.display()));
+        }
+    }
+    Ok(())
+}
+
+/// Replace lines of `content` (1-based `line`), keeping its trailing newline
+fn splice_lines(content: &str, line: usize, remove: usize, insert: &[String]) -> Option<String> {
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+    let idx = line.checked_sub(1)?;
+    if idx + remove > lines.len() {
+        return None;
+    }
+    lines.splice(idx..idx + remove, insert.iter().cloned());
+    let mut out = lines.join("\n");
+    if content.ends_with('\n') {
+        out.push('\n');
+    }
+    Some(out)
+}
+
+fn indent_of(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// Replace assume(...) with proof { accept(...); }, handling nested parens.
+pub fn replace_assume_with_proof_accept(line: &str) -> Option<String> {
+    let start = line.find("assume(").or_else(|| line.find("assume ("))?;
+    let open_paren = start + line[start..].find('(')?;
+    let mut depth = 1u32;
+    let mut end = open_paren;
+    for (i, c) in line[open_paren + 1..].chars().enumerate() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    end = open_paren + 1 + i;
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return None;
+    }
+    let arg = &line[open_paren + 1..end];
+    let replacement = format!("proof {{ accept({}); }}", arg);
+    let mut result = line.to_string();
+    result.replace_range(start..=end, &replacement);
+    Some(result)
+}
+
+/// Check if content already has the accept import (by path substring).
+pub fn has_accept_import(content: &str, accept_import: &str) -> bool {
+    let path = accept_import
+        .trim()
+        .strip_prefix("use ")
+        .and_then(|s| s.strip_suffix(';'))
+        .map(|s| s.trim())
+        .unwrap_or(accept_import);
+    content.contains(path)
+}
+
+/// Insert accept import inside the verus! block, after the last use there.
+/// Returns true if a line was inserted (caller must add 1 to line_idx if it was before the hole).
+/// No-op if the import already exists (avoids duplicate).
+pub fn add_accept_import(lines: &mut Vec<String>, accept_import: &str) -> bool {
+    if lines.iter().any(|l| l.trim() == accept_import.trim()) {
+        return false;
+    }
+    let verus_start = lines.iter().position(|l| l.contains("verus!"));
+    let Some(vs) = verus_start else {
+        return false;
+    };
+    let mut last_use_idx = None;
+    for (i, line) in lines.iter().enumerate().skip(vs + 1) {
+        let t = line.trim_start();
+        if t.starts_with("use ") {
+            last_use_idx = Some(i);
+        } else if !t.is_empty() && !t.starts_with("//") && !t.starts_with("#[") && !t.starts_with("#!") {
+            break;
+        }
+    }
+    if let Some(idx) = last_use_idx {
+        lines.insert(idx + 1, accept_import.to_string());
+    } else {
+        lines.insert(vs + 1, accept_import.to_string());
+    }
+    true
+}
+
+/// Accept the hole at `line`: assume(P) becomes proof { accept(P); } (adding the import),
+/// an `#[verifier::external*]` attribute gets `// accept hole`. None if there is nothing to change.
+pub fn accept_hole(content: &str, line: usize, kind: HoleKind, accept_import: &str) -> Option<String> {
+    let text = content.lines().nth(line.checked_sub(1)?)?;
+    if matches!(kind, HoleKind::Assume | HoleKind::AssumeFalse) {
+        let new_line = replace_assume_with_proof_accept(text)?;
+        let mut lines: Vec<String> = content.lines().map(String::from).collect();
+        // The import goes at the top of the verus! block, above the hole
+        let offset = usize::from(!has_accept_import(content, accept_import) && add_accept_import(&mut lines, accept_import));
+        lines[line - 1 + offset] = new_line;
+        let mut out = lines.join("\n");
+        if content.ends_with('\n') {
+            out.push('\n');
+        }
+        Some(out)
+    } else if kind.is_external() {
+        if has_accept_hole_comment(content, line) {
+            return None;
+        }
+        splice_lines(content, line, 1, &[format!("{} // accept hole", text.trim_end())])
+    } else {
+        None
+    }
+}
+
+/// Replace `#[verifier::external_body]` at `line` with `#[accepted_external_body]`
+pub fn to_accepted_external_body(content: &str, line: usize) -> Option<String> {
+    let text = content.lines().nth(line.checked_sub(1)?)?;
+    let attr = ["#[verifier::external_body]", "#[verifier(external_body)]"]
+        .into_iter()
+        .find(|a| text.contains(a))?;
+    splice_lines(content, line, 1, &[text.replacen(attr, "#[accepted_external_body]", 1)])
+}
+
+/// Insert `// veracity-wontfix: <reason>` above `line`, at its indentation
+pub fn mark_wont_fix(content: &str, line: usize, reason: &str) -> Option<String> {
+    let text = content.lines().nth(line.checked_sub(1)?)?;
+    let comment = format!("{}// {} {}", indent_of(text), WONT_FIX_MARKER, reason.trim());
+    splice_lines(content, line, 0, &[comment.trim_end().to_string()])
+}
+
+/// The reason of a `// veracity-wontfix:` comment on `line` or the comment lines just above it
+pub fn wont_fix_reason(content: &str, line: usize) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let reason = |n: usize| {
+        let (_, comment) = lines.get(n.checked_sub(1)?)?.split_once("//")?;
+        comment.split_once(WONT_FIX_MARKER).map(|(_, r)| r.trim().to_string())
+    };
+    if let Some(r) = reason(line) {
+        return Some(r);
+    }
+    let mut above = line.saturating_sub(1);
+    while above > 0 && lines[above - 1].trim_start().starts_with("//") {
+        if let Some(r) = reason(above) {
+            return Some(r);
+        }
+        above -= 1;
+    }
+    None
+}
+
+// ============================================================================
+// Triage session (-i)
+// ============================================================================
+
+/// How triage groups holes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriageGrouping {
+    Directory,
+    /// The configured hierarchy (chapters by default)
+    Hierarchy,
+    HoleType,
+}
+
+impl TriageGrouping {
+    pub fn next(self) -> Self {
+        match self {
+            TriageGrouping::Directory => TriageGrouping::Hierarchy,
+            TriageGrouping::Hierarchy => TriageGrouping::HoleType,
+            TriageGrouping::HoleType => TriageGrouping::Directory,
+        }
+    }
+
+    pub fn label(self, hierarchy: &Grouping) -> String {
+        match self {
+            TriageGrouping::Directory => "directory".to_string(),
+            TriageGrouping::Hierarchy => hierarchy.label().to_lowercase(),
+            TriageGrouping::HoleType => "hole type".to_string(),
+        }
+    }
+
+    pub fn key(self, hole: &TriageHole, hierarchy: &Grouping) -> String {
+        match self {
+            TriageGrouping::Directory => Path::new(&hole.rel).parent()
+                .map(|p| p.display().to_string())
+                .filter(|p| !p.is_empty())
+                .unwrap_or_else(|| ".".to_string()),
+            TriageGrouping::Hierarchy => hierarchy.group_of_path(&hole.path)
+                .unwrap_or_else(|| format!("(no {})", hierarchy.label().to_lowercase())),
+            TriageGrouping::HoleType => hole.hole.kind.to_string(),
+        }
+    }
+}
+
+/// A hole in the triage list
+pub struct TriageHole {
+    pub path: PathBuf,
+    pub rel: String,
+    pub hole: DetectedHole,
+    /// Reason from a `// veracity-wontfix:` comment
+    pub wont_fix: Option<String>,
+}
+
+/// Triage state: holes per file, grouping, selection and the edit log
+pub struct Triage<'a> {
+    pub analyzer: Analyzer<'a>,
+    pub base_dir: PathBuf,
+    pub accept_import: String,
+    pub files: BTreeMap<PathBuf, Vec<DetectedHole>>,
+    pub holes: Vec<TriageHole>,
+    pub grouping: TriageGrouping,
+    pub hierarchy: Grouping,
+    /// Group name -> indices into `holes`
+    pub groups: Vec<(String, Vec<usize>)>,
+    pub group: usize,
+    pub selected: usize,
+    pub log: EditLog,
+    pub status: String,
+    /// Won't-fix reason being typed
+    pub input: Option<String>,
+    pub help: bool,
+}
+
+impl<'a> Triage<'a> {
+    pub fn new(args: &'a StandardArgs, files: &[PathBuf], accept_import: &str, use_cache: bool) -> Result<Self> {
+        let mut triage = Triage {
+            analyzer: Analyzer::new(args, use_cache),
+            base_dir: args.base_dir(),
+            accept_import: accept_import.to_string(),
+            files: BTreeMap::new(),
+            holes: Vec::new(),
+            grouping: TriageGrouping::Directory,
+            hierarchy: Grouping::new(&args.config)?,
+            groups: Vec::new(),
+            group: 0,
+            selected: 0,
+            log: EditLog::default(),
+            status: String::new(),
+            input: None,
+            help: false,
+        };
+        for file in files {
+            triage.refresh_file(file);
+        }
+        triage.rebuild();
+        Ok(triage)
+    }
+
+    /// Re-analyze one file (after an edit or undo)
+    pub fn refresh_file(&mut self, file: &Path) {
+        let path = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+        self.analyzer.invalidate(&path);
+        match self.analyzer.analyze(&path) {
+            Ok(stats) if !stats.holes.holes.is_empty() => {
+                self.files.insert(path, stats.holes.holes);
+            }
+            _ => {
+                self.files.remove(&path);
+            }
+        }
+    }
+
+    /// Rebuild the hole list and groups, keeping the selection near where it was
+    pub fn rebuild(&mut self) {
+        let current = self.current().map(|h| (h.path.clone(), h.hole.line));
+        self.holes.clear();
+        for (path, holes) in &self.files {
+            let content = fs::read_to_string(path).unwrap_or_default();
+            let rel = path.strip_prefix(&self.base_dir).unwrap_or(path).display().to_string();
+            for hole in holes {
+                self.holes.push(TriageHole {
+                    path: path.clone(),
+                    rel: rel.clone(),
+                    hole: hole.clone(),
+                    wont_fix: wont_fix_reason(&content, hole.line),
+                });
+            }
+        }
+        let mut groups: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (i, h) in self.holes.iter().enumerate() {
+            groups.entry(self.grouping.key(h, &self.hierarchy)).or_default().push(i);
+        }
+        self.groups = groups.into_iter().collect();
+        if self.grouping == TriageGrouping::Hierarchy {
+            self.groups.sort_by(|a, b| self.hierarchy.compare(&a.0, &b.0));
+        }
+        self.group = 0;
+        self.selected = 0;
+        if let Some((path, line)) = current {
+            // Same file, first hole at or after the old line
+            let found = self.groups.iter().enumerate().find_map(|(g, (_, members))| {
+                members.iter()
+                    .position(|&i| self.holes[i].path == path && self.holes[i].hole.line >= line)
+                    .map(|s| (g, s))
+            });
+            if let Some((g, s)) = found {
+                self.group = g;
+                self.selected = s;
+            }
+        }
+    }
+
+    pub fn current(&self) -> Option<&TriageHole> {
+        let (_, members) = self.groups.get(self.group)?;
+        members.get(self.selected).map(|&i| &self.holes[i])
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        let len = self.groups.get(self.group).map_or(0, |g| g.1.len());
+        if len > 0 {
+            self.selected = self.selected.saturating_add_signed(delta).min(len - 1);
+        }
+    }
+
+    pub fn move_group(&mut self, delta: isize) {
+        if !self.groups.is_empty() {
+            self.group = self.group.saturating_add_signed(delta).min(self.groups.len() - 1);
+            self.selected = 0;
+        }
+    }
+
+    /// Select the next hole not marked won't-fix, in group order, wrapping around
+    pub fn next_open_hole(&mut self) {
+        let order: Vec<(usize, usize)> = self.groups.iter().enumerate()
+            .flat_map(|(g, (_, members))| (0..members.len()).map(move |s| (g, s)))
+            .collect();
+        let Some(at) = order.iter().position(|&p| p == (self.group, self.selected)) else {
+            return;
+        };
+        let next = (1..=order.len())
+            .map(|k| order[(at + k) % order.len()])
+            .find(|&(g, s)| self.holes[self.groups[g].1[s]].wont_fix.is_none());
+        match next {
+            Some((g, s)) => {
+                self.group = g;
+                self.selected = s;
+            }
+            None => self.status = "No open holes left.".to_string(),
+        }
+    }
+
+    /// Build the edit for an action on the selected hole, apply it and re-analyze the file
+    pub fn act(&mut self, action: char, reason: &str) {
+        let Some(current) = self.current() else { return };
+        let (path, line, kind) = (current.path.clone(), current.hole.line, current.hole.kind);
+        let where_ = format!("{}:{}", current.rel, line);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                self.status = format!("Cannot read {}: {}", path.display(), e);
+                return;
+            }
+        };
+        let (label, edited) = match action {
+            'a' => ("accept", accept_hole(&content, line, kind, &self.accept_import)
+                .ok_or_else(|| format!("cannot accept {}", kind))),
+            'e' if kind == HoleKind::ExternalBody => ("accepted_external_body", to_accepted_external_body(&content, line)
+                .ok_or_else(|| "no #[verifier::external_body] on this line".to_string())),
+            'e' => ("accepted_external_body", Err(format!("{} is not external_body", kind))),
+            'l' if kind == HoleKind::Assume => {
+                let result = lift_assumes(&content, &[line]);
+                let edited = match result.skipped.first() {
+                    Some(skipped) => Err(format!("not lifted: {}", skipped.reason)),
+                    None => Ok(result.content),
+                };
+                ("lift", edited)
+            }
+            'l' => ("lift", Err(format!("only assume(..) can be lifted, not {}", kind))),
+            'w' => ("won't fix", mark_wont_fix(&content, line, reason)
+                .ok_or_else(|| "line not found".to_string())),
+            _ => return,
+        };
+        let edited = match edited {
+            Ok(edited) => edited,
+            Err(msg) => {
+                self.status = format!("{}: {}", where_, msg);
+                return;
+            }
+        };
+        let tx = Transaction::new(format!("{} {}", label, where_)).edit(&path, content, edited);
+        if tx.is_empty() {
+            self.status = format!("{}: no change", where_);
+            return;
+        }
+        match self.log.apply(tx) {
+            Ok(()) => {
+                self.status = format!("{} {} (u to undo)", label, where_);
+                self.refresh_file(&path);
+                self.rebuild();
+            }
+            Err(e) => self.status = format!("{:#}", e),
+        }
+    }
+
+    pub fn undo(&mut self) {
+        match self.log.undo() {
+            Ok(Some(tx)) => {
+                self.status = format!("Undid {}", tx.label);
+                for edit in &tx.edits {
+                    self.refresh_file(&edit.path);
+                }
+                self.rebuild();
+            }
+            Ok(None) => self.status = "Nothing to undo.".to_string(),
+            Err(e) => self.status = format!("Undo failed: {:#}", e),
+        }
+    }
+}
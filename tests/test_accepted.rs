@@ -0,0 +1,72 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Tests for accepted-hole justifications.
+
+use veracity::accepted::{accept_sites, parse_justification, AcceptedInventory, JustificationProblem};
+use veracity::holes::{DetectedHole, HoleKind};
+use veracity::VeracityConfig;
+
+const SRC: &str = "verus! {
+fn eq(&self, other: &Self) -> (equal: bool) {
+    let equal = self.v == other.v;
+    // veracity-accept: reason=eq-clone-workaround ticket=#42 Verus cannot see Clone::clone
+    proof { accept(equal == (*self == *other)); }
+    proof { accept(self.v == other.v); }
+    equal
+}
+
+#[verifier::external_body] // veracity-accept: reason=rwlock
+fn new_lock() {}
+
+// veracity-accept: ticket=#7
+proof fn p() { accept(true); }
+} // verus!
+";
+
+fn info(line: usize, kind: HoleKind) -> DetectedHole {
+    DetectedHole { line, kind, context: String::new(), blame: None }
+}
+
+#[test]
+fn test_parse_justification() {
+    let j = parse_justification("reason=verus-limitation ticket=#12 iterator  spec").unwrap();
+    assert_eq!(j.reason, "verus-limitation");
+    assert_eq!(j.ticket.as_deref(), Some("#12"));
+    assert_eq!(j.note, "iterator spec");
+    assert!(parse_justification("ticket=#12").is_err());
+    assert!(parse_justification("reason=").is_err());
+    assert!(parse_justification("reason=x owner=me").is_err());
+}
+
+#[test]
+fn test_accept_sites() {
+    let config = VeracityConfig::from_str("[justification]\ncategories = [\"eq-clone-workaround\", \"verus-limitation\"]\n").unwrap();
+    let infos = vec![
+        info(5, HoleKind::Accept),
+        info(6, HoleKind::Accept),
+        info(10, HoleKind::ExternalBodyAcceptHole),
+        info(14, HoleKind::Accept),
+        // Not an accept site
+        info(14, HoleKind::AssumeFalseDiverge),
+    ];
+    let sites = accept_sites(SRC, &infos, &config.justification);
+    let problems: Vec<Option<JustificationProblem>> = sites.iter().map(|s| s.problem.clone()).collect();
+    assert_eq!(problems, vec![
+        None,
+        Some(JustificationProblem::Missing),
+        Some(JustificationProblem::UnknownCategory("rwlock".to_string())),
+        Some(JustificationProblem::Malformed("missing reason=<category>".to_string())),
+    ]);
+
+    let mut inventory = AcceptedInventory::default();
+    for site in &sites {
+        inventory.add("src/a.rs", String::new(), site);
+    }
+    let categories: Vec<&str> = inventory.by_category.keys().map(String::as_str).collect();
+    assert_eq!(categories, vec!["eq-clone-workaround", "rwlock"]);
+    assert_eq!(inventory.by_category["eq-clone-workaround"][0].ticket.as_deref(), Some("#42"));
+    assert_eq!(inventory.unjustified.len(), 2);
+    assert_eq!(inventory.total(), 4);
+    assert_eq!(inventory.problems().count(), 3);
+}
@@ -0,0 +1,151 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Tests for the incremental analysis cache.
+
+use veracity::cache::{content_hash, crate_module_files, AnalysisCache};
+use std::fs;
+use std::path::PathBuf;
+
+/// Fresh scratch project: src/Chap05/SetStEph.rs and src/Chap05/Uses.rs
+fn scratch_project(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("veracity_test_cache_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("src/Chap05")).unwrap();
+    fs::write(dir.join("src/Chap05/SetStEph.rs"), "pub fn f() {}\n").unwrap();
+    fs::write(dir.join("src/Chap05/Uses.rs"), "use crate::Chap05::SetStEph::f;\n").unwrap();
+    dir
+}
+
+#[test]
+fn test_content_hash_is_stable() {
+    assert_eq!(content_hash(b""), "cbf29ce484222325");
+    assert_eq!(content_hash(b"verus"), content_hash(b"verus"));
+    assert_ne!(content_hash(b"assume(x)"), content_hash(b"assert(x)"));
+}
+
+#[test]
+fn test_hit_after_save_and_reload() {
+    let dir = scratch_project("hit");
+    let file = dir.join("src/Chap05/SetStEph.rs");
+    
+    let mut cache: AnalysisCache<Vec<usize>> = AnalysisCache::load(&dir, "test-tool", "");
+    assert_eq!(cache.get(&file), None);
+    cache.insert(&file, &[], vec![1, 2, 3]);
+    cache.save().unwrap();
+    assert!(dir.join("analyses/.cache/test-tool.json").is_file());
+    
+    let mut cache: AnalysisCache<Vec<usize>> = AnalysisCache::load(&dir, "test-tool", "");
+    assert_eq!(cache.get(&file), Some(vec![1, 2, 3]));
+    assert_eq!(cache.hits, 1);
+    
+    // A different salt is a different cache
+    let mut cache: AnalysisCache<Vec<usize>> = AnalysisCache::load(&dir, "test-tool", "-av");
+    assert_eq!(cache.get(&file), None);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_miss_when_file_or_dep_changes() {
+    let dir = scratch_project("miss");
+    let dep = dir.join("src/Chap05/SetStEph.rs");
+    let file = dir.join("src/Chap05/Uses.rs");
+    
+    let deps = crate_module_files(&dir.join("src"), &["Chap05::SetStEph".to_string()]);
+    assert_eq!(deps, vec![dep.clone()]);
+    
+    let mut cache: AnalysisCache<String> = AnalysisCache::load(&dir, "test-tool", "");
+    cache.insert(&file, &deps, "ok".to_string());
+    assert_eq!(cache.get(&file), Some("ok".to_string()));
+    cache.save().unwrap();
+    
+    fs::write(&dep, "pub fn f() { admit(); }\n").unwrap();
+    let mut cache: AnalysisCache<String> = AnalysisCache::load(&dir, "test-tool", "");
+    assert_eq!(cache.get(&file), None);
+    cache.insert(&file, &deps, "ok".to_string());
+    cache.save().unwrap();
+    
+    fs::write(&file, "use crate::Chap05::SetStEph::*;\n").unwrap();
+    let mut cache: AnalysisCache<String> = AnalysisCache::load(&dir, "test-tool", "");
+    assert_eq!(cache.get(&file), None);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_forget_rereads_an_edited_file() {
+    let dir = scratch_project("forget");
+    let dep = dir.join("src/Chap05/SetStEph.rs");
+    let file = dir.join("src/Chap05/Uses.rs");
+    let deps = crate_module_files(&dir.join("src"), &["Chap05::SetStEph".to_string()]);
+
+    let mut cache: AnalysisCache<String> = AnalysisCache::load(&dir, "test-tool", "");
+    cache.insert(&file, &deps, "ok".to_string());
+
+    // Hashes are remembered for the run, so an edit goes unseen until forgotten
+    fs::write(&file, "use crate::Chap05::SetStEph::*;\n").unwrap();
+    assert_eq!(cache.get(&file), Some("ok".to_string()));
+    cache.forget(&file);
+    assert_eq!(cache.get(&file), None);
+
+    cache.insert(&file, &deps, "ok".to_string());
+    fs::write(&dep, "pub fn f() { admit(); }\n").unwrap();
+    assert_eq!(cache.get(&file), Some("ok".to_string()));
+    cache.forget(&dep);
+    assert_eq!(cache.get(&file), None);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_subset_run_keeps_other_entries() {
+    let dir = scratch_project("subset");
+    let a = dir.join("src/Chap05/SetStEph.rs");
+    let b = dir.join("src/Chap05/Uses.rs");
+    let mut cache: AnalysisCache<usize> = AnalysisCache::load(&dir, "test-tool", "");
+    cache.insert(&a, &[], 1);
+    cache.insert(&b, &[], 2);
+    cache.save().unwrap();
+
+    // A run over just `a` still saves `b`'s entry
+    let mut cache: AnalysisCache<usize> = AnalysisCache::load(&dir, "test-tool", "");
+    assert_eq!(cache.get(&a), Some(1));
+    cache.save().unwrap();
+    let mut cache: AnalysisCache<usize> = AnalysisCache::load(&dir, "test-tool", "");
+    assert_eq!(cache.get(&b), Some(2));
+
+    // A deleted file's entry goes, even if the file comes back unchanged
+    let content = fs::read(&a).unwrap();
+    fs::remove_file(&a).unwrap();
+    cache.save().unwrap();
+    fs::write(&a, content).unwrap();
+    let mut cache: AnalysisCache<usize> = AnalysisCache::load(&dir, "test-tool", "");
+    assert_eq!(cache.get(&a), None);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_crate_module_files_uses_longest_module_prefix() {
+    let dir = scratch_project("modules");
+    let src = dir.join("src");
+    let modules = vec![
+        "Chap05::SetStEph::SetStEph".to_string(),
+        "Chap05::SetStEph".to_string(),
+        "Chap99::Missing".to_string(),
+    ];
+    assert_eq!(crate_module_files(&src, &modules), vec![src.join("Chap05/SetStEph.rs")]);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_content_entries_survive_reload() {
+    let dir = scratch_project("content");
+    let mut cache: AnalysisCache<usize> = AnalysisCache::load(&dir, "test-tool", "");
+    assert_eq!(cache.get_content("8d3f1c0"), None);
+    cache.insert_content("8d3f1c0", 7);
+    cache.save().unwrap();
+
+    let mut cache: AnalysisCache<usize> = AnalysisCache::load(&dir, "test-tool", "");
+    assert_eq!(cache.get_content("8d3f1c0"), Some(7));
+    assert_eq!(cache.get_content("b0a3e55"), None);
+    assert_eq!((cache.hits, cache.misses), (1, 1));
+    let _ = fs::remove_dir_all(&dir);
+}
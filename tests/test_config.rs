@@ -0,0 +1,94 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Tests for veracity.toml project policy parsing.
+
+use veracity::config::{path_matches, Severity, VeracityConfig};
+use std::path::{Path, PathBuf};
+
+const POLICY: &str = r#"
+exclude = ["src/experiments", "attic/**"]
+accept = "crate::vstdplus::accept::accept"
+
+[severity]
+assume_eq_clone_workaround = "off"
+admit = "error"
+
+[[severity.paths]]
+glob = "src/vstdplus/**"
+external_body = "info"
+
+[[severity.paths]]
+glob = "src/vstdplus/seq.rs"
+external_body = "warning"
+
+[[budget]]
+dir = "src/Chap05"
+max_holes = 2
+"#;
+
+#[test]
+fn test_parse_policy() {
+    let config = VeracityConfig::from_str(POLICY).unwrap();
+    assert_eq!(config.exclude, vec!["src/experiments", "attic/**"]);
+    assert_eq!(config.accept_import().unwrap(), "use crate::vstdplus::accept::accept;");
+    assert_eq!(config.severity.paths.len(), 2);
+    assert_eq!(config.budget[0].dir, "src/Chap05");
+    assert_eq!(config.budget[0].max_holes, 2);
+    assert!(config.has_severity_overrides());
+}
+
+#[test]
+fn test_empty_policy() {
+    let config = VeracityConfig::from_str("").unwrap();
+    assert!(config.exclude.is_empty());
+    assert!(config.accept_import().is_none());
+    assert!(!config.has_severity_overrides());
+    assert_eq!(
+        config.severity_for("external_body", Path::new("src/a.rs"), Severity::Error),
+        Severity::Error
+    );
+}
+
+#[test]
+fn test_severity_by_type_and_glob() {
+    let config = VeracityConfig::from_str(POLICY).unwrap();
+    let sev = |t: &str, p: &str| config.severity_for(t, Path::new(p), Severity::Error);
+    assert_eq!(sev("external_body", "src/Chap05/SetStEph.rs"), Severity::Error);
+    assert_eq!(sev("external_body", "src/vstdplus/set.rs"), Severity::Info);
+    // Last matching glob wins
+    assert_eq!(sev("external_body", "src/vstdplus/seq.rs"), Severity::Warning);
+    // "admit" covers "admit()"
+    assert_eq!(sev("admit()", "src/vstdplus/set.rs"), Severity::Error);
+    assert_eq!(sev("assume_eq_clone_workaround", "src/Chap05/SetStEph.rs"), Severity::Off);
+}
+
+#[test]
+fn test_exclude_and_globs() {
+    let config = VeracityConfig::from_str(POLICY).unwrap();
+    assert!(config.is_excluded(Path::new("src/experiments/Foo.rs")));
+    assert!(config.is_excluded(Path::new("attic/old/Bar.rs")));
+    assert!(!config.is_excluded(Path::new("src/experiments_ok/Foo.rs")));
+    assert!(path_matches("src/*/mod.rs", "src/Chap05/mod.rs"));
+    assert!(!path_matches("src/*/mod.rs", "src/Chap05/sub/mod.rs"));
+    assert!(path_matches("**/tests/*.rs", "crates/a/tests/t.rs"));
+}
+
+#[test]
+fn test_budget_usage() {
+    let config = VeracityConfig::from_str(POLICY).unwrap();
+    let files = vec![
+        (PathBuf::from("src/Chap05/SetStEph.rs"), 2),
+        (PathBuf::from("src/Chap05/SetMtEph.rs"), 1),
+        (PathBuf::from("src/Chap06/Graph.rs"), 7),
+    ];
+    let usage = config.budget_usage(&files);
+    assert_eq!(usage.len(), 1);
+    assert_eq!(usage[0].holes, 3);
+    assert!(usage[0].exceeded());
+}
+
+#[test]
+fn test_invalid_severity_rejected() {
+    assert!(VeracityConfig::from_str("[severity]\nexternal_body = \"fatal\"\n").is_err());
+}
@@ -0,0 +1,37 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Tests for local dependency discovery.
+
+use std::fs;
+use std::path::Path;
+use veracity::deps::{local_dependencies, DepSource};
+
+fn write_crate(dir: &Path, name: &str, deps: &str) {
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("Cargo.toml"), format!("[package]\nname = \"{}\"\nversion = \"1.0.0\"\n\n{}", name, deps)).unwrap();
+    fs::write(dir.join("src/lib.rs"), "").unwrap();
+}
+
+#[test]
+fn test_path_and_vendored_deps() {
+    let root = std::env::temp_dir().join(format!("veracity_deps_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    write_crate(&root.join("app"), "app", "[dependencies]\nhelpers = { path = \"../helpers\" }\nvendored = \"1\"\nremote = \"2\"\n\n[dev-dependencies]\ntesting = { path = \"../testing\" }\n");
+    write_crate(&root.join("helpers"), "helpers", "[dependencies]\nbase = { path = \"../base\" }\napp = { path = \"../app\" }\n");
+    write_crate(&root.join("base"), "base", "");
+    write_crate(&root.join("testing"), "testing", "");
+    write_crate(&root.join("app/vendor/vendored-1.0.0"), "vendored", "");
+
+    let deps = local_dependencies(&root.join("app/Cargo.toml")).unwrap();
+    let found: Vec<(&str, DepSource, &str)> = deps.iter()
+        .map(|d| (d.name.as_str(), d.source, d.required_by.as_str()))
+        .collect();
+    assert_eq!(found, vec![
+        ("helpers", DepSource::Path, "app"),
+        ("vendored", DepSource::Vendored, "app"),
+        ("base", DepSource::Path, "helpers"),
+    ]);
+    assert!(deps[0].source_dir().ends_with("helpers/src"));
+    fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,151 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Tests for parsing Verus diagnostics.
+
+use veracity::diagnostics::{innermost_fn, parse_output, parse_stderr, DiagnosticKind, Level, Span, VerifyScope};
+use veracity::holes::FnNode;
+
+const STDERR: &str = "\
+note: automatically chose triggers for this expression:
+  --> src/seq.rs:8:9
+   |
+8  |         forall|i: int| 0 <= i < s.len() ==> s[i] > 0
+   |         ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+note:   trigger 1 of 1:
+  --> src/seq.rs:8:45
+   |
+8  |         forall|i: int| 0 <= i < s.len() ==> s[i] > 0
+   |                                             ^^^^
+
+error: precondition not satisfied
+  --> src/seq.rs:20:5
+   |
+20 |     lemma_pos(s);
+   |     ^^^^^^^^^^^^
+   |
+  ::: src/lemmas.rs:3:14
+   |
+3  |     requires s.len() > 0,
+   |              ----------- failed precondition
+
+error: postcondition not satisfied
+  --> src/seq.rs:30:1
+   |
+27 |       ensures r > 0,
+   |               ----- failed this postcondition
+...
+30 | / {
+31 | |     0
+32 | | }
+   | |_^ at the end of the function body
+
+error: assertion failed
+  --> src/seq.rs:41:12
+   |
+41 |     assert(x - y > 0);
+   |            ^^^^^^^^^ assertion failed
+   |
+   = note: the assertion is in a proof block
+
+error[E0425]: cannot find function `lemma_gone` in this scope
+  --> src/seq.rs:50:5
+   |
+50 |     lemma_gone(s);
+   |     ^^^^^^^^^^ not found in this scope
+
+error: Resource limit (rlimit) exceeded; consider rerunning with --profile for more details
+  --> src/seq.rs:60:1
+   |
+60 | fn slow() {
+   | ^^^^^^^^^
+
+error: aborting due to 4 previous errors; 1 warning emitted
+
+thread 'rustc' panicked at rust_verify/src/verifier.rs:100:5:
+index out of bounds
+";
+
+#[test]
+fn test_parse_stderr() {
+    let ds = parse_stderr(STDERR);
+    let kinds: Vec<DiagnosticKind> = ds.iter().map(|d| d.kind).collect();
+    assert_eq!(kinds, vec![
+        DiagnosticKind::Trigger,
+        DiagnosticKind::Trigger,
+        DiagnosticKind::PreconditionFailed,
+        DiagnosticKind::PostconditionFailed,
+        DiagnosticKind::AssertionFailed,
+        DiagnosticKind::Other,
+        DiagnosticKind::RlimitExceeded,
+        DiagnosticKind::Panic,
+    ]);
+
+    assert_eq!(ds[1].level, Level::Note);
+    assert_eq!(ds[1].message, "trigger 1 of 1:");
+    let trigger = ds[1].primary.as_ref().unwrap();
+    assert_eq!((trigger.line, trigger.column, trigger.end_column), (8, 45, 49));
+
+    let pre = &ds[2];
+    assert_eq!(pre.primary, Some(Span { file: "src/seq.rs".into(), line: 20, column: 5, end_line: 20, end_column: 17, label: None }));
+    assert_eq!(pre.secondary, vec![Span {
+        file: "src/lemmas.rs".into(), line: 3, column: 14, end_line: 3, end_column: 25, label: Some("failed precondition".into()),
+    }]);
+
+    let post = &ds[3];
+    let body = post.primary.as_ref().unwrap();
+    assert_eq!((body.line, body.column, body.end_line, body.end_column), (30, 1, 32, 2));
+    assert_eq!(body.label.as_deref(), Some("at the end of the function body"));
+    assert_eq!(post.secondary[0].line, 27);
+    assert_eq!(post.secondary[0].label.as_deref(), Some("failed this postcondition"));
+
+    assert_eq!(ds[4].notes, vec!["note: the assertion is in a proof block"]);
+    assert_eq!(ds[5].code.as_deref(), Some("E0425"));
+    assert_eq!(ds[7].notes, vec!["index out of bounds"]);
+    assert!(ds.iter().all(|d| !d.message.starts_with("aborting")));
+}
+
+#[test]
+fn test_parse_json() {
+    let stdout = r#"{"reason":"compiler-artifact","target":{"name":"dep"}}
+{"reason":"compiler-message","message":{"$message_type":"diagnostic","message":"precondition not satisfied","code":null,"level":"error","spans":[{"file_name":"src/seq.rs","line_start":20,"line_end":20,"column_start":5,"column_end":17,"is_primary":true,"label":null},{"file_name":"src/lemmas.rs","line_start":3,"line_end":3,"column_start":14,"column_end":25,"is_primary":false,"label":"failed precondition"}],"children":[]}}
+{"reason":"compiler-message","message":{"$message_type":"diagnostic","message":"aborting due to 1 previous error","code":null,"level":"error","spans":[],"children":[]}}
+"#;
+    let stderr = "   Compiling seq v0.1.0\nerror: could not compile `seq` (lib) due to 1 previous error\n";
+    let ds = parse_output(stdout, stderr);
+    assert_eq!(ds.len(), 1);
+    assert_eq!(ds[0].kind, DiagnosticKind::PreconditionFailed);
+    assert_eq!(ds[0].primary.as_ref().unwrap().end_column, 17);
+    assert_eq!(ds[0].secondary[0].label.as_deref(), Some("failed precondition"));
+
+    // No JSON: the rendered stderr is parsed instead
+    assert_eq!(parse_output("", STDERR).len(), 8);
+}
+
+#[test]
+fn test_kinds_and_enclosing_fn() {
+    assert_eq!(DiagnosticKind::classify("invariant not satisfied before loop"), DiagnosticKind::OtherObligation);
+    assert_eq!(DiagnosticKind::classify("possible arithmetic underflow/overflow"), DiagnosticKind::OtherObligation);
+    assert_eq!(DiagnosticKind::classify("expected one of `,` or `}`, found `;`"), DiagnosticKind::Syntax);
+    assert!(DiagnosticKind::RlimitExceeded.is_verification_failure());
+    assert!(!DiagnosticKind::Syntax.is_verification_failure());
+
+    let fns = vec![
+        FnNode { name: "outer".into(), start_line: 1, end_line: 20, ..Default::default() },
+        FnNode { name: "len".into(), self_type: Some("Seq".into()), start_line: 5, end_line: 9, ..Default::default() },
+    ];
+    assert_eq!(innermost_fn(&fns, 6).map(FnNode::qualified_name).as_deref(), Some("Seq::len"));
+    assert_eq!(innermost_fn(&fns, 12).map(FnNode::qualified_name).as_deref(), Some("outer"));
+    assert!(innermost_fn(&fns, 30).is_none());
+}
+
+#[test]
+fn test_verify_scope_args() {
+    assert!(VerifyScope::Crate.args().is_empty());
+    let modules = VerifyScope::Modules(vec!["".into(), "Chap05::SetStEph".into()]);
+    assert_eq!(modules.args(), vec!["--verify-root", "--verify-module", "Chap05::SetStEph"]);
+    assert_eq!(modules.module_count(), Some(2));
+    let function = VerifyScope::Function { module: "Chap05::SetStEph".into(), function: "lemma_len".into() };
+    assert_eq!(function.args(), vec!["--verify-module", "Chap05::SetStEph", "--verify-function", "lemma_len"]);
+}
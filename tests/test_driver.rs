@@ -0,0 +1,117 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Tests for the single-parse analysis driver and its analyzers.
+
+use std::path::{Path, PathBuf};
+use veracity::analyzers;
+use veracity::config::{Severity, VeracityConfig};
+use veracity::driver::ParsedFile;
+
+const SRC: &str = "use vstd::prelude::*;
+
+verus! {
+spec fn sum(n: nat) -> nat {
+    if n == 0 { 0 } else { n + sum((n - 1) as nat) }
+}
+fn count_up(n: u64) {
+    let mut i = 0;
+    while i < n {
+        i = i + 1;
+    }
+}
+proof fn reflexive() ensures forall|x: int| x == x { }
+} // verus!
+";
+
+fn finding_lines(driver: &veracity::driver::Driver, file: &ParsedFile, name: &str) -> Vec<usize> {
+    let idx = driver.analyzer_names().iter().position(|n| *n == name).unwrap();
+    driver.analyze_file(file)[idx].findings.iter().map(|f| f.line).collect()
+}
+
+#[test]
+fn test_parse_once() {
+    let file = ParsedFile::parse(Path::new("a.rs"), SRC.to_string());
+    assert_eq!(file.verus_blocks.len(), 1);
+    assert_eq!(file.line_at(0), 1);
+    assert_eq!(file.line_at(SRC.find("verus!").unwrap()), 3);
+    assert!(file.verus_syn_file(&file.verus_blocks[0]).is_some());
+}
+
+#[test]
+fn test_all_analyzers_on_one_parse() {
+    let file = ParsedFile::parse(Path::new("a.rs"), SRC.to_string());
+    let driver = analyzers::driver_for(&[]).unwrap();
+    assert_eq!(finding_lines(&driver, &file, "termination"), vec![4]);
+    assert_eq!(finding_lines(&driver, &file, "invariants"), vec![9]);
+    assert_eq!(finding_lines(&driver, &file, "trigger-patterns"), vec![13]);
+    assert_eq!(finding_lines(&driver, &file, "requires-ensures"), vec![7]);
+    assert_eq!(finding_lines(&driver, &file, "proof-state"), vec![7]);
+}
+
+#[test]
+fn test_severity_policy_by_analyzer_name() {
+    let config = VeracityConfig::from_str("
+[severity]
+termination = \"error\"
+invariants = \"off\"
+
+[[severity.paths]]
+glob = \"lib/**\"
+termination = \"info\"
+").unwrap();
+    let driver = analyzers::driver_for(&[]).unwrap().with_config(config);
+    let severities = |path: &str, name: &str| {
+        let file = ParsedFile::parse(Path::new(path), SRC.to_string());
+        let idx = driver.analyzer_names().iter().position(|n| *n == name).unwrap();
+        driver.analyze_file(&file)[idx].findings.iter().map(|f| f.severity).collect::<Vec<_>>()
+    };
+    assert_eq!(severities("a.rs", "termination"), vec![Severity::Error]);
+    assert_eq!(severities("lib/a.rs", "termination"), vec![Severity::Info]);
+    assert!(severities("a.rs", "invariants").is_empty());
+    assert_eq!(severities("a.rs", "trigger-patterns"), vec![Severity::Warning]);
+}
+
+#[test]
+fn test_run_combines_files() {
+    let dir = std::env::temp_dir().join(format!("veracity_driver_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let a = dir.join("a.rs");
+    let b = dir.join("b.rs");
+    std::fs::write(&a, SRC).unwrap();
+    std::fs::write(&b, SRC).unwrap();
+    let missing: PathBuf = dir.join("missing.rs");
+
+    let driver = analyzers::driver_for(&["termination".to_string()]).unwrap();
+    let report = driver.run(&[a.clone(), missing, b.clone()]);
+    assert_eq!(report.files, 3);
+    assert_eq!(report.verus_files, 2);
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.analyzers.len(), 1);
+    let paths: Vec<&PathBuf> = report.analyzers[0].findings.iter().map(|f| &f.path).collect();
+    assert_eq!(paths, vec![&a, &b]);
+    assert_eq!(report.analyzers[0].counts["recursive"], 2);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_unknown_analyzer_rejected() {
+    assert!(analyzers::driver_for(&["no-such-review".to_string()]).is_err());
+}
+
+#[test]
+fn test_signature_name_is_not_recursion() {
+    let src = "verus! {
+spec fn double(x: int) -> int { x + x }
+proof fn lemma_double(x: int) ensures double(x) == 2 * x { }
+spec fn count(n: nat) -> nat { if n == 0 { 0 } else { 1 + count((n - 1) as nat) } }
+} // verus!
+";
+    let file = ParsedFile::parse(Path::new("a.rs"), src.to_string());
+    let stats = analyzers::termination::analyze(&file);
+    let recursive: Vec<&str> = stats.functions.iter()
+        .filter(|f| f.is_recursive)
+        .map(|f| f.name.as_str())
+        .collect();
+    assert_eq!(recursive, vec!["count"]);
+}
@@ -0,0 +1,73 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Tests for grouping files into a reporting hierarchy.
+
+use veracity::grouping::{natural_cmp, Grouping};
+use veracity::VeracityConfig;
+use std::cmp::Ordering;
+use std::fs;
+use std::path::Path;
+
+fn grouping(toml: &str) -> Grouping {
+    Grouping::new(&VeracityConfig::from_str(toml).unwrap()).unwrap()
+}
+
+#[test]
+fn test_chapters_preset() {
+    let g = Grouping::chapters(Path::new(""));
+    assert_eq!(g.label(), "Chapter");
+    assert_eq!(g.group_of("src/Chap43/OrderedSet.rs").as_deref(), Some("Chap43"));
+    assert_eq!(g.group_of("src/Concurrency.rs").as_deref(), Some("Concurrency"));
+    assert_eq!(g.group_of("tests/Chap43/Test.rs"), None);
+
+    let mut groups: Vec<String> = ["ParaPairs", "Chap10", "Concurrency", "Chap02"].map(String::from).to_vec();
+    g.sort(&mut groups);
+    assert_eq!(groups, vec!["Chap02", "Chap10", "Concurrency", "ParaPairs"]);
+    assert!(g.is_foundation("Concurrency"));
+    assert!(!g.is_foundation("Chap10"));
+}
+
+#[test]
+fn test_regex_and_module() {
+    let g = grouping("[grouping]\nby = \"regex\"\npattern = \"^crates/([^/]+)/src/([^/]+)/\"\nlabel = \"Component\"\n");
+    assert_eq!(g.label(), "Component");
+    assert_eq!(g.group_of("crates/core/src/seq/Seq.rs").as_deref(), Some("core/seq"));
+    assert_eq!(g.group_of("crates/core/src/lib.rs"), None);
+    assert!(!g.is_foundation("core/seq"));
+
+    let g = grouping("[grouping]\nby = \"module\"\ndepth = 2\n");
+    assert_eq!(g.group_of("src/graph/bfs/queue.rs").as_deref(), Some("graph::bfs"));
+    assert_eq!(g.group_of("crates/a/src/graph/mod.rs").as_deref(), Some("graph"));
+    assert_eq!(g.group_of("src/lib.rs"), None);
+
+    assert!(VeracityConfig::from_str("[grouping]\nby = \"regex\"\n").is_err());
+    assert!(VeracityConfig::from_str("[grouping]\nby = \"regex\"\npattern = \"(\"\n").is_err());
+}
+
+#[test]
+fn test_workspace_members() {
+    let dir = std::env::temp_dir().join(format!("veracity_test_grouping_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    for (member, name) in [("crates/alpha", "alpha-core"), ("crates/beta", "beta"), ("tools/gen", "gen")] {
+        fs::create_dir_all(dir.join(member).join("src")).unwrap();
+        fs::write(dir.join(member).join("Cargo.toml"), format!("[package]\nname = \"{}\"\n", name)).unwrap();
+    }
+    fs::write(dir.join("Cargo.toml"), "[workspace]\nmembers = [\"crates/*\", \"tools/gen\"]\n").unwrap();
+    fs::write(dir.join("veracity.toml"), "[grouping]\nby = \"workspace\"\n").unwrap();
+
+    let g = Grouping::new(&VeracityConfig::from_file(&dir.join("veracity.toml")).unwrap()).unwrap();
+    assert_eq!(g.label(), "Crate");
+    assert_eq!(g.group_of("crates/alpha/src/lib.rs").as_deref(), Some("alpha-core"));
+    assert_eq!(g.group_of("tools/gen/src/main.rs").as_deref(), Some("gen"));
+    assert_eq!(g.group_of("docs/x.rs"), None);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_natural_cmp() {
+    assert_eq!(natural_cmp("part2", "part10"), Ordering::Less);
+    assert_eq!(natural_cmp("part02", "part2"), Ordering::Greater);
+    assert_eq!(natural_cmp("alpha", "beta"), Ordering::Less);
+    assert_eq!(natural_cmp("v1.10", "v1.9"), Ordering::Greater);
+}
@@ -0,0 +1,525 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Tests for the proof-hole detection library API.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+use veracity::args::StandardArgs;
+use veracity::config::{JustificationPolicy, VeracityConfig};
+use veracity::diagnostics::{parse_stderr, VerusRun};
+use veracity::holes::baseline::{collect_hole_keys, compare};
+use veracity::holes::collect::{
+    blame_holes, parse_blame_porcelain, record_verification_failures, verus_errors, Analyzer, HistoryAnalyzer, VerusError,
+};
+use veracity::holes::report::{build_json_report, HoleAttribution, AGE_BUCKETS};
+use veracity::holes::summary::{compute_depends_upon, compute_summary};
+use veracity::holes::{analyze_source, DetectedHole, FileHoles, HoleKind};
+
+const SRC: &str = "verus! {
+proof fn p()
+    ensures true,
+{
+    admit();
+}
+#[verifier::external_body]
+fn e() -> u64 { 0 }
+} // verus!
+";
+
+#[test]
+fn test_analyze_source_finds_holes() {
+    let file = analyze_source(SRC, Path::new("src/a.rs"));
+    assert_eq!(file.path, Path::new("src/a.rs"));
+    let admit = file.holes.holes.iter().find(|h| h.kind == HoleKind::Admit).unwrap();
+    assert_eq!(admit.line, 5);
+    assert_eq!(file.holes.admit_count, 1);
+    assert_eq!(file.holes.external_body_count, 1);
+    assert!(file.holes.holes.iter().any(|h| h.kind == HoleKind::ExternalBody));
+    assert!(!file.is_not_verusified());
+}
+
+#[test]
+fn test_plain_rust_not_verusified() {
+    let file = analyze_source("fn main() {}\n", Path::new("src/main.rs"));
+    assert!(file.is_not_verusified());
+    assert_eq!(file.holes.total_holes, 0);
+}
+
+#[test]
+fn test_hole_kind_names() {
+    for kind in HoleKind::ALL {
+        assert_eq!(HoleKind::from_name(kind.as_str()), Some(*kind));
+    }
+    assert_eq!(HoleKind::AssumeFalse.to_string(), "assume(false)");
+    assert!(HoleKind::ExternalBodyAcceptHole.is_accept_hole());
+    assert!(HoleKind::FnMissingEnsures.is_missing_spec());
+}
+
+#[test]
+fn test_hole_serializes_as_hole_type() {
+    let hole = DetectedHole { line: 3, kind: HoleKind::Admit, context: "admit();".to_string(), blame: None };
+    let json = serde_json::to_string(&hole).unwrap();
+    assert!(json.contains("\"hole_type\":\"admit()\""));
+    assert!(!json.contains("blame"));
+    let back: DetectedHole = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.kind, HoleKind::Admit);
+}
+
+#[test]
+fn test_soft_holes_reported_separately() {
+    let src = "verus! {
+#[verifier::rlimit(infinity)]
+proof fn slow() ensures true { }
+#[verifier::rlimit(20)]
+proof fn bounded() ensures true { }
+#[verifier::loop_isolation(false)]
+#[verifier::exec_allows_no_decreases_clause]
+fn spin() { loop { } }
+} // verus!
+";
+    let file = analyze_source(src, Path::new("src/a.rs"));
+    let soft: Vec<(usize, HoleKind)> = file.soft_holes.iter().map(|h| (h.line, h.kind)).collect();
+    assert_eq!(
+        soft,
+        vec![
+            (2, HoleKind::RlimitInfinity),
+            (6, HoleKind::LoopIsolationFalse),
+            (7, HoleKind::ExecAllowsNoDecreasesClause),
+        ]
+    );
+    assert_eq!(file.holes.total_holes, 0);
+    assert_eq!(file.soft_hole_counts()[&HoleKind::RlimitInfinity], 1);
+    assert!(HoleKind::Truncate.is_soft_hole());
+}
+
+#[test]
+fn test_macro_holes_counted_at_use_and_definition() {
+    let src = "macro_rules! trust_me {
+    ($e:expr) => { assume($e) };
+}
+verus! {
+macro_rules! give_up {
+    () => { trust_me!(false); admit(); };
+}
+proof fn p(x: int)
+    ensures x == x,
+{
+    give_up!();
+}
+} // verus!
+";
+    let file = analyze_source(src, Path::new("src/a.rs"));
+    let at_use: Vec<(usize, HoleKind)> = file.holes.holes.iter().map(|h| (h.line, h.kind)).collect();
+    assert_eq!(at_use, vec![(11, HoleKind::Admit), (11, HoleKind::Assume)]);
+    assert_eq!(file.holes.total_holes, 2);
+    assert_eq!(file.holed_proof_functions, 1);
+    let defs: Vec<usize> = file.warnings.iter()
+        .filter(|w| w.kind == HoleKind::MacroWithHoles)
+        .map(|w| w.line)
+        .collect();
+    assert_eq!(defs, vec![2, 6]);
+}
+
+#[test]
+fn test_accepted_external_body_is_info() {
+    let src = "verus! {
+#[accepted_external_body]
+fn new_lock() -> u64 { 0 }
+
+// veracity-accept: reason=verus-limitation
+#[verifier::external_body]
+fn e() -> u64 { 0 }
+} // verus!
+";
+    let mut file = analyze_source(src, Path::new("src/a.rs"));
+    assert_eq!(file.holes.total_holes, 0);
+    let infos: Vec<(usize, HoleKind)> = file.infos.iter().map(|i| (i.line, i.kind)).collect();
+    assert_eq!(infos, vec![(2, HoleKind::ExternalBodyAcceptHole), (6, HoleKind::ExternalBodyAcceptHole)]);
+
+    // Any category passes without a policy; the unjustified attribute stays accepted for the inventory to flag
+    file.apply_justification_policy(src, &JustificationPolicy::default());
+    assert_eq!(file.holes.total_holes, 0);
+    assert_eq!(file.infos.len(), 2);
+
+    // A category the policy does not list accepts nothing
+    let policy = JustificationPolicy { categories: vec!["eq-clone-workaround".into()], require_ticket: false };
+    file.apply_justification_policy(src, &policy);
+    assert_eq!(file.holes.total_holes, 1);
+    assert_eq!(file.holes.external_body_count, 1);
+    assert_eq!(file.holes.holes[0].line, 6);
+    assert!(file.holes.holes[0].context.contains("unknown justification category `verus-limitation`"));
+    let infos: Vec<(usize, HoleKind)> = file.infos.iter().map(|i| (i.line, i.kind)).collect();
+    assert_eq!(infos, vec![(2, HoleKind::ExternalBodyAcceptHole)]);
+}
+
+#[test]
+fn test_malformed_accept_marker_is_still_a_hole() {
+    let src = "verus! {
+// veracity-accept: trust me
+#[verifier::external_body]
+fn e() -> u64 { 0 }
+
+// accept hole
+// veracity-accept: reason=
+#[verifier::external_body]
+fn f() -> u64 { 0 }
+
+#[verifier::external_body]
+fn g() -> &'static str { \"veracity-accept: reason=verus-limitation\" }
+
+// veracity-accept: reason=verus-limitation
+#[verifier::external_body]
+fn h() -> u64 { 0 }
+} // verus!
+";
+    // f is marked `// accept hole`, but its justification is malformed; g's marker is in a string
+    let mut file = analyze_source(src, Path::new("src/a.rs"));
+    let holes: Vec<(usize, HoleKind)> = file.holes.holes.iter().map(|h| (h.line, h.kind)).collect();
+    assert_eq!(holes, vec![(3, HoleKind::ExternalBody), (8, HoleKind::ExternalBody), (11, HoleKind::ExternalBody)]);
+
+    let policy = JustificationPolicy { categories: Vec::new(), require_ticket: true };
+    file.apply_justification_policy(src, &policy);
+    let holes: Vec<(usize, HoleKind)> = file.holes.holes.iter().map(|h| (h.line, h.kind)).collect();
+    assert_eq!(holes, vec![(3, HoleKind::ExternalBody), (8, HoleKind::ExternalBody), (11, HoleKind::ExternalBody), (15, HoleKind::ExternalBody)]);
+    assert_eq!(file.holes.total_holes, 4);
+    assert!(file.holes.holes[3].context.contains("no ticket="));
+    assert!(file.infos.is_empty());
+}
+
+/// SRC as src/Chap01/A.rs, and a clean src/Chap01/B.rs whose fn calls A's external_body fn
+fn two_file_project() -> HashMap<String, FileHoles> {
+    let b = "use crate::Chap01::A::e;
+
+verus! {
+fn c() -> (r: u64) ensures r == 0 { e() }
+} // verus!
+";
+    let mut files = HashMap::new();
+    files.insert("src/Chap01/A.rs".to_string(), analyze_source(SRC, Path::new("src/Chap01/A.rs")));
+    files.insert("src/Chap01/B.rs".to_string(), analyze_source(b, Path::new("src/Chap01/B.rs")));
+    files
+}
+
+#[test]
+fn test_summary_and_json_report() {
+    let files = two_file_project();
+    let summary = compute_summary(&files, Path::new("."));
+    assert_eq!(summary.total_files, 2);
+    assert_eq!((summary.clean_modules, summary.holed_modules), (1, 1));
+    assert_eq!(summary.holes.total_holes, 2);
+
+    // B is clean itself, but rests on A's external_body through its call
+    let c = summary.trust.iter().find(|t| t.function == "c").unwrap();
+    assert_eq!(c.own_holes, 0);
+    assert_eq!(c.trusted_base.len(), 1);
+    assert_eq!(c.trusted_base[0].hole_type, "external_body");
+
+    let depends = compute_depends_upon(&files);
+    assert_eq!(depends[1], ("Chap01::B".to_string(), "src/Chap01/B.rs".to_string(), vec!["Chap01::A".to_string()]));
+
+    let report = build_json_report(Path::new("."), &files, &summary);
+    assert_eq!(report.files.len(), 2);
+    assert_eq!(report.files[0].module, "Chap01::A");
+    assert!(!report.files[0].clean && report.files[1].clean);
+    assert_eq!(report.summary.truly_clean_fns, 0);
+}
+
+#[test]
+fn test_baseline_survives_line_shifts() {
+    let dir = std::env::temp_dir().join(format!("veracity_test_holes_baseline_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let analyze = |src: &str| {
+        fs::write(dir.join("a.rs"), src).unwrap();
+        let mut files = HashMap::new();
+        files.insert("a.rs".to_string(), analyze_source(src, &dir.join("a.rs")));
+        collect_hole_keys(&dir, &files)
+    };
+    let recorded: std::collections::BTreeMap<_, _> = analyze(SRC).into_iter().map(|(k, lines)| (k, lines.len())).collect();
+    // admit, external_body and p's proof_fn_with_holes error
+    assert_eq!(recorded.len(), 3);
+    assert!(recorded.keys().any(|k| k.function == "p" && k.hole_type == "admit()"));
+
+    // Moving the holes is no change
+    let shifted = format!("\n\n{}", SRC);
+    let current = analyze(&shifted);
+    let diff = compare(&current, &recorded);
+    assert!(diff.new.is_empty() && diff.fixed.is_empty());
+
+    // A second admit in p is new, at its own line
+    let changed = SRC.replace("    admit();", "    admit();\n    admit();");
+    let current = analyze(&changed);
+    let diff = compare(&current, &recorded);
+    assert_eq!(diff.new.len(), 1);
+    assert_eq!(diff.new[0].1, 6);
+    assert!(diff.fixed.is_empty());
+
+    // Against a baseline with both, going back to one admit fixes one
+    let recorded: std::collections::BTreeMap<_, _> = current.into_iter().map(|(k, lines)| (k, lines.len())).collect();
+    let current = analyze(SRC);
+    let diff = compare(&current, &recorded);
+    assert!(diff.new.is_empty());
+    assert_eq!(diff.fixed.len(), 1);
+    assert_eq!((diff.fixed[0].0.hole_type.as_str(), diff.fixed[0].1), ("admit()", 1));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_parse_blame_porcelain() {
+    let sha = "0123456789abcdef0123456789abcdef01234567";
+    let porcelain = format!("{sha} 3 5 1
+author Ada
+author-time 1700000000
+summary add p
+filename src/a.rs
+\t    admit();
+{sha} 9 12 1
+\t    assume(false);
+");
+    let blame = parse_blame_porcelain(&porcelain);
+    assert_eq!(blame.len(), 2);
+    assert_eq!(blame[&5].author, "Ada");
+    assert_eq!(blame[&12].commit, sha);
+    assert_eq!(blame[&12].date, "2023-11-14");
+}
+
+#[test]
+fn test_analyzer_sees_an_edit_after_invalidate() {
+    let dir = std::env::temp_dir().join(format!("veracity_test_holes_invalidate_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("src/Chap01")).unwrap();
+    let file = dir.join("src/Chap01/A.rs");
+    fs::write(&file, "verus! {\nproof fn p(x: bool) {\n    assume(x);\n}\n} // verus!\n").unwrap();
+    let args = standard_args(&dir);
+    let holes = |stats: FileHoles| stats.holes.holes.iter().map(|h| (h.line, h.kind)).collect::<Vec<_>>();
+
+    let mut analyzer = Analyzer::new(&args, true);
+    assert_eq!(holes(analyzer.analyze(&file).unwrap()), vec![(3, HoleKind::Assume)]);
+
+    // Triage edits files mid-run, as here, and invalidates each one it edits
+    fs::write(&file, "verus! {\nproof fn p(x: bool) {\n}\n} // verus!\n").unwrap();
+    analyzer.invalidate(&file);
+    assert_eq!(holes(analyzer.analyze(&file).unwrap()), vec![]);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+/// Arguments for analyzing `dir`, with no veracity.toml
+fn standard_args(dir: &Path) -> StandardArgs {
+    StandardArgs {
+        paths: vec![dir.to_path_buf()],
+        is_module_search: false,
+        project: None,
+        language: "Verus".to_string(),
+        repositories: None,
+        multi_codebase: None,
+        src_dirs: vec!["src".to_string()],
+        test_dirs: Vec::new(),
+        bench_dirs: Vec::new(),
+        config: VeracityConfig::default(),
+    }
+}
+
+/// Run git in `dir`, as a fixed author
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.name=Ada", "-c", "user.email=ada@example.com", "-c", "commit.gpgsign=false"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+#[test]
+fn test_history_analyzer_per_commit() {
+    let dir = std::env::temp_dir().join(format!("veracity_test_holes_history_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("src")).unwrap();
+    git(&dir, &["init", "-q"]);
+    fs::write(dir.join("src/a.rs"), SRC).unwrap();
+    git(&dir, &["add", "-A"]);
+    git(&dir, &["commit", "-q", "-m", "add a"]);
+    fs::write(dir.join("src/b.rs"), "verus! {\nproof fn q(x: bool) {\n    assume(x);\n}\n} // verus!\n").unwrap();
+    git(&dir, &["add", "-A"]);
+    git(&dir, &["commit", "-q", "-m", "add b"]);
+    let first = git(&dir, &["rev-parse", "HEAD~1"]);
+    let args = standard_args(&dir);
+
+    let mut history = HistoryAnalyzer::new(&args, &[], true).unwrap();
+    let commits = history.commits("HEAD").unwrap();
+    let subjects: Vec<&str> = commits.iter().map(|(_, _, s)| s.as_str()).collect();
+    assert_eq!(subjects, vec!["add a", "add b"]);
+    assert_eq!(commits[0].0, first);
+
+    let holes = |files: &HashMap<String, FileHoles>| {
+        let mut holes: Vec<(String, usize)> = files.iter().map(|(p, s)| (p.clone(), s.holes.total_holes)).collect();
+        holes.sort();
+        holes
+    };
+    let at_first = history.analyze_commit(&commits[0].0).unwrap();
+    assert_eq!(holes(&at_first), vec![("src/a.rs".to_string(), 2)]);
+    let at_second = history.analyze_commit(&commits[1].0).unwrap();
+    assert_eq!(holes(&at_second), vec![("src/a.rs".to_string(), 2), ("src/b.rs".to_string(), 1)]);
+    assert_eq!(at_second["src/b.rs"].holes.holes[0].kind, HoleKind::Assume);
+
+    // a.rs is the same blob in both commits, so the second analyzes only b.rs
+    assert_eq!(history.finish(), (1, 2));
+    let mut history = HistoryAnalyzer::new(&args, &[], true).unwrap();
+    history.analyze_commit(&commits[1].0).unwrap();
+    assert_eq!(history.finish(), (2, 0));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_hole_attribution() {
+    let src = "verus! {\nproof fn p() {\n    admit();\n    admit();\n    admit();\n    admit();\n    admit();\n}\n} // verus!\n";
+    let mut file = analyze_source(src, Path::new("src/a.rs"));
+    assert_eq!(file.holes.holes.iter().map(|h| h.line).collect::<Vec<_>>(), vec![3, 4, 5, 6, 7]);
+
+    // Ada's headers come only with her commit's first line; ages straddle 7 and 30 days
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64;
+    let day = 86_400;
+    let header = |n: u8, line: usize, author: &str, age: i64| {
+        format!("{} {line} {line} 1\nauthor {author}\nauthor-time {}\n\t    admit();\n", n.to_string().repeat(40), now - age)
+    };
+    let porcelain = [
+        header(1, 3, "Ada", 7 * day - 3600),
+        format!("{} 4 4\n\t    admit();\n", "1".repeat(40)),
+        header(2, 5, "Bob", 7 * day),
+        header(3, 6, "Ada", 30 * day - 3600),
+        header(4, 7, "Cy", 30 * day),
+    ].concat();
+    let blame = parse_blame_porcelain(&porcelain);
+    for hole in &mut file.holes.holes {
+        hole.blame = blame.get(&hole.line).cloned();
+    }
+    assert_eq!(file.holes.holes[1].blame.as_ref().map(|b| b.author.as_str()), Some("Ada"));
+
+    // An untracked file has no blame
+    let dir = std::env::temp_dir().join(format!("veracity_test_holes_blame_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    git(&dir, &["init", "-q"]);
+    let untracked = dir.join("b.rs");
+    fs::write(&untracked, SRC).unwrap();
+    let mut other = analyze_source(SRC, &untracked);
+    blame_holes(&untracked, &mut other.holes.holes);
+    assert!(other.holes.holes.iter().all(|h| h.blame.is_none()));
+
+    let files = HashMap::from([("src/a.rs".to_string(), file), ("b.rs".to_string(), other)]);
+    let attribution = HoleAttribution::new(&files);
+    let count = |map: &std::collections::BTreeMap<String, usize>, key: &str| map.get(key).copied().unwrap_or(0);
+    assert_eq!((count(&attribution.by_author, "Ada"), count(&attribution.by_author, "Bob"), count(&attribution.by_author, "Cy")), (3, 1, 1));
+    let ages: Vec<usize> = AGE_BUCKETS.iter().map(|(label, _)| count(&attribution.by_age, label)).collect();
+    assert_eq!(ages, vec![2, 2, 1]);
+    assert_eq!(attribution.unattributed, 2);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+const VERIFY_SRC: &str = "verus! {
+pub struct Counter { pub n: u64 }
+
+impl Counter {
+    pub fn bump(&mut self)
+        requires old(self).n < 10,
+        ensures self.n == old(self).n + 1,
+    {
+        self.n = self.n + 2;
+    }
+}
+
+proof fn lemma_pos(x: int)
+    requires x > 0,
+    ensures x >= 1,
+{
+    assert(x > 1);
+    assert(x > 2);
+}
+
+fn ok() {}
+} // verus!
+";
+
+/// Verus stderr for VERIFY_SRC at src/Chap01/A.rs, trimmed to the spans that matter
+const VERIFY_STDERR: &str = "\
+error: postcondition not satisfied
+  --> src/Chap01/A.rs:10:5
+   |
+7  |         ensures self.n == old(self).n + 1,
+   |                 ------------------------- failed this postcondition
+...
+10 |     }
+   |     ^ at the end of the function body
+
+error: assertion failed
+  --> src/Chap01/A.rs:17:12
+   |
+17 |     assert(x > 1);
+   |            ^^^^^ assertion failed
+
+error: assertion failed
+  --> src/Chap01/A.rs:18:12
+   |
+18 |     assert(x > 2);
+   |            ^^^^^ assertion failed
+
+error: struct invariant not satisfied
+  --> src/Chap01/A.rs:2:1
+   |
+2  | pub struct Counter { pub n: u64 }
+   | ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+error: assertion failed
+  --> src/Chap01/B.rs:4:12
+   |
+4  |     assert(false);
+   |            ^^^^^ assertion failed
+
+error: aborting due to 5 previous errors
+
+thread 'rustc' panicked at rust_verify/src/verifier.rs:100:5:
+index out of bounds
+";
+
+#[test]
+fn test_record_verification_failures_from_verus_stderr() {
+    let dir = std::env::temp_dir().join(format!("veracity_test_verify_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("src/Chap01")).unwrap();
+    let path = dir.join("src/Chap01/A.rs");
+    fs::write(&path, VERIFY_SRC).unwrap();
+    let run = VerusRun {
+        success: false,
+        stderr: VERIFY_STDERR.to_string(),
+        diagnostics: parse_stderr(VERIFY_STDERR),
+        duration: Duration::from_secs(3),
+    };
+
+    // The panic has no span and is dropped; B.rs does not exist, so its path stays as joined
+    let errors = verus_errors(&run, &dir);
+    let path = path.canonicalize().unwrap();
+    let in_a: Vec<VerusError> = errors.iter().filter(|e| e.path == path).cloned().collect();
+    assert_eq!(in_a.iter().map(|e| e.line).collect::<Vec<_>>(), vec![10, 17, 18, 2]);
+    assert_eq!(errors.len(), 5);
+    assert_eq!(errors[4].path, dir.join("src/Chap01/B.rs"));
+
+    let mut file = analyze_source(VERIFY_SRC, &path);
+    record_verification_failures(&mut file, &in_a);
+    let failures: Vec<(usize, &str)> = file.holes.holes.iter()
+        .filter(|h| h.kind == HoleKind::VerificationFailure)
+        .map(|h| (h.line, h.context.as_str()))
+        .collect();
+    assert_eq!(failures, vec![
+        (2, "struct invariant not satisfied"),
+        (5, "fn Counter::bump: postcondition not satisfied"),
+        (13, "fn lemma_pos: assertion failed"),
+    ]);
+    assert_eq!(file.holes.verification_failure_count, 3);
+    assert!(file.holes.holes.windows(2).all(|w| w[0].line <= w[1].line));
+    let _ = fs::remove_dir_all(&dir);
+}
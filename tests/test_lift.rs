@@ -0,0 +1,40 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Tests for lifting assume(P) into admitted lemmas.
+
+use veracity::lift::lift_assumes;
+
+const SRC: &str = "verus! {
+//\t\t6. spec fns
+spec fn f(x: int) -> int { x }
+
+//\t\t7. proof fns/broadcast groups
+proof fn existing() { }
+
+//\t\t9. impls
+fn push_all(v: Vec<u64>, n: u64) {
+    let k: u64 = n + 1;
+    assume(v.len() < k);
+    for i in 0..n {
+        assume(i < k);
+    }
+}
+} // verus!
+";
+
+#[test]
+fn test_lift_assume_into_lemma() {
+    let result = lift_assumes(SRC, &[11, 13]);
+    let lifted: Vec<(usize, &str)> = result.lifted.iter().map(|l| (l.line, l.lemma.as_str())).collect();
+    assert_eq!(lifted, vec![(11, "lemma_push_all_1")]);
+    assert_eq!(result.skipped.len(), 1);
+    assert_eq!(result.skipped[0].line, 13);
+    assert!(result.skipped[0].reason.contains("`i`"));
+
+    assert!(result.content.contains("    proof { lemma_push_all_1(v, k); }\n    for i in 0..n {"));
+    assert!(result.content.contains(
+        "proof fn existing() { }\n\nproof fn lemma_push_all_1(v: Vec<u64>, k: u64)\n    ensures\n        v.len() < k,\n{\n    admit();\n}\n\n//\t\t9. impls"
+    ));
+    assert!(result.content.contains("        assume(i < k);"));
+}
@@ -0,0 +1,246 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Tests for minimize-lib's group testing and resume checks.
+
+use anyhow::Result;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use veracity::minimize::{group_test, merge_verdicts, partition_search, InFlightEdit, RemovalSet, ResumeGuard, SearchEnv};
+
+/// Which candidates are commented out, shared by the fake set and the fake Verus
+#[derive(Default)]
+struct FakeTree {
+    removed: BTreeSet<usize>,
+    pending: Vec<usize>,
+}
+
+/// Candidates c0, c1, ...; comments them out of the FakeTree
+struct FakeSet<'a> {
+    labels: Vec<String>,
+    tree: &'a RefCell<FakeTree>,
+}
+
+impl RemovalSet for FakeSet<'_> {
+    fn label(&self, i: usize) -> &str {
+        &self.labels[i]
+    }
+
+    fn files(&self, _idx: &[usize]) -> Vec<&Path> {
+        Vec::new()
+    }
+
+    fn comment_out(&mut self, idx: &[usize]) -> Result<()> {
+        self.tree.borrow_mut().pending.extend_from_slice(idx);
+        Ok(())
+    }
+
+    fn restore(&mut self) -> Result<()> {
+        self.tree.borrow_mut().pending.clear();
+        Ok(())
+    }
+
+    fn keep(&mut self) -> Result<()> {
+        let mut tree = self.tree.borrow_mut();
+        let pending: Vec<usize> = tree.pending.drain(..).collect();
+        tree.removed.extend(pending);
+        Ok(())
+    }
+}
+
+/// Verifies while none of `needed` is commented out, counting Verus calls
+struct FakeVerus<'a> {
+    needed: BTreeSet<usize>,
+    tree: &'a RefCell<FakeTree>,
+    calls: usize,
+    settled: Vec<(usize, bool)>,
+    merged: Vec<usize>,
+}
+
+impl SearchEnv for FakeVerus<'_> {
+    fn verify(&mut self) -> Result<(bool, Duration)> {
+        self.calls += 1;
+        let tree = self.tree.borrow();
+        let success = !tree.removed.iter().chain(&tree.pending).any(|i| self.needed.contains(i));
+        Ok((success, Duration::from_secs(1)))
+    }
+
+    fn settle(&mut self, i: usize, needed: bool, _verify_time: Duration) {
+        self.settled.push((i, needed));
+    }
+
+    fn merged(&mut self, i: usize) {
+        self.merged.push(i);
+    }
+}
+
+/// Group-test `n` candidates of which `needed` must stay; returns the needed
+/// verdicts, the Verus calls made and the candidates settled
+fn run_search(n: usize, needed: &[usize], settled: &[Option<(bool, Duration)>]) -> (Vec<usize>, usize, usize) {
+    let tree = RefCell::new(FakeTree::default());
+    let mut set = FakeSet { labels: (0..n).map(|i| format!("c{}", i)).collect(), tree: &tree };
+    let mut verus = FakeVerus { needed: needed.iter().copied().collect(), tree: &tree, calls: 0, settled: Vec::new(), merged: Vec::new() };
+    let verdicts = group_test(&mut set, &mut verus, settled, Duration::from_secs(2)).unwrap();
+    let found: Vec<usize> = (0..n).filter(|&i| verdicts[i].0).collect();
+    (found, verus.calls, verus.settled.len())
+}
+
+#[test]
+fn test_partition_search_finds_needed() {
+    // 8 candidates, 2 of them needed
+    let (found, calls, settled) = run_search(8, &[2, 5], &[None; 8]);
+    assert_eq!(found, vec![2, 5]);
+    assert_eq!(calls, 9);
+    assert_eq!(settled, 8);
+
+    // Nothing needed: one run removes everything
+    let (found, calls, _) = run_search(8, &[], &[None; 8]);
+    assert!(found.is_empty());
+    assert_eq!(calls, 1);
+
+    // Everything needed: every batch fails down to single candidates
+    let (found, calls, _) = run_search(4, &[0, 1, 2, 3], &[None; 4]);
+    assert_eq!(found, vec![0, 1, 2, 3]);
+    assert_eq!(calls, 7);
+}
+
+#[test]
+fn test_group_test_skips_settled() {
+    // c1 was settled as needed before the run stopped; c3 as removable
+    let mut settled = vec![None; 4];
+    settled[1] = Some((true, Duration::ZERO));
+    settled[3] = Some((false, Duration::ZERO));
+    let (found, calls, newly_settled) = run_search(4, &[1, 2], &settled);
+    assert_eq!(found, vec![1, 2]);
+    assert_eq!(newly_settled, 2);
+    // {c0, c2} fails, c0 goes, so c2 is needed without a run of its own
+    assert_eq!(calls, 2);
+}
+
+#[test]
+fn test_partition_search_empty() {
+    let tree = RefCell::new(FakeTree::default());
+    let mut set = FakeSet { labels: Vec::new(), tree: &tree };
+    let mut verus = FakeVerus { needed: BTreeSet::new(), tree: &tree, calls: 0, settled: Vec::new(), merged: Vec::new() };
+    assert!(partition_search(&mut set, &mut verus, &[], false, Duration::ZERO, &mut []).unwrap());
+    assert_eq!(verus.calls, 0);
+}
+
+/// What a merge did: whether it held, the tree's removals and the fake Verus's record
+struct MergeRun {
+    confirmed: bool,
+    removed: BTreeSet<usize>,
+    calls: usize,
+    settled: Vec<(usize, bool)>,
+    merged: Vec<usize>,
+}
+
+/// Merge scratch-copy `verdicts` for c0..c3 into a fake tree where `needed` must stay
+fn run_merge(needed: &[usize], verdicts: &mut [(bool, Duration)]) -> MergeRun {
+    let tree = RefCell::new(FakeTree::default());
+    let mut set = FakeSet { labels: (0..4).map(|i| format!("c{}", i)).collect(), tree: &tree };
+    let mut verus = FakeVerus { needed: needed.iter().copied().collect(), tree: &tree, calls: 0, settled: Vec::new(), merged: Vec::new() };
+    let confirmed = merge_verdicts(&mut set, &mut verus, &[0, 1, 2, 3], verdicts).unwrap();
+    assert!(tree.borrow().pending.is_empty());
+    let removed = tree.borrow().removed.clone();
+    MergeRun { confirmed, removed, calls: verus.calls, settled: verus.settled, merged: verus.merged }
+}
+
+#[test]
+fn test_merge_verdicts_confirmed() {
+    let mut verdicts = vec![(false, Duration::ZERO), (true, Duration::ZERO), (false, Duration::ZERO), (false, Duration::ZERO)];
+    let run = run_merge(&[1], &mut verdicts);
+    assert!(run.confirmed);
+    assert_eq!(run.removed, BTreeSet::from([0, 2, 3]));
+    assert_eq!(run.calls, 1);
+    assert!(run.settled.is_empty());
+    assert_eq!(run.merged, vec![0, 1, 2, 3]);
+    assert_eq!(verdicts.iter().map(|v| v.0).collect::<Vec<_>>(), vec![false, true, false, false]);
+}
+
+#[test]
+fn test_merge_verdicts_rolls_back_a_failed_merge() {
+    // The copies said c3 could go, but the real tree needs it
+    let mut verdicts = vec![(false, Duration::ZERO), (true, Duration::ZERO), (false, Duration::ZERO), (false, Duration::ZERO)];
+    let run = run_merge(&[1, 3], &mut verdicts);
+    assert!(!run.confirmed);
+    assert_eq!(run.removed, BTreeSet::from([0, 2]));
+    // The merge, then c0, c2 and c3 one at a time
+    assert_eq!(run.calls, 4);
+    assert_eq!(run.settled, vec![(0, false), (2, false), (3, true)]);
+    assert_eq!(run.merged, vec![1, 0, 2, 3]);
+    assert_eq!(verdicts.iter().map(|v| v.0).collect::<Vec<_>>(), vec![false, true, false, true]);
+    assert_eq!(verdicts[3].1, Duration::from_secs(1));
+
+    // Nothing to remove: no Verus run at all
+    let mut verdicts = vec![(true, Duration::ZERO); 4];
+    let run = run_merge(&[0, 1, 2, 3], &mut verdicts);
+    assert!(run.confirmed && run.removed.is_empty());
+    assert_eq!(run.calls, 0);
+    assert_eq!(run.merged, vec![0, 1, 2, 3]);
+}
+
+fn scratch_tree(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("veracity_test_minimize_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("src/a.rs"), "fn a() {}\n").unwrap();
+    fs::write(dir.join("src/b.rs"), "fn b() {}\n").unwrap();
+    dir
+}
+
+#[test]
+fn test_resume_refused_after_change() {
+    let dir = scratch_tree("resume");
+    let roots = vec![dir.clone()];
+    let args = vec!["-c".to_string(), dir.display().to_string()];
+    let mut guard = ResumeGuard { args: args.clone(), ..Default::default() };
+    guard.record(&roots).unwrap();
+    assert!(guard.problems(&args, &roots).unwrap().is_empty());
+
+    // Different options
+    let other = vec!["-c".to_string(), "elsewhere".to_string()];
+    assert_eq!(guard.problems(&other, &roots).unwrap().len(), 1);
+
+    // A file changed, one removed and one added after the checkpoint
+    fs::write(dir.join("src/a.rs"), "fn a() { assert(true); }\n").unwrap();
+    fs::remove_file(dir.join("src/b.rs")).unwrap();
+    fs::write(dir.join("src/c.rs"), "fn c() {}\n").unwrap();
+    let problems = guard.problems(&args, &roots).unwrap();
+    assert_eq!(problems.len(), 3);
+    assert!(problems[0].starts_with("Changed since the run stopped:") && problems[0].ends_with("a.rs"));
+    assert!(problems[1].starts_with("Removed since the run stopped:") && problems[1].ends_with("b.rs"));
+    assert!(problems[2].starts_with("Added since the run stopped:") && problems[2].ends_with("c.rs"));
+
+    // Checkpointing again accepts the tree as it now is
+    guard.record(&roots).unwrap();
+    assert!(guard.problems(&args, &roots).unwrap().is_empty());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_recover_in_flight_edit() {
+    let dir = scratch_tree("recover");
+    let roots = vec![dir.clone()];
+    let a = dir.join("src/a.rs");
+    let mut guard = ResumeGuard::default();
+    guard.record(&roots).unwrap();
+
+    // The run stops mid-edit: the hashes stay those from before it
+    let original = fs::read_to_string(&a).unwrap();
+    guard.in_flight = Some(InFlightEdit { label: "assert L1 in a".to_string(), originals: vec![(a.clone(), original.clone())] });
+    fs::write(&a, "// TESTING fn a() {}\n").unwrap();
+    guard.record(&roots).unwrap();
+    let problems = guard.problems(&[], &roots).unwrap();
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].contains("--recover"));
+
+    // --recover puts the file back, and the run can resume
+    guard.in_flight.take().unwrap().restore().unwrap();
+    assert_eq!(fs::read_to_string(&a).unwrap(), original);
+    assert!(guard.problems(&[], &roots).unwrap().is_empty());
+    fs::remove_dir_all(&dir).unwrap();
+}
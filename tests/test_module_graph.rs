@@ -0,0 +1,66 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Tests for the module dependency graph.
+
+use veracity::module_graph::{module_path, ModuleGraph, ModuleNode};
+
+fn file(module: &str, holes: usize, uses: &[&str]) -> (ModuleNode, Vec<String>) {
+    let node = ModuleNode {
+        module: module.to_string(),
+        path: format!("src/{}.rs", module.replace("::", "/")),
+        holes,
+        holed: holes > 0,
+        verusified: true,
+        ..Default::default()
+    };
+    (node, uses.iter().map(|u| u.to_string()).collect())
+}
+
+#[test]
+fn test_frontier_and_cycles() {
+    let graph = ModuleGraph::new(vec![
+        file("Chap01::Base", 0, &[]),
+        file("Chap02::A", 2, &["Chap02::B", "Chap01"]),
+        file("Chap02::B", 1, &["Chap02::A"]),
+        file("Chap03::Top", 4, &["Chap02::A"]),
+        file("Chap03::Self", 1, &["Chap03"]),
+    ]);
+    let edges: Vec<(&str, &str)> = graph.edges.iter().map(|e| (e.from.as_str(), e.to.as_str())).collect();
+    assert_eq!(edges, vec![
+        ("Chap02::A", "Chap01::Base"),
+        ("Chap02::A", "Chap02::B"),
+        ("Chap02::B", "Chap02::A"),
+        ("Chap03::Self", "Chap03::Top"),
+        ("Chap03::Top", "Chap02::A"),
+    ]);
+    assert_eq!(graph.cycles, vec![vec!["Chap02::A".to_string(), "Chap02::B".to_string()]]);
+
+    // A and B depend only on each other and clean modules, so both are targets
+    let frontier: Vec<&str> = graph.nodes.iter().filter(|n| n.frontier).map(|n| n.module.as_str()).collect();
+    assert_eq!(frontier, vec!["Chap02::A", "Chap02::B"]);
+    assert_eq!(graph.nodes[1].cycle, Some(0));
+
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("digraph modules {"));
+    assert!(dot.contains("\"Chap02::A\" -> \"Chap02::B\" [color="));
+    assert!(dot.contains("\"Chap03::Top\" -> \"Chap02::A\";"));
+}
+
+#[test]
+fn test_dependents() {
+    let graph = ModuleGraph::new(vec![
+        file("Chap01::Base", 0, &[]),
+        file("Chap02::A", 0, &["Chap01::Base"]),
+        file("Chap03::Top", 0, &["Chap02::A"]),
+        file("Chap04::Other", 0, &["Chap01"]),
+        file("Chap05::Alone", 0, &[]),
+    ]);
+    assert_eq!(graph.dependents(&["Chap02::A"]), vec!["Chap02::A", "Chap03::Top"]);
+    assert_eq!(graph.dependents(&["Chap01::Base"]), vec!["Chap01::Base", "Chap02::A", "Chap03::Top", "Chap04::Other"]);
+    assert_eq!(graph.dependents(&["Chap05::Alone"]), vec!["Chap05::Alone"]);
+
+    assert_eq!(module_path("src/Chap05/SetStEph.rs"), "Chap05::SetStEph");
+    assert_eq!(module_path("src/Chap05/mod.rs"), "Chap05");
+    assert_eq!(module_path("src/lib.rs"), "");
+}
@@ -0,0 +1,70 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Tests that veracity-review-proof-holes --format json matches its schema.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Path to the shipped schema
+fn schema_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("schemas/proof_holes.schema.json")
+}
+
+/// Scratch project with one holed file, one clean file that uses it, and one plain Rust file
+fn scratch_project() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("veracity_test_proof_holes_json_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("src/Chap01")).unwrap();
+    fs::write(dir.join("src/Chap01/Holed.rs"), "use vstd::prelude::*;
+
+verus! {
+proof fn lemma_a(x: int) ensures x + 0 == x {
+    assume(x > 0);
+}
+
+#[verifier::external_body]
+fn b() -> (r: u64) ensures r == 1 { 1 }
+
+proof fn lemma_c() ensures false {
+    admit();
+}
+} // verus!
+").unwrap();
+    fs::write(dir.join("src/Chap01/Clean.rs"), "use vstd::prelude::*;
+use crate::Chap01::Holed::b;
+
+verus! {
+fn c() -> (r: u64) ensures r == 1 { b() }
+} // verus!
+").unwrap();
+    fs::write(dir.join("src/Chap01/Plain.rs"), "pub fn d() -> u64 { 1 }\n").unwrap();
+    dir
+}
+
+#[test]
+fn test_json_report_matches_schema() {
+    let dir = scratch_project();
+    let output = Command::new(env!("CARGO_BIN_EXE_veracity-review-proof-holes"))
+        .args(["-d", "src", "--format", "json", "--no-cache"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let schema: serde_json::Value = serde_json::from_str(&fs::read_to_string(schema_path()).unwrap()).unwrap();
+    let validator = jsonschema::validator_for(&schema).unwrap();
+    let errors: Vec<String> = validator.iter_errors(&report)
+        .map(|e| format!("{} at {}", e, e.instance_path()))
+        .collect();
+    assert!(errors.is_empty(), "report does not match the schema:\n{}", errors.join("\n"));
+
+    // The report covers what the project holds
+    let files = report["files"].as_array().unwrap();
+    assert_eq!(files.len(), 3);
+    assert_eq!(report["summary"]["holes"]["total_holes"], 3);
+    let _ = fs::remove_dir_all(&dir);
+}
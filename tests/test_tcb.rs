@@ -0,0 +1,170 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Tests for the trusted computing base report.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use veracity::holes::analyze_source;
+use veracity::tcb::{compute_trust, tcb_totals, trusted_items, vstd_commit, verus_version, Attestation, Drift, TcbTotals, TrustKind};
+
+const SRC: &str = "verus! {
+pub assume_specification<T> [ Vec::<T>::len ](v: &Vec<T>) -> (n: usize)
+    ensures n == v@.len();
+
+pub broadcast axiom fn axiom_len(s: Seq<int>)
+    ensures #[trigger] s.len() >= 0;
+
+impl Counter {
+    #[verifier::external_body]
+    fn bump(&mut self)
+        ensures self.n == old(self).n + 1,
+    {
+        self.n = self.n + 1;
+    }
+}
+
+proof fn p(x: int) {
+    accept(x > 0);
+}
+} // verus!
+";
+
+#[test]
+fn test_trusted_items() {
+    let items = trusted_items(SRC);
+    let found: Vec<(TrustKind, &str, usize)> = items.iter().map(|i| (i.kind, i.name.as_str(), i.line)).collect();
+    assert_eq!(found, vec![
+        (TrustKind::AssumeSpecification, "Vec::<T>::len", 2),
+        (TrustKind::BroadcastAxiom, "axiom_len", 5),
+        (TrustKind::ExternalBody, "Counter::bump", 10),
+        (TrustKind::Accept, "p", 18),
+    ]);
+
+    // Only the body of an external_body fn is trusted code
+    let bump = &items[2];
+    assert_eq!(bump.lines, 3);
+    assert!(bump.spec.starts_with("ensures self.n == old(self).n + 1"));
+    assert_eq!(items[3].spec, "x > 0");
+    assert_eq!(items[3].lines, 1);
+
+    let mut totals = TcbTotals::default();
+    for item in &items {
+        totals.add(item);
+    }
+    assert_eq!(totals.items, 4);
+    assert!(totals.tokens > totals.lines);
+}
+
+/// One assume_specification, axiom and external_body, each reached from a caller
+const TRUST_SRC: &str = "verus! {
+pub assume_specification [ u64::clone ](x: &u64) -> (r: u64)
+    ensures r == *x;
+
+pub axiom fn axiom_zero(x: int)
+    ensures x * 0 == 0;
+
+#[verifier::external_body]
+fn read() -> (n: u64)
+    ensures n > 0,
+{
+    1
+}
+
+fn uses() -> (n: u64)
+    ensures n > 0,
+{
+    read()
+}
+
+fn dup(x: &u64) -> (r: u64)
+    ensures r == *x,
+{
+    x.clone()
+}
+
+proof fn zero(x: int)
+    ensures x * 0 == 0,
+{
+    axiom_zero(x);
+}
+
+fn clean(x: u64) -> (r: u64)
+    ensures r == x,
+{
+    x
+}
+} // verus!
+";
+
+#[test]
+fn test_compute_trust_and_tcb_totals() {
+    let dir = std::env::temp_dir().join(format!("veracity_test_tcb_trust_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("src/Chap01")).unwrap();
+    let file = dir.join("src/Chap01/Trust.rs");
+    fs::write(&file, TRUST_SRC).unwrap();
+
+    let mut files = HashMap::new();
+    files.insert("src/Chap01/Trust.rs".to_string(), analyze_source(TRUST_SRC, Path::new("src/Chap01/Trust.rs")));
+    let trust = compute_trust(&files, &dir);
+    let found: Vec<_> = trust.iter()
+        .map(|t| {
+            let base: Vec<_> = t.trusted_base.iter().map(|h| (h.function.as_str(), h.line, h.hole_type.as_str())).collect();
+            (t.function.as_str(), t.line, t.own_holes, base)
+        })
+        .collect();
+    assert_eq!(found, vec![
+        ("axiom_zero", 5, 1, vec![("axiom_zero", 5, "axiom")]),
+        ("read", 9, 1, vec![("read", 8, "external_body")]),
+        ("uses", 15, 0, vec![("read", 8, "external_body")]),
+        // x.clone() reaches the assume_specification of u64::clone
+        ("dup", 21, 0, vec![("u64::clone", 2, "assume_specification")]),
+        ("zero", 27, 0, vec![("axiom_zero", 5, "axiom")]),
+        ("clean", 33, 0, vec![]),
+    ]);
+    assert!(trust.iter().all(|t| t.module == "Chap01::Trust"));
+
+    // Whole items for the spec and the axiom, only the body `{ 1 }` for external_body;
+    // tokens count `==` and `->` as two and a delimiter pair as two
+    let items: Vec<(TrustKind, usize, usize, usize)> = trusted_items(TRUST_SRC).iter()
+        .map(|i| (i.kind, i.line, i.lines, i.tokens))
+        .collect();
+    assert_eq!(items, vec![
+        (TrustKind::AssumeSpecification, 2, 2, 28),
+        (TrustKind::Axiom, 5, 2, 17),
+        (TrustKind::ExternalBody, 9, 3, 3),
+    ]);
+    assert_eq!(tcb_totals(&[file]), TcbTotals { items: 3, lines: 7, tokens: 48 });
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_attestation_drift() {
+    let hashes = |files: &[(&str, &str)]| -> BTreeMap<String, String> {
+        files.iter().map(|(f, h)| (f.to_string(), h.to_string())).collect()
+    };
+    let attestation = Attestation {
+        tool: "veracity-review-proof-holes".to_string(),
+        veracity_version: env!("CARGO_PKG_VERSION").to_string(),
+        verus_version: verus_version(),
+        vstd_commit: vstd_commit(),
+        generated: String::new(),
+        files: hashes(&[("src/a.rs", "1"), ("src/b.rs", "2"), ("Cargo.toml", "3")]),
+        holes: Vec::new(),
+        accepted: Vec::new(),
+        trusted: Vec::new(),
+    };
+    assert!(attestation.drift(&attestation.files).is_empty());
+
+    let current = hashes(&[("src/a.rs", "1"), ("src/b.rs", "2b"), ("src/c.rs", "4")]);
+    assert_eq!(attestation.drift(&current), vec![
+        Drift::Removed("Cargo.toml".to_string()),
+        Drift::Changed("src/b.rs".to_string()),
+        Drift::Added("src/c.rs".to_string()),
+    ]);
+
+    let old = Attestation { veracity_version: "0.0.0".to_string(), ..attestation };
+    assert_eq!(old.drift(&old.files), vec![Drift::Tool("veracity", "0.0.0".to_string(), env!("CARGO_PKG_VERSION").to_string())]);
+}
@@ -0,0 +1,115 @@
+// Copyright (c) 2025 Brian G. Milnes
+// SPDX-License-Identifier: MIT
+
+//! Tests for triage edits and their undo log.
+
+use veracity::holes::HoleKind;
+use veracity::triage::{accept_hole, mark_wont_fix, to_accepted_external_body, wont_fix_reason, EditLog, Transaction, Triage};
+use veracity::{StandardArgs, VeracityConfig};
+use std::fs;
+use std::path::PathBuf;
+
+const SRC: &str = "verus! {
+use vstd::prelude::*;
+
+fn f(x: u64) {
+    assume(x > 0);
+}
+
+#[verifier::external_body]
+fn g() {}
+} // verus!
+";
+
+fn scratch_file(name: &str, content: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("veracity_test_triage_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("a.rs");
+    fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn test_edits() {
+    let accepted = accept_hole(SRC, 5, HoleKind::Assume, "use crate::accept::accept;").unwrap();
+    let lines: Vec<&str> = accepted.lines().collect();
+    assert_eq!(lines[2], "use crate::accept::accept;");
+    assert_eq!(lines[5], "    proof { accept(x > 0); };");
+    assert!(accepted.ends_with("} // verus!\n"));
+
+    let external = to_accepted_external_body(SRC, 8).unwrap();
+    assert_eq!(external.lines().nth(7), Some("#[accepted_external_body]"));
+    assert!(to_accepted_external_body(SRC, 5).is_none());
+
+    let wont_fix = mark_wont_fix(SRC, 5, "needs a seq lemma").unwrap();
+    assert_eq!(wont_fix.lines().nth(4), Some("    // veracity-wontfix: needs a seq lemma"));
+    assert_eq!(wont_fix_reason(&wont_fix, 6).as_deref(), Some("needs a seq lemma"));
+    assert_eq!(wont_fix_reason(SRC, 5), None);
+}
+
+#[test]
+fn test_apply_and_undo() {
+    let path = scratch_file("undo", SRC);
+    let mut log = EditLog::default();
+    let edited = mark_wont_fix(SRC, 5, "later").unwrap();
+    log.apply(Transaction::new("won't fix a.rs:5").edit(&path, SRC.to_string(), edited.clone())).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), edited);
+
+    // A transaction made against stale content is refused
+    let stale = Transaction::new("stale").edit(&path, SRC.to_string(), String::new());
+    assert!(log.apply(stale).is_err());
+    assert_eq!(fs::read_to_string(&path).unwrap(), edited);
+
+    let undone = log.undo().unwrap().unwrap();
+    assert_eq!(undone.label, "won't fix a.rs:5");
+    assert_eq!(fs::read_to_string(&path).unwrap(), SRC);
+    assert!(log.undo().unwrap().is_none());
+}
+
+#[test]
+fn test_act_refresh_act_in_one_file() {
+    let src = "verus! {
+use vstd::prelude::*;
+
+fn f(x: u64, y: u64) {
+    assume(x > 0);
+    assume(y > 0);
+}
+} // verus!
+";
+    let path = scratch_file("session", src);
+    let dir = path.parent().unwrap().to_path_buf();
+    let args = StandardArgs {
+        paths: vec![dir.clone()],
+        is_module_search: false,
+        project: None,
+        language: "Verus".to_string(),
+        repositories: None,
+        multi_codebase: None,
+        src_dirs: vec!["src".to_string()],
+        test_dirs: Vec::new(),
+        bench_dirs: Vec::new(),
+        config: VeracityConfig::default(),
+    };
+    let lines = |triage: &Triage| triage.holes.iter().map(|h| (h.hole.line, h.hole.kind)).collect::<Vec<_>>();
+
+    let mut triage = Triage::new(&args, std::slice::from_ref(&path), "use crate::accept::accept;", true).unwrap();
+    assert_eq!(lines(&triage), vec![(5, HoleKind::Assume), (6, HoleKind::Assume)]);
+
+    // Accepting the first adds the import, so the second moves down a line and stays selected
+    triage.act('a', "");
+    assert_eq!(lines(&triage), vec![(7, HoleKind::Assume)]);
+    assert_eq!(triage.current().map(|h| h.hole.line), Some(7));
+    triage.act('a', "");
+    assert!(triage.holes.is_empty(), "{}", triage.status);
+    let accepted = fs::read_to_string(&path).unwrap();
+    assert_eq!(accepted.matches("proof { accept(").count(), 2);
+
+    triage.undo();
+    assert_eq!(lines(&triage), vec![(7, HoleKind::Assume)]);
+    triage.undo();
+    assert_eq!(lines(&triage), vec![(5, HoleKind::Assume), (6, HoleKind::Assume)]);
+    assert_eq!(fs::read_to_string(&path).unwrap(), src);
+    let _ = fs::remove_dir_all(&dir);
+}